@@ -0,0 +1,189 @@
+/// # Benchmarks (~ a pocket `criterion`)
+/// `cargo bench` compiles the files in `benches/` with optimizations (the
+/// `bench` profile, which inherits from `release`) and runs them. By
+/// default, they use the built-in `#[bench]` harness, which is still
+/// unstable: this target sets `harness = false` in Cargo.toml, and brings
+/// its own `main`, like `criterion` does.
+///
+/// The harness below follows `criterion` on a small scale:
+/// - a warm-up, so that caches and branch predictors are primed
+/// - several samples, reported by their median, which ignores outliers,
+///   and their spread, which tells whether the numbers can be trusted
+/// - `black_box` around the inputs and outputs (see src/black_box.rs)
+///
+/// The benchmarks are the showpieces of the runnables: dispatch (see
+/// src/patterns/strategy.rs), iterators (see src/zero_cost.rs) and interior
+/// mutability (see src/interior_mutability.rs). Run them alone with
+/// `cargo bench --bench showpieces` (plain `cargo bench` also lists the
+/// unit tests, as ignored), and a subset with a filter:
+/// `cargo bench --bench showpieces -- iterators`.
+use std::cell::{Cell, RefCell};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// ## The Harness
+const WARM_UP: Duration = Duration::from_millis(100);
+const SAMPLES: usize = 10;
+
+struct Group {
+    name: &'static str,
+    baseline: Option<(&'static str, Duration)>,
+}
+
+impl Group {
+    fn new(name: &'static str) -> Group {
+        println!("{}", name);
+        Group { name, baseline: None }
+    }
+
+    /// Measure `routine`, print its median time per iteration, in the same
+    /// format as `benchmark!`, and compare it with the first benchmark of
+    /// the group.
+    fn bench<T>(&mut self, label: &'static str, iterations: u32,
+        mut routine: impl FnMut() -> T)
+    {
+        let start: Instant = Instant::now();
+        while start.elapsed() < WARM_UP { black_box(routine()); }
+
+        let mut samples: Vec<Duration> = (0..SAMPLES)
+            .map(|_| {
+                let start: Instant = Instant::now();
+                for _ in 0..iterations { black_box(routine()); }
+                start.elapsed() / iterations
+            })
+            .collect();
+        samples.sort();
+        let median: Duration = samples[SAMPLES / 2];
+        let spread: f64 = (samples[SAMPLES - 1] - samples[0]).as_secs_f64()
+            / median.as_secs_f64().max(1e-12);
+        println!("{}/{}: {:?} per iteration ({} iterations) [±{:.0}%]",
+            self.name, label, median, iterations, spread * 50.0);
+        match self.baseline {
+            None => self.baseline = Some((label, median)),
+            Some((base, base_median)) => println!("{}/{}: {:.2}x {}",
+                self.name, label,
+                median.as_secs_f64() / base_median.as_secs_f64().max(1e-12),
+                base),
+        }
+    }
+}
+
+/// ## Dispatch
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 { self.0 * self.0 }
+}
+
+fn total_area_generic<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(Shape::area).sum()
+}
+
+fn total_area_dyn(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+fn square_area(side: f64) -> f64 { side * side }
+
+fn total_area_fn(sides: &[f64], area: fn(f64) -> f64) -> f64 {
+    sides.iter().map(|side| area(*side)).sum()
+}
+
+fn dispatch() {
+    let mut group: Group = Group::new("dispatch");
+    let sides: Vec<f64> = (0..10_000).map(|i| i as f64 / 100.0).collect();
+    let squares: Vec<Square> = sides.iter().map(|s| Square(*s)).collect();
+    // The same squares behind `dyn`: only the dispatch differs
+    let boxed: Vec<Box<dyn Shape>> = sides.iter()
+        .map(|side| -> Box<dyn Shape> { Box::new(Square(*side)) })
+        .collect();
+    group.bench("generic", 1_000, || total_area_generic(black_box(&squares)));
+    group.bench("dyn", 1_000, || total_area_dyn(black_box(&boxed)));
+    let area: fn(f64) -> f64 = square_area;
+    group.bench("fn pointer", 1_000, || {
+        total_area_fn(black_box(&sides), black_box(area))
+    });
+}
+
+/// ## Iterators
+fn index_loop(values: &[u64]) -> u64 {
+    let mut total: u64 = 0;
+    let mut i: usize = 0;
+    while i < values.len() {
+        if values[i].is_multiple_of(2) { total += values[i] * values[i]; }
+        i += 1;
+    }
+    total
+}
+
+fn iterator_chain(values: &[u64]) -> u64 {
+    values.iter().filter(|value| value.is_multiple_of(2))
+        .map(|value| value * value)
+        .sum()
+}
+
+fn iterators() {
+    let mut group: Group = Group::new("iterators");
+    let values: Vec<u64> = (0..100_000).collect();
+    group.bench("index loop", 100, || index_loop(black_box(&values)));
+    group.bench("iterator chain", 100, || iterator_chain(black_box(&values)));
+}
+
+/// ## Interior Mutability
+/// The counters are hidden behind `black_box`, so that the increments are
+/// not folded into a single addition.
+const INCREMENTS: u64 = 10_000;
+
+fn interior_mutability() {
+    let mut group: Group = Group::new("interior_mutability");
+    let mut plain: u64 = 0;
+    let cell: Cell<u64> = Cell::new(0);
+    let ref_cell: RefCell<u64> = RefCell::new(0);
+    let mutex: Mutex<u64> = Mutex::new(0);
+    let atomic: AtomicU64 = AtomicU64::new(0);
+    group.bench("&mut", 100, || {
+        for _ in 0..INCREMENTS { *black_box(&mut plain) += 1; }
+    });
+    group.bench("Cell", 100, || {
+        for _ in 0..INCREMENTS {
+            let cell: &Cell<u64> = black_box(&cell);
+            cell.set(cell.get() + 1);
+        }
+    });
+    group.bench("RefCell", 100, || {
+        for _ in 0..INCREMENTS { *black_box(&ref_cell).borrow_mut() += 1; }
+    });
+    group.bench("Mutex", 100, || {
+        for _ in 0..INCREMENTS { *black_box(&mutex).lock().unwrap() += 1; }
+    });
+    group.bench("AtomicU64", 100, || {
+        for _ in 0..INCREMENTS {
+            black_box(&atomic).fetch_add(1, Ordering::Relaxed);
+        }
+    });
+}
+
+/// ## Running
+/// `cargo bench` passes `--bench` to the target: flags are skipped, and any
+/// other argument selects the groups whose name contains it.
+fn main() {
+    let filters: Vec<String> = std::env::args().skip(1)
+        .filter(|argument| !argument.starts_with('-'))
+        .collect();
+    let groups: [(&str, fn()); 3] = [
+        ("dispatch", dispatch),
+        ("iterators", iterators),
+        ("interior_mutability", interior_mutability),
+    ];
+    for (name, group) in groups {
+        if filters.is_empty() || filters.iter().any(|f| name.contains(f)) {
+            group();
+        }
+    }
+}
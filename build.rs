@@ -0,0 +1,100 @@
+/// # Build Script
+/// Cargo compiles and runs this file before compiling the crate (see
+/// src/build_scripts.rs). Here it generates `generated.rs` into `OUT_DIR`,
+/// containing a table of the playground topics and some build information.
+/// With the `native-c` feature, it also compiles the C library in `native`
+/// (see src/native_linking.rs).
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    // Only run again if the sources (for the topics) or this script change,
+    // instead of after every change to the package
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src");
+
+    let out_dir: String = env::var("OUT_DIR").unwrap();
+    let target: String = env::var("TARGET").unwrap();
+    let profile: String = env::var("PROFILE").unwrap();
+
+    // Environment variables can also be passed to the compiler, to be read
+    // with `env!` in the crate
+    println!("cargo:rustc-env=PLAYGROUND_BUILD_TARGET={}", target);
+
+    let mut code: String = String::from("// Generated by build.rs\n");
+    code += &format!("pub const BUILD_PROFILE: &str = {:?};\n", profile);
+    code += "pub const TOPICS: &[(&str, &str)] = &[\n";
+    for (module, title) in topics(Path::new("src")) {
+        code += &format!("    ({:?}, {:?}),\n", module, title);
+    }
+    code += "];\n";
+
+    fs::write(Path::new(&out_dir).join("generated.rs"), code).unwrap();
+
+    // Cargo sets `CARGO_FEATURE_<NAME>` for each enabled feature
+    if env::var_os("CARGO_FEATURE_NATIVE_C").is_some() {
+        compile_native(Path::new(&out_dir));
+    }
+}
+
+/// Compile `native/checksum.c` into a static library in `out_dir`, and tell
+/// Cargo to link it. The system tools are invoked directly (`cc` and `ar`,
+/// or the `CC` and `AR` variables), so this only supports Unix-like
+/// toolchains: the `cc` crate handles every platform and cross-compilation.
+fn compile_native(out_dir: &Path) {
+    println!("cargo:rerun-if-changed=native");
+    println!("cargo:rerun-if-env-changed=CC");
+    println!("cargo:rerun-if-env-changed=AR");
+    let compiler: String = env::var("CC").unwrap_or_else(|_| "cc".into());
+    let archiver: String = env::var("AR").unwrap_or_else(|_| "ar".into());
+    let object: std::path::PathBuf = out_dir.join("checksum.o");
+
+    let status = Command::new(&compiler)
+        .args(["-c", "native/checksum.c", "-O2", "-fPIC", "-o"])
+        .arg(&object)
+        .status()
+        .unwrap_or_else(|error| panic!("cannot run `{}`: {}", compiler, error));
+    assert!(status.success(), "compilation of native/checksum.c failed");
+
+    // A static library is an archive of object files, named `lib<name>.a`
+    let status = Command::new(&archiver)
+        .arg("crs")
+        .arg(out_dir.join("libplayground_native.a"))
+        .arg(&object)
+        .status()
+        .unwrap_or_else(|error| panic!("cannot run `{}`: {}", archiver, error));
+    assert!(status.success(), "archiving of checksum.o failed");
+
+    println!("cargo:rustc-link-search=native={}", out_dir.display());
+    println!("cargo:rustc-link-lib=static=playground_native");
+}
+
+/// Collect the name and the title of each lesson in `src`, sorted by name.
+/// The crate roots (`main.rs` of the binary, `lib.rs` of the library) are
+/// not lessons, and files without headers are skipped.
+fn topics(src: &Path) -> Vec<(String, String)> {
+    let mut topics: Vec<(String, String)> = fs::read_dir(src).unwrap()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter(|path| !path.ends_with("main.rs") && !path.ends_with("lib.rs"))
+        .filter_map(|path| {
+            let module: String = path.file_stem()?.to_str()?.to_string();
+            let source: String = fs::read_to_string(&path).ok()?;
+            // The title is the first header in a doc comment (usually
+            // `/// # Title`, but `/// ## Title` is accepted too), or in an
+            // inner doc comment for the lessons of the library (`//! # Title`)
+            let title: &str = source.lines()
+                .find_map(|line| {
+                    line.strip_prefix("/// #")
+                        .or_else(|| line.strip_prefix("//! #"))
+                })?
+                .trim_start_matches(['#', ' ']);
+            Some((module, title.to_string()))
+        })
+        .collect();
+    topics.sort();
+    topics
+}
\ No newline at end of file
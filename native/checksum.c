@@ -0,0 +1,26 @@
+/* A small C library compiled and linked by build.rs (with the `native-c`
+ * feature), see src/native_linking.rs. */
+#include <stddef.h>
+#include <stdint.h>
+
+/* Fletcher-32 checksum: the bytes are read as little-endian 16-bit words
+ * (an odd last byte is padded with zero). */
+uint32_t native_fletcher32(const uint8_t *data, size_t length) {
+    uint32_t sum1 = 0, sum2 = 0;
+    for (size_t i = 0; i < length; i += 2) {
+        uint32_t word = data[i];
+        if (i + 1 < length) word |= (uint32_t)data[i + 1] << 8;
+        sum1 = (sum1 + word) % 65535;
+        sum2 = (sum2 + sum1) % 65535;
+    }
+    return sum2 << 16 | sum1;
+}
+
+/* Reverse `length` bytes in place. */
+void native_reverse(uint8_t *buffer, size_t length) {
+    for (size_t i = 0; i < length / 2; i++) {
+        uint8_t byte = buffer[i];
+        buffer[i] = buffer[length - 1 - i];
+        buffer[length - 1 - i] = byte;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,358 @@
+/// # Playground Derive
+/// Procedural macros used by the playground (see `src/proc_macros.rs`).
+///
+/// Procedural macros must be defined in a dedicated crate of type
+/// `proc-macro`, because the compiler loads them as plugins and runs them
+/// while compiling the crates that use them. Such a crate can only export
+/// procedural macros.
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, ItemFn, LitStr, Type};
+
+/// ## Custom Derive
+/// `#[derive(Describe)]` adds two associated functions to a struct:
+/// - `field_names()`, returning the names of its fields
+/// - `describe()`, returning a description of its fields and their types
+///
+/// A derive macro receives the tokens of the item it is attached to, and
+/// returns new tokens that are appended after the item (the item itself
+/// cannot be modified).
+#[proc_macro_derive(Describe)]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    // Parse the tokens into a syntax tree
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields: Vec<(String, String)> = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter()
+                .map(|field| (
+                    field.ident.as_ref().unwrap().to_string(),
+                    type_name(&field.ty),
+                ))
+                .collect(),
+            Fields::Unnamed(fields) => fields.unnamed.iter().enumerate()
+                .map(|(i, field)| (i.to_string(), type_name(&field.ty)))
+                .collect(),
+            Fields::Unit => Vec::new(),
+        },
+        // Errors are reported as compile errors, pointing at the given span
+        _ => return syn::Error::new_spanned(
+            &input.ident,
+            "`Describe` can only be derived for structs",
+        ).to_compile_error().into(),
+    };
+
+    let field_names = fields.iter().map(|(name, _)| name);
+    let description: String = if fields.is_empty() {
+        name.to_string()
+    } else {
+        format!(
+            "{} {{ {} }}",
+            name,
+            fields.iter()
+                .map(|(name, ty)| format!("{}: {}", name, ty))
+                .collect::<Vec<String>>()
+                .join(", "),
+        )
+    };
+
+    // Generics must be forwarded to the generated `impl` block
+    let (impl_generics, type_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    // Generate the output tokens, interpolating variables with `#`
+    let output = quote! {
+        impl #impl_generics #name #type_generics #where_clause {
+            pub fn field_names() -> &'static [&'static str] {
+                &[#(#field_names),*]
+            }
+            pub fn describe() -> &'static str {
+                #description
+            }
+        }
+    };
+    output.into()
+}
+
+/// ## Attribute Macros
+/// `#[timed]` wraps the body of a function with the same timing logic as
+/// `runnable!` (see src/util.rs), printing when the function starts and how
+/// long it took.
+///
+/// An attribute macro receives two token streams: the arguments of the
+/// attribute (`#[timed(...)]`) and the annotated item, which is replaced by
+/// the returned tokens.
+#[proc_macro_attribute]
+pub fn timed(attribute: TokenStream, item: TokenStream) -> TokenStream {
+    if !attribute.is_empty() {
+        return syn::Error::new_spanned(
+            proc_macro2::TokenStream::from(attribute),
+            "`timed` does not take any argument",
+        ).to_compile_error().into();
+    }
+    let function: ItemFn = parse_macro_input!(item as ItemFn);
+    let ItemFn { attrs, vis, sig, block } = function;
+    let name: String = sig.ident.to_string();
+
+    // The body runs in a closure, so that an early `return` still reaches
+    // the end of the timing logic
+    let output = quote! {
+        #(#attrs)*
+        #vis #sig {
+            let function_name = #name;
+            println!("{} [start]", function_name);
+            let start_time = ::std::time::Instant::now();
+            let result = (move || #block)();
+            println!(
+                "{} [end]: took {} ms...",
+                function_name,
+                start_time.elapsed().as_millis()
+            );
+            result
+        }
+    };
+    output.into()
+}
+
+/// ## Function-Like Macros
+/// `csv_row!("42, 3.5, true, text")` parses a CSV row at compile time and
+/// expands to a tuple of typed values: `(42i64, 3.5f64, true, "text")`.
+/// Each cell becomes an `i64`, an `f64`, a `bool` or a `&'static str`,
+/// depending on its content.
+///
+/// A function-like macro receives the tokens between its delimiters, which
+/// do not need to be valid Rust: the macro defines its own syntax.
+#[proc_macro]
+pub fn csv_row(input: TokenStream) -> TokenStream {
+    // Anything but a single string literal is rejected by the parser, with
+    // an error pointing at the unexpected tokens
+    let row: LitStr = parse_macro_input!(input as LitStr);
+    let text: String = row.value();
+
+    let mut cells: Vec<proc_macro2::TokenStream> = Vec::new();
+    for (column, cell) in text.split(',').map(str::trim).enumerate() {
+        if cell.is_empty() {
+            // Errors point at the whole literal: spans of parts of a literal
+            // are not available on stable Rust
+            let message = format!("empty cell in column {}", column + 1);
+            return syn::Error::new(row.span(), message)
+                .to_compile_error().into();
+        }
+        cells.push(if let Ok(integer) = cell.parse::<i64>() {
+            quote!(#integer)
+        } else if let Ok(float) = cell.parse::<f64>() {
+            // `nan`, `inf` and overflows like `1e400` parse too, but have no
+            // literal to expand to
+            if !float.is_finite() {
+                let message = format!("not a finite number in column {}",
+                    column + 1);
+                return syn::Error::new(row.span(), message)
+                    .to_compile_error().into();
+            }
+            quote!(#float)
+        } else if let Ok(boolean) = cell.parse::<bool>() {
+            quote!(#boolean)
+        } else {
+            quote!(#cell)
+        });
+    }
+    // A trailing comma makes single-cell rows tuples too
+    quote!((#(#cells,)*)).into()
+}
+
+/// Print a type as written in the source. Tokens are printed separated by
+/// spaces (e.g. `Vec < u8 >`), so the most common ones are compacted.
+fn type_name(ty: &Type) -> String {
+    quote!(#ty).to_string()
+        .replace(" < ", "<")
+        .replace(" <", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
+/// ## Derived Errors
+/// `#[derive(Error)]` implements `Display` and `std::error::Error` for an
+/// enum (or a struct), like the `thiserror` crate:
+/// - `#[error("...")]` on each variant gives its message. The message is a
+///   format string, where `{0}` or `{name}` refers to a field
+/// - `#[from]` on the only field of a variant generates a `From` impl for
+///   the field type (so `?` converts it), and returns the field as `source`
+///
+/// The generated code is the one written by hand in `src/errors.rs`.
+#[proc_macro_derive(Error, attributes(error, from))]
+pub fn derive_error(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    match expand_error(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// What the generated code needs to know about each variant (or about the
+/// struct): how to match it, its message, and its `#[from]` field if any.
+struct ErrorVariant {
+    pattern: proc_macro2::TokenStream,
+    message: proc_macro2::TokenStream,
+    from: Option<(proc_macro2::TokenStream, Type)>,
+}
+
+fn expand_error(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let variants: Vec<ErrorVariant> = match &input.data {
+        Data::Enum(data) => data.variants.iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                error_variant(quote!(#name::#ident), &variant.attrs,
+                    &variant.fields, variant)
+            })
+            .collect::<syn::Result<_>>()?,
+        Data::Struct(data) => vec![
+            error_variant(quote!(#name), &input.attrs, &data.fields, name)?
+        ],
+        Data::Union(_) => return Err(syn::Error::new_spanned(
+            name,
+            "`Error` can only be derived for enums and structs",
+        )),
+    };
+
+    let (impl_generics, type_generics, where_clause) =
+        input.generics.split_for_impl();
+    let patterns = variants.iter().map(|variant| &variant.pattern);
+    let messages = variants.iter().map(|variant| &variant.message);
+    let sources = variants.iter().map(|variant| match &variant.from {
+        Some((binding, _)) => quote!(::std::option::Option::Some(#binding)),
+        None => quote!(::std::option::Option::None),
+    });
+    let source_patterns = variants.iter().map(|variant| &variant.pattern);
+    let from_impls = variants.iter()
+        .filter_map(|variant| Some((&variant.pattern, variant.from.as_ref()?)))
+        .map(|(pattern, (binding, ty))| quote! {
+            impl #impl_generics ::std::convert::From<#ty>
+                for #name #type_generics #where_clause
+            {
+                fn from(#binding: #ty) -> Self { #pattern }
+            }
+        });
+
+    Ok(quote! {
+        impl #impl_generics ::std::fmt::Display
+            for #name #type_generics #where_clause
+        {
+            #[allow(unused_variables)]
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match self {
+                    #(#patterns => #messages,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::error::Error
+            for #name #type_generics #where_clause
+        {
+            #[allow(unused_variables)]
+            fn source(&self)
+                -> ::std::option::Option<&(dyn ::std::error::Error + 'static)>
+            {
+                match self {
+                    #(#source_patterns => #sources,)*
+                }
+            }
+        }
+
+        #(#from_impls)*
+    })
+}
+
+/// Fields are bound by name, or as `_0`, `_1`... for tuple fields, so that
+/// the message can refer to them.
+fn error_variant(
+    path: proc_macro2::TokenStream,
+    attrs: &[syn::Attribute],
+    fields: &Fields,
+    spanned: impl quote::ToTokens,
+) -> syn::Result<ErrorVariant> {
+    let message: LitStr = attrs.iter()
+        .find(|attribute| attribute.path().is_ident("error"))
+        .ok_or_else(|| syn::Error::new_spanned(
+            &spanned,
+            "missing `#[error(\"...\")]` attribute",
+        ))?
+        .parse_args()?;
+
+    let bindings: Vec<syn::Ident> = fields.iter().enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => ident.clone(),
+            None => quote::format_ident!("_{}", i),
+        })
+        .collect();
+    let pattern = match fields {
+        Fields::Named(_) => quote!(#path { #(#bindings),* }),
+        Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+        Fields::Unit => quote!(#path),
+    };
+
+    let from_fields: Vec<usize> = fields.iter().enumerate()
+        .filter(|(_, field)| {
+            field.attrs.iter().any(|attr| attr.path().is_ident("from"))
+        })
+        .map(|(i, _)| i)
+        .collect();
+    let from = match from_fields[..] {
+        [] => None,
+        [i] if fields.len() == 1 => {
+            let field = fields.iter().next().unwrap();
+            let binding = &bindings[i];
+            Some((quote!(#binding), field.ty.clone()))
+        }
+        _ => return Err(syn::Error::new_spanned(
+            &spanned,
+            "`#[from]` must be on the only field of a variant",
+        )),
+    };
+
+    let (format, arguments) = format_arguments(&message)?;
+    let format = LitStr::new(&format, message.span());
+    let arguments = arguments.iter()
+        .map(|argument| quote::format_ident!("{}", argument));
+    let message = quote! {
+        ::std::write!(f, #format, #(#arguments = #arguments),*)
+    };
+    Ok(ErrorVariant { pattern, message, from })
+}
+
+/// Rewrite `{0}` into `{_0}`, and list the fields used by the message: they
+/// are passed as named arguments, since unused ones would be an error.
+fn format_arguments(message: &LitStr) -> syn::Result<(String, Vec<String>)> {
+    let text: String = message.value();
+    let mut format: String = String::new();
+    let mut arguments: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        format.push(c);
+        if c != '{' { continue; }
+        if chars.peek() == Some(&'{') {             // escaped `{{`
+            format.push(chars.next().unwrap());
+            continue;
+        }
+        let mut argument: String = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '}' || c == ':' { break; }
+            argument.push(c);
+            chars.next();
+        }
+        if argument.is_empty() {
+            return Err(syn::Error::new_spanned(
+                message,
+                "positional `{}` is not supported: name a field, e.g. `{0}`",
+            ));
+        }
+        if argument.chars().all(|c| c.is_ascii_digit()) {
+            argument.insert(0, '_');
+        }
+        format.push_str(&argument);
+        if !arguments.contains(&argument) { arguments.push(argument); }
+    }
+    Ok((format, arguments))
+}
@@ -0,0 +1,51 @@
+/* C interface of the `playground-ffi` crate (see src/lib.rs).
+ * Link with -lplayground_ffi. */
+#ifndef PLAYGROUND_FFI_H
+#define PLAYGROUND_FFI_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/* Plain values */
+int32_t playground_add(int32_t a, int32_t b);
+
+typedef struct PlaygroundPoint {
+    double x;
+    double y;
+} PlaygroundPoint;
+
+double playground_distance(PlaygroundPoint a, PlaygroundPoint b);
+
+/* Pointers: returns false if any pointer is NULL */
+bool playground_midpoint(const PlaygroundPoint *a, const PlaygroundPoint *b,
+                         PlaygroundPoint *out);
+
+/* Ownership transfer: an opaque counter, owned by the caller until freed
+ * with playground_counter_free (never with free) */
+typedef struct PlaygroundCounter PlaygroundCounter;
+
+PlaygroundCounter *playground_counter_new(uint64_t step);
+uint64_t playground_counter_increment(PlaygroundCounter *counter);
+void playground_counter_free(PlaygroundCounter *counter);
+
+/* Panics: reported as error codes */
+#define PLAYGROUND_OK 0
+#define PLAYGROUND_NULL_POINTER 1
+#define PLAYGROUND_PANIC 2
+
+int32_t playground_divide(int32_t a, int32_t b, int32_t *out);
+
+/* Strings: NUL-terminated, UTF-8 */
+const char *playground_version(void);  /* static, never freed */
+int64_t playground_count_chars(const char *text);  /* -1 if not UTF-8 */
+char *playground_greet(const char *name);  /* free with the function below */
+void playground_string_free(char *text);
+
+/* Callbacks: user_data is passed back to each call of the visitor */
+typedef void (*PlaygroundVisitor)(void *user_data, int32_t value);
+
+bool playground_for_each(const int32_t *values, size_t count,
+                         PlaygroundVisitor visitor, void *user_data);
+
+#endif /* PLAYGROUND_FFI_H */
\ No newline at end of file
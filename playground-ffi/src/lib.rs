@@ -0,0 +1,183 @@
+/// # Playground FFI
+/// A Rust library callable from C (see `src/reverse_ffi.rs` in the
+/// playground for the lesson). The functions below are the whole C API,
+/// declared for C programs in `include/playground_ffi.h`:
+/// - `#[no_mangle]` keeps the exact symbol name, instead of a mangled one
+///   like `_ZN14playground_ffi14playground_add17h...E`
+/// - `extern "C"` uses the C calling convention
+/// - every name is prefixed, since C has a single global namespace
+///
+/// `tests/c_program.rs` compiles and runs the C program `tests/c/main.c`
+/// against the shared library.
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+
+/// ## Plain Values
+/// Integers, floats and `#[repr(C)]` types cross the boundary by value.
+#[no_mangle]
+pub extern "C" fn playground_add(a: i32, b: i32) -> i32 {
+    a.wrapping_add(b)  // C code cannot handle a panic on overflow
+}
+
+/// `#[repr(C)]` lays out the fields in declaration order, with the padding
+/// of C, so that the struct matches `struct PlaygroundPoint` in the header.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[no_mangle]
+pub extern "C" fn playground_distance(a: Point, b: Point) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+/// ## Pointers
+/// Pointers from C may be null or invalid: the function is `unsafe`, and
+/// states what the caller must guarantee.
+///
+/// # Safety
+/// `a` and `b` must be null or point to valid points, and `out` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn playground_midpoint(
+    a: *const Point, b: *const Point, out: *mut Point
+) -> bool {
+    if a.is_null() || b.is_null() || out.is_null() { return false; }
+    let (a, b): (Point, Point) = (*a, *b);
+    out.write(Point { x: (a.x + b.x) / 2.0, y: (a.y + b.y) / 2.0 });
+    true
+}
+
+/// ## Ownership Transfer
+/// A Rust value given to C is boxed, and the box turned into a raw pointer
+/// with `Box::into_raw`: Rust forgets about it, and C owns it. C must give it
+/// back to the matching `free` function, which rebuilds the box with
+/// `Box::from_raw` and drops it. Memory must always be freed by the side
+/// that allocated it (C `free` on a Rust allocation is undefined behaviour).
+///
+/// `Counter` is not `#[repr(C)]`: C only handles pointers to it, declared as
+/// an incomplete type in the header.
+pub struct Counter {
+    count: u64,
+    step: u64,
+}
+
+#[no_mangle]
+pub extern "C" fn playground_counter_new(step: u64) -> *mut Counter {
+    Box::into_raw(Box::new(Counter { count: 0, step }))
+}
+
+/// # Safety
+/// `counter` must come from `playground_counter_new`, and not be freed.
+#[no_mangle]
+pub unsafe extern "C" fn playground_counter_increment(
+    counter: *mut Counter
+) -> u64 {
+    let counter: &mut Counter = &mut *counter;
+    counter.count += counter.step;
+    counter.count
+}
+
+/// # Safety
+/// `counter` must be null, or come from `playground_counter_new` and not be
+/// freed already. It must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn playground_counter_free(counter: *mut Counter) {
+    if !counter.is_null() { drop(Box::from_raw(counter)); }
+}
+
+/// ## Panics
+/// A panic must never unwind into C code, which knows nothing about Rust
+/// destructors: a panic escaping an `extern "C"` function aborts the whole
+/// process. `catch_unwind` stops it at the boundary, so that it can be
+/// reported as an error code instead (C has no exceptions).
+pub const PLAYGROUND_OK: i32 = 0;
+pub const PLAYGROUND_NULL_POINTER: i32 = 1;
+pub const PLAYGROUND_PANIC: i32 = 2;
+
+/// # Safety
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn playground_divide(
+    a: i32, b: i32, out: *mut i32
+) -> i32 {
+    if out.is_null() { return PLAYGROUND_NULL_POINTER; }
+    // Panics when `b == 0` (or on `i32::MIN / -1`)
+    match panic::catch_unwind(AssertUnwindSafe(|| a / b)) {
+        Ok(quotient) => { out.write(quotient); PLAYGROUND_OK }
+        Err(_) => PLAYGROUND_PANIC,
+    }
+}
+
+/// ## Strings
+/// C strings are pointers to bytes ending with a `NUL` (`\0`), with no
+/// length and no encoding. A static string can be returned as is: it lives
+/// as long as the program.
+#[no_mangle]
+pub extern "C" fn playground_version() -> *const c_char {
+    c"playground-ffi 0.1.0".as_ptr()
+}
+
+/// Strings from C are borrowed with `CStr::from_ptr`, which scans for the
+/// `NUL`, then validated: C data is not guaranteed to be UTF-8. Returns the
+/// number of characters, or -1 if the text is null or not valid UTF-8.
+///
+/// # Safety
+/// `text` must be null or point to a `NUL`-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn playground_count_chars(text: *const c_char) -> i64 {
+    if text.is_null() { return -1; }
+    match CStr::from_ptr(text).to_str() {
+        Ok(text) => text.chars().count() as i64,
+        Err(_) => -1,
+    }
+}
+
+/// Allocated strings are returned with `CString::into_raw`, and must be
+/// given back to `playground_string_free`. Returns null if `name` is null.
+/// Invalid UTF-8 is replaced with `U+FFFD` (`to_string_lossy`).
+///
+/// # Safety
+/// `name` must be null or point to a `NUL`-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn playground_greet(name: *const c_char) -> *mut c_char {
+    if name.is_null() { return std::ptr::null_mut(); }
+    let name: String = CStr::from_ptr(name).to_string_lossy().into_owned();
+    // Cannot fail: `name` comes from a C string, so it contains no `NUL`
+    CString::new(format!("Hello, {}!", name)).unwrap().into_raw()
+}
+
+/// # Safety
+/// `text` must be null, or come from `playground_greet` and not be freed
+/// already.
+#[no_mangle]
+pub unsafe extern "C" fn playground_string_free(text: *mut c_char) {
+    if !text.is_null() { drop(CString::from_raw(text)); }
+}
+
+/// ## Callbacks
+/// C APIs accept functions as pointers, usually with a `void *user_data`
+/// argument given back to every call, so that the callback can reach the
+/// state of the caller. A null function pointer is represented by `None`.
+pub type Visitor =
+    Option<unsafe extern "C" fn(user_data: *mut c_void, value: i32)>;
+
+/// Call `visitor` on each of the `count` values, in order. Returns false if
+/// `values` or `visitor` is null.
+///
+/// # Safety
+/// `values` must point to `count` integers, and `visitor` must be safe to
+/// call with `user_data`.
+#[no_mangle]
+pub unsafe extern "C" fn playground_for_each(
+    values: *const i32, count: usize, visitor: Visitor, user_data: *mut c_void
+) -> bool {
+    let Some(visitor) = visitor else { return false; };
+    if values.is_null() { return false; }
+    for &value in std::slice::from_raw_parts(values, count) {
+        visitor(user_data, value);
+    }
+    true
+}
\ No newline at end of file
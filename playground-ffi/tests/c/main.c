@@ -0,0 +1,55 @@
+/* A C program using the Rust library (run by tests/c_program.rs).
+ * Exits with 0 if every check passes. */
+#include <stdio.h>
+#include <string.h>
+#include "playground_ffi.h"
+
+#define CHECK(condition)                                        \
+    if (!(condition)) {                                         \
+        fprintf(stderr, "check failed: %s\n", #condition);      \
+        return 1;                                               \
+    }
+
+static void add_to(void *user_data, int32_t value) {
+    *(int64_t *)user_data += value;
+}
+
+int main(void) {
+    CHECK(playground_add(40, 2) == 42);
+
+    PlaygroundPoint origin = { 0.0, 0.0 };
+    PlaygroundPoint point = { 3.0, 4.0 };
+    CHECK(playground_distance(origin, point) == 5.0);
+
+    PlaygroundPoint middle;
+    CHECK(playground_midpoint(&origin, &point, &middle));
+    CHECK(middle.x == 1.5 && middle.y == 2.0);
+    CHECK(!playground_midpoint(&origin, NULL, &middle));
+
+    PlaygroundCounter *counter = playground_counter_new(10);
+    playground_counter_increment(counter);
+    CHECK(playground_counter_increment(counter) == 20);
+    playground_counter_free(counter);
+
+    int32_t quotient = 0;
+    CHECK(playground_divide(7, 2, &quotient) == PLAYGROUND_OK);
+    CHECK(quotient == 3);
+    CHECK(playground_divide(1, 0, &quotient) == PLAYGROUND_PANIC);
+    CHECK(playground_divide(1, 1, NULL) == PLAYGROUND_NULL_POINTER);
+
+    CHECK(strncmp(playground_version(), "playground-ffi", 14) == 0);
+    CHECK(playground_count_chars("caf\xc3\xa9") == 4);  /* UTF-8 */
+    CHECK(playground_count_chars("caf\xe9") == -1);      /* Latin-1 */
+    char *greeting = playground_greet("C");
+    CHECK(strcmp(greeting, "Hello, C!") == 0);
+    playground_string_free(greeting);
+
+    int32_t values[] = { 1, 2, 3, 4 };
+    int64_t sum = 0;
+    CHECK(playground_for_each(values, 4, add_to, &sum));
+    CHECK(sum == 10);
+    CHECK(!playground_for_each(values, 4, NULL, &sum));
+
+    printf("all checks passed\n");
+    return 0;
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+/// # C Program Test
+/// Compile `tests/c/main.c` with the system C compiler, link it against the
+/// shared library built by Cargo, and run it: the library is tested from C,
+/// as its users would call it.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The directory of the shared library built for the tests, which is also
+/// the directory of the test binaries (e.g. `target/debug/deps`).
+fn library_directory() -> PathBuf {
+    let test_binary: PathBuf = std::env::current_exe().unwrap();
+    test_binary.parent().unwrap().to_path_buf()
+}
+
+#[cfg(unix)]
+#[test]
+fn c_program() {
+    let manifest: &Path = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let program: PathBuf =
+        Path::new(env!("CARGO_TARGET_TMPDIR")).join("playground_ffi_c");
+    let library: PathBuf = library_directory();
+
+    let compilation = Command::new("cc")
+        .arg(manifest.join("tests/c/main.c"))
+        .arg("-I").arg(manifest.join("include"))
+        .arg("-L").arg(&library)
+        .arg("-lplayground_ffi")
+        .arg("-o").arg(&program)
+        .status();
+    match compilation {
+        Ok(status) => assert!(status.success(), "C compilation failed"),
+        Err(error) => {
+            println!("skipped: no C compiler available ({})", error);
+            return;
+        }
+    }
+
+    // Where the dynamic loader finds the library (Linux and macOS), which
+    // overrides the paths set by Cargo (which also contain older builds)
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &library)
+        .env("DYLD_LIBRARY_PATH", &library)
+        .output()
+        .unwrap();
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+// Avoid linking the standard library, except for tests (the test harness
+// needs it). Must be on top of the crate root
+#![cfg_attr(not(test), no_std)]
+
+/// # Playground No-Std
+/// A library built without the standard library (see `src/no_std.rs`), as
+/// needed for embedded systems, kernels or bootloaders. It only relies on:
+/// - `core`: the platform-independent part of `std` (primitives, `Option`,
+///   `Result`, iterators, `fmt`, ...), which needs no operating system
+/// - `alloc`: heap-allocated types (`Box`, `Vec`, `String`, ...), which need
+///   a global allocator to be provided by the final binary
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+/// ## Formatting Without Allocation
+/// `println!` and `format!` are not available, but `core::fmt` is: any type
+/// implementing `fmt::Write` can be the target of `write!`. Here, a string
+/// stored in a fixed-size buffer, which never allocates.
+pub struct FixedString<const N: usize> {
+    buffer: [u8; N],
+    length: usize,
+}
+
+impl<const N: usize> FixedString<N> {
+    pub const fn new() -> Self { FixedString { buffer: [0; N], length: 0 } }
+    pub fn as_str(&self) -> &str {
+        // Only whole `&str`s are ever copied into the buffer
+        core::str::from_utf8(&self.buffer[..self.length]).unwrap()
+    }
+}
+
+impl<const N: usize> Default for FixedString<N> {
+    fn default() -> Self { FixedString::new() }
+}
+
+impl<const N: usize> Write for FixedString<N> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        let end: usize = self.length + text.len();
+        if end > N { return Err(fmt::Error); }  // full: fail instead of grow
+        self.buffer[self.length..end].copy_from_slice(text.as_bytes());
+        self.length = end;
+        Ok(())
+    }
+}
+
+/// Format a temperature reading into a fixed buffer.
+pub fn format_reading(sensor: u8, celsius: i16) -> FixedString<32> {
+    let mut text: FixedString<32> = FixedString::new();
+    // Cannot fail: the longest reading fits, as checked while compiling
+    const LONGEST: &str = "sensor 255: -32768 C";
+    const _: () = assert!(LONGEST.len() <= 32);
+    let _ = write!(text, "sensor {}: {} C", sensor, celsius);
+    text
+}
+
+/// ## Heap Allocation with `alloc`
+/// Collections come from `alloc` instead of `std`. Only the collections that
+/// do not depend on the operating system are there: `HashMap` is missing,
+/// because its default hasher needs random numbers from the system, while
+/// `BTreeMap` is available.
+pub fn moving_average(samples: &[i16], window: usize) -> Vec<i16> {
+    samples.windows(window)
+        .map(|values| {
+            let sum: i32 = values.iter().map(|&value| value as i32).sum();
+            (sum / window as i32) as i16
+        })
+        .collect()
+}
+
+pub fn join(words: &[&str], separator: &str) -> String {
+    let mut joined: String = String::new();
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 { joined.push_str(separator); }
+        joined.push_str(word);
+    }
+    joined
+}
+
+/// ## Panic Handler
+/// Without `std`, nothing defines what happens on a panic: a `no_std`
+/// binary must provide exactly one function marked `#[panic_handler]`. On a
+/// microcontroller, it could print the message on a serial port, then halt
+/// or reset the device. Here it just spins forever.
+///
+/// A library usually leaves this choice to the binary: this one defines it
+/// to show what it looks like, which also prevents linking it into a binary
+/// using `std` (which has its own handler).
+#[cfg(not(test))]
+#[panic_handler]
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    let _message = info.message();  // where it would be reported
+    loop { core::hint::spin_loop(); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_string_formats_without_allocating() {
+        assert_eq!(format_reading(3, -12).as_str(), "sensor 3: -12 C");
+        assert_eq!(format_reading(255, i16::MIN).as_str(),
+            "sensor 255: -32768 C");
+    }
+
+    #[test]
+    fn fixed_string_rejects_overflows() {
+        let mut text: FixedString<4> = FixedString::new();
+        assert!(write!(text, "{}", 1234).is_ok());
+        assert!(write!(text, "5").is_err());
+        assert_eq!(text.as_str(), "1234");
+    }
+
+    #[test]
+    fn collections_come_from_alloc() {
+        assert_eq!(moving_average(&[1, 2, 3, 4, 5], 3), [2, 3, 4]);
+        assert_eq!(join(&["core", "alloc"], " + "), "core + alloc");
+    }
+}
\ No newline at end of file
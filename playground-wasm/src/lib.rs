@@ -0,0 +1,110 @@
+/// # Playground Wasm
+/// A library compiled to a WebAssembly module (see `src/wasm.rs` in the
+/// playground for the lesson):
+/// ```sh
+/// rustup target add wasm32-unknown-unknown
+/// cargo build -p playground-wasm --target wasm32-unknown-unknown --release
+/// ```
+/// The module (`target/wasm32-unknown-unknown/release/playground_wasm.wasm`)
+/// exports the `#[no_mangle] extern "C"` functions below, and imports
+/// `env.host_now_millis` from its host. From JavaScript:
+/// ```js
+/// const { instance } = await WebAssembly.instantiateStreaming(
+///     fetch("playground_wasm.wasm"),
+///     { env: { host_now_millis: () => Date.now() } });
+/// instance.exports.wasm_fibonacci(50);  // 12586269025n (a BigInt)
+/// ```
+/// The same code also builds natively, thanks to the shims in `platform`.
+use std::{mem, slice, str};
+
+/// ## Platform Shims
+/// `wasm32-unknown-unknown` has no operating system: no clock, no threads,
+/// no files. The standard library still compiles for it, but these APIs
+/// panic or fail at runtime. Code shared between targets hides the
+/// differences behind functions with one implementation per target.
+pub mod platform {
+    /// Milliseconds since the Unix epoch: provided by the host (e.g.
+    /// `Date.now()` in JavaScript), through an imported function.
+    #[cfg(target_arch = "wasm32")]
+    pub fn now_millis() -> f64 {
+        #[link(wasm_import_module = "env")]
+        extern "C" {
+            fn host_now_millis() -> f64;
+        }
+        // SAFETY: the host provides the import when instantiating the module.
+        unsafe { host_now_millis() }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn now_millis() -> f64 {
+        let now = std::time::SystemTime::now();
+        now.duration_since(std::time::UNIX_EPOCH).unwrap().as_millis() as f64
+    }
+
+    /// How many threads could run in parallel: a single one in the browser
+    /// (without the `atomics` proposal and web workers).
+    #[cfg(target_arch = "wasm32")]
+    pub fn parallelism() -> usize { 1 }
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn parallelism() -> usize {
+        std::thread::available_parallelism().map_or(1, |count| count.get())
+    }
+}
+
+/// ## Exported Functions
+/// Only numbers (`i32`, `i64`, `f32`, `f64`) cross the boundary of a wasm
+/// module: `u64` becomes a JavaScript `BigInt`, pointers become `i32`
+/// offsets into the linear memory of the module.
+#[no_mangle]
+pub extern "C" fn wasm_add(a: i32, b: i32) -> i32 {
+    a.wrapping_add(b)
+}
+
+#[no_mangle]
+pub extern "C" fn wasm_fibonacci(n: u32) -> u64 {
+    let (mut current, mut next): (u64, u64) = (0, 1);
+    for _ in 0..n {
+        (current, next) = (next, current.wrapping_add(next));
+    }
+    current
+}
+
+#[no_mangle]
+pub extern "C" fn wasm_parallelism() -> u32 {
+    platform::parallelism() as u32
+}
+
+/// ## Passing Data Through Linear Memory
+/// The host cannot pass a string directly: it asks the module for a buffer
+/// (`wasm_alloc`), copies the bytes into the memory of the module, calls the
+/// function with the pointer and length, then frees the buffer.
+#[no_mangle]
+pub extern "C" fn wasm_alloc(length: usize) -> *mut u8 {
+    let mut buffer: Vec<u8> = Vec::with_capacity(length);
+    let pointer: *mut u8 = buffer.as_mut_ptr();
+    mem::forget(buffer);  // owned by the host until `wasm_free`
+    pointer
+}
+
+/// # Safety
+/// `pointer` and `length` must come from a single call to `wasm_alloc`.
+#[no_mangle]
+pub unsafe extern "C" fn wasm_free(pointer: *mut u8, length: usize) {
+    drop(Vec::from_raw_parts(pointer, 0, length));
+}
+
+/// Count the vowels of a UTF-8 text, or return -1 if it is not valid UTF-8.
+///
+/// # Safety
+/// `pointer` must be valid for reads of `length` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn wasm_count_vowels(
+    pointer: *const u8, length: usize
+) -> i32 {
+    let bytes: &[u8] = slice::from_raw_parts(pointer, length);
+    match str::from_utf8(bytes) {
+        Ok(text) => {
+            text.chars().filter(|c| "aeiouAEIOU".contains(*c)).count() as i32
+        }
+        Err(_) => -1,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,259 @@
+/// # Advanced Macros
+/// Declarative macros (see macros.rs) match their whole input against one
+/// pattern. When the input is a small language of its own, with optional
+/// parts and items of different shapes, a single pattern is not enough: the
+/// input must be consumed incrementally, a few tokens at a time.
+///
+/// The techniques below are described in "The Little Book of Rust Macros".
+/// See: https://veykril.github.io/tlborm/
+fn advanced_macros() {}
+
+/// ## Internal Rules
+/// A macro can have "private" arms, by convention starting with a literal
+/// token like `@name` which users never write. They split a macro into
+/// helper steps without polluting the global namespace with more macros.
+macro_rules! describe {
+    (@kind $value: literal) => ("a literal");
+    (@kind $value: ident) => ("an identifier");
+    (@kind $($other: tt)*) => ("an expression");
+    ($($input: tt)*) => (
+        format!("`{}` is {}",
+            stringify!($($input)*), describe!(@kind $($input)*))
+    );
+}
+
+runnable!(internal_rules, {
+    let x: i32 = 1;
+    println!("{}", describe!(42));
+    println!("{}", describe!(x));
+    println!("{}", describe!(x + 1));
+});
+
+/// ## Push-Down Accumulation
+/// A macro cannot build its output piece by piece, because every expansion
+/// must be a complete expression or item. Instead, the partial output is
+/// carried along in an `accumulator` (here between `[...]`), passed to each
+/// recursive call and emitted at once in the final step.
+macro_rules! reverse_tuple {
+    // Entry point: start with an empty accumulator
+    ($($items: expr),* $(,)?) => (reverse_tuple!(@acc [] $($items),*));
+    // Move the head in front of the accumulator, then recurse on the tail
+    (@acc [$($acc: expr),*] $head: expr $(, $tail: expr)*) => (
+        reverse_tuple!(@acc [$head $(, $acc)*] $($tail),*)
+    );
+    // Nothing left: emit the accumulator
+    (@acc [$($acc: expr),*]) => (($($acc,)*));
+}
+
+runnable!(push_down_accumulation, {
+    let reversed: (char, &str, i32) = reverse_tuple!(1, "two", '3');
+    println!("reversed: {:?}", reversed);
+});
+
+/// ## TT Munchers
+/// A `tt muncher` matches a few tokens at the beginning of its input, and
+/// recurses on the rest, captured as a sequence of `tt` (token trees: any
+/// single token, or a group delimited by `()`, `[]` or `{}`). Each step can
+/// choose a different arm, so every item of the input can have a different
+/// shape.
+///
+/// `define_struct!` parses a small struct definition language, where each
+/// field can have a default value:
+/// ```
+/// define_struct! {
+///     struct Name {
+///         field: Type = default_value,
+///         field_without_default: Type,
+///     }
+/// }
+/// ```
+/// It generates the struct and an implementation of `Default`, using
+/// `Default::default()` for fields without a default value. Two accumulators
+/// collect the fields and their default values.
+macro_rules! define_struct {
+    // Entry point
+    (struct $name: ident { $($body: tt)* }) => (
+        define_struct!(@munch $name [] [] $($body)*);
+    );
+    // A field with a default value
+    (@munch $name: ident [$($fields: tt)*] [$($defaults: tt)*]
+        $field: ident : $ty: ty = $default: expr $(, $($rest: tt)*)?
+    ) => (
+        define_struct!(@munch $name
+            [$($fields)* $field: $ty,]
+            [$($defaults)* $field: $default,]
+            $($($rest)*)?
+        );
+    );
+    // A field without a default value
+    (@munch $name: ident [$($fields: tt)*] [$($defaults: tt)*]
+        $field: ident : $ty: ty $(, $($rest: tt)*)?
+    ) => (
+        define_struct!(@munch $name
+            [$($fields)* $field: $ty,]
+            [$($defaults)* $field: ::std::default::Default::default(),]
+            $($($rest)*)?
+        );
+    );
+    // All the input has been munched: emit the accumulated code
+    (@munch $name: ident [$($fields: tt)*] [$($defaults: tt)*]) => (
+        #[derive(Debug, Clone, PartialEq)]
+        struct $name { $($fields)* }
+
+        impl ::std::default::Default for $name {
+            fn default() -> Self { $name { $($defaults)* } }
+        }
+    );
+}
+
+define_struct! {
+    struct Config {
+        name: String = String::from("playground"),
+        port: u16 = 8080,
+        verbose: bool,
+        tags: Vec<&'static str>,
+        ratio: f64 = 0.5
+    }
+}
+
+runnable!(tt_munchers, {
+    let config: Config = Config::default();
+    println!("{:?}", config);
+    assert_eq!(config.port, 8080);
+    assert!(!config.verbose);
+
+    // Struct update syntax works as usual with the generated `Default`
+    let verbose: Config = Config { verbose: true, ..Config::default() };
+    println!("{:?}", verbose);
+
+    // define_struct! { struct Broken { port = 8080 } }
+    // ^ Error: no rules expected `=`
+});
+
+/// ## Recursion Limit
+/// Every recursive call is one more level of macro expansion, and the
+/// compiler stops at 128 levels by default. A muncher consuming one token
+/// per step can only handle inputs of about 128 tokens.
+macro_rules! count_recursive {
+    () => (0usize);
+    ($head: tt $($tail: tt)*) => (1usize + count_recursive!($($tail)*));
+}
+
+/// The limit can be raised for the whole crate, with an attribute at the top
+/// of the crate root (main.rs):
+/// ```
+/// #![recursion_limit = "256"]
+/// ```
+/// A better solution is to avoid deep recursion: a repetition expands all
+/// the items in a single step. Here each token is replaced by `()` (with an
+/// internal rule), and the length of the resulting array is computed.
+macro_rules! count_flat {
+    (@unit $token: tt) => (());
+    ($($tokens: tt)*) => (<[()]>::len(&[$(count_flat!(@unit $tokens)),*]));
+}
+
+runnable!(recursion_limit, {
+    println!("recursive: {}", count_recursive!(a b c d e));
+    println!("flat: {}", count_flat!(a b c d e));
+
+    // 210 tokens
+    let flat: usize = count_flat!(
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4
+        5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4
+        5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4
+        5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9
+    );
+    println!("flat (many tokens): {}", flat);
+    assert_eq!(flat, 210);
+    // count_recursive!(0 1 2 ... 9);  // with the same 210 tokens
+    // ^ Error: recursion limit reached while expanding `count_recursive!`
+    //   help: consider increasing the recursion limit by adding a
+    //         `#![recursion_limit = "256"]` attribute to your crate
+});
+
+/// ## Capstone: a State Machine DSL
+/// `state_machine!` combines the techniques above into a small language for
+/// finite state machines (see state_machines.rs for hand-written ones):
+/// ```
+/// state_machine! {
+///     states State { A, B, ... }
+///     events Event { X, Y, ... }
+///     transitions {
+///         A + X => B,
+///         ...
+///     }
+/// }
+/// ```
+/// It generates the two enums, and a `transition()` method matching on every
+/// pair (state, event). Since the match has no catch-all arm, the compiler
+/// checks that the table is complete: a missing transition is a compile
+/// error, and a duplicated one is denied as an unreachable pattern.
+macro_rules! state_machine {
+    (
+        states $state: ident { $($states: ident),+ $(,)? }
+        events $event: ident { $($events: ident),+ $(,)? }
+        transitions {
+            $($from: ident + $on: ident => $to: ident),+ $(,)?
+        }
+    ) => (
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum $state { $($states),+ }
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        enum $event { $($events),+ }
+
+        impl $state {
+            const ALL: &'static [$state] = &[$($state::$states),+];
+
+            fn transition(self, event: $event) -> $state {
+                #[deny(unreachable_patterns)]
+                match (self, event) {
+                    $(($state::$from, $event::$on) => $state::$to,)+
+                }
+            }
+
+            fn run(self, events: &[$event]) -> $state {
+                events.iter().fold(self, |state, &e| state.transition(e))
+            }
+        }
+    );
+}
+
+/// A turnstile is locked until a coin is inserted, and locks again once
+/// somebody pushes through it.
+state_machine! {
+    states Turnstile { Locked, Unlocked }
+    events Action { Coin, Push }
+    transitions {
+        Locked + Coin => Unlocked,
+        Locked + Push => Locked,
+        Unlocked + Coin => Unlocked,
+        Unlocked + Push => Locked,
+    }
+}
+
+runnable!(state_machine_dsl, {
+    let mut turnstile: Turnstile = Turnstile::Locked;
+    for action in [Action::Push, Action::Coin, Action::Coin, Action::Push] {
+        let next: Turnstile = turnstile.transition(action);
+        println!("{:?} + {:?} => {:?}", turnstile, action, next);
+        turnstile = next;
+    }
+    assert_eq!(turnstile, Turnstile::Locked);
+
+    let final_state: Turnstile =
+        Turnstile::Locked.run(&[Action::Coin, Action::Push, Action::Coin]);
+    println!("after three actions: {:?}", final_state);
+    println!("states: {:?}", Turnstile::ALL);
+
+    // Removing `Unlocked + Push => Locked` from the table:
+    // ^ Error: non-exhaustive patterns: `(Turnstile::Unlocked, Action::Push)`
+    //          not covered
+    // Adding a second `Locked + Coin => Locked` to the table:
+    // ^ Error: unreachable pattern
+    // Using an undeclared state, e.g. `Broken + Push => Locked`:
+    // ^ Error: no variant or associated item named `Broken` found for enum
+    //          `Turnstile`
+});
\ No newline at end of file
@@ -0,0 +1,222 @@
+/// # Global Allocator (~ malloc/free)
+/// Every heap allocation (`Box`, `Vec`, `String`, `Rc`, ...) goes through the
+/// `global allocator`, an implementation of the unsafe trait
+/// `std::alloc::GlobalAlloc`:
+/// - `alloc(layout)`: return a pointer to a block of `layout.size()` bytes,
+///   aligned to `layout.align()` (see layout.rs), or null on failure
+/// - `dealloc(pointer, layout)`: free a block returned by `alloc`
+/// - `realloc` and `alloc_zeroed`: optional, with default implementations
+///
+/// By default, it is `std::alloc::System` (`malloc`/`free` on Unix). A
+/// program can replace it with `#[global_allocator]`, on a `static` of any
+/// type implementing `GlobalAlloc` (only one per program).
+///
+/// The allocator below wraps `System` and counts allocations. It is only
+/// installed with `--features count-allocations` (see features.rs), which
+/// also makes the `verbose-harness` report allocations (see util.rs).
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Write;
+use std::ops::Sub;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn allocator() {}
+
+/// ## Counting Allocator
+/// Allocation statistics, counted both for the whole program and for each
+/// thread (tests run in parallel threads, so the global counters mix the
+/// allocations of every test).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AllocationStats {
+    pub allocations: usize,
+    pub reallocations: usize,
+    pub deallocations: usize,
+    pub bytes: usize,  // total bytes requested, never decreased by frees
+}
+
+impl AllocationStats {
+    const ZERO: AllocationStats = AllocationStats {
+        allocations: 0, reallocations: 0, deallocations: 0, bytes: 0,
+    };
+}
+
+impl Sub for AllocationStats {
+    type Output = AllocationStats;
+    fn sub(self, earlier: AllocationStats) -> AllocationStats {
+        AllocationStats {
+            allocations: self.allocations - earlier.allocations,
+            reallocations: self.reallocations - earlier.reallocations,
+            deallocations: self.deallocations - earlier.deallocations,
+            bytes: self.bytes - earlier.bytes,
+        }
+    }
+}
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static DEALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // A `const` initializer without destructor: accessing it never allocates,
+    // which would recursively call the allocator
+    static THREAD_STATS: Cell<AllocationStats> =
+        const { Cell::new(AllocationStats::ZERO) };
+}
+
+/// Update the counters of the current thread, if it still has them (they
+/// are destroyed when the thread exits, while it may still free memory).
+fn record(update: impl FnOnce(&mut AllocationStats)) {
+    let _ = THREAD_STATS.try_with(|stats| {
+        let mut current: AllocationStats = stats.get();
+        update(&mut current);
+        stats.set(current);
+    });
+}
+
+pub struct CountingAllocator;
+
+// SAFETY: every call is forwarded to `System` with the same arguments, so
+// the blocks returned satisfy the contract of `GlobalAlloc`. Counting only
+// touches atomics and a thread-local `Cell`, and never allocates.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        record(|stats| {
+            stats.allocations += 1;
+            stats.bytes += layout.size();
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        record(|stats| stats.deallocations += 1);
+        System.dealloc(pointer, layout)
+    }
+
+    /// Forwarded to `System`, which may grow the block in place, instead of
+    /// the default implementation (allocate, copy, free)
+    unsafe fn realloc(
+        &self, pointer: *mut u8, layout: Layout, new_size: usize
+    ) -> *mut u8 {
+        REALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size, Ordering::Relaxed);
+        record(|stats| {
+            stats.reallocations += 1;
+            stats.bytes += new_size;
+        });
+        System.realloc(pointer, layout, new_size)
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Statistics of the whole program.
+pub fn global_stats() -> AllocationStats {
+    AllocationStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        reallocations: REALLOCATIONS.load(Ordering::Relaxed),
+        deallocations: DEALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// Statistics of the current thread (always zero unless the allocator is
+/// installed).
+pub fn thread_stats() -> AllocationStats {
+    THREAD_STATS.try_with(Cell::get).unwrap_or_default()
+}
+
+/// Count the allocations made by the current thread while running `f`.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocationStats) {
+    let before: AllocationStats = thread_stats();
+    let result: T = f();
+    (result, thread_stats() - before)
+}
+
+/// Only check the counts when they are actually counted.
+fn expect(condition: bool, message: &str) {
+    if cfg!(feature = "count-allocations") {
+        assert!(condition, "{}", message);
+    }
+}
+
+runnable!(counting_allocator, {
+    if !cfg!(feature = "count-allocations") {
+        println!("Run with `--features count-allocations` to count");
+    }
+    let (_, kept) = measure(|| Box::new(42u64));  // dropped after `measure`
+    let (_, dropped) = measure(|| *Box::new(42u64));
+    println!("Box::new(42u64), returned: {:?}", kept);
+    println!("Box::new(42u64), dropped:  {:?}", dropped);
+    expect(kept.allocations == 1 && kept.bytes == 8, "one 8-byte block");
+    expect(kept.deallocations == 0, "freed after the measure");
+    expect(dropped.deallocations == 1, "freed during the measure");
+    println!("whole program so far: {:?}", global_stats());
+});
+
+/// ## Vec Growth
+/// A `Vec` doubles its capacity when it is full, so pushing `n` elements one
+/// by one reallocates about `log2(n)` times. Reserving the capacity upfront
+/// needs a single allocation.
+runnable!(vec_growth, {
+    let (_, growing) = measure(|| {
+        let mut numbers: Vec<u32> = Vec::new();
+        for i in 0..1000 { numbers.push(i); }
+        numbers
+    });
+    let (_, reserved) = measure(|| {
+        let mut numbers: Vec<u32> = Vec::with_capacity(1000);
+        for i in 0..1000 { numbers.push(i); }
+        numbers
+    });
+    println!("Vec::new + 1000 pushes:           {:?}", growing);
+    println!("Vec::with_capacity + 1000 pushes: {:?}", reserved);
+    expect(growing.reallocations >= 5, "repeated growth");
+    expect(reserved.allocations == 1 && reserved.reallocations == 0,
+        "a single allocation");
+});
+
+/// ## String Formatting
+/// `format!` allocates a new `String` every time, while `write!` into a
+/// reused `String` only allocates when it has to grow.
+runnable!(string_formatting, {
+    let (_, formatted) = measure(|| {
+        (0..100).map(|i| format!("item {}", i).len()).sum::<usize>()
+    });
+    let (_, written) = measure(|| {
+        let mut buffer: String = String::with_capacity(32);
+        let mut total: usize = 0;
+        for i in 0..100 {
+            buffer.clear();
+            write!(buffer, "item {}", i).unwrap();
+            total += buffer.len();
+        }
+        total
+    });
+    println!("100 x format!:           {:?}", formatted);
+    println!("100 x write! into buffer: {:?}", written);
+    expect(formatted.allocations >= 100, "one string per format!");
+    expect(written.allocations == 1, "only the buffer");
+});
+
+/// ## Box Churn
+/// Allocating and freeing many short-lived boxes costs a call to the
+/// allocator each time. Keeping values on the stack, or reusing a
+/// collection, avoids the churn.
+runnable!(box_churn, {
+    let (boxed_sum, churn) = measure(|| {
+        (0..1000u64).map(|i| *Box::new(i)).sum::<u64>()
+    });
+    let (stack_sum, stack) = measure(|| (0..1000u64).sum::<u64>());
+    assert_eq!(boxed_sum, stack_sum);
+    println!("1000 boxes: {:?}", churn);
+    println!("no boxes:   {:?}", stack);
+    expect(churn.allocations == 1000 && churn.deallocations == 1000,
+        "one allocation and one free per box");
+    expect(stack == AllocationStats::default(), "no allocation at all");
+});
\ No newline at end of file
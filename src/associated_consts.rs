@@ -0,0 +1,117 @@
+/// # Associated Constants
+/// Besides methods and types (see traits.rs and generics.rs), a trait can
+/// declare `associated constants`: values that every implementation must
+/// provide (or may override, if the trait gives a default).
+///
+/// Unlike methods, constants are evaluated at compile time, so they can
+/// configure generic code without any runtime cost.
+fn associated_consts() {}
+
+/// ## Declaring Associated Constants
+/// A checksum algorithm processes data in blocks: each algorithm names
+/// itself, chooses its block size and its initial state.
+trait Checksum {
+    const NAME: &'static str;
+    const BLOCK_SIZE: usize;
+    const INITIAL: u32 = 0;  // default value, may be overridden
+    fn update(state: u32, block: &[u8]) -> u32;
+}
+
+/// XOR of the data read as little-endian 32-bit words
+struct Xor32;
+impl Checksum for Xor32 {
+    const NAME: &'static str = "XOR-32";
+    const BLOCK_SIZE: usize = 4;
+    fn update(state: u32, block: &[u8]) -> u32 {
+        let mut word: [u8; 4] = [0; 4];
+        word[..block.len()].copy_from_slice(block);
+        state ^ u32::from_le_bytes(word)
+    }
+}
+
+/// Adler-32 (used by zlib): two sums modulo 65521. The modulo can be delayed
+/// for up to 5552 bytes without overflowing, which makes it a natural block.
+struct Adler32;
+impl Checksum for Adler32 {
+    const NAME: &'static str = "Adler-32";
+    const BLOCK_SIZE: usize = 5552;
+    const INITIAL: u32 = 1;
+    fn update(state: u32, block: &[u8]) -> u32 {
+        const MODULO: u32 = 65521;
+        let (mut a, mut b): (u32, u32) = (state & 0xFFFF, state >> 16);
+        for &byte in block {
+            a += byte as u32;
+            b += a;
+        }
+        ((b % MODULO) << 16) | (a % MODULO)
+    }
+}
+
+/// ## Reading Constants in Generic Code
+/// Generic code reads the constants through the type parameter. After
+/// monomorphization (see generics.rs), each copy of the function sees the
+/// constants of its own type, as literals.
+fn checksum<C: Checksum>(data: &[u8]) -> u32 {
+    data.chunks(C::BLOCK_SIZE).fold(C::INITIAL, C::update)
+}
+
+fn report<C: Checksum>(data: &[u8]) -> String {
+    format!("{:>8} (blocks of {:>4} bytes): {:#010x}",
+        C::NAME, C::BLOCK_SIZE, checksum::<C>(data))
+}
+
+runnable!(reading_associated_consts, {
+    let data: &[u8] = b"Wikipedia";
+    println!("{}", report::<Xor32>(data));
+    println!("{}", report::<Adler32>(data));
+    assert_eq!(checksum::<Adler32>(data), 0x11E60398);
+
+    // Constants of concrete types can be used in any constant expression
+    const LONGEST_NAME: usize = if Xor32::NAME.len() > Adler32::NAME.len() {
+        Xor32::NAME.len()
+    } else {
+        Adler32::NAME.len()
+    };
+    println!("longest name: {} characters", LONGEST_NAME);
+});
+
+/// ## Compile-Time Checks
+/// An inline `const { ... }` block can refer to the constants of a type
+/// parameter, and is evaluated at compile time, once per instantiation. A
+/// failing assertion becomes a compilation error for that type only.
+struct Broken;
+impl Checksum for Broken {
+    const NAME: &'static str = "broken";
+    const BLOCK_SIZE: usize = 0;
+    fn update(state: u32, block: &[u8]) -> u32 { state }
+}
+
+fn checked_checksum<C: Checksum>(data: &[u8]) -> u32 {
+    const { assert!(C::BLOCK_SIZE > 0, "block size must be positive") };
+    checksum::<C>(data)
+}
+
+runnable!(compile_time_checks, {
+    println!("{:#x}", checked_checksum::<Xor32>(b"data"));
+    // checked_checksum::<Broken>(b"data");
+    // ^ Error: evaluation panicked: block size must be positive
+    //   (without the check, `chunks(0)` would panic at runtime)
+});
+
+/// ## Limitations
+/// Associated constants of a type parameter cannot (yet) be used in types,
+/// such as the length of an array. This requires the unstable
+/// `generic_const_exprs` feature; a `Vec` is the usual workaround.
+fn buffer<C: Checksum>() -> Vec<u8> {
+    // let buffer: [u8; C::BLOCK_SIZE] = [0; C::BLOCK_SIZE];
+    // ^ Error: generic parameters may not be used in const operations
+    vec![0; C::BLOCK_SIZE]
+}
+
+runnable!(limitations, {
+    println!("{} buffer: {} bytes", Xor32::NAME, buffer::<Xor32>().len());
+    // Traits with associated constants cannot be used as trait objects
+    // (see object_safety.rs)
+    // let algorithm: &dyn Checksum;
+    // ^ Error: the trait `Checksum` is not dyn compatible
+});
\ No newline at end of file
@@ -0,0 +1,151 @@
+/// # Async/Await
+/// An `async fn` does not run when called. Instead, it returns a `Future`: a
+/// value representing a computation that will complete at some point.
+///
+/// A `Future` makes progress only when it is `polled`. Polling is the job of
+/// an `executor`, which repeatedly calls `Future::poll` until the future
+/// returns `Poll::Ready(output)`. When a future cannot make progress, it
+/// returns `Poll::Pending` and promises to notify the executor (through a
+/// `Waker`) when it is worth polling it again.
+///
+/// Rust does not ship an executor in `std`: here we build a minimal one, so
+/// that the async lessons only depend on the standard library.
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread::{self, Thread};
+
+fn async_await() {}
+
+/// ## Wakers
+/// A `Waker` is a handle that a future uses to tell its executor "poll me
+/// again". Internally, it is a data pointer plus a `RawWakerVTable`: a table
+/// of functions telling how to clone, wake and drop that data pointer.
+///
+/// Our executor runs on a single thread and parks it while the future is
+/// pending, so waking simply means unparking that thread.
+const VTABLE: RawWakerVTable = RawWakerVTable::new(
+    clone_waker,
+    wake,
+    wake_by_ref,
+    drop_waker,
+);
+
+// The data pointer is an `Arc<Thread>` turned into a raw pointer
+fn raw_waker(thread: Arc<Thread>) -> RawWaker {
+    RawWaker::new(Arc::into_raw(thread) as *const (), &VTABLE)
+}
+unsafe fn clone_waker(data: *const ()) -> RawWaker {
+    // SAFETY: `data` was created by `Arc::into_raw` in `raw_waker`
+    Arc::increment_strong_count(data as *const Thread);
+    RawWaker::new(data, &VTABLE)
+}
+unsafe fn wake(data: *const ()) {
+    // SAFETY: `data` was created by `Arc::into_raw`; we consume that count
+    let thread: Arc<Thread> = Arc::from_raw(data as *const Thread);
+    thread.unpark();
+}
+unsafe fn wake_by_ref(data: *const ()) {
+    // SAFETY: `data` is a valid `Thread` for as long as the waker lives
+    (*(data as *const Thread)).unpark();
+}
+unsafe fn drop_waker(data: *const ()) {
+    // SAFETY: `data` was created by `Arc::into_raw`; we release that count
+    drop(Arc::from_raw(data as *const Thread));
+}
+
+/// ## Executor
+/// `block_on` runs a future to completion on the current thread:
+/// 1. the future is pinned, because `poll` requires `Pin<&mut Self>` (see
+///    pinning.rs)
+/// 2. the future is polled with a `Waker` that unparks the current thread
+/// 3. while the future is pending, the current thread is parked
+///
+/// Note: `thread::park` may also return spuriously, which only causes an
+///       extra (harmless) poll.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future: Pin<Box<F>> = Box::pin(future);
+    let thread: Arc<Thread> = Arc::new(thread::current());
+    // SAFETY: `VTABLE` upholds the `RawWaker` contract for `Arc<Thread>`
+    let waker: Waker = unsafe { Waker::from_raw(raw_waker(thread)) };
+    let mut context: Context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// ## Async Functions
+/// An `async fn` returning `T` is sugar for a function returning
+/// `impl Future<Output = T>`. Inside an async context, `.await` suspends the
+/// current future until the awaited one is ready.
+async fn answer() -> u32 { 42 }
+
+async fn double_answer() -> u32 {
+    let x: u32 = answer().await;
+    x * 2
+}
+
+runnable!(async_functions, {
+    let future = double_answer();
+    // <-- Here nothing has been computed yet: futures are lazy
+
+    // let x: u32 = double_answer().await;
+    // ^ Error: `await` is only allowed inside `async` functions and blocks
+    let x: u32 = block_on(future);
+    println!("double_answer: {}", x);
+    assert_eq!(x, 84);
+});
+
+/// ## Async Blocks
+/// Similarly to closures, `async` blocks create anonymous futures, capturing
+/// variables from their scope (use `async move` to capture by value).
+runnable!(async_blocks, {
+    let name: String = String::from("Ferris");
+    let greeting = async move {
+        let answer: u32 = answer().await;
+        format!("Hello {}, the answer is {}", name, answer)
+    };
+    // println!("{}", name);
+    // ^ Error: `name` has been moved into the async block
+    println!("{}", block_on(greeting));
+});
+
+/// ## Joining Futures
+/// Awaiting futures one after the other runs them sequentially. To make
+/// progress on more futures at the same time, they can be `joined`: the
+/// joined future polls each of them until all of them are ready.
+///
+/// Note: this is concurrency, not parallelism. Everything still runs on the
+///       single thread of the executor.
+pub(crate) async fn join<A, B>(a: A, b: B) -> (A::Output, B::Output)
+where A: Future, B: Future {
+    let (mut a, mut b) = (Box::pin(a), Box::pin(b));
+    let (mut a_output, mut b_output) = (None, None);
+    std::future::poll_fn(move |context: &mut Context| {
+        if a_output.is_none() {
+            if let Poll::Ready(output) = a.as_mut().poll(context) {
+                a_output = Some(output);
+            }
+        }
+        if b_output.is_none() {
+            if let Poll::Ready(output) = b.as_mut().poll(context) {
+                b_output = Some(output);
+            }
+        }
+        if a_output.is_some() && b_output.is_some() {
+            Poll::Ready((a_output.take().unwrap(), b_output.take().unwrap()))
+        } else {
+            Poll::Pending
+        }
+    }).await
+}
+
+runnable!(joining_futures, {
+    let (x, y): (u32, u32) = block_on(join(answer(), double_answer()));
+    println!("joined: x={} y={}", x, y);
+    assert_eq!((x, y), (42, 84));
+});
\ No newline at end of file
@@ -0,0 +1,137 @@
+/// # Async with Tokio
+/// The hand-rolled executor in async_await.rs runs one future on one thread,
+/// and the reactor in reactor.rs only knows about timers. Real applications
+/// use a `runtime` such as `tokio`, which provides:
+/// - a multi-threaded, work-stealing executor for many concurrent `tasks`
+/// - a reactor for timers, sockets, files, signals, ...
+/// - async versions of the std APIs (`tokio::net`, `tokio::fs`, ...)
+///
+/// This lesson is only compiled with the `tokio` feature enabled:
+/// `cargo test --features tokio async_tokio`.
+///
+/// Note: futures are runtime-agnostic, but the I/O types of a runtime only
+///       work on that runtime (e.g. a `tokio::net::TcpStream` needs the tokio
+///       reactor to be woken). This is why `block_on` cannot drive them.
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+fn async_tokio() {}
+
+/// ## Tokio Tests
+/// `#[tokio::test]` turns an `async fn` into a test, by wrapping its body in
+/// a freshly created runtime (single-threaded by default).
+#[cfg(test)]
+#[tokio::test]
+async fn tokio_test() {
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    println!("slept on the tokio runtime");
+}
+
+/// ## Runtimes
+/// Outside of `#[tokio::main]` and `#[tokio::test]`, a runtime can be built
+/// explicitly. `block_on` plays the same role as our own `block_on`.
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+/// ## Spawning Tasks
+/// `tokio::spawn` hands a future to the runtime, which runs it concurrently
+/// (and possibly in parallel, on another worker thread). It returns a
+/// `JoinHandle`, which is itself a future resolving to the task output.
+///
+/// Since a task can move across threads, its future must be `Send + 'static`.
+runnable!(spawning_tasks, {
+    let total: u64 = runtime().block_on(async {
+        let handles: Vec<tokio::task::JoinHandle<u64>> = (1..=4)
+            .map(|i| tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(5 * i)).await;
+                i * 10
+            }))
+            .collect();
+        let mut total: u64 = 0;
+        for handle in handles { total += handle.await.unwrap(); }
+        total
+    });
+    println!("total: {}", total);
+    assert_eq!(total, 100);
+
+    // let rc = std::rc::Rc::new(0);
+    // tokio::spawn(async move { println!("{}", rc); });
+    // ^ Error: `Rc<i32>` cannot be sent between threads safely
+});
+
+/// ## Selecting
+/// `tokio::select!` waits on many futures at the same time, running the branch
+/// of the first one that completes. The other futures are dropped (i.e.
+/// cancelled): a future that is not polled anymore simply stops running.
+runnable!(selecting, {
+    let winner: &str = runtime().block_on(async {
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(50)) => "slow",
+            _ = tokio::time::sleep(Duration::from_millis(5)) => "fast",
+        }
+    });
+    println!("winner: {}", winner);
+    assert_eq!(winner, "fast");
+
+    /// A common use case is adding a timeout to an operation.
+    /// Note: tokio timers must be created inside the runtime, hence the
+    ///       `async` block.
+    let result = runtime().block_on(async {
+        tokio::time::timeout(
+            Duration::from_millis(5),
+            tokio::time::sleep(Duration::from_millis(50)),
+        ).await
+    });
+    println!("timeout: {:?}", result);
+    assert!(result.is_err());
+});
+
+/// ## Async TCP Echo
+/// An echo server accepting connections in a loop, spawning one task per
+/// connection. With blocking I/O, each connection would need its own thread;
+/// here, thousands of connections can share the runtime worker threads,
+/// because a task waiting for data is just a pending future.
+async fn echo_server(listener: TcpListener) {
+    loop {
+        let (mut socket, _) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(_) => return,
+        };
+        tokio::spawn(async move {
+            let mut buffer: [u8; 1024] = [0; 1024];
+            loop {
+                match socket.read(&mut buffer).await {
+                    Ok(0) | Err(_) => return,  // connection closed
+                    Ok(n) => {
+                        if socket.write_all(&buffer[..n]).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+runnable!(tcp_echo, {
+    let reply: String = runtime().block_on(async {
+        let listener: TcpListener =
+            TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(echo_server(listener));
+
+        let mut client: TcpStream = TcpStream::connect(address).await.unwrap();
+        client.write_all(b"hello tokio").await.unwrap();
+        let mut buffer: [u8; 11] = [0; 11];
+        client.read_exact(&mut buffer).await.unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    });
+    println!("echo: {}", reply);
+    assert_eq!(reply, "hello tokio");
+});
\ No newline at end of file
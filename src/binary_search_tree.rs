@@ -0,0 +1,164 @@
+/// # Binary Search Trees (~ Haskell algebraic data types)
+/// A tree is either empty, or a node holding a value between two subtrees:
+/// a recursive enum. This lesson combines recursive types, `Box`, recursion
+/// and pattern matching into a small sorted set.
+fn binary_search_tree() {}
+
+/// ## Recursive Types Need Indirection
+/// The size of an enum is the size of its largest variant. If a variant
+/// contained a `Tree` directly, a `Tree` would contain a `Tree`, which
+/// contains a `Tree`...: its size would be infinite.
+/// ```
+/// enum Tree<T> { Leaf, Node(Tree<T>, T, Tree<T>) }
+/// // ^ Error: recursive type `Tree` has infinite size
+/// //   help: insert some indirection (e.g., a `Box`, `Rc`, or `&`) to break
+/// //         the cycle
+/// ```
+/// A `Box` has the size of a pointer, whatever it points to.
+#[derive(Debug, PartialEq)]
+pub enum Tree<T> {
+    Leaf,
+    Node(Box<Tree<T>>, T, Box<Tree<T>>),
+}
+
+impl<T: Ord> Tree<T> {
+    pub fn new() -> Tree<T> { Tree::Leaf }
+
+    fn node(left: Tree<T>, value: T, right: Tree<T>) -> Tree<T> {
+        Tree::Node(Box::new(left), value, Box::new(right))
+    }
+
+    /// ## Insertion
+    /// Recursion follows the shape of the type: a `Leaf` becomes a node, a
+    /// node forwards the value to one of its subtrees. Matching on `self`
+    /// (a `&mut Tree<T>`) binds `left`, `value` and `right` as mutable
+    /// references (see the binding modes in pattern_matching.rs).
+    ///
+    /// Returns `false` if the value was already in the tree.
+    pub fn insert(&mut self, new: T) -> bool {
+        match self {
+            Tree::Leaf => {
+                *self = Tree::node(Tree::Leaf, new, Tree::Leaf);
+                true
+            }
+            Tree::Node(left, value, right) => match new.cmp(value) {
+                std::cmp::Ordering::Less => left.insert(new),
+                std::cmp::Ordering::Greater => right.insert(new),
+                std::cmp::Ordering::Equal => false,
+            },
+        }
+    }
+
+    /// ## Lookup
+    /// The same recursion, on a shared reference.
+    pub fn contains(&self, target: &T) -> bool {
+        match self {
+            Tree::Leaf => false,
+            Tree::Node(left, value, right) => match target.cmp(value) {
+                std::cmp::Ordering::Less => left.contains(target),
+                std::cmp::Ordering::Greater => right.contains(target),
+                std::cmp::Ordering::Equal => true,
+            },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Tree::Leaf => 0,
+            Tree::Node(left, _, right) => left.len() + 1 + right.len(),
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        match self {
+            Tree::Leaf => 0,
+            Tree::Node(left, _, right) => 1 + left.depth().max(right.depth()),
+        }
+    }
+}
+
+runnable!(insertion_and_lookup, {
+    let mut tree: Tree<u32> = Tree::new();
+    for value in [5, 3, 8, 1, 4, 9] {
+        assert!(tree.insert(value));
+    }
+    assert!(!tree.insert(4));                   // already there
+    assert!(tree.contains(&8));
+    assert!(!tree.contains(&7));
+    assert_eq!((tree.len(), tree.depth()), (6, 3));
+
+    // Pattern matching looks through the boxes with `deref` (`**left`)
+    if let Tree::Node(left, root, _) = &tree {
+        assert_eq!(*root, 5);
+        assert!(matches!(**left, Tree::Node(_, 3, _)));
+    }
+});
+
+/// ## In-Order Iteration with an Explicit Stack
+/// A recursive traversal cannot pause between two values, but an iterator
+/// must. The iterator keeps the path of nodes still to visit in a `Vec`:
+/// - start by pushing the leftmost path from the root
+/// - on `next`, pop a node, yield its value, then push the leftmost path
+///   of its right subtree
+///
+/// The stack holds at most `depth` references into the tree, which stays
+/// borrowed while iterating.
+pub struct InOrder<'a, T> {
+    stack: Vec<(&'a T, &'a Tree<T>)>,   // a value, and its right subtree
+}
+
+impl<'a, T> InOrder<'a, T> {
+    fn push_leftmost(&mut self, mut tree: &'a Tree<T>) {
+        while let Tree::Node(left, value, right) = tree {
+            self.stack.push((value, right));
+            tree = left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for InOrder<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        let (value, right): (&'a T, &'a Tree<T>) = self.stack.pop()?;
+        self.push_leftmost(right);
+        Some(value)
+    }
+}
+
+impl<T> Tree<T> {
+    pub fn iter(&self) -> InOrder<'_, T> {
+        let mut iterator: InOrder<T> = InOrder { stack: Vec::new() };
+        iterator.push_leftmost(self);
+        iterator
+    }
+}
+
+runnable!(in_order_iteration, {
+    let mut tree: Tree<&str> = Tree::new();
+    for word in ["m", "c", "x", "a", "e", "z"] {
+        tree.insert(word);
+    }
+    let sorted: Vec<&&str> = tree.iter().collect();
+    assert_eq!(sorted, [&"a", &"c", &"e", &"m", &"x", &"z"]);
+    assert_eq!(tree.iter().count(), tree.len());
+
+    // Iterators compose as usual
+    let after_d: Vec<&str> =
+        tree.iter().copied().skip_while(|word| *word < "d").collect();
+    assert_eq!(after_d, ["e", "m", "x", "z"]);
+});
+
+/// ## Degenerate Trees
+/// Without rebalancing (as in `BTreeMap`, or red-black trees), inserting
+/// sorted values builds a list: operations become O(n) instead of O(log n),
+/// and the recursion as deep as the tree (see the stack depth in
+/// recursion.rs). The iterator, using a heap-allocated stack, is not
+/// affected.
+runnable!(degenerate_trees, {
+    let mut tree: Tree<u32> = Tree::new();
+    for value in 0..500 {
+        tree.insert(value);
+    }
+    assert_eq!(tree.depth(), 500);
+    assert!(tree.iter().copied().eq(0..500));
+});
\ No newline at end of file
@@ -0,0 +1,101 @@
+/// # Benchmark Pitfalls and `black_box` (~ the optimizer is watching)
+/// A benchmark asks "how long does this take?", but the optimizer only
+/// promises that the program behaves the same: computations whose results
+/// are never used may be deleted, computations on known inputs may run at
+/// compile time, and repeated computations may run once. A benchmark which
+/// falls for it reports impossibly fast timings, for work which never ran.
+///
+/// `std::hint::black_box(value)` returns `value` unchanged, but the
+/// optimizer must assume that it reads and modifies it in any way: the
+/// value has to be computed, and its content is unknown afterwards.
+///
+/// The pitfalls only appear with optimizations: run this lesson with
+/// `cargo test --release black_box -- --nocapture`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+fn black_box_lesson() {}
+
+/// ## The Workload
+/// A hash of its input, mixed over many rounds: the loop has no closed form,
+/// so the optimizer cannot replace it with a formula, and its cost is real.
+pub fn mix(mut value: u64) -> u64 {
+    for _ in 0..100 {
+        value ^= value >> 33;
+        value = value.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    }
+    value
+}
+
+/// Time `iterations` runs of `body`, per iteration. A hand-written loop:
+/// `benchmark!` already protects against the pitfalls below.
+fn time_per_iteration(iterations: u32, mut body: impl FnMut())
+    -> Duration
+{
+    let start: Instant = Instant::now();
+    for _ in 0..iterations { body(); }
+    start.elapsed() / iterations
+}
+
+runnable!(the_workload, {
+    assert_eq!(mix(0), 0);
+    assert_ne!(mix(1), mix(2));
+    assert_eq!(mix(42), mix(42));
+});
+
+/// ## Pitfall 1: Unused Results
+/// `mix` has no side effect, and always terminates: once it is inlined in
+/// the timing loop, the optimizer sees that its result is discarded, and
+/// deletes the call. The "discarded" loop then takes no time at all.
+/// Wrapping the result in `black_box` forces the computation to happen.
+///
+/// Benchmarks sometimes look right by accident: a function marked
+/// `#[inline(never)]` (see inlining.rs) may keep its calls, and a loop
+/// which may never terminate cannot be deleted, because an infinite loop is
+/// a valid behaviour in Rust. Neither is a guarantee.
+runnable!(unused_results, {
+    let values: Vec<u64> = (1..1_000).collect();
+    let discarded = time_per_iteration(100, || {
+        for value in &values { let _ = mix(*value); }
+    });
+    let used = time_per_iteration(100, || {
+        for value in &values { black_box(mix(*value)); }
+    });
+    println!("discarded: {:?}, black-boxed: {:?}", discarded, used);
+});
+
+/// ## Pitfall 2: Known Inputs
+/// With a constant input, the optimizer may run the whole computation at
+/// compile time (`constant folding`), or compute it once, outside of the
+/// timing loop (`loop-invariant code motion`): the benchmark then measures
+/// an empty loop. Passing the input through `black_box` hides its value.
+runnable!(known_inputs, {
+    let constant = time_per_iteration(100_000, || {
+        black_box(mix(42));
+    });
+    let hidden = time_per_iteration(100_000, || {
+        black_box(mix(black_box(42)));
+    });
+    println!("constant input: {:?}, black-boxed: {:?}", constant, hidden);
+});
+
+/// ## `black_box` Is Not a Cure-All
+/// - It is a hint: the documentation promises a "best effort", not a
+///   guarantee, so surprising timings still deserve a look at the generated
+///   code (see bounds_checks.rs).
+/// - Too much of it also distorts: hiding every intermediate value stops
+///   optimizations which real code would get. Hide the inputs and the
+///   output, not the work in between.
+/// - It does not fix the other pitfalls: unoptimized builds (see
+///   zero_cost.rs), cold caches on the first iteration, noisy machines, and
+///   single measurements. Tools such as `criterion` repeat the measurements
+///   and report their spread.
+///
+/// ## In the Harness
+/// `benchmark!` (see util.rs) passes every result through `black_box`, so
+/// its expressions are never deleted. The inputs are up to the caller:
+/// `benchmark!("label", 100, mix(black_box(42)))`.
+runnable!(in_the_harness, {
+    let average: Duration = benchmark!("mix", 10_000, mix(black_box(42)));
+    assert!(average > Duration::ZERO);
+});
@@ -0,0 +1,137 @@
+/// # Blanket Implementations
+/// A `blanket implementation` implements a trait for every type satisfying
+/// some bounds (`impl<T: Bound> Trait for T`), instead of one type at a time.
+/// The standard library uses them extensively, e.g.:
+/// - `impl<T: Display> ToString for T`: every printable type has `to_string`
+/// - `impl<T, U: From<T>> Into<U> for T`: every `From` gives an `Into`
+/// - `impl<T: ?Sized + Trait> Trait for &T`: references forward the trait
+use std::fmt::{Debug, Display};
+
+fn blanket_impls() {}
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl<T: Display> Describe for T {
+    fn describe(&self) -> String {
+        format!("`{}` ({} bytes)", self, std::mem::size_of_val(self))
+    }
+}
+
+runnable!(blanket_implementations, {
+    // Any `Display` type gets `describe`, including types defined later on
+    println!("{}", 42.describe());
+    println!("{}", "text".describe());
+    println!("{}", 'x'.describe());
+
+    struct Celsius(f64);
+    impl Display for Celsius {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "{}°C", self.0)
+        }
+    }
+    println!("{}", Celsius(21.5).describe());
+});
+
+/// ## Coherence and Overlap
+/// For each pair (trait, type) there must be at most one implementation, so
+/// that method calls are never ambiguous. This property is called
+/// `coherence`: the compiler rejects implementations that may `overlap`.
+///
+/// Since `i32: Display`, a specific implementation would overlap with the
+/// blanket one:
+/// ```
+/// impl Describe for i32 { ... }
+/// // ^ Error: conflicting implementations of trait `Describe` for type `i32`
+/// ```
+/// Two blanket implementations overlap if a type can satisfy both bounds,
+/// even if no such type exists yet:
+/// ```
+/// impl<T: Debug> Describe for T { ... }
+/// // ^ Error: conflicting implementations of trait `Describe`
+/// ```
+/// Specialization (choosing the most specific implementation, like C++
+/// template specialization) would allow this, but it is still unstable.
+///
+/// Types which are known not to satisfy the bounds can still be implemented
+/// individually: the compiler knows every implementation of a local type.
+#[derive(Debug)]
+struct Matrix([[u8; 2]; 2]);
+
+impl Describe for Matrix {  // `Matrix` is not `Display`: no overlap
+    fn describe(&self) -> String { format!("matrix {:?}", self.0) }
+}
+
+runnable!(coherence, {
+    println!("{}", Matrix([[1, 0], [0, 1]]).describe());
+    // Adding `impl Display for Matrix` would now be an error, because
+    // `Matrix` would get two implementations of `Describe`
+});
+
+/// ## How `Into` Works
+/// The standard library only asks to implement `From`, because of this
+/// blanket implementation:
+/// ```
+/// impl<T, U> Into<U> for T where U: From<T> {
+///     fn into(self) -> U { U::from(self) }
+/// }
+/// ```
+/// The same can be reproduced with custom traits:
+trait MyFrom<T> { fn my_from(value: T) -> Self; }
+trait MyInto<U> { fn my_into(self) -> U; }
+
+impl<T, U: MyFrom<T>> MyInto<U> for T {
+    fn my_into(self) -> U { U::my_from(self) }
+}
+
+#[derive(Debug)]
+struct Meters(f64);
+impl MyFrom<f64> for Meters {
+    fn my_from(value: f64) -> Self { Meters(value) }
+}
+impl From<f64> for Meters {
+    fn from(value: f64) -> Self { Meters(value) }
+}
+
+runnable!(how_into_works, {
+    let custom: Meters = 3.0.my_into();   // from `MyFrom<f64> for Meters`
+    let standard: Meters = 4.0.into();    // from `From<f64> for Meters`
+    println!("custom: {:?} standard: {:?}", custom, standard);
+
+    // This is also why `Into` should not be implemented directly: an
+    // `impl Into<f64> for Meters` would not provide `From<Meters> for f64`,
+    // and `impl From<Meters> for f64` would provide both
+});
+
+/// ## Locking Out Downstream Crates
+/// A blanket implementation is a commitment: once a library writes
+/// `impl<T: Display> Describe for T`, no downstream crate can implement
+/// `Describe` for its own `Display` types, e.g. to customize the description.
+/// ```
+/// // In a crate depending on this one
+/// struct Point(i32, i32);
+/// impl Display for Point { ... }
+/// impl Describe for Point { ... }
+/// // ^ Error: conflicting implementations of trait `Describe` for type
+/// //          `Point`: upstream crate implements it for `T: Display`
+/// ```
+/// Adding a blanket implementation to an existing trait is also a breaking
+/// change, as it may overlap with implementations written by users.
+///
+/// When customization matters, a common alternative is to provide the
+/// behaviour through a wrapper type (see patterns/newtype.rs) or through a
+/// default method, leaving the implementation to each type:
+trait Summary: Display {
+    fn summary(&self) -> String { format!("summary of {}", self) }
+}
+impl Summary for i32 {}  // opt-in, with the default behaviour
+impl Summary for bool {  // opt-in, with custom behaviour
+    fn summary(&self) -> String {
+        String::from(if *self { "yes" } else { "no" })
+    }
+}
+
+runnable!(locking_out_downstream, {
+    println!("{} / {}", 7.summary(), true.summary());
+});
\ No newline at end of file
@@ -0,0 +1,141 @@
+/// # Boolean Pattern Tests (~ Scala `isInstanceOf`, `Option.exists`)
+/// Often, a `match` is only used to answer a yes/no question: "is this value
+/// of that shape?". Writing the whole `match` with `true`/`false` arms is
+/// verbose: the standard library has shorter forms for these tests.
+fn boolean_patterns() {}
+
+#[derive(Debug)]
+enum WebEvent {
+    PageLoaded,
+    KeyPressed(char),
+    Paste(String),
+    Clicked { x: i64, y: i64 },
+}
+
+/// ## The `matches!` Macro
+/// `matches!(value, pattern)` expands to a `match` returning `true` for the
+/// given pattern, and `false` otherwise. Like match arms, it supports `|`
+/// alternatives, ranges and guards.
+fn is_input(event: &WebEvent) -> bool {
+    matches!(event, WebEvent::KeyPressed(_) | WebEvent::Paste(_))
+}
+
+fn is_in_corner(event: &WebEvent) -> bool {
+    matches!(event, WebEvent::Clicked { x: 0..=10, y } if *y <= 10)
+}
+
+runnable!(matches_macro, {
+    assert!(is_input(&WebEvent::KeyPressed('a')));
+    assert!(!is_input(&WebEvent::PageLoaded));
+    assert!(is_in_corner(&WebEvent::Clicked { x: 3, y: 4 }));
+    assert!(!is_in_corner(&WebEvent::Clicked { x: 3, y: 40 }));
+
+    let letters: usize = "a1b2c".chars()
+        .filter(|c| matches!(c, 'a'..='z' | 'A'..='Z'))
+        .count();
+    assert_eq!(letters, 3);
+});
+
+/// ## `Option` and `Result` Predicates
+/// For the most common enums, methods take a predicate on the content:
+/// - `Option::is_some_and(f)`: `Some` and `f(value)` holds
+/// - `Option::is_none_or(f)`: `None`, or `f(value)` holds
+/// - `Result::is_ok_and(f)` and `Result::is_err_and(f)`: the same on `Ok`
+///   and `Err`
+///
+/// They consume the value: call them on `as_ref()` to keep it.
+runnable!(option_and_result_predicates, {
+    let name: Option<String> = Some("ferris".to_string());
+    assert!(name.as_ref().is_some_and(|name| name.starts_with('f')));
+    assert!(name.as_ref().is_none_or(|name| !name.is_empty()));
+    assert!(None::<u8>.is_none_or(|age| age > 18));
+
+    let parsed: Result<u8, std::num::ParseIntError> = "42".parse();
+    assert!(parsed.as_ref().is_ok_and(|number| number % 2 == 0));
+    assert!("x".parse::<u8>().is_err_and(|error| {
+        *error.kind() == std::num::IntErrorKind::InvalidDigit
+    }));
+});
+
+/// ## Rewriting Verbose Matches
+/// Other lessons use the short forms where a `match` would only answer yes
+/// or no. Each of them replaces a longer `match`.
+///
+/// ### An `ErrorKind` test (in the `non_exhaustive_enums` runnable of
+/// non_exhaustive.rs)
+/// ```text
+/// let retry: bool = match kind {
+///     ErrorKind::Interrupted | ErrorKind::TimedOut => true,
+///     _ => false,
+/// };
+/// // is written
+/// let retry: bool =
+///     matches!(kind, ErrorKind::Interrupted | ErrorKind::TimedOut);
+/// ```
+/// ### A state test (in the `resumable_parser` runnable of
+/// state_machines.rs)
+/// ```text
+/// assert!(match parser.state {
+///     ParserState::Failed(_) => true,
+///     _ => false,
+/// });
+/// // is written
+/// assert!(matches!(parser.state, ParserState::Failed(_)));
+/// ```
+/// ### Several checks at once (in `join` of async_await.rs)
+/// A rewrite is not always better: `join` keeps its two `is_some` calls,
+/// arguably clearer than the `matches!` on a tuple of references.
+/// ```text
+/// if a_output.is_some() && b_output.is_some() { ... }
+/// // could be written
+/// if matches!((&a_output, &b_output), (Some(_), Some(_))) { ... }
+/// ```
+fn rewriting_verbose_matches() {}
+
+runnable!(rewritten_forms_are_equivalent, {
+    let events: Vec<WebEvent> = vec![
+        WebEvent::PageLoaded,
+        WebEvent::Paste("text".to_string()),
+        WebEvent::Clicked { x: 1, y: 2 },
+    ];
+    for event in &events {
+        let verbose: bool = match event {
+            WebEvent::KeyPressed(_) | WebEvent::Paste(_) => true,
+            _ => false,
+        };
+        assert_eq!(verbose, is_input(event));
+    }
+
+    let age: Option<u8> = Some(20);
+    let verbose: bool = match age {
+        Some(age) if age >= 18 => true,
+        _ => false,
+    };
+    assert_eq!(verbose, age.is_some_and(|age| age >= 18));
+    assert_eq!(verbose, matches!(age, Some(18..)));
+});
+
+/// ## When It Hurts Readability
+/// - `matches!` cannot bind: the names in its pattern do not exist after
+///   it, so if the content is needed, `if let` or `match` is the right tool
+/// - long patterns with guards inside a condition are harder to read than a
+///   `match`, and `!matches!(...)` reads like a double negation
+/// - `matches!` hides a `_ => false` arm: adding a variant to the enum
+///   compiles without any warning, while an exhaustive `match` would point
+///   at the code to review
+/// - chaining predicates (`is_some_and(|x| x.is_ok_and(...))`) quickly
+///   becomes harder to follow than the pattern `Some(Ok(...))`
+runnable!(when_it_hurts_readability, {
+    let event: WebEvent = WebEvent::KeyPressed('q');
+    // if matches!(event, WebEvent::KeyPressed(key)) { println!("{}", key); }
+    // ^ Error: cannot find value `key` in this scope
+    if let WebEvent::KeyPressed(key) = event {
+        assert_eq!(key, 'q');
+    }
+
+    let nested: Option<Result<u8, String>> = Some(Ok(3));
+    let hard: bool = nested.as_ref()
+        .is_some_and(|result| result.as_ref().is_ok_and(|n| *n > 2));
+    let easy: bool = matches!(nested, Some(Ok(3..)));
+    assert_eq!(hard, easy);
+});
\ No newline at end of file
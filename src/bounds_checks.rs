@@ -0,0 +1,235 @@
+/// # Bounds-Check Elimination (~ proving the guard redundant)
+/// Every `slice[i]` checks that `i < slice.len()`, and panics otherwise:
+/// out-of-bounds reads are impossible in safe Rust. The check is a compare
+/// and a branch, cheap on its own, but a loop whose iterations may panic
+/// half-way cannot be vectorized: the panic must happen after exactly the
+/// right number of iterations.
+///
+/// The optimizer removes a check when it can prove that it never fails.
+/// This lesson computes the same sum five ways, and measures which ones
+/// actually lose their checks.
+use crate::util::TestDir;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn bounds_checks() {}
+
+/// ## Five Convolutions
+/// The sum of `a[i] * b[n - 1 - i]` for `i` in `0..n`: one coefficient of
+/// the product of two polynomials, reading `a` forwards and `b` backwards.
+///
+/// Each function is `#[inline(never)]`, so that it is measured (and
+/// compiled) as a whole, as it would be called from another module.
+///
+/// Plain indexing: nothing tells the optimizer that `n` fits in either
+/// slice, so both accesses keep their check, inside the loop.
+#[inline(never)]
+pub fn indexed(a: &[u32], b: &[u32], n: usize) -> u32 {
+    let mut total: u32 = 0;
+    for i in 0..n {
+        total = total.wrapping_add(a[i].wrapping_mul(b[n - 1 - i]));
+    }
+    total
+}
+
+/// A leading `assert!`: in theory, `i < n <= len` is now known. In
+/// practice, the optimizer does not connect the assertion with `n - 1 - i`,
+/// and a check stays in the loop. An assertion is a hint, which may or may
+/// not help.
+#[inline(never)]
+pub fn asserted(a: &[u32], b: &[u32], n: usize) -> u32 {
+    assert!(a.len() >= n && b.len() >= n);
+    let mut total: u32 = 0;
+    for i in 0..n {
+        total = total.wrapping_add(a[i].wrapping_mul(b[n - 1 - i]));
+    }
+    total
+}
+
+/// Re-slicing: `&a[..n]` checks `n` once, and produces a slice whose length
+/// is exactly the loop bound, a fact the optimizer tracks reliably. The
+/// checks inside the loop are removed.
+#[inline(never)]
+pub fn resliced(a: &[u32], b: &[u32], n: usize) -> u32 {
+    let (a, b): (&[u32], &[u32]) = (&a[..n], &b[..n]);
+    let mut total: u32 = 0;
+    for i in 0..n {
+        total = total.wrapping_add(a[i].wrapping_mul(b[n - 1 - i]));
+    }
+    total
+}
+
+/// `get_unchecked`: no check at all, and undefined behaviour if the caller
+/// breaks the contract (see unsafe_code.rs).
+///
+/// # Safety
+/// `a` and `b` must both hold at least `n` elements.
+#[inline(never)]
+pub unsafe fn unchecked(a: &[u32], b: &[u32], n: usize) -> u32 {
+    debug_assert!(a.len() >= n && b.len() >= n);
+    let mut total: u32 = 0;
+    for i in 0..n {
+        // SAFETY: `i < n` and `n - 1 - i < n`, and the caller guarantees
+        // `n <= a.len()` and `n <= b.len()`.
+        let (x, y): (u32, u32) =
+            unsafe { (*a.get_unchecked(i), *b.get_unchecked(n - 1 - i)) };
+        total = total.wrapping_add(x.wrapping_mul(y));
+    }
+    total
+}
+
+/// Iterators: slicing checks `n` once per slice, then `zip` and `rev` yield
+/// pairs without indexing, so there is nothing left to check.
+#[inline(never)]
+pub fn zipped(a: &[u32], b: &[u32], n: usize) -> u32 {
+    a[..n].iter().zip(b[..n].iter().rev())
+        .fold(0, |total, (x, y)| total.wrapping_add(x.wrapping_mul(*y)))
+}
+
+runnable!(five_convolutions, {
+    let a: Vec<u32> = (0..100).collect();
+    let b: Vec<u32> = (0..100).map(|x| x * 2).collect();
+    let expected: u32 = (0..100).map(|i| i * (99 - i) * 2).sum();
+    assert_eq!(indexed(&a, &b, 100), expected);
+    assert_eq!(asserted(&a, &b, 100), expected);
+    assert_eq!(resliced(&a, &b, 100), expected);
+    // SAFETY: both vectors hold 100 elements.
+    assert_eq!(unsafe { unchecked(&a, &b, 100) }, expected);
+    assert_eq!(zipped(&a, &b, 100), expected);
+    assert_eq!(zipped(&a, &b, 0), 0);
+
+    // Short inputs: the safe versions panic, instead of reading past the end
+    let short: Vec<u32> = vec![1, 2];
+    for convolution in [indexed, asserted, resliced, zipped] {
+        let result = std::panic::catch_unwind(|| convolution(&a, &short, 3));
+        assert!(result.is_err());
+    }
+});
+
+/// ## Measurements
+/// In debug builds, every check stays, and so does every other call: the
+/// numbers say little.
+///
+/// In release builds (`cargo test --release bounds_checks --
+/// --nocapture`), the differences are often smaller than expected: a check
+/// which never fails is a perfectly predicted branch, nearly free on a
+/// modern processor. What matters is what the checks prevent, such as
+/// vectorization, and that depends on the processor, the element type and
+/// the operation: on some machines the five versions run within noise of
+/// each other, on others the checked ones are several times slower. The
+/// unsafe version never buys anything over re-slicing.
+runnable!(measurements, {
+    let a: Vec<u32> = (0..10_000).collect();
+    let b: Vec<u32> = (0..10_000).map(|x| x ^ 0x5555).collect();
+    let n: usize = a.len();
+    let build: &str = if cfg!(debug_assertions) { "debug" } else { "release" };
+    println!("{} build", build);
+    let resliced = benchmark!("resliced", 500,
+        resliced(black_box(&a), black_box(&b), black_box(n)));
+    let indexed = benchmark!("indexed", 500,
+        indexed(black_box(&a), black_box(&b), black_box(n)));
+    let asserted = benchmark!("asserted", 500,
+        asserted(black_box(&a), black_box(&b), black_box(n)));
+    // SAFETY: both vectors hold `n` elements.
+    let unchecked = benchmark!("unchecked", 500, unsafe {
+        unchecked(black_box(&a), black_box(&b), black_box(n))
+    });
+    let zipped = benchmark!("zipped", 500,
+        zipped(black_box(&a), black_box(&b), black_box(n)));
+    for (label, average) in [
+        ("indexed", indexed), ("asserted", asserted),
+        ("unchecked", unchecked), ("zipped", zipped),
+    ] {
+        println!("{}: {:.2}x resliced", label,
+            average.as_secs_f64() / resliced.as_secs_f64().max(1e-9));
+    }
+});
+
+/// ## Reading the Generated Code
+/// Timings vary, the generated code does not: the only reliable way to know
+/// whether a check is gone is to look for it. This runnable compiles copies
+/// of three of the functions above with `rustc -C opt-level=3 --emit=asm`,
+/// and counts the calls to `panic_bounds_check` left in each.
+///
+/// The optimizer improves with every release: a plain forward loop, such as
+/// `for i in 0..n { total += a[i] * b[i] }`, is already split by recent
+/// compilers into an unchecked part, up to the shortest length, and a
+/// checked remainder. Check the generated code (e.g. with `cargo asm`, or
+/// on the Compiler Explorer) before reaching for `unsafe`.
+const CONVOLUTIONS: &str = "
+#[no_mangle]
+pub fn indexed(a: &[u32], b: &[u32], n: usize) -> u32 {
+    let mut total: u32 = 0;
+    for i in 0..n {
+        total = total.wrapping_add(a[i].wrapping_mul(b[n - 1 - i]));
+    }
+    total
+}
+#[no_mangle]
+pub fn asserted(a: &[u32], b: &[u32], n: usize) -> u32 {
+    assert!(a.len() >= n && b.len() >= n);
+    let mut total: u32 = 0;
+    for i in 0..n {
+        total = total.wrapping_add(a[i].wrapping_mul(b[n - 1 - i]));
+    }
+    total
+}
+#[no_mangle]
+pub fn resliced(a: &[u32], b: &[u32], n: usize) -> u32 {
+    let (a, b): (&[u32], &[u32]) = (&a[..n], &b[..n]);
+    let mut total: u32 = 0;
+    for i in 0..n {
+        total = total.wrapping_add(a[i].wrapping_mul(b[n - 1 - i]));
+    }
+    total
+}
+";
+
+/// The number of bounds checks in each function of `CONVOLUTIONS`, or
+/// `None` if `rustc` cannot be run.
+fn count_bounds_checks() -> Option<Vec<(String, usize)>> {
+    let directory: TestDir = TestDir::new("bounds-checks");
+    let source: PathBuf = directory.join("convolutions.rs");
+    let assembly: PathBuf = directory.join("convolutions.s");
+    std::fs::write(&source, CONVOLUTIONS).ok()?;
+    let rustc: String =
+        std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let status = Command::new(rustc)
+        .args(["--crate-type=lib", "-C", "opt-level=3"])
+        .args(["--emit=asm", "-o"]).arg(&assembly).arg(&source)
+        .status();
+    if !status.ok()?.success() { return None; }
+    let text: Option<String> = std::fs::read_to_string(&assembly).ok();
+
+    // Functions start with `name:`, and end with `.cfi_endproc`
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for line in text?.lines() {
+        match line.strip_suffix(':') {
+            // Mach-O (macOS) symbols start with an underscore
+            Some(name) if !name.starts_with('.') =>
+                counts.push((name.trim_start_matches('_').to_string(), 0)),
+            _ if line.contains("panic_bounds_check") =>
+                counts.last_mut()?.1 += 1,
+            _ => {}
+        }
+    }
+    Some(counts)
+}
+
+runnable!(reading_the_generated_code, {
+    let Some(counts) = count_bounds_checks() else {
+        println!("cannot run rustc: skipping the inspection");
+        return;
+    };
+    for (name, checks) in &counts {
+        println!("{}: {} bounds check(s)", name, checks);
+    }
+    let checks = |name: &str| counts.iter()
+        .find(|(function, _)| function == name)
+        .map(|(_, checks)| *checks);
+    // Only the reslicing guarantees the outcome: whether the checks of
+    // `indexed` and `asserted` survive is up to the optimizer, and may change
+    // with the next release of the compiler
+    assert_eq!(checks("resliced"), Some(0));
+});
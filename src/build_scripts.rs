@@ -0,0 +1,70 @@
+/// # Build Scripts
+/// A `build script` is a Rust program that Cargo compiles and runs before
+/// compiling a package (see cargo.rs). By default, it is the file `build.rs`
+/// at the root of the package. Build scripts are used to:
+/// - generate code (e.g. bindings, parsers, lookup tables)
+/// - compile and link native libraries (see native_linking.rs)
+/// - detect features of the target platform or of the compiler
+///
+/// The build script of this playground (see build.rs) generates a table of
+/// the playground topics, read from the headers of the files in `src`.
+fn build_scripts() {}
+
+/// ## Including Generated Code
+/// Build scripts must write their outputs into `OUT_DIR`, a directory inside
+/// `target` reserved for the package (never into `src`). The generated code
+/// is then pasted into the crate with `include!`, which inserts the content
+/// of a file as source code, at compile time.
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+}
+
+runnable!(generated_code, {
+    println!("Playground topics ({} profile):", generated::BUILD_PROFILE);
+    for (module, title) in generated::TOPICS {
+        println!("- {:<24} {}", module, title);
+    }
+    assert!(generated::TOPICS.iter().any(|(module, _)| *module == "macros"));
+});
+
+/// ## Communicating with Cargo
+/// A build script talks to Cargo by printing lines starting with `cargo:` to
+/// its standard output. The most common instructions are:
+/// - `cargo:rerun-if-changed=PATH`: only run the script again if the file
+///   (or anything inside the directory) has changed. Without any such line,
+///   the script runs again whenever any file of the package changes
+/// - `cargo:rerun-if-env-changed=VAR`: also run again if a variable changes
+/// - `cargo:rustc-env=VAR=VALUE`: set an environment variable for the
+///   compilation of the crate, readable with `env!("VAR")`
+/// - `cargo:rustc-cfg=NAME`: enable `#[cfg(NAME)]` in the crate
+/// - `cargo:rustc-link-lib=NAME` and `cargo:rustc-link-search=PATH`: link a
+///   native library
+/// - `cargo:warning=MESSAGE`: show a warning to the user
+///
+/// The output of the last run can be found in
+/// `target/debug/build/<package>-<hash>/output`.
+runnable!(communicating_with_cargo, {
+    // Set by build.rs with `cargo:rustc-env`
+    let target: &str = env!("PLAYGROUND_BUILD_TARGET");
+    println!("built for: {}", target);
+});
+
+/// ## Build Script Environment
+/// Cargo passes information to the build script through environment
+/// variables, read at runtime with `std::env::var` (the script runs on the
+/// host, not on the target):
+/// - `OUT_DIR`: where to write the outputs
+/// - `TARGET` and `HOST`: the target and host triples (e.g.
+///   `x86_64-unknown-linux-gnu`), which differ when cross-compiling
+/// - `PROFILE`: `debug` or `release`
+/// - `CARGO_MANIFEST_DIR`: the directory containing `Cargo.toml`
+/// - `CARGO_FEATURE_<NAME>`: set for each enabled feature (see Cargo.toml)
+/// - `CARGO_CFG_<CFG>`: the configuration of the target (e.g.
+///   `CARGO_CFG_TARGET_OS`), since `cfg!` in the script describes the host
+///
+/// Some of them are also available to the crate itself, through `env!`.
+runnable!(build_script_environment, {
+    println!("package: {} {}",
+        env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+    println!("manifest directory: {}", env!("CARGO_MANIFEST_DIR"));
+});
\ No newline at end of file
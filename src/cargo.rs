@@ -37,6 +37,7 @@
 /// [package]
 /// build = ".../my_build.rs
 /// ```
+/// This playground has a build script too (see build.rs and build_scripts.rs).
 /// 
 /// ## Binaries
 /// A project can contain multiple binaries, in addition to the cargo root.
@@ -45,4 +46,17 @@
 /// 
 /// You can target a specific binary in the project using the `--bin` flag.
 /// For example, you can run one using `cargo run --bin other_bin`.
+/// 
+/// ## Benchmarks
+/// Benchmarks are located in the `benches` folder, and run with
+/// `cargo bench`, which compiles them with optimizations. The built-in
+/// `#[bench]` harness is unstable, so stable projects set `harness = false`
+/// and provide their own `main`, usually through the `criterion` crate:
+/// ```
+/// [[bench]]
+/// name = "showpieces"                     // benches/showpieces.rs
+/// harness = false
+/// ```
+/// This playground has a benchmark with its own small harness (see
+/// benches/showpieces.rs).
 fn cargo() {}
\ No newline at end of file
@@ -125,3 +125,245 @@ runnable!(function_composition, {
     let add_two = compose(add_one, add_one);
     println!("x={} x+2={}", x, add_two(x));
 });
+
+/// ## Storing Closures
+/// Each closure has its own anonymous type, so a collection of closures (or
+/// a struct field that can hold any closure) needs a common type: a trait
+/// object (see traits.rs) behind a pointer, e.g. `Box<dyn FnMut(Event)>`.
+mod stored_closures {
+    #[derive(Debug, Clone, Copy)]
+    pub enum Event { Click { x: i32, y: i32 }, KeyPress(char) }
+
+    /// ### Callbacks
+    /// `FnMut` lets callbacks keep state between calls, so calling them
+    /// requires `&mut self`.
+    ///
+    /// A boxed trait object has an implicit lifetime bound: in a struct
+    /// without lifetime parameters, `Box<dyn FnMut(Event)>` means
+    /// `Box<dyn FnMut(Event) + 'static>`, so the stored closures cannot borrow
+    /// local variables, only own (`move`) their captured values.
+    pub struct EventBus {
+        listeners: Vec<Box<dyn FnMut(Event)>>,
+    }
+
+    impl EventBus {
+        pub fn new() -> Self { EventBus { listeners: Vec::new() } }
+        pub fn subscribe(&mut self, listener: impl FnMut(Event) + 'static) {
+            self.listeners.push(Box::new(listener));
+        }
+        pub fn emit(&mut self, event: Event) {
+            for listener in self.listeners.iter_mut() { listener(event); }
+        }
+    }
+
+    runnable!(callbacks, {
+        let mut bus: EventBus = EventBus::new();
+        let mut clicks: u32 = 0;
+        bus.subscribe(move |event| {
+            if let Event::Click { .. } = event {
+                clicks += 1;    // state owned by the closure
+                println!("click #{}", clicks);
+            }
+        });
+        bus.subscribe(|event| println!("logged: {:?}", event));
+        bus.emit(Event::Click { x: 1, y: 2 });
+        bus.emit(Event::KeyPress('a'));
+        bus.emit(Event::Click { x: 3, y: 4 });
+
+        // let mut log: Vec<Event> = Vec::new();
+        // bus.subscribe(|event| log.push(event));
+        // ^ Error: closure may outlive the current function, but it borrows
+        //          `log`, which is owned by the current function
+    });
+
+    /// ### Borrowing Callbacks
+    /// A lifetime parameter relaxes the bound to `dyn FnMut(Event) + 'a`: the
+    /// closures may borrow anything that outlives the bus.
+    pub struct ScopedBus<'a> {
+        listeners: Vec<Box<dyn FnMut(Event) + 'a>>,
+    }
+
+    impl<'a> ScopedBus<'a> {
+        pub fn new() -> Self { ScopedBus { listeners: Vec::new() } }
+        pub fn subscribe(&mut self, listener: impl FnMut(Event) + 'a) {
+            self.listeners.push(Box::new(listener));
+        }
+        pub fn emit(&mut self, event: Event) {
+            for listener in self.listeners.iter_mut() { listener(event); }
+        }
+    }
+
+    runnable!(borrowing_callbacks, {
+        let mut log: Vec<Event> = Vec::new();
+        {
+            let mut bus: ScopedBus = ScopedBus::new();
+            bus.subscribe(|event| log.push(event));  // borrows `log` mutably
+            bus.emit(Event::KeyPress('x'));
+            bus.emit(Event::Click { x: 0, y: 0 });
+            // println!("{:?}", log);
+            // ^ Error: cannot borrow `log` as immutable because it is also
+            //          borrowed as mutable (by the closure in `bus`)
+        }   // <-- `bus` is dropped here, releasing the borrow
+        println!("log: {:?}", log);
+    });
+
+    /// ### One-Shot Callbacks
+    /// Calling a `Box<dyn FnOnce()>` consumes the box, but a field cannot be
+    /// moved out of `&mut self`. Storing it in an `Option` solves this:
+    /// `Option::take` moves the closure out, leaving `None` behind, which
+    /// also guarantees that it runs at most once.
+    pub struct Connection {
+        on_close: Option<Box<dyn FnOnce(&str)>>,
+    }
+
+    impl Connection {
+        pub fn new() -> Self { Connection { on_close: None } }
+        pub fn on_close(&mut self, callback: impl FnOnce(&str) + 'static) {
+            self.on_close = Some(Box::new(callback));
+        }
+        pub fn close(&mut self) {
+            // let callback = self.on_close.unwrap();
+            // ^ Error: cannot move out of `self.on_close` which is behind a
+            //          mutable reference
+            if let Some(callback) = self.on_close.take() { callback("closed"); }
+        }
+    }
+
+    runnable!(one_shot_callbacks, {
+        let mut connection: Connection = Connection::new();
+        let resource: String = String::from("socket #1");
+        connection.on_close(move |reason| {
+            println!("{} {}", resource, reason);
+            drop(resource);    // consumes a captured value: only `FnOnce`
+        });
+        connection.close();
+        connection.close();    // no-op: the callback has already been taken
+    });
+}
+
+/// ## Capture Modes
+/// A closure captures each variable it uses in the least demanding way its
+/// body allows:
+/// - by shared reference (`&x`), if it only reads it
+/// - by mutable reference (`&mut x`), if it modifies it
+/// - by value (moving or copying `x`), if it consumes it
+///
+/// The capture mode decides what the surrounding code can still do with the
+/// variable while the closure exists. It is independent from the traits the
+/// closure implements: a closure capturing by value may still be `Fn`.
+mod capture_modes {
+    /// ### Inferred Captures
+    runnable!(inferred_captures, {
+        let text: String = String::from("hello");
+        let length = || text.len();              // captures `&text`
+        println!("{} {}", text, length());        // `text` is still usable
+
+        let mut count: u32 = 0;
+        let mut increment = || count += 1;        // captures `&mut count`
+        increment();
+        increment();
+        // println!("{}", count);
+        // increment();
+        // ^ Error: cannot borrow `count` as immutable because it is also
+        //          borrowed as mutable
+        assert_eq!(count, 2);                     // `increment` is done
+
+        let consume = || drop(text);              // captures `text` by value
+        // println!("{}", text);
+        // ^ Error: borrow of moved value: `text`
+        consume();
+    });
+
+    /// ### Disjoint Field Captures (Edition 2021)
+    /// Since edition 2021, a closure captures the exact paths it uses (e.g.
+    /// `point.x`), not whole variables: other fields stay available. In
+    /// edition 2018, `|| point.x += 1` captured all of `point`, and the code
+    /// below did not compile.
+    #[derive(Debug)]
+    struct Point { x: i32, y: i32 }
+
+    struct Person { name: String, age: u8 }
+
+    runnable!(disjoint_field_captures, {
+        let mut point: Point = Point { x: 0, y: 0 };
+        let mut move_right = || point.x += 1;     // captures `&mut point.x`
+        let y: &i32 = &point.y;                   // fine: a different field
+        move_right();
+        assert_eq!((*y, point.x), (0, 1));
+
+        let person: Person = Person { name: "Ann".to_string(), age: 30 };
+        let name_length = move || person.name.len();  // moves `person.name`
+        assert_eq!(person.age, 30);               // `person.age` is untouched
+        // let moved: Person = person;
+        // ^ Error: use of partially moved value: `person`
+        assert_eq!(name_length(), 3);
+    });
+
+    /// ### `move` Closures
+    /// `move` forces every capture to be by value. For a reference, that
+    /// means copying the reference, not the data: the closure still borrows
+    /// the data, and its type carries the lifetime of the borrow.
+    fn length_of<'a>(text: &'a str) -> impl Fn() -> usize + 'a {
+        move || text.len()                        // moves the `&str` only
+    }
+    // fn length_of<'a>(text: &'a str) -> impl Fn() -> usize + 'a {
+    //     || text.len()
+    // }
+    // ^ Error: closure may outlive the current function, but it borrows
+    //          `text`, which is owned by the current function
+
+    /// The returned closure can hand out borrowed data too.
+    fn words_of<'a>(text: &'a str) -> impl Fn(usize) -> Option<&'a str> {
+        move |index| text.split_whitespace().nth(index)
+    }
+
+    runnable!(move_closures, {
+        let owned: String = String::from("borrowed by the closure");
+        let length = length_of(&owned);
+        let words = words_of(&owned);
+        assert_eq!(length(), owned.len());
+        assert_eq!(words(2), Some("the"));
+        // drop(owned);
+        // ^ Error: cannot move out of `owned` because it is borrowed
+        assert_eq!(words(3), Some("closure"));
+    });
+
+    /// ### "Closure May Outlive the Current Function"
+    /// A closure returned from a function, or passed to `thread::spawn`,
+    /// may run after the function returns: it cannot borrow its locals.
+    /// ```
+    /// fn counter() -> impl FnMut() -> u32 {
+    ///     let mut count: u32 = 0;
+    ///     || { count += 1; count }
+    /// }
+    /// // ^ Error: closure may outlive the current function, but it borrows
+    /// //          `count`, which is owned by the current function
+    /// ```
+    /// Fixes, depending on who needs the data afterwards:
+    /// 1. nobody else: `move` the data into the closure
+    /// 2. the caller too: `move` a clone (or an `Arc`) into the closure
+    /// 3. the data outlives the closure: borrow within a scope that
+    ///    guarantees it, e.g. `std::thread::scope`
+    fn counter() -> impl FnMut() -> u32 {
+        let mut count: u32 = 0;
+        move || { count += 1; count }             // fix 1
+    }
+
+    runnable!(closure_may_outlive_the_current_function, {
+        let mut next = counter();
+        assert_eq!((next(), next()), (1, 2));
+
+        let names: Vec<&str> = vec!["a", "b"];
+        // std::thread::spawn(|| println!("{:?}", names));
+        // ^ Error: closure may outlive the current function, but it borrows
+        //          `names`, which is owned by the current function
+        let copy: Vec<&str> = names.clone();      // fix 2
+        let handle = std::thread::spawn(move || copy.len());
+        assert_eq!(handle.join().unwrap(), 2);
+
+        std::thread::scope(|scope| {              // fix 3
+            scope.spawn(|| assert_eq!(names.len(), 2));
+        });
+        assert_eq!(names, ["a", "b"]);
+    });
+}
\ No newline at end of file
@@ -0,0 +1,238 @@
+//! # Compile Errors as Tests
+//! Most lessons show invalid code in comments, followed by the expected
+//! error (`// ^ Error: ...`). Nothing checks these comments: they may rot
+//! when the compiler or the lesson changes.
+//!
+//! Doctests can do better: a code block marked ```` ```compile_fail ````
+//! passes only if the code fails to compile, and ```` ```compile_fail,E0382
+//! ```` also checks the error code. Doctests only run for library targets,
+//! so this lesson lives in the library of the playground (see src/lib.rs),
+//! and runs with `cargo test --doc`.
+//!
+//! Each section below turns a commented snippet of another lesson into a
+//! verified artifact, next to the code which fixes it.
+
+/// ## Non-Exhaustive Matches
+/// Every value must be handled by some arm (see pattern_matching.rs):
+/// ```compile_fail,E0004
+/// enum Direction { North, East, South, West }
+/// fn turn(direction: Direction) -> Direction {
+///     match direction {
+///         Direction::North => Direction::East,
+///         Direction::East => Direction::South,
+///         Direction::South => Direction::West,
+///     }
+///     // ^ Error: non-exhaustive patterns: `Direction::West` not covered
+/// }
+/// ```
+/// Integers must be covered in full, even when the domain seems obvious:
+/// ```compile_fail,E0004
+/// fn sign(value: i8) -> &'static str {
+///     match value {
+///         i8::MIN..=-1 => "negative",
+///         1..=i8::MAX => "positive",
+///     }
+///     // ^ Error: non-exhaustive patterns: `0_i8` not covered
+/// }
+/// ```
+/// ```
+/// fn sign(value: i8) -> &'static str {
+///     match value {
+///         i8::MIN..=-1 => "negative",
+///         0 => "zero",
+///         1..=i8::MAX => "positive",
+///     }
+/// }
+/// assert_eq!(sign(0), "zero");
+/// ```
+pub fn non_exhaustive_matches() {}
+
+/// ## Refutable Patterns in `let`
+/// See the `refutability` module of pattern_matching.rs:
+/// ```compile_fail,E0005
+/// let option: Option<u8> = Some(1);
+/// let Some(value) = option;
+/// // ^ Error: refutable pattern in local binding: pattern `None` not covered
+/// ```
+/// ```
+/// let option: Option<u8> = Some(1);
+/// let Some(value) = option else { return };
+/// assert_eq!(value, 1);
+/// ```
+pub fn refutable_patterns() {}
+
+/// ## Missing Lifetimes and Lifetime Bounds
+/// An output reference with two candidate inputs needs an annotation (see
+/// `lifetime_elision` in ownership.rs):
+/// ```compile_fail,E0106
+/// fn first(x: &str, y: &str) -> &str { x }
+/// // ^ Error: missing lifetime specifier
+/// ```
+/// Returning a reference with an unrelated lifetime needs a bound:
+/// ```compile_fail
+/// fn identity<'a, 'b>(x: &'a i32) -> &'b i32 { x }
+/// // ^ Error: lifetime may not live long enough
+/// ```
+/// A generic type stored in a `'static` trait object must be `'static`
+/// itself:
+/// ```compile_fail,E0310
+/// fn boxed<T: std::fmt::Debug>(value: T) -> Box<dyn std::fmt::Debug> {
+///     Box::new(value)
+///     // ^ Error: the parameter type `T` may not live long enough
+/// }
+/// ```
+/// ```
+/// fn first<'a>(x: &'a str, y: &str) -> &'a str { x }
+/// fn identity<'a: 'b, 'b>(x: &'a i32) -> &'b i32 { x }
+/// fn boxed<T: std::fmt::Debug + 'static>(value: T)
+///     -> Box<dyn std::fmt::Debug> {
+///     Box::new(value)
+/// }
+/// assert_eq!(format!("{:?}", boxed(first("a", "b"))), "\"a\"");
+/// ```
+pub fn lifetime_bounds() {}
+
+/// ## Moved Values
+/// Using a value after moving it (see ownership.rs):
+/// ```compile_fail,E0382
+/// let x: Box<u8> = Box::new(0);
+/// let y: Box<u8> = x;
+/// println!("{}", x);
+/// // ^ Error: borrow of moved value: `x`
+/// ```
+/// Using a struct after moving one of its fields (see `partial_move` in
+/// ownership.rs):
+/// ```compile_fail,E0382
+/// #[derive(Debug)]
+/// struct Person { name: String, age: u8 }
+/// let paul: Person = Person { name: String::from("Paul"), age: 20 };
+/// let name: String = paul.name;
+/// println!("{:?}", paul);
+/// // ^ Error: borrow of partially moved value: `paul`
+/// ```
+/// ```
+/// let x: Box<u8> = Box::new(0);
+/// let y: Box<u8> = x.clone();  // or borrow: `let y: &Box<u8> = &x;`
+/// println!("{} {}", x, y);
+/// ```
+pub fn moved_values() {}
+
+/// ## Conflicting Borrows
+/// Mutating a value while it is borrowed (see references.rs):
+/// ```compile_fail,E0506
+/// let mut x: u8 = 0;
+/// let y: &u8 = &x;
+/// x += 1;
+/// println!("{}", y);
+/// // ^ Error: cannot assign to `x` because it is borrowed
+/// ```
+/// Two mutable borrows at once:
+/// ```compile_fail,E0499
+/// let mut numbers: Vec<u8> = vec![1, 2];
+/// let first: &mut u8 = &mut numbers[0];
+/// let second: &mut u8 = &mut numbers[1];
+/// *first += *second;
+/// // ^ Error: cannot borrow `numbers` as mutable more than once at a time
+/// ```
+/// ```
+/// let mut numbers: Vec<u8> = vec![1, 2];
+/// let (head, tail) = numbers.split_at_mut(1);
+/// head[0] += tail[0];
+/// assert_eq!(numbers, [3, 2]);
+/// ```
+pub fn conflicting_borrows() {}
+
+/// ## Immutable Variables
+/// ```compile_fail,E0384
+/// let x: u8 = 0;
+/// x = 1;
+/// // ^ Error: cannot assign twice to immutable variable `x`
+/// ```
+pub fn immutable_variables() {}
+
+/// ## Dyn Compatibility
+/// A trait can only be used as a trait object if every method can be called
+/// through a vtable (see object_safety.rs). Generic methods cannot:
+/// ```compile_fail,E0038
+/// trait Logger {
+///     fn log<T: std::fmt::Debug>(&self, value: T);
+/// }
+/// fn use_logger(logger: &dyn Logger) {}
+/// // ^ Error: the trait `Logger` is not dyn compatible
+/// ```
+/// Neither can methods returning `Self`, nor associated functions without a
+/// `self` receiver:
+/// ```compile_fail,E0038
+/// trait Shape {
+///     fn area(&self) -> f64;
+///     fn scaled(&self, factor: f64) -> Self;
+/// }
+/// fn use_shape(shape: &dyn Shape) {}
+/// // ^ Error: the trait `Shape` is not dyn compatible
+/// ```
+/// ```compile_fail,E0038
+/// trait Shape {
+///     fn unit() -> Self;
+/// }
+/// fn use_shape(shape: &dyn Shape) {}
+/// // ^ Error: the trait `Shape` is not dyn compatible
+/// ```
+/// Default methods count too, unless bound by `where Self: Sized`:
+/// ```compile_fail,E0038
+/// trait Counter {
+///     fn next(&mut self) -> Option<u32>;
+///     fn sum_mapped<F: Fn(u32) -> u32>(&self, f: F) -> u32 { 0 }
+/// }
+/// fn use_counter(counter: &dyn Counter) {}
+/// // ^ Error: the trait `Counter` is not dyn compatible
+/// ```
+/// ```
+/// trait Counter {
+///     fn next(&mut self) -> Option<u32>;
+///     fn sum_mapped<F: Fn(u32) -> u32>(&self, f: F) -> u32
+///     where Self: Sized { 0 }
+/// }
+/// fn use_counter(counter: &dyn Counter) {}
+/// ```
+/// Associated consts are never dyn compatible:
+/// ```compile_fail,E0038
+/// trait Unit {
+///     const SYMBOL: &'static str;
+///     fn amount(&self) -> f64;
+/// }
+/// fn use_unit(unit: &dyn Unit) {}
+/// // ^ Error: the trait `Unit` is not dyn compatible
+/// ```
+pub fn dyn_compatibility() {}
+
+/// ## Sealed Traits
+/// A public trait with a supertrait in a private module (see
+/// patterns/sealed.rs) can be used by downstream crates, but not
+/// implemented: they cannot implement the supertrait, which they cannot
+/// even name. The `Shape` of that lesson lives in this library (see
+/// src/lib.rs), so these doctests check the real one:
+/// ```
+/// use rust_plauground::{Shape, Square};
+/// assert_eq!(Square(2.0).area(), 4.0);
+/// ```
+/// ```compile_fail,E0277
+/// use rust_plauground::Shape;
+/// struct Triangle;
+/// impl Shape for Triangle {
+///     fn area(&self) -> f64 { 0.0 }
+/// }
+/// // ^ Error: the trait bound `Triangle: Sealed` is not satisfied
+/// ```
+/// ```compile_fail,E0603
+/// struct Triangle;
+/// impl rust_plauground::private::Sealed for Triangle {}
+/// // ^ Error: module `private` is private
+/// ```
+pub fn sealed_traits() {}
+
+/// ## Limits
+/// `compile_fail` passes whatever the reason of the failure, even a typo:
+/// an error code makes the test precise. Error messages themselves are not
+/// checked: the compiler test suite (`ui` tests, comparing whole outputs)
+/// and crates like `trybuild` do that.
+pub fn limits() {}
\ No newline at end of file
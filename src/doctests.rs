@@ -0,0 +1,144 @@
+//! # Doctest Attributes
+//! Every code block in a doc comment is a test: `cargo test --doc` extracts
+//! it, wraps it in a `fn main`, compiles it against the library, and runs
+//! it. The info string after the opening fence changes what "passing"
+//! means:
+//!
+//! | Attribute          | Compiled | Run | Passes if                       |
+//! |--------------------|----------|-----|---------------------------------|
+//! | (none), `rust`     | yes      | yes | it does not panic               |
+//! | `should_panic`     | yes      | yes | it panics                       |
+//! | `no_run`           | yes      | no  | it compiles                     |
+//! | `compile_fail`     | yes      | no  | it does not compile             |
+//! | `ignore`           | no       | no  | always (skipped)                |
+//! | `text`, `sh`, ...  | no       | no  | not a test: not Rust code       |
+//!
+//! Doctests only run for library targets, so this lesson lives in the
+//! library of the playground (see src/lib.rs, and compile_errors.rs for
+//! more `compile_fail` examples).
+
+/// ## The Example Type
+/// A counter which refuses to overflow, documented like a public API.
+///
+/// Doc comments can link to other items by path, checked by `cargo doc`
+/// (`intra-doc links`): [`Counter::increment`], [`Self::get`], a type of
+/// the standard library ([`Option`]), or another module of this crate
+/// ([`crate::compile_errors`]). Broken links are reported as warnings.
+#[derive(Debug, Default)]
+pub struct Counter {
+    count: u8,
+}
+
+impl Counter {
+    pub fn new() -> Counter { Counter::default() }
+
+    /// ## Plain Doctests
+    /// The example passes if it compiles and runs without panicking: the
+    /// assertions are the test.
+    /// ```
+    /// use rust_plauground::doctests::Counter;
+    ///
+    /// let mut counter = Counter::new();
+    /// counter.increment();
+    /// assert_eq!(counter.get(), 1);
+    /// ```
+    ///
+    /// ## `should_panic`
+    /// The example passes only if it panics. Unlike `#[should_panic]` on
+    /// unit tests, the panic message cannot be checked.
+    /// ```should_panic
+    /// use rust_plauground::doctests::Counter;
+    ///
+    /// let mut counter = Counter::new();
+    /// for _ in 0..=255 { counter.increment(); }   // 256 increments
+    /// ```
+    pub fn increment(&mut self) {
+        self.count = self.count.checked_add(1).expect("counter overflow");
+    }
+
+    pub fn get(&self) -> u8 { self.count }
+
+    /// ## Hidden Lines
+    /// Lines starting with `# ` are compiled, but hidden from the rendered
+    /// documentation: imports and setup stay out of the way of the reader.
+    /// ```
+    /// # use rust_plauground::doctests::Counter;
+    /// # let mut counter = Counter::new();
+    /// # counter.increment();
+    /// let value: u8 = counter.try_add(41)?;
+    /// assert_eq!(value, 42);
+    /// # Ok::<(), String>(())
+    /// ```
+    /// The last hidden line makes `?` work: the example becomes the body of
+    /// a `main` returning the `Result` of its final expression, whose type
+    /// must be spelled out.
+    pub fn try_add(&mut self, amount: u8) -> Result<u8, String> {
+        self.count = self.count.checked_add(amount)
+            .ok_or(format!("cannot add {} to {}", amount, self.count))?;
+        Ok(self.count)
+    }
+
+    /// ## `no_run` vs `ignore`
+    /// `no_run` compiles the example without running it: for code which
+    /// never ends, touches the network, or the file system. A typo still
+    /// fails the test.
+    /// ```no_run
+    /// use rust_plauground::doctests::Counter;
+    ///
+    /// let mut counter = Counter::new();
+    /// loop { counter.reset(); }                   // would never end
+    /// ```
+    /// `ignore` does not even compile it: the example can rot silently.
+    /// It is meant for pseudo-code, or examples needing a setup which the
+    /// doctest cannot provide. `cargo test --doc -- --ignored` still tries
+    /// to run them, and fails on this one.
+    /// ```ignore
+    /// let counter = connect_to_remote_counter("10.0.0.1")?;
+    /// counter.reset();
+    /// ```
+    /// For anything which is not Rust, `text` (or the name of the language)
+    /// is better than `ignore`: it is not reported as an ignored test.
+    /// ```text
+    /// $ cargo test --doc doctests
+    /// ```
+    pub fn reset(&mut self) { self.count = 0; }
+}
+
+/// ## `compile_fail`
+/// The example passes only if it does not compile. Adding the error code
+/// checks that it fails for the expected reason, and not because of a typo:
+/// ```compile_fail,E0616
+/// use rust_plauground::doctests::Counter;
+///
+/// let counter = Counter::new();
+/// let count: u8 = counter.count;
+/// // ^ Error: field `count` of struct `Counter` is private
+/// ```
+pub fn compile_fail() {}
+
+/// ## Editions
+/// Examples are compiled with the edition of the crate, unless an
+/// `editionYYYY` attribute overrides it. `async` became a keyword in 2018:
+/// ```edition2015
+/// let async: u32 = 1;                             // a plain identifier
+/// assert_eq!(async, 1);
+/// ```
+/// ```compile_fail,edition2018
+/// let async: u32 = 1;
+/// // ^ Error: expected identifier, found keyword `async`
+/// ```
+pub fn editions() {}
+
+/// ## `test_harness`
+/// By default, an example is the body of a `main`. With `test_harness`, it
+/// is compiled as a test crate instead: its `#[test]` functions run, as in a
+/// unit test module (see unit_testing.rs).
+/// ```test_harness
+/// use rust_plauground::doctests::Counter;
+///
+/// #[test]
+/// fn starts_at_zero() {
+///     assert_eq!(Counter::new().get(), 0);
+/// }
+/// ```
+pub fn test_harness() {}
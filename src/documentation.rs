@@ -16,7 +16,9 @@ fn enclosing_item() {
 /// let x: u8 = y;     // this compiles, because `y` is defined, but hidden
 /// ```
 /// These samples will be compiled and executed as `Documentation Tests` if the
-/// crate is a library (see unit_testing.rs).
+/// crate is a library (see unit_testing.rs). Hidden lines, attributes such as
+/// `should_panic` or `ignore`, and links to other items are covered in
+/// doctests.rs.
 fn documentation() {}
 
 /// ## Documentation in Cargo
@@ -0,0 +1,148 @@
+/// # Drop Check (~ C++ destructors using dangling members)
+/// When a value goes out of scope, its `Drop` implementation runs, and it may
+/// read the references the value holds. The borrow checker must prove that
+/// the borrowed data is still alive at that point: this is the `drop check`
+/// (dropck). See the `phantom_drop_check` runnable in generics.rs for how
+/// `PhantomData` takes part in it.
+///
+/// Values are dropped in the reverse order of their declaration, so the
+/// order of `let` statements matters for types implementing `Drop`.
+use std::cell::Cell;
+use std::mem::ManuallyDrop;
+
+fn drop_check() {}
+
+/// ## An Inspector
+/// A value holding a borrow, and reading it when dropped.
+struct Inspector<'a> {
+    name: &'a String,
+    log: &'a Cell<usize>,
+}
+
+impl Drop for Inspector<'_> {
+    fn drop(&mut self) {
+        // Reads `self.name`: it must not be dropped already
+        self.log.set(self.log.get() + self.name.len());
+    }
+}
+
+/// The same value without a `Drop` implementation.
+struct Observer<'a> {
+    name: &'a String,
+}
+
+/// ## Same-Scope Declarations
+/// Declared after `name`, the inspector is dropped before it: fine. Declared
+/// before, it would be dropped after `name`, and its `drop` would read freed
+/// memory:
+/// ```
+/// let log: Cell<usize> = Cell::new(0);
+/// let inspector: Inspector;
+/// let name: String = String::from("ferris");
+/// inspector = Inspector { name: &name, log: &log };
+/// // ^ Error: `name` does not live long enough: borrow might be used here,
+/// //          when `inspector` is dropped and runs the `Drop` code for type
+/// //          `Inspector`
+/// ```
+/// Declaring both at once does not help either: fields of a tuple are
+/// dropped in order, but the borrow checker does not rely on it.
+/// ```
+/// let (inspector, name): (Inspector, String);
+/// name = String::from("ferris");
+/// inspector = Inspector { name: &name, log: &log };
+/// // ^ Error: `name` does not live long enough
+/// ```
+/// Note: calling `drop(inspector)` before the end of the scope does not
+///       make the first example compile either.
+runnable!(same_scope_declarations, {
+    let log: Cell<usize> = Cell::new(0);
+    {
+        let name: String = String::from("ferris");
+        let inspector: Inspector = Inspector { name: &name, log: &log };
+    }   // <-- `inspector` is dropped (reading `name`), then `name`
+    assert_eq!(log.get(), 6);
+
+    // Without `Drop`, nothing reads the borrow when `observer` goes away:
+    // the borrow only needs to live until the last use of `observer`
+    let observer: Observer;
+    let name: String = String::from("ferris");
+    observer = Observer { name: &name };
+    assert_eq!(observer.name, "ferris");
+});
+
+/// ## Borrowing a Sibling Field
+/// A struct cannot hold an inspector of one of its own fields: all fields
+/// are dropped together with the struct, whose drop glue runs the `Drop` of
+/// the inspector.
+/// ```
+/// struct World<'a> { inspector: Option<Inspector<'a>>, name: String }
+/// let mut world = World { inspector: None, name: String::from("world") };
+/// world.inspector = Some(Inspector { name: &world.name, log: &log });
+/// // ^ Error: `world.name` does not live long enough: borrow might be used
+/// //          here, when `world` is dropped and runs the destructor for type
+/// //          `World<'_>`
+/// ```
+/// The struct is also self-referential (see self_referential.rs): even
+/// without dropck, it could not be moved anymore.
+fn borrowing_a_sibling_field() {}
+
+/// ## Escaping with `ManuallyDrop`
+/// `ManuallyDrop<T>` never runs the `Drop` of `T`, so it does not take part
+/// in the drop check: the borrow may dangle when the wrapper goes away. The
+/// inspector is then never dropped (it is leaked), unless dropped explicitly
+/// with `ManuallyDrop::drop`, an `unsafe` operation since the borrow may be
+/// dangling by then.
+struct World<'a> {
+    inspector: Option<ManuallyDrop<Inspector<'a>>>,
+    name: String,
+}
+
+runnable!(escaping_with_manually_drop, {
+    let log: Cell<usize> = Cell::new(0);
+    {
+        let mut world: World = World { inspector: None, name: "world".into() };
+        let inspector: Inspector = Inspector { name: &world.name, log: &log };
+        world.inspector = Some(ManuallyDrop::new(inspector));
+        // drop(world);
+        // ^ Error: cannot move out of `world` because it is borrowed (by its
+        //          own inspector)
+    }   // <-- `world` is dropped, the inspector is leaked
+    assert_eq!(log.get(), 0);
+
+    // Dropping explicitly, while the borrowed data is known to be alive
+    let name: String = String::from("ferris");
+    let mut inspector: ManuallyDrop<Inspector> =
+        ManuallyDrop::new(Inspector { name: &name, log: &log });
+    // SAFETY: `name` is alive, and `inspector` is not used afterwards.
+    unsafe { ManuallyDrop::drop(&mut inspector) };
+    assert_eq!(log.get(), 6);
+});
+
+/// ## Resolving Drop Check Errors
+/// 1. Declare the borrowed data first, so that it is dropped last
+/// 2. Move the data into an outer scope than the value borrowing it
+/// 3. Remove the `Drop` implementation, if it does not need the borrow, or
+///    store owned data (`String`, `Rc<String>`) instead of references
+/// 4. As a last resort, `ManuallyDrop` opts out of the check, moving the
+///    responsibility of dropping (or leaking) the value to the programmer
+///
+/// Note: `std` containers (`Vec`, `Box`) do not read their elements when
+///       dropped, only drop them: the unstable `#[may_dangle]` attribute tells
+///       the compiler so, and lets `Vec<&'a T>` hold dangling references at
+///       the time it is dropped.
+runnable!(resolving_drop_check_errors, {
+    let log: Cell<usize> = Cell::new(0);
+    let name: String = String::from("ferris");    // 1. declared first
+    let inspectors: Vec<Inspector> = vec![
+        Inspector { name: &name, log: &log },
+        Inspector { name: &name, log: &log },
+    ];
+    drop(inspectors);
+    assert_eq!(log.get(), 12);
+
+    // `Vec` does not read its elements: this compiles, unlike `Inspector`
+    let references: Vec<&String>;
+    let other: String = String::from("other");
+    references = vec![&other];
+    assert_eq!(references.len(), 1);
+});
\ No newline at end of file
@@ -0,0 +1,210 @@
+/// # A Dynamic Error Type (~ the `anyhow` crate)
+/// Applications rarely match on errors: they propagate them up to a place
+/// which reports them. Defining an enum for every function's errors (see
+/// errors.rs) is then a lot of boilerplate for little benefit. Instead, a
+/// single type can hold any error, behind a `Box<dyn Error>`, plus the
+/// context gathered while it was propagated.
+///
+/// This lesson rebuilds the core of `anyhow`:
+/// - `AnyError`: a boxed error, `Send + Sync` so it can cross threads
+/// - a blanket `From`, so that `?` converts any error into it
+/// - `.context(...)`, recording where and why the error was propagated
+use std::error::Error;
+use std::fmt;
+use std::panic::Location;
+
+fn dyn_error() {}
+
+/// A context message, and where it was added.
+struct Frame {
+    message: String,
+    location: &'static Location<'static>,
+}
+
+pub struct AnyError {
+    error: Box<dyn Error + Send + Sync + 'static>,
+    frames: Vec<Frame>,                 // innermost first
+}
+
+pub type Result<T, E = AnyError> = std::result::Result<T, E>;
+
+/// ## Conversion from Any Error
+/// Every error converts into `AnyError`, so `?` works on any `Result` in a
+/// function returning `Result<T, AnyError>`.
+///
+/// The catch: `AnyError` itself must not implement `Error`. Otherwise the
+/// blanket impl would include `From<AnyError> for AnyError`, which overlaps
+/// with the reflexive `impl<T> From<T> for T` of the standard library (see
+/// blanket_impls.rs):
+/// ```
+/// impl Error for AnyError {}
+/// // ^ Error: conflicting implementations of trait `From<AnyError>` for
+/// //          type `AnyError`
+/// ```
+/// `anyhow::Error` does not implement `Error` for the same reason.
+impl<E: Error + Send + Sync + 'static> From<E> for AnyError {
+    fn from(error: E) -> AnyError {
+        AnyError { error: Box::new(error), frames: Vec::new() }
+    }
+}
+
+/// Errors which are just a message, for `AnyError::msg`.
+#[derive(Debug)]
+struct MessageError(String);
+
+impl fmt::Display for MessageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for MessageError {}
+
+impl AnyError {
+    pub fn msg(message: impl fmt::Display) -> AnyError {
+        AnyError::from(MessageError(message.to_string()))
+    }
+
+    /// ## Context Frames
+    /// `#[track_caller]` makes `Location::caller()` return the location of
+    /// the call to `context`, not a line in this file: a cheap backtrace,
+    /// with one frame per context, and no debug symbols needed.
+    #[track_caller]
+    pub fn context(mut self, message: impl fmt::Display) -> AnyError {
+        let location: &'static Location<'static> = Location::caller();
+        self.frames.push(Frame { message: message.to_string(), location });
+        self
+    }
+
+    /// The messages from the outermost context down to the root cause,
+    /// following `source` below the wrapped error (see errors.rs).
+    pub fn chain(&self) -> Vec<String> {
+        let mut messages: Vec<String> = self.frames.iter().rev()
+            .map(|frame| frame.message.clone())
+            .collect();
+        let mut current: Option<&(dyn Error + 'static)> = Some(&*self.error);
+        while let Some(error) = current {
+            messages.push(error.to_string());
+            current = error.source();
+        }
+        messages
+    }
+
+    /// Recover the concrete type of the wrapped error (see dyn_internals.rs).
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref::<E>()
+    }
+}
+
+/// ## Reporting
+/// `Display` shows the outermost message, as a one-line summary. `Debug`,
+/// which is what `main` prints when it returns an `Err`, shows everything:
+/// the causes, then the context frames with their locations.
+impl fmt::Display for AnyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.frames.last() {
+            Some(frame) => f.write_str(&frame.message),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl fmt::Debug for AnyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chain: Vec<String> = self.chain();
+        write!(f, "{}", chain[0])?;
+        if chain.len() > 1 {
+            write!(f, "\n\nCaused by:")?;
+            for (index, message) in chain[1..].iter().enumerate() {
+                write!(f, "\n    {}: {}", index, message)?;
+            }
+        }
+        if !self.frames.is_empty() {
+            write!(f, "\n\nContext:")?;
+            for frame in self.frames.iter().rev() {
+                write!(f, "\n    at {}:{}", frame.location.file(),
+                    frame.location.line())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// ## Context on Results
+/// An extension trait (see patterns/extension_traits.rs) adds `.context` to
+/// any `Result` whose error converts into `AnyError`, including `AnyError`
+/// itself through the reflexive `From`.
+pub trait Context<T> {
+    fn context(self, message: impl fmt::Display) -> Result<T>;
+    /// The message is only built if there is an error.
+    fn with_context<M: fmt::Display>(self, message: impl FnOnce() -> M)
+        -> Result<T>;
+}
+
+impl<T, E: Into<AnyError>> Context<T> for Result<T, E> {
+    #[track_caller]
+    fn context(self, message: impl fmt::Display) -> Result<T> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.into().context(message)),
+        }
+    }
+
+    #[track_caller]
+    fn with_context<M: fmt::Display>(self, message: impl FnOnce() -> M)
+        -> Result<T>
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.into().context(message())),
+        }
+    }
+}
+
+fn parse_port(text: &str) -> Result<u16> {
+    let port: u16 = text.trim().parse()?;          // ParseIntError -> AnyError
+    if port < 1024 {
+        return Err(AnyError::msg(format!("port {} is reserved", port)));
+    }
+    Ok(port)
+}
+
+fn load_port(config: &str) -> Result<u16> {
+    let line: &str = config.lines()
+        .find(|line| line.starts_with("port="))
+        .ok_or(AnyError::msg("no `port` key"))?;
+    parse_port(&line["port=".len()..])
+        .with_context(|| format!("invalid line `{}`", line))
+}
+
+runnable!(context_and_chains, {
+    assert_eq!(load_port("host=a\nport=8080").unwrap(), 8080);
+
+    let error: AnyError = load_port("port=80")
+        .context("cannot load the configuration")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "cannot load the configuration");
+    assert_eq!(error.chain(), [
+        "cannot load the configuration",
+        "invalid line `port=80`",
+        "port 80 is reserved",
+    ]);
+    println!("{:?}", error);
+    assert!(format!("{:?}", error).contains("at src/dyn_error.rs:"));
+
+    let error: AnyError = load_port("port=eighty").unwrap_err();
+    assert!(error.downcast_ref::<std::num::ParseIntError>().is_some());
+    assert_eq!(error.chain()[1], "invalid digit found in string");
+});
+
+/// ## Sending Errors Across Threads
+/// The `Send + Sync` bounds reject errors holding an `Rc` or a `RefCell`,
+/// but let any `AnyError` be returned from a thread, or an async task.
+runnable!(sending_errors_across_threads, {
+    let handle = std::thread::spawn(|| -> Result<u16> {
+        let port: u16 = parse_port("22").context("in the worker")?;
+        Ok(port)
+    });
+    let error: AnyError = handle.join().unwrap().unwrap_err();
+    assert_eq!(error.chain(), ["in the worker", "port 22 is reserved"]);
+});
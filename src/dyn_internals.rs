@@ -0,0 +1,152 @@
+/// # Trait Object Internals (~ C++ Virtual Tables)
+/// A reference to a trait object (`&dyn Trait`, `Box<dyn Trait>`, ...) is a
+/// `fat pointer`: it is made of two pointers (see traits.rs):
+/// - a `data pointer`, to the concrete value
+/// - a `vtable pointer`, to a static table generated by the compiler for each
+///   pair (concrete type, trait), containing the size and alignment of the
+///   type, its destructor and a pointer to each method of the trait
+///
+/// Unlike C++, the vtable pointer is stored in the reference, not in the
+/// value: values do not pay for dynamic dispatch unless it is used.
+use std::mem::{align_of, size_of, transmute};
+
+fn dyn_internals() {}
+
+trait Speak {
+    fn speak(&self) -> String;
+}
+
+struct Dog { name: &'static str }
+struct Robot { id: u32, volume: u8 }
+
+impl Speak for Dog {
+    fn speak(&self) -> String { format!("{}: Woff", self.name) }
+}
+impl Speak for Robot {
+    fn speak(&self) -> String {
+        format!("#{}: BEEP (volume {})", self.id, self.volume)
+    }
+}
+
+/// ## Fat Pointers
+/// Thin references to sized types have the size of one pointer. References
+/// to unsized types (trait objects, but also slices and `str`) carry extra
+/// metadata: a vtable pointer for trait objects, a length for slices.
+runnable!(fat_pointers, {
+    let word: usize = size_of::<usize>();
+    println!("&Dog:           {} bytes", size_of::<&Dog>());
+    println!("&dyn Speak:     {} bytes", size_of::<&dyn Speak>());
+    println!("Box<dyn Speak>: {} bytes", size_of::<Box<dyn Speak>>());
+    println!("&[u8]:          {} bytes", size_of::<&[u8]>());
+    assert_eq!(size_of::<&Dog>(), word);
+    assert_eq!(size_of::<&dyn Speak>(), 2 * word);
+    assert_eq!(size_of::<&[u8]>(), 2 * word);
+});
+
+/// ## Splitting a Trait Object
+/// A fat pointer can be reinterpreted as a pair of thin pointers with
+/// `transmute` (see unsafe_code.rs). This is for demonstration only: the
+/// layout of fat pointers and of vtables is not specified, and may change
+/// between compiler versions. The proper way to get the metadata of a pointer
+/// will be `std::ptr::metadata`, currently unstable.
+///
+/// With the current compiler, the vtable starts with a header containing the
+/// destructor, the size and the alignment of the concrete type, followed by
+/// the methods of the trait, in declaration order.
+unsafe fn split(object: &dyn Speak) -> (*const (), *const usize) {
+    transmute::<&dyn Speak, (*const (), *const usize)>(object)
+}
+
+runnable!(splitting_trait_objects, {
+    let dog: Dog = Dog { name: "Rex" };
+    let robot: Robot = Robot { id: 42, volume: 11 };
+    let objects: [&dyn Speak; 2] = [&dog, &robot];
+
+    for object in objects {
+        let (data, vtable): (*const (), *const usize) =
+            unsafe { split(object) };
+        let (size, align): (usize, usize) =
+            unsafe { (*vtable.add(1), *vtable.add(2)) };
+        println!("data: {:p} vtable: {:p} size: {} align: {}",
+            data, vtable, size, align);
+        // The header matches what `size_of_val` reads from the vtable
+        assert_eq!(size, std::mem::size_of_val(object));
+        assert_eq!(align, std::mem::align_of_val(object));
+    }
+    // The data pointer is just the address of the value
+    let (data, _) = unsafe { split(&dog) };
+    assert_eq!(data, &dog as *const Dog as *const ());
+    // The same type behind the same trait uses the same vtable
+    let other: Dog = Dog { name: "Fido" };
+    let (_, first) = unsafe { split(&dog) };
+    let (_, second) = unsafe { split(&other) };
+    println!("same vtable for both dogs: {}", first == second);
+});
+
+/// ## Hand-Built Vtables
+/// Dynamic dispatch is nothing more than calling a function pointer (see
+/// closures.rs) read from a table. The following reproduces `Box<dyn Speak>`
+/// without `dyn`: a type-erased data pointer, plus a static table of
+/// functions that know the concrete type.
+struct SpeakVTable {
+    drop: unsafe fn(*mut ()),
+    size: usize,
+    align: usize,
+    speak: unsafe fn(*const ()) -> String,
+}
+
+/// Functions generic over the concrete type, each instantiated (see
+/// generics.rs) once per type: they restore the type erased from the data
+/// pointer.
+unsafe fn drop_erased<T>(data: *mut ()) { drop(Box::from_raw(data as *mut T)); }
+unsafe fn speak_erased<T: Speak>(data: *const ()) -> String {
+    (*(data as *const T)).speak()
+}
+
+/// One static vtable per concrete type, like the ones generated by the
+/// compiler. A constant is used so that `&VTable` is promoted to `'static`.
+trait HasVTable: Speak + Sized {
+    const VTABLE: SpeakVTable = SpeakVTable {
+        drop: drop_erased::<Self>,
+        size: size_of::<Self>(),
+        align: align_of::<Self>(),
+        speak: speak_erased::<Self>,
+    };
+}
+impl<T: Speak> HasVTable for T {}
+
+/// A manual `Box<dyn Speak>`
+struct DynSpeak {
+    data: *mut (),
+    vtable: &'static SpeakVTable,
+}
+
+impl DynSpeak {
+    fn new<T: HasVTable>(value: T) -> Self {
+        DynSpeak {
+            data: Box::into_raw(Box::new(value)) as *mut (),
+            vtable: &T::VTABLE,
+        }
+    }
+    fn speak(&self) -> String {
+        // Dynamic dispatch: look up the method, then call it on the data
+        unsafe { (self.vtable.speak)(self.data) }
+    }
+}
+
+impl Drop for DynSpeak {
+    fn drop(&mut self) { unsafe { (self.vtable.drop)(self.data) } }
+}
+
+runnable!(hand_built_vtables, {
+    let speakers: Vec<DynSpeak> = vec![
+        DynSpeak::new(Dog { name: "Rex" }),
+        DynSpeak::new(Robot { id: 7, volume: 3 }),
+    ];
+    for speaker in &speakers {
+        println!("{} (size: {}, align: {})",
+            speaker.speak(), speaker.vtable.size, speaker.vtable.align);
+    }
+    // Same size as the compiler's version: data pointer + vtable pointer
+    assert_eq!(size_of::<DynSpeak>(), size_of::<Box<dyn Speak>>());
+});
\ No newline at end of file
@@ -0,0 +1,90 @@
+/// # Embedded Assets
+/// Files can be embedded in the binary at compile time:
+/// - `include_str!("path")` expands to a `&'static str` with the content of
+///   a UTF-8 text file
+/// - `include_bytes!("path")` expands to a `&'static [u8; N]` with the
+///   content of any file
+///
+/// The program then needs no file at runtime: the assets travel with the
+/// binary, and cannot be missing or modified (see also `include!` in
+/// build_scripts.rs, which includes source code).
+fn embedded_assets() {}
+
+/// ## Text Assets
+/// The banner printed by the entry point (see main.rs).
+pub const BANNER: &str = include_str!("../assets/banner.txt");
+
+runnable!(text_assets, {
+    print!("{}", BANNER);
+    println!("{} lines, {} bytes", BANNER.lines().count(), BANNER.len());
+    // An invalid UTF-8 file is rejected at compile time by `include_str!`
+});
+
+/// ## Binary Assets
+/// A 16x8 black and white image of a crab, in the binary `PBM` format: a
+/// short text header (`P4`, width, height), followed by the pixels packed as
+/// bits, one row after the other.
+const CRAB: &[u8; 24] = include_bytes!("../assets/crab.pbm");
+
+/// Render a binary PBM image as ASCII art.
+fn render_pbm(image: &[u8]) -> Option<String> {
+    // Header: "P4\n<width> <height>\n"
+    let mut fields = image.split(|byte| byte.is_ascii_whitespace());
+    if fields.next()? != b"P4" { return None; }
+    let width: usize = std::str::from_utf8(fields.next()?).ok()?.parse().ok()?;
+    let height: usize = std::str::from_utf8(fields.next()?).ok()?.parse().ok()?;
+    let header_length: usize = image.len() - (width + 7) / 8 * height;
+    let pixels: &[u8] = &image[header_length..];
+
+    let mut art: String = String::new();
+    for row in pixels.chunks((width + 7) / 8).take(height) {
+        for x in 0..width {
+            let bit: u8 = row[x / 8] >> (7 - x % 8) & 1;
+            art.push(if bit == 1 { '#' } else { ' ' });
+        }
+        art.push('\n');
+    }
+    Some(art)
+}
+
+runnable!(binary_assets, {
+    // The length of the array is known at compile time
+    println!("crab: {} bytes", CRAB.len());
+    print!("{}", render_pbm(CRAB).unwrap());
+});
+
+/// ## Path Resolution
+/// The path is relative to the file containing the macro call, not to the
+/// crate root or to the working directory: `src/embedded_assets.rs` uses
+/// `../assets/banner.txt`, while a lesson in `src/patterns/` would need
+/// `../../assets/banner.txt`.
+///
+/// To make paths independent of the location of the source file, they can
+/// start from the directory of `Cargo.toml` instead:
+/// ```
+/// include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/banner.txt"))
+/// ```
+/// A missing file is a compile error:
+/// ```
+/// include_str!("../assets/missing.txt");
+/// // ^ Error: couldn't read `src/../assets/missing.txt`: No such file or
+/// //          directory
+/// ```
+runnable!(path_resolution, {
+    const SAME_BANNER: &str =
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/banner.txt"));
+    assert_eq!(SAME_BANNER, BANNER);
+});
+
+/// ## Binary Size
+/// - Embedded files are copied into the read-only data of the binary: the
+///   binary grows by the size of each asset, and every copy of the program
+///   (or every crate embedding the same file) carries it
+/// - Large assets also slow down compilation, and Cargo recompiles the crate
+///   whenever an embedded file changes
+/// - The bytes are only aligned to 1 byte, so they cannot be reinterpreted as
+///   wider types (e.g. `&[u32]`) without copying them first
+///
+/// Big or frequently changing assets (images, models, databases) are better
+/// loaded at runtime from files shipped next to the binary.
+fn binary_size() {}
\ No newline at end of file
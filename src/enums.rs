@@ -51,4 +51,121 @@ enum Color {
 /// ## Enum Value
 /// Finally, you can extract the value of a variant through casting.
 const ZERO_VALUE: i32 = Number::Zero as i32;
-const RED_VALUE: i32 = Color::Red as i32;
\ No newline at end of file
+const RED_VALUE: i32 = Color::Red as i32;
+
+/// ## Memory Representation
+/// An enum is stored as a `discriminant` (which variant it holds) next to
+/// the data of its largest variant (see layout.rs for niches and padding).
+mod memory_representation {
+    use std::mem::{self, size_of, Discriminant};
+
+    /// ### Sizes
+    /// Fieldless enums only store their discriminant, in the smallest integer
+    /// fitting all the values. Data-carrying enums add the largest variant,
+    /// unless the discriminant can hide in a `niche` of the data (e.g. a null
+    /// pointer for `Option<Box<T>>`).
+    enum Direction { North, East, South, West }
+    enum Shape { Circle(f64), Rectangle(f64, f64), Point }
+
+    runnable!(sizes, {
+        assert_eq!(size_of::<Direction>(), 1);
+        // 16 bytes for `Rectangle`, 8 for the discriminant (aligned to `f64`)
+        assert_eq!(size_of::<Shape>(), 24);
+        // A box is never null: `None` is stored as the null pointer
+        assert_eq!(size_of::<Option<Box<u64>>>(), size_of::<Box<u64>>());
+        assert_eq!(size_of::<Option<Box<u64>>>(), size_of::<usize>());
+        println!("Direction:        size={}", size_of::<Direction>());
+        println!("Shape:            size={}", size_of::<Shape>());
+        println!("Option<Box<u64>>: size={}", size_of::<Option<Box<u64>>>());
+    });
+
+    /// ### Choosing the Discriminant Type
+    /// `#[repr(u8)]` (or any other integer type) fixes the type of the
+    /// discriminant, and puts it first in memory. Values outside the range of
+    /// the type are rejected at compile-time.
+    /// ```
+    /// #[repr(u8)]
+    /// enum Big { Value = 256 }
+    /// // ^ Error: literal out of range for `u8`
+    /// ```
+    #[repr(u8)]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Opcode { Push = 0x01, Pop = 0x02, Jump = 0x10 }
+
+    /// With data, `#[repr(u8)]` gives a C-compatible layout: a `u8` tag
+    /// followed by the fields of the variant.
+    #[repr(u8)]
+    enum Instruction { Push(u32) = 0x01, Pop = 0x02, Jump(u16) = 0x10 }
+
+    impl Instruction {
+        fn opcode(&self) -> u8 {
+            // SAFETY: with `#[repr(u8)]`, the enum starts with its `u8` tag.
+            unsafe { *(self as *const Instruction as *const u8) }
+        }
+    }
+
+    /// The other way around is not a cast: a conversion must check the value.
+    impl TryFrom<u8> for Opcode {
+        type Error = u8;
+        fn try_from(byte: u8) -> Result<Opcode, u8> {
+            match byte {
+                0x01 => Ok(Opcode::Push),
+                0x02 => Ok(Opcode::Pop),
+                0x10 => Ok(Opcode::Jump),
+                other => Err(other),
+            }
+        }
+    }
+
+    runnable!(choosing_the_discriminant_type, {
+        assert_eq!(size_of::<Opcode>(), 1);
+        // 1 byte of tag + 3 bytes of padding + 4 bytes for `Push(u32)`
+        assert_eq!(size_of::<Instruction>(), 8);
+        assert_eq!(Opcode::Jump as u8, 0x10);
+        assert_eq!(Instruction::Jump(3).opcode(), 0x10);
+        assert_eq!(Opcode::try_from(0x02), Ok(Opcode::Pop));
+        assert_eq!(Opcode::try_from(0x03), Err(0x03));
+        // let opcode: Opcode = 0x02 as Opcode;
+        // ^ Error: non-primitive cast: `u8` as `Opcode`
+    });
+
+    /// ### Reading Discriminants
+    /// `std::mem::discriminant` returns an opaque value, equal for two values
+    /// of the same variant whatever their data. It works on every enum, and
+    /// does not reveal the actual number.
+    runnable!(reading_discriminants, {
+        let circle: Discriminant<Shape> =
+            mem::discriminant(&Shape::Circle(1.0));
+        assert_eq!(circle, mem::discriminant(&Shape::Circle(2.0)));
+        assert_ne!(circle, mem::discriminant(&Shape::Point));
+
+        let shapes: Vec<Shape> =
+            vec![Shape::Point, Shape::Circle(1.0), Shape::Circle(3.0)];
+        let circles: usize = shapes.iter()
+            .filter(|shape| mem::discriminant(*shape) == circle)
+            .count();
+        assert_eq!(circles, 2);
+    });
+
+    /// ### The Dangers of `as`
+    /// `as` only works on enums without data: on data-carrying enums, even
+    /// for a fieldless variant, it does not compile.
+    /// ```
+    /// let point: i32 = Shape::Point as i32;
+    /// // ^ Error: non-primitive cast: `Shape` as `i32`
+    /// ```
+    /// On fieldless enums, `as` silently truncates to smaller integer types,
+    /// and keeps compiling when variants are added, removed or reordered,
+    /// which changes implicit discriminants. Prefer explicit values, and
+    /// `From`/`TryFrom` implementations for conversions.
+    #[derive(Clone, Copy)]
+    enum Color { Red = 0xff0000, Green = 0x00ff00, Blue = 0x0000ff }
+
+    runnable!(dangers_of_as, {
+        assert_eq!(Color::Red as u32, 0xff0000);
+        assert_eq!(Color::Red as u8, 0x00);          // truncated!
+        assert_eq!(Color::Blue as u8, 0xff);
+        assert_eq!(Direction::West as u8, 3);        // position dependent
+        assert_eq!(u8::try_from(Color::Red as u32).ok(), None);  // checked
+    });
+}
\ No newline at end of file
@@ -17,7 +17,7 @@ runnable!(unrecoverable_errors, {
     let branch: &str = if true { "correct!" } else { panic!("cannot recover") };
 
     /// The bottom type is experimental and cannot be used explicitly for typing
-    /// variables. It can still be used to type functions.
+    /// variables. It can still be used to type functions (see never_type.rs).
     fn bottom() -> ! { panic!("I am the bottom type"); }
     // let bottom: ! = panic!("I am the bottom type");
     // ^ Error: type `!` is experimental
@@ -241,4 +241,112 @@ runnable!(error_hierachies, {
         Err(downcast_error) =>
             println!("downcasting failed with {}", downcast_error),
     }
-});
\ No newline at end of file
+});
+
+/// ## Error Source Chains
+/// `VectorError` has no cause, so its `source` always returns `None`. When
+/// an error is caused by another one, it should keep it, and return it from
+/// `source`: each layer adds its own context, while the original error stays
+/// available below it. Here, an `io::Error` (low level) causes a
+/// `ConfigError` (mid level), which causes a `StartupError` (app level).
+///
+/// `Display` only describes the current layer, never its source: printing
+/// the chain is the job of whoever reports the error, e.g. `report` below.
+#[derive(Debug)]
+enum ConfigError {
+    Unreadable { path: String, cause: std::io::Error },
+    MissingKey(&'static str),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConfigError::Unreadable { path, .. } =>
+                write!(f, "cannot read config file `{}`", path),
+            ConfigError::MissingKey(key) =>
+                write!(f, "missing config key `{}`", key),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Unreadable { cause, .. } => Some(cause),
+            ConfigError::MissingKey(_) => None,
+        }
+    }
+}
+
+/// The app-level error is derived instead (see proc_macros.rs): the
+/// attributes generate the same `Display`, `source` and `From` impls as
+/// those written by hand for `ConfigError`.
+#[derive(Debug, playground_derive::Error)]
+enum StartupError {
+    #[error("cannot start the server")]
+    Config(#[from] ConfigError),
+}
+
+fn read_port(path: &str) -> Result<u16, ConfigError> {
+    let content: String = std::fs::read_to_string(path).map_err(|cause| {
+        ConfigError::Unreadable { path: path.to_string(), cause }
+    })?;
+    content.lines()
+        .find_map(|line| line.strip_prefix("port="))
+        .and_then(|port| port.trim().parse().ok())
+        .ok_or(ConfigError::MissingKey("port"))
+}
+
+fn start_server(config_path: &str) -> Result<u16, StartupError> {
+    let port: u16 = read_port(config_path)?;
+    Ok(port)
+}
+
+/// Walk the chain through `source`, one message per layer.
+fn report(error: &dyn std::error::Error) -> Vec<String> {
+    let mut messages: Vec<String> = vec![error.to_string()];
+    let mut current: Option<&dyn std::error::Error> = error.source();
+    while let Some(cause) = current {
+        messages.push(cause.to_string());
+        current = cause.source();
+    }
+    messages
+}
+
+runnable!(error_source_chains, {
+    use std::error::Error;
+
+    let error: StartupError = start_server("/no/such/config.toml").unwrap_err();
+    let chain: Vec<String> = report(&error);
+    for (depth, message) in chain.iter().enumerate() {
+        println!("{:>1$}{2}", "", 2 * depth, message);
+    }
+    assert_eq!(chain.len(), 3);
+    assert_eq!(chain[0], "cannot start the server");
+    assert_eq!(chain[1], "cannot read config file `/no/such/config.toml`");
+
+    // Any layer can be downcast, e.g. to react to the low-level cause
+    let io_error = error.source().and_then(|cause| cause.source())
+        .and_then(|cause| cause.downcast_ref::<std::io::Error>())
+        .unwrap();
+    assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound);
+
+    // A chain may also stop in the middle
+    let directory: crate::util::TestDir = crate::util::TestDir::new("errors");
+    let path: std::path::PathBuf = directory.join("no_port.toml");
+    std::fs::write(&path, "host=localhost\n").unwrap();
+    let error: StartupError = start_server(path.to_str().unwrap()).unwrap_err();
+    assert_eq!(report(&error),
+        ["cannot start the server", "missing config key `port`"]);
+});
+
+/// The whole chain at once, checked against src/snapshots (see
+/// testing/snapshots.rs). The chain ending with an `io::Error` is left out:
+/// its `Debug` output depends on the operating system.
+runnable!(error_chain_snapshot, {
+    let directory: crate::util::TestDir = crate::util::TestDir::new("errors");
+    let path: std::path::PathBuf = directory.join("no_port.toml");
+    std::fs::write(&path, "host=localhost\n").unwrap();
+    let error: StartupError = start_server(path.to_str().unwrap()).unwrap_err();
+    assert_debug_snapshot!("errors_missing_port", error);
+});
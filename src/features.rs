@@ -0,0 +1,79 @@
+/// # Cargo Features
+/// `Features` are named flags declared in the `[features]` table of
+/// `Cargo.toml`, which users of a package can enable to opt into optional
+/// code or dependencies. Each enabled feature `name` becomes a configuration
+/// option `feature = "name"` (see the conditional compilation section of
+/// annotations.rs), so code can be included or removed with `#[cfg]`.
+///
+/// The features of this playground are:
+/// ```
+/// [features]
+/// default = []                                 // enabled unless opted out
+/// tokio = ["dep:tokio"]                        // enables a dependency
+/// extra-lessons = []                           // enables code only
+/// verbose-harness = []
+/// count-allocations = []
+/// native-c = []
+/// all-lessons = ["extra-lessons", "tokio"]     // enables other features
+/// ```
+/// They are enabled from the command line:
+/// - `cargo test --features extra-lessons,verbose-harness`
+/// - `cargo test --all-features`
+/// - `cargo test --no-default-features` (disable the `default` features)
+fn features() {}
+
+/// ## Checking Features
+/// `cfg!(feature = "...")` evaluates to a boolean at compile time, while the
+/// code is still compiled (and type-checked) in every configuration.
+pub fn enabled_features() -> Vec<&'static str> {
+    let mut enabled: Vec<&'static str> = Vec::new();
+    if cfg!(feature = "tokio") { enabled.push("tokio"); }
+    if cfg!(feature = "extra-lessons") { enabled.push("extra-lessons"); }
+    if cfg!(feature = "verbose-harness") { enabled.push("verbose-harness"); }
+    if cfg!(feature = "count-allocations") {
+        enabled.push("count-allocations");
+    }
+    if cfg!(feature = "native-c") { enabled.push("native-c"); }
+    if cfg!(feature = "all-lessons") { enabled.push("all-lessons"); }
+    enabled
+}
+
+runnable!(checking_features, {
+    println!("enabled features: {:?}", enabled_features());
+    // `all-lessons` implies the features it lists
+    if cfg!(feature = "all-lessons") {
+        assert!(cfg!(feature = "extra-lessons") && cfg!(feature = "tokio"));
+    }
+});
+
+/// ## Conditional Compilation with Features
+/// `#[cfg(feature = "...")]` removes an item entirely when the feature is
+/// disabled: the code is not even type-checked, and the dependencies it uses
+/// can be optional. This is how `async_tokio.rs` is only compiled with the
+/// `tokio` feature (see main.rs), and how `verbose-harness` changes the
+/// output of `runnable!` (see util.rs).
+#[cfg(feature = "extra-lessons")]
+mod extra {
+    /// Only compiled (and run) with `--features extra-lessons`
+    runnable!(extra_lesson, {
+        println!("This lesson only exists with the `extra-lessons` feature");
+        let answer: u32 = (1..=6).product::<u32>() / 17;
+        println!("a bonus fact: 6! / 17 = {}", answer);
+    });
+}
+
+#[cfg(not(feature = "extra-lessons"))]
+runnable!(extra_lesson_disabled, {
+    println!("Run `cargo test --features extra-lessons` for a bonus lesson");
+});
+
+/// ## Guidelines
+/// - Features should be additive: enabling a feature should only add code,
+///   never remove or change existing behaviour, because Cargo enables the
+///   union of the features requested by all the packages depending on a
+///   crate (`#[cfg(not(feature = "..."))]` should be used sparingly)
+/// - Code must compile with any combination of features (tools like
+///   `cargo hack --feature-powerset` check them all)
+/// - Optional dependencies are enabled with `dep:name`, so that the
+///   dependency does not implicitly create a feature with the same name
+fn guidelines() {}
\ No newline at end of file
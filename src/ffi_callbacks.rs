@@ -0,0 +1,126 @@
+/// # FFI Callbacks (~ C function pointers)
+/// Many C APIs call back into the caller: comparison functions for sorting,
+/// event handlers, visitors. From Rust, a callback is a function with the C
+/// calling convention, `extern "C" fn`, passed as a function pointer (see
+/// function_pointers.rs).
+use playground_ffi::playground_for_each;
+use std::cmp::Ordering;
+use std::ffi::{c_int, c_void};
+
+fn ffi_callbacks() {}
+
+/// ## Registering a Callback
+/// `qsort` from the C standard library sorts any array, given the size of
+/// its elements and a function comparing two of them through `void *`
+/// pointers (returning a negative, zero or positive number).
+extern "C" {
+    fn qsort(
+        base: *mut c_void, count: usize, size: usize,
+        compare: extern "C" fn(*const c_void, *const c_void) -> c_int,
+    );
+}
+
+/// Called by C: it must not panic, since unwinding out of an `extern "C"`
+/// function aborts the process (see reverse_ffi.rs).
+extern "C" fn compare_i32(a: *const c_void, b: *const c_void) -> c_int {
+    // SAFETY: `qsort` passes pointers to elements of the array, which holds
+    // `i32`s (see `sort_with_qsort`).
+    let (a, b): (i32, i32) =
+        unsafe { (*(a as *const i32), *(b as *const i32)) };
+    match a.cmp(&b) {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+fn sort_with_qsort(values: &mut [i32]) {
+    // SAFETY: the pointer, count and element size describe `values`, and
+    // `compare_i32` compares `i32`s.
+    unsafe {
+        qsort(values.as_mut_ptr() as *mut c_void, values.len(),
+            std::mem::size_of::<i32>(), compare_i32);
+    }
+}
+
+runnable!(registering_a_callback, {
+    let mut values: [i32; 6] = [5, -3, 8, 0, 8, 1];
+    sort_with_qsort(&mut values);
+    assert_eq!(values, [-3, 0, 1, 5, 8, 8]);
+});
+
+/// ## Closures Cannot Be Passed Directly
+/// A function pointer is only the address of some code. A closure capturing
+/// variables also carries data (see closures.rs), which does not fit in a
+/// function pointer, and the ABI of closures is not `extern "C"` anyway:
+/// ```
+/// let descending: bool = true;
+/// qsort(base, count, size, |a, b| if descending { ... } else { ... });
+/// // ^ Error: mismatched types: expected fn pointer, found closure
+/// //   note: closures can only be coerced to `fn` types if they do not
+/// //         capture any variables
+/// ```
+/// `qsort` has no way to receive extra data: the only way to sort in
+/// descending order is another function, or global state. That is why most
+/// C APIs with callbacks also accept a `void *user_data` argument (e.g.
+/// `qsort_r`, whose argument order differs between glibc and BSD).
+extern "C" fn compare_i32_descending(
+    a: *const c_void, b: *const c_void
+) -> c_int {
+    compare_i32(b, a)
+}
+
+runnable!(closures_cannot_be_passed_directly, {
+    let mut values: [i32; 4] = [2, 9, -1, 4];
+    // SAFETY: as in `sort_with_qsort`.
+    unsafe {
+        qsort(values.as_mut_ptr() as *mut c_void, values.len(),
+            std::mem::size_of::<i32>(), compare_i32_descending);
+    }
+    assert_eq!(values, [9, 4, 2, -1]);
+});
+
+/// ## The Trampoline Pattern
+/// With a `user_data` pointer, any closure can be passed:
+/// - the closure is passed as `user_data`, type-erased into a `*mut c_void`
+/// - the callback is a generic `extern "C" fn` (the `trampoline`),
+///   instantiated for the type of the closure: it casts `user_data` back to
+///   that type, and calls the closure
+///
+/// The C library `playground-ffi` (see reverse_ffi.rs) provides such an API:
+/// ```c
+/// typedef void (*PlaygroundVisitor)(void *user_data, int32_t value);
+/// bool playground_for_each(const int32_t *values, size_t count,
+///                          PlaygroundVisitor visitor, void *user_data);
+/// ```
+unsafe extern "C" fn trampoline<F: FnMut(i32)>(
+    user_data: *mut c_void, value: i32
+) {
+    // SAFETY: `user_data` is the `&mut F` given by `for_each`, which is
+    // still borrowed during the call.
+    let closure: &mut F = &mut *(user_data as *mut F);
+    closure(value);  // a panic here would abort: the closure must not panic
+}
+
+/// A safe wrapper: the closure is borrowed for the duration of the call, so
+/// C cannot keep `user_data` after it returns.
+fn for_each<F: FnMut(i32)>(values: &[i32], mut closure: F) {
+    let user_data: *mut c_void = &mut closure as *mut F as *mut c_void;
+    // SAFETY: `values` describes a valid array, and `trampoline::<F>`
+    // receives the `F` it expects.
+    unsafe {
+        playground_for_each(values.as_ptr(), values.len(),
+            Some(trampoline::<F>), user_data);
+    }
+}
+
+runnable!(trampoline_pattern, {
+    let mut sum: i64 = 0;
+    let mut seen: Vec<i32> = Vec::new();
+    for_each(&[3, 1, 4, 1, 5], |value| {
+        sum += value as i64;  // the closure captures local state
+        seen.push(value);
+    });
+    assert_eq!(sum, 14);
+    assert_eq!(seen, [3, 1, 4, 1, 5]);
+});
\ No newline at end of file
@@ -0,0 +1,101 @@
+/// # FFI Strings (~ char*)
+/// Rust and C represent strings differently:
+/// - `String`/`&str`: pointer + length, always valid UTF-8, may contain `\0`
+/// - C strings: pointer only, the end is marked by a `NUL` byte (`\0`), any
+///   encoding
+///
+/// `std::ffi` provides the C-compatible equivalents, owned and borrowed:
+/// - `CString` (~ `String`): an owned, `NUL`-terminated buffer, without any
+///   interior `NUL`
+/// - `CStr` (~ `&str`): a borrowed C string, e.g. received from C. Literals
+///   are written `c"text"`
+///
+/// See unsafe_code.rs for calling C, and reverse_ffi.rs for being called.
+use playground_ffi::{
+    playground_count_chars, playground_greet, playground_string_free,
+    playground_version,
+};
+use std::ffi::{c_char, CStr, CString, NulError};
+
+fn ffi_strings() {}
+
+extern "C" {
+    fn strlen(text: *const c_char) -> usize;
+}
+
+/// ## Rust to C
+/// `CString::new` copies the bytes and appends the `NUL`. It fails if the
+/// text contains a `NUL`, which C would take for the end of the string.
+runnable!(rust_to_c, {
+    let text: CString = CString::new("hello").unwrap();
+    // SAFETY: `text` is `NUL`-terminated, and alive during the call.
+    assert_eq!(unsafe { strlen(text.as_ptr()) }, 5);
+    assert_eq!(text.as_bytes_with_nul(), b"hello\0");
+
+    let error: NulError = CString::new("hel\0lo").unwrap_err();
+    println!("{}", error);  // nul byte found in provided data at position: 3
+    assert_eq!(error.nul_position(), 3);
+});
+
+/// ### Pointer Lifetimes
+/// `as_ptr` borrows the `CString`, but a raw pointer does not carry the
+/// borrow: the `CString` must outlive every use of the pointer.
+/// ```
+/// let pointer: *const c_char = CString::new("hi").unwrap().as_ptr();
+/// // ^ Warning: this creates a dangling pointer because temporary
+/// //            `CString` is dropped at end of statement
+/// unsafe { strlen(pointer); }  // UB: use after free
+/// ```
+fn pointer_lifetimes() {}
+
+/// ## C to Rust
+/// `CStr::from_ptr` wraps a pointer received from C, finding its length by
+/// scanning for the `NUL`. The lifetime of the resulting `&CStr` is chosen
+/// by the caller, unchecked: it must not outlive the C buffer, so it is best
+/// bound explicitly to its owner (here, a `'static` string).
+fn version() -> &'static CStr {
+    // SAFETY: the library returns a static, `NUL`-terminated string.
+    unsafe { CStr::from_ptr(playground_version()) }
+}
+
+/// The bytes are then validated as UTF-8:
+/// - `to_str`: borrows as `&str`, or fails on invalid UTF-8
+/// - `to_string_lossy`: replaces invalid sequences with `U+FFFD`, and only
+///   allocates when it has to
+runnable!(c_to_rust, {
+    let version: &str = version().to_str().expect("valid UTF-8");
+    assert!(version.starts_with("playground-ffi"));
+
+    let latin1: &CStr = c"caf\xe9";  // `é` in Latin-1, invalid in UTF-8
+    assert!(latin1.to_str().is_err());
+    assert_eq!(latin1.to_string_lossy(), "caf\u{fffd}");
+    assert_eq!(latin1.to_bytes().len(), 4);  // the raw bytes, without `NUL`
+});
+
+/// ## Validating Foreign Data
+/// A library receiving C strings cannot trust their encoding:
+/// `playground_count_chars` validates its input, and reports an error
+/// instead of assuming UTF-8.
+runnable!(validating_foreign_data, {
+    // SAFETY: both literals are `NUL`-terminated.
+    unsafe {
+        assert_eq!(playground_count_chars(c"caf\xc3\xa9".as_ptr()), 4);
+        assert_eq!(playground_count_chars(c"caf\xe9".as_ptr()), -1);
+        assert_eq!(playground_count_chars(std::ptr::null()), -1);
+    }
+});
+
+/// ## Returning Allocated Strings
+/// A library returning an allocated string gives up its ownership with
+/// `CString::into_raw`, and exports a matching `free` function rebuilding
+/// it with `CString::from_raw`: the string must be freed by the allocator
+/// that allocated it, never by C `free` (see reverse_ffi.rs).
+runnable!(returning_allocated_strings, {
+    // SAFETY: the argument is `NUL`-terminated, and the result is freed once,
+    // with the matching function, after its last use.
+    unsafe {
+        let greeting: *mut c_char = playground_greet(c"Rust".as_ptr());
+        assert_eq!(CStr::from_ptr(greeting).to_str(), Ok("Hello, Rust!"));
+        playground_string_free(greeting);
+    }
+});
\ No newline at end of file
@@ -0,0 +1,129 @@
+/// # Test Fixtures (~ setting the stage before the play)
+/// A fixture is the state a test needs before it starts: some data, a
+/// directory of files, a database. Many test frameworks have hooks for it:
+/// `@BeforeEach` and `@AfterEach` in JUnit, `setUp` and `tearDown` in
+/// Python's `unittest`, `beforeAll` in Jest.
+///
+/// The Rust test harness has none, and needs none. Tests are independent
+/// functions, run in parallel threads, in any order, and any subset of them
+/// can be selected by name: there is no test class to hang hooks on, and no
+/// moment when "all the tests" are done. Instead:
+/// - the setup is a plain function, called at the start of the test
+/// - the teardown is `Drop`: it runs when the fixture goes out of scope,
+///   even if the test panics
+/// - state shared between tests is a `static`, initialized by the first
+///   test which needs it (`OnceLock`)
+use crate::util::TestDir;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+fn fixtures() {}
+
+/// ## Setup Is a Function
+/// Each test calls it, and gets its own copy: no test can see what another
+/// one did to it. Arguments replace the many variants of a setup hook.
+#[derive(Debug)]
+struct Inventory {
+    items: Vec<(String, u32)>,
+}
+
+fn inventory(extra: &[(&str, u32)]) -> Inventory {
+    let mut items: Vec<(String, u32)> =
+        vec![("apples".to_string(), 3), ("pears".to_string(), 5)];
+    items.extend(extra.iter().map(|(item, count)| (item.to_string(), *count)));
+    Inventory { items }
+}
+
+runnable!(setup_functions, {
+    let mut first: Inventory = inventory(&[]);
+    first.items.clear();
+    let second: Inventory = inventory(&[("plums", 1)]);
+    assert_eq!(second.items.len(), 3);          // unaffected by `first`
+});
+
+/// ## Teardown Is `Drop`
+/// A test touching the file system needs a directory of its own: sharing
+/// `std::env::temp_dir()` directly, two tests running at the same time may
+/// overwrite each other's files, and a failed assertion skips the cleanup at
+/// the end of the test, leaving files behind. `TestDir` (see util.rs) is an
+/// RAII guard (see ownership.rs): a unique directory, removed when dropped.
+runnable!(test_directories, {
+    let directory: TestDir = TestDir::new("fixtures");
+    let path: PathBuf = directory.join("notes.txt");
+    std::fs::write(&path, "remember the milk").unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "remember the milk");
+
+    let other: TestDir = TestDir::new("fixtures");
+    assert_ne!(directory.path(), other.path());
+
+    let root: PathBuf = directory.path().to_path_buf();
+    drop(directory);
+    assert!(!root.exists());
+});
+
+/// Unwinding drops the values of every frame it leaves, so the teardown
+/// also runs when an assertion fails (but not if the process aborts, see
+/// fuzzing.rs).
+runnable!(teardown_on_panic, {
+    let mut root: Option<PathBuf> = None;
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let directory: TestDir = TestDir::new("fixtures");
+        root = Some(directory.path().to_path_buf());
+        assert_eq!(1 + 1, 3, "a failing test");
+    }));
+    assert!(result.is_err());
+    assert!(!root.unwrap().exists());
+});
+
+/// ## Expensive Shared State
+/// Some fixtures are too slow to build for every test, such as a large
+/// table computed once. A `static OnceLock` holds it: the first test to
+/// call `primes` computes it, the others wait for it if it is in progress,
+/// then share it (see reactor.rs for another lazily initialized static).
+static PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+static INITIALIZATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The primes below one million, with the sieve of Eratosthenes.
+fn primes() -> &'static [u32] {
+    PRIMES.get_or_init(|| {
+        INITIALIZATIONS.fetch_add(1, Ordering::Relaxed);
+        const LIMIT: usize = 1_000_000;
+        let mut composite: Vec<bool> = vec![false; LIMIT];
+        let mut primes: Vec<u32> = Vec::new();
+        for n in 2..LIMIT {
+            if composite[n] { continue; }
+            primes.push(n as u32);
+            for multiple in (n * n..LIMIT).step_by(n) {
+                composite[multiple] = true;
+            }
+        }
+        primes
+    })
+}
+
+runnable!(shared_state_first_user, {
+    assert_eq!(primes().len(), 78_498);
+    assert_eq!(INITIALIZATIONS.load(Ordering::Relaxed), 1);
+});
+
+runnable!(shared_state_second_user, {
+    assert_eq!(primes()[..5], [2, 3, 5, 7, 11]);
+    assert_eq!(INITIALIZATIONS.load(Ordering::Relaxed), 1);
+});
+
+/// The state is shared within one process only: a harness running each test
+/// in its own process (see tests/custom_harness.rs) builds it once per test.
+///
+/// Shared state should be immutable. Behind a `Mutex`, tests could modify
+/// it, but then their results would depend on which tests ran before them,
+/// and in which order: the flaky tests that the isolation was meant to
+/// prevent.
+///
+/// Statics are never dropped, so there is no "after all" teardown either:
+/// a shared fixture owning external resources (a `TestDir`, a server) is
+/// never cleaned up. Such fixtures belong in integration tests with their
+/// own `main` (see tests/custom_harness.rs), or in a script around
+/// `cargo test`.
+fn shared_state_caveats() {}
@@ -0,0 +1,111 @@
+/// # Function Pointers (~ C Function Pointers)
+/// A `function pointer` (type `fn(inputs...) -> output`, lowercase) is the
+/// address of some code. Unlike closures (see closures.rs), it carries no
+/// captured state, so all function pointers with the same signature have the
+/// same concrete type, and can be stored, compared and passed around freely.
+use std::collections::HashMap;
+use std::mem::size_of_val;
+
+fn function_pointers() {}
+
+fn double(x: i32) -> i32 { x * 2 }
+fn square(x: i32) -> i32 { x * x }
+
+/// ## Function Items and Function Pointers
+/// Each function has its own zero-sized type, called `function item`, which
+/// the compiler can call directly (and inline). A function item coerces to a
+/// function pointer, which is called indirectly through its address.
+runnable!(function_items_and_pointers, {
+    let item = double;                     // type: `fn(i32) -> i32 {double}`
+    let pointer: fn(i32) -> i32 = double;  // type: `fn(i32) -> i32`
+    println!("item: {} bytes, pointer: {} bytes",
+        size_of_val(&item), size_of_val(&pointer));
+
+    // Different function items have different types...
+    // let mut f = double;
+    // f = square;
+    // ^ Error: expected fn item, found a different fn item
+    // ...but the same function pointer type
+    let mut f: fn(i32) -> i32 = double;
+    println!("double(5) = {}", f(5));
+    f = square;
+    println!("square(5) = {}", f(5));
+
+    // Function pointers implement all the closure traits, so they can be
+    // passed to any higher-order function
+    let results: Vec<i32> = (1..=3).map(f).collect();
+    println!("squares: {:?}", results);
+});
+
+/// ## Coercing Closures
+/// A closure that captures nothing is just code, so it also coerces to a
+/// function pointer. A capturing closure cannot: its captured values must
+/// be stored somewhere, and a function pointer has no room for them.
+runnable!(coercing_closures, {
+    let negate: fn(i32) -> i32 = |x| -x;    // non-capturing: ok
+    println!("negate(3) = {}", negate(3));
+
+    let offset: i32 = 10;
+    // let shift: fn(i32) -> i32 = |x| x + offset;
+    // ^ Error: expected fn pointer, found closure
+    //   note: closures can only be coerced to `fn` types if they do not
+    //         capture any variables
+    let shift = |x: i32| x + offset;        // fine as a closure
+    println!("shift(3) = {}", shift(3));
+});
+
+/// ## Dispatch Tables
+/// Since function pointers of the same signature share a type, they can be
+/// stored in arrays or maps, to choose the code to run at runtime (like the
+/// vtables of trait objects, see dyn_internals.rs).
+fn add(a: f64, b: f64) -> f64 { a + b }
+fn sub(a: f64, b: f64) -> f64 { a - b }
+
+type BinaryOp = fn(f64, f64) -> f64;
+
+runnable!(dispatch_tables, {
+    // Indexed by position
+    let unary: [fn(i32) -> i32; 3] = [double, square, |x| x + 1];
+    for (i, op) in unary.iter().enumerate() {
+        println!("unary[{}](7) = {}", i, op(7));
+    }
+
+    // Indexed by name
+    let mut binary: HashMap<&str, BinaryOp> = HashMap::new();
+    binary.insert("+", add);
+    binary.insert("-", sub);
+    binary.insert("*", |a, b| a * b);
+    binary.insert("max", f64::max);        // methods are functions too
+    let inputs: [(&str, f64, f64); 4] =
+        [("+", 1.0, 2.0), ("*", 3.0, 4.0), ("max", 5.0, 6.0), ("/", 7.0, 8.0)];
+    for (symbol, a, b) in inputs {
+        match binary.get(symbol) {
+            Some(op) => println!("{} {} {} = {}", a, symbol, b, op(a, b)),
+            None => println!("unknown operator {}", symbol),
+        }
+    }
+});
+
+/// ## Sizes
+/// - A function item or a non-capturing closure is zero-sized: the code to
+///   call is known from the type
+/// - A capturing closure has the size of its captured values
+/// - A function pointer has the size of an address
+/// - A boxed closure (`Box<dyn Fn>`) is a fat pointer (data + vtable), plus
+///   a heap allocation for the captured values
+runnable!(sizes, {
+    let offset: i64 = 1;
+    let item = double;
+    let non_capturing = |x: i64| x + 1;
+    let capturing = move |x: i64| x + offset;
+    let pointer: fn(i64) -> i64 = non_capturing;
+    let boxed: Box<dyn Fn(i64) -> i64> = Box::new(capturing);
+
+    println!("function item:         {:>2} bytes", size_of_val(&item));
+    println!("non-capturing closure: {:>2} bytes", size_of_val(&non_capturing));
+    println!("capturing closure:     {:>2} bytes", size_of_val(&capturing));
+    println!("function pointer:      {:>2} bytes", size_of_val(&pointer));
+    println!("boxed closure:         {:>2} bytes", size_of_val(&boxed));
+    assert_eq!(size_of_val(&capturing), size_of_val(&offset));
+    assert_eq!(size_of_val(&boxed), 2 * size_of_val(&pointer));
+});
\ No newline at end of file
@@ -0,0 +1,158 @@
+/// # Hand-Written Futures
+/// `async fn` and `.await` are sugar over the `Future` trait:
+/// ```
+/// trait Future {
+///     type Output;
+///     fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output>;
+/// }
+/// ```
+/// Implementing `Future` by hand shows what the compiler generates for us:
+/// a value that, when polled, either completes with `Poll::Ready(output)` or
+/// returns `Poll::Pending` after arranging for its `Waker` to be woken.
+///
+/// Note: returning `Poll::Pending` without ever waking the `Waker` is a bug:
+///       the executor will never poll the future again (see async_await.rs).
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use crate::async_await::{block_on, join};
+use crate::reactor;
+
+fn futures() {}
+
+/// ## YieldNow
+/// The simplest useful future: it is pending the first time it is polled,
+/// and ready the second time. It yields control back to the executor once,
+/// giving other futures (e.g. in a `join`) a chance to make progress.
+pub(crate) struct YieldNow { yielded: bool }
+
+pub(crate) fn yield_now() -> YieldNow { YieldNow { yielded: false } }
+
+impl Future for YieldNow {
+    type Output = ();
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            // Ask to be polled again, otherwise we would never complete
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// ## Delay
+/// A future completing after a given duration. It stores the `Waker` of the
+/// last poll: the executor may change waker between polls (e.g. if the
+/// future is moved to another task), so only the latest one must be woken.
+///
+/// The waker is handed to the timer reactor, which wakes it once the deadline
+/// passes (see reactor.rs). Waking the waker immediately would also work, but
+/// the executor would then poll the delay over and over (busy-polling).
+pub(crate) struct Delay {
+    deadline: Instant,
+    waker: Option<Waker>,
+}
+
+impl Delay {
+    pub(crate) fn new(duration: Duration) -> Delay {
+        Delay { deadline: Instant::now() + duration, waker: None }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        // Register the waker, unless it would wake the same task anyway
+        match &self.waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            _ => {
+                self.waker = Some(cx.waker().clone());
+                reactor::register(self.deadline, cx.waker().clone());
+            }
+        }
+        Poll::Pending
+    }
+}
+
+runnable!(yield_now_future, {
+    block_on(async {
+        println!("before yield");
+        yield_now().await;
+        println!("after yield");
+    });
+});
+
+runnable!(delay_future, {
+    let start: Instant = Instant::now();
+    block_on(Delay::new(Duration::from_millis(20)));
+    let elapsed: Duration = start.elapsed();
+    println!("delay elapsed: {} ms", elapsed.as_millis());
+    assert!(elapsed >= Duration::from_millis(20));
+});
+
+/// ## Interleaving
+/// Joining two futures that yield shows that they take turns on the same
+/// thread: each `.await` on a pending future is a point where the executor
+/// can switch to another future.
+runnable!(interleaving_futures, {
+    async fn count(name: &str, n: u32) -> u32 {
+        for i in 0..n {
+            println!("{}: {}", name, i);
+            yield_now().await;
+        }
+        n
+    }
+    let (a, b): (u32, u32) = block_on(join(count("a", 3), count("b", 3)));
+    assert_eq!((a, b), (3, 3));
+});
+
+/// ## Desugaring Async Functions
+/// The following `async fn` and the hand-written future below are
+/// equivalent: the compiler turns the body of an `async fn` into a state
+/// machine, whose states are the points where the body is suspended (see
+/// state_machines.rs).
+async fn wait_then_add(x: u32, y: u32) -> u32 {
+    Delay::new(Duration::from_millis(1)).await;
+    x + y
+}
+
+enum WaitThenAdd {
+    Waiting { delay: Delay, x: u32, y: u32 },
+    Done,
+}
+
+impl Future for WaitThenAdd {
+    type Output = u32;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<u32> {
+        match &mut *self {
+            WaitThenAdd::Waiting { delay, x, y } => {
+                // `Delay` is `Unpin`, so it can be pinned again here
+                match Pin::new(delay).poll(cx) {
+                    Poll::Pending => Poll::Pending,
+                    Poll::Ready(()) => {
+                        let sum: u32 = *x + *y;
+                        *self = WaitThenAdd::Done;
+                        Poll::Ready(sum)
+                    }
+                }
+            }
+            WaitThenAdd::Done => panic!("polled after completion"),
+        }
+    }
+}
+
+runnable!(desugared_future, {
+    let sugar: u32 = block_on(wait_then_add(1, 2));
+    let desugared: u32 = block_on(WaitThenAdd::Waiting {
+        delay: Delay::new(Duration::from_millis(1)), x: 1, y: 2,
+    });
+    println!("sugar: {} desugared: {}", sugar, desugared);
+    assert_eq!(sugar, desugared);
+});
\ No newline at end of file
@@ -0,0 +1,138 @@
+/// # Fuzzing (~ a monkey at the keyboard)
+/// Unit tests check the inputs their author thought of. A fuzzer feeds a
+/// program inputs nobody thought of, millions of them, and watches for
+/// crashes: panics, stack overflows, out-of-memory errors, or broken
+/// assertions. Anything parsing untrusted input, such as the expression
+/// parser (see parser.rs), is a prime target: it must reject garbage with
+/// an error, never with a crash.
+///
+/// Real fuzzers, such as `cargo fuzz` (libFuzzer), are `coverage-guided`:
+/// they instrument the program, and mutate the inputs which reach new
+/// branches. This lesson uses plain random inputs instead, from the
+/// property-based testing helpers (see util/proptest.rs): weaker, but
+/// std-only, and fast enough to run with the other tests.
+use crate::parser::{parse, Expr};
+use crate::patterns::visitor::{fold, Printer};
+use crate::util::proptest::Arbitrary;
+use crate::util::Rng;
+
+fn fuzzing() {}
+
+/// ## The Oracle
+/// A fuzzer needs to know when something went wrong. The weakest oracle is
+/// "it did not crash"; stronger ones check properties of the output:
+/// - errors point inside the input, on a character boundary
+/// - a parsed tree, printed and parsed again, gives the same tree
+fn check(input: &str) {
+    match parse(input) {
+        Ok(expr) => {
+            let printed: String = fold(&expr, &mut Printer);
+            assert_eq!(parse(&printed), Ok(expr), "printed as {}", printed);
+        }
+        Err(error) => {
+            assert!(error.position <= input.len());
+            assert!(input.is_char_boundary(error.position));
+        }
+    }
+}
+
+/// The runnables below call `check` inside `for_all!`, which treats a
+/// panicking property as a failing one (see util/proptest.rs): the input
+/// which caused the panic is shrunk, and reported with the seed.
+fn the_oracle() {}
+
+/// ## Random Bytes
+/// Fuzzers produce bytes, not strings. Invalid UTF-8 is replaced with
+/// `U+FFFD`, so that every input reaches the parser.
+runnable!(random_bytes, {
+    for_all!(cases = 10_000, |bytes: Vec<u8>| {
+        check(&String::from_utf8_lossy(&bytes));
+        true
+    });
+});
+
+/// ## Dictionaries
+/// Random bytes rarely form a valid expression, so they only exercise the
+/// error paths. Fuzzers accept a `dictionary` of tokens to combine instead:
+/// the inputs get past the first character, and deeper into the parser.
+#[derive(Clone, Debug)]
+struct Token(&'static str);
+
+const DICTIONARY: [&str; 16] = [
+    "1", "x", "+", "-", "*", "/", "(", ")", " ", "42", "_y2",
+    "99999999999999999999", "\t", "é", "٣", "\u{0}",
+];
+
+impl Arbitrary for Token {
+    fn arbitrary(rng: &mut Rng) -> Token {
+        Token(DICTIONARY[rng.below(DICTIONARY.len() as u64) as usize])
+    }
+
+    /// Every token shrinks to the simplest one, `1`.
+    fn shrink(&self) -> Vec<Token> {
+        if self.0 == DICTIONARY[0] { Vec::new() } else { vec![Token("1")] }
+    }
+}
+
+runnable!(dictionary_inputs, {
+    for_all!(cases = 10_000, |tokens: Vec<Token>| {
+        let input: String = tokens.iter().map(|token| token.0).collect();
+        check(&input);
+        true
+    });
+    // The dictionary does reach the success paths
+    assert!(parse("(x+42)*-_y2 / 1").is_ok());
+});
+
+/// ## Stack Overflows
+/// The first version of the parser had no depth limit: each `(` recursed
+/// once more, and 100 000 of them overflowed the stack. A stack overflow is
+/// not a panic, `catch_unwind` cannot stop it: the process aborts with
+/// "thread has overflowed its stack", taking every other test with it. This
+/// is why fuzzers run each target in a separate process, and report the
+/// input which killed it.
+///
+/// Small random inputs rarely nest deep enough, so this runnable builds the
+/// pathological cases by hand. Deep trees are dangerous even without
+/// recursion in the parser: a chain `1+1+...+1` is parsed by a loop, but
+/// dropping (or printing) the resulting tree recurses once per level.
+runnable!(stack_overflows, {
+    let inputs: [String; 4] = [
+        "(".repeat(100_000),
+        "-".repeat(100_000) + "1",
+        "1+".repeat(100_000) + "1",
+        "2*".repeat(100_000) + "2",
+    ];
+    for input in &inputs {
+        let error = parse(input).unwrap_err();
+        assert_eq!(error.message, "expression nested too deeply");
+    }
+    let deep_but_valid: String = "(".repeat(100) + "1" + &")".repeat(100);
+    assert_eq!(parse(&deep_but_valid), Ok(Expr::Number(1)));
+});
+
+/// ## Fuzzing for Real
+/// With `cargo-fuzz` (nightly only, Linux or macOS), the parser would need
+/// to be in the library target (see lib.rs), then:
+/// ```text
+/// cargo install cargo-fuzz
+/// cargo fuzz init                   # creates fuzz/, a separate crate
+/// cargo fuzz add parse              # creates fuzz/fuzz_targets/parse.rs
+/// cargo fuzz run parse              # runs until a crash, or Ctrl+C
+/// ```
+/// with the target calling the parser on every input:
+/// ```text
+/// #![no_main]
+/// use libfuzzer_sys::fuzz_target;
+///
+/// fuzz_target!(|data: &[u8]| {
+///     if let Ok(input) = std::str::from_utf8(data) {
+///         let _ = rust_plauground::parser::parse(input);
+///     }
+/// });
+/// ```
+/// Crashing inputs are saved in `fuzz/artifacts/parse/`, and the inputs
+/// reaching new branches in `fuzz/corpus/parse/`: checked in, the corpus
+/// makes the next run start where the last one stopped, and each crashing
+/// input becomes a regression test (like the inputs of `stack_overflows`).
+fn fuzzing_for_real() {}
@@ -27,6 +27,11 @@ fn generic3<A>(x: GenericWrapper<A>) {}
 
 /// ## Generic Function Application
 /// Input type arguments can be specified using the `turbofish` syntax.
+///
+/// Each distinct list of type arguments produces a separate copy of the
+/// function, specialized for those types (`monomorphization`): calls are
+/// static and can be inlined, but every copy adds to compile times and to
+/// the size of the binary (see monomorphization.rs).
 runnable!(generic_call, {
     generic1::<u8>(0);            // Explicit Type Argument
     generic1(0u8);                // Inferred Type Argument
@@ -114,6 +119,58 @@ impl <A> Wrapper2 for Option<A> {
 }
 fn consume_wrapper2<A: Wrapper2>(x: A) -> A::Inner { x.get() }  // more readable
 
+/// ## Generic Associated Types (GATs)
+/// Type members can be generic themselves, taking type or lifetime
+/// parameters. This allows the type member to depend on the lifetime of a
+/// borrow of `self`.
+///
+/// Example: a `LendingIterator` yields items borrowing from the iterator
+/// itself, so each item must be dropped before asking for the next one.
+/// A standard `Iterator` cannot do that, because its `Item` is fixed once for
+/// all the calls to `next`.
+trait LendingIterator {
+    type Item<'a> where Self: 'a;   // Generic type member
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+// Yields overlapping mutable windows over a buffer
+struct WindowsMut<'b, T> { buffer: &'b mut [T], size: usize, start: usize }
+
+impl<'b, T> LendingIterator for WindowsMut<'b, T> {
+    type Item<'a> = &'a mut [T] where Self: 'a;
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let end: usize = self.start + self.size;
+        let window = self.buffer.get_mut(self.start..end)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+// Without GATs, the item cannot borrow from the `&mut self` of `next`:
+// impl<'b, T> Iterator for WindowsMut<'b, T> {
+//     type Item = &'b mut [T];
+//     fn next(&mut self) -> Option<Self::Item> {
+//         let end: usize = self.start + self.size;
+//         let window = self.buffer.get_mut(self.start..end)?;
+//         self.start += 1;
+//         Some(window)
+//     }
+// }
+// ^ Error: lifetime may not live long enough (`window` borrows `self`, but
+//          `Self::Item` must live for 'b)
+// Note: with an `Iterator`, two windows could be alive at the same time,
+//       giving two overlapping `&mut` to the same elements.
+
+runnable!(generic_associated_types, {
+    let mut buffer: [u32; 4] = [1, 2, 3, 4];
+    let mut windows = WindowsMut { buffer: &mut buffer, size: 2, start: 0 };
+    while let Some(window) = windows.next() {
+        window[1] += window[0];     // prefix sums, one window at a time
+    }
+    println!("prefix sums: {:?}", buffer);
+    assert_eq!(buffer, [1, 3, 6, 10]);
+});
+
 /// ## Phantom Types
 /// Phantom Types can be declared using `std::marker::PhantomData`.
 /// 
@@ -141,4 +198,137 @@ runnable!(phantom_types, {
     let p1 = Phantom::new::<Markers::Red>(0u8);
     let p2 = Phantom::new::<Markers::Blue>(0u8);
     // println!("{}", p1 == p2) // Error: type mismatch
+});
+
+/// ### What PhantomData Communicates
+/// Besides tagging types, `PhantomData<X>` tells the compiler to treat a
+/// struct as if it contained a field of type `X`. This matters for structs
+/// that use raw pointers, which carry no ownership or lifetime information.
+/// The choice of `X` decides:
+/// - `PhantomData<T>`: the struct *owns* a `T`. It is `Send`/`Sync` only if
+///   `T` is, and dropping it may drop a `T`
+/// - `PhantomData<&'a T>`: the struct *borrows* a `T` for `'a`, so it cannot
+///   outlive the borrowed data
+/// - `PhantomData<fn(T)>`: the struct only *consumes* `T`s and never stores
+///   them. It is always `Send`/`Sync`, and dropping it never drops a `T`
+///
+/// See variance.rs for the effect of these markers on subtyping.
+struct RawBox<T> {
+    pointer: *mut T,
+    marker: PhantomData<T>,     // `RawBox<T>` owns the `T` behind `pointer`
+}
+impl<T> RawBox<T> {
+    fn new(value: T) -> RawBox<T> {
+        RawBox { pointer: Box::into_raw(Box::new(value)), marker: PhantomData }
+    }
+    fn get(&self) -> &T {
+        // SAFETY: `pointer` comes from `Box::into_raw` and is freed only once
+        unsafe { &*self.pointer }
+    }
+}
+impl<T> Drop for RawBox<T> {
+    fn drop(&mut self) {
+        // SAFETY: `pointer` comes from `Box::into_raw` and is freed only here
+        unsafe { drop(Box::from_raw(self.pointer)); }
+    }
+}
+// `*mut T` is never `Send`: since `RawBox` owns its `T` (like a `Box<T>`), it
+// can be sent to another thread whenever `T` can.
+unsafe impl<T: Send> Send for RawBox<T> {}
+
+/// #### Drop Check
+/// When a value with a `Drop` implementation goes out of scope, its `drop`
+/// may access the data it contains. The compiler must ensure that any data
+/// borrowed by that value is still alive at that point (`drop check`).
+///
+/// Note: `std` containers use the unstable `#[may_dangle]` attribute to relax
+///       this rule. In that case, `PhantomData<T>` is what tells the compiler
+///       that dropping the container still drops a `T`, keeping it sound.
+runnable!(phantom_drop_check, {
+    struct Plain<T>(T);             // no `Drop` implementation
+    {
+        let plain;
+        let string: String = String::from("plain");
+        plain = Plain(&string);
+        // <-- `string` is dropped before `plain`: fine, `Plain` has no `drop`
+    }
+    {
+        let string: String = String::from("raw box");
+        let raw_box: RawBox<&String> = RawBox::new(&string);
+        println!("raw_box: {}", raw_box.get());
+        // <-- `raw_box` is dropped before `string`: fine
+    }
+    // {
+    //     let raw_box;
+    //     let string: String = String::from("raw box");
+    //     raw_box = RawBox::new(&string);
+    // }
+    // ^ Error: `string` does not live long enough: the borrow might be used
+    //          when `raw_box` is dropped and runs the `Drop` code of `RawBox`
+
+    let sendable: RawBox<u8> = RawBox::new(1);
+    std::thread::spawn(move || println!("sent: {}", sendable.get()))
+        .join()
+        .unwrap();
+    // let rc: RawBox<std::rc::Rc<u8>> = RawBox::new(std::rc::Rc::new(1));
+    // std::thread::spawn(move || println!("{}", rc.get()));
+    // ^ Error: `Rc<u8>` cannot be sent between threads safely
+});
+
+/// #### Borrowing Markers
+/// An iterator over raw pointers does not borrow the slice it iterates, unless
+/// it says so with `PhantomData<&'a T>`.
+struct RawIter<'a, T> {
+    current: *const T,
+    end: *const T,
+    marker: PhantomData<&'a T>, // `RawIter<'a, T>` borrows `T`s for `'a`
+}
+impl<'a, T> RawIter<'a, T> {
+    /// Zero-sized `T`s all share one address, so `start == end` for any
+    /// length: counting by address only works for sized elements.
+    fn new(slice: &'a [T]) -> RawIter<'a, T> {
+        assert!(std::mem::size_of::<T>() != 0, "zero-sized types unsupported");
+        let range: std::ops::Range<*const T> = slice.as_ptr_range();
+        RawIter { current: range.start, end: range.end, marker: PhantomData }
+    }
+}
+impl<'a, T> Iterator for RawIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        if self.current == self.end { return None; }
+        // SAFETY: `current` is in bounds and the slice is borrowed for 'a
+        let item: &'a T = unsafe { &*self.current };
+        // SAFETY: `current < end`, so one past it is at most `end`, still
+        // in (or one past the end of) the same allocation
+        self.current = unsafe { self.current.add(1) };
+        Some(item)
+    }
+}
+
+/// #### Consuming Markers
+/// A type that only processes `T`s, without storing them.
+struct Sink<T> { processed: usize, marker: PhantomData<fn(T)> }
+impl<T: std::fmt::Debug> Sink<T> {
+    fn process(&mut self, value: T) {
+        println!("processing {:?}", value);
+        self.processed += 1;
+    }
+}
+
+runnable!(phantom_markers, {
+    let mut numbers: Vec<u8> = vec![1, 2, 3];
+    let iter: RawIter<u8> = RawIter::new(&numbers);
+    // numbers.clear();
+    // ^ Error: cannot borrow `numbers` as mutable: `iter` borrows it
+    let sum: u8 = iter.sum();
+    println!("sum: {}", sum);
+
+    let mut sink: Sink<std::rc::Rc<u8>> = Sink {
+        processed: 0, marker: PhantomData
+    };
+    sink.process(std::rc::Rc::new(1));
+    // `Sink` never contains an `Rc`, so it can be sent to another thread
+    std::thread::spawn(move || println!("processed: {}", sink.processed))
+        .join()
+        .unwrap();
 });
\ No newline at end of file
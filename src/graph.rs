@@ -0,0 +1,211 @@
+/// # Index-Based Graphs (~ adjacency lists in C or Go)
+/// Instead of nodes pointing to each other (see shared_graph.rs), the graph
+/// owns all of its nodes and edges in two `Vec`s, and refers to them by
+/// position. A reference to a node is just a number, which borrows nothing:
+/// the borrow checker only sees one owner, the graph.
+///
+/// This is the design of `petgraph`, and of most ECS and compiler IRs.
+use std::collections::VecDeque;
+
+fn graph() {}
+
+/// ## Typed Indices
+/// A bare `usize` could index nodes or edges, or any other `Vec`. Newtypes
+/// (see types.rs) make the compiler reject mixing them up, at no runtime
+/// cost: `NodeId` has the layout of `usize`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeId(usize);
+
+/// ## Nodes and Edges
+/// Each node keeps the head of a linked list of its outgoing edges, threaded
+/// through the edges themselves (`next`): adding an edge is O(1), with no
+/// `Vec` per node.
+#[derive(Clone, Debug)]
+pub struct Node<N> {
+    pub data: N,
+    first_edge: Option<EdgeId>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Edge<E> {
+    pub data: E,
+    pub from: NodeId,
+    pub to: NodeId,
+    next: Option<EdgeId>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Graph<N, E> {
+    nodes: Vec<Node<N>>,
+    edges: Vec<Edge<E>>,
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new() -> Graph<N, E> {
+        Graph { nodes: Vec::new(), edges: Vec::new() }
+    }
+
+    pub fn add_node(&mut self, data: N) -> NodeId {
+        self.nodes.push(Node { data, first_edge: None });
+        NodeId(self.nodes.len() - 1)
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, data: E) -> EdgeId {
+        let id: EdgeId = EdgeId(self.edges.len());
+        let next: Option<EdgeId> = self.nodes[from.0].first_edge.replace(id);
+        self.edges.push(Edge { data, from, to, next });
+        id
+    }
+
+    pub fn node(&self, id: NodeId) -> &N { &self.nodes[id.0].data }
+    pub fn node_mut(&mut self, id: NodeId) -> &mut N {
+        &mut self.nodes[id.0].data
+    }
+    pub fn edge(&self, id: EdgeId) -> &Edge<E> { &self.edges[id.0] }
+    pub fn node_count(&self) -> usize { self.nodes.len() }
+
+    /// The outgoing edges of a node, most recently added first. The
+    /// iterator borrows the graph, but yields plain `Copy` ids.
+    pub fn edges_from(&self, from: NodeId)
+        -> impl Iterator<Item = EdgeId> + '_
+    {
+        let mut current: Option<EdgeId> = self.nodes[from.0].first_edge;
+        std::iter::from_fn(move || {
+            let id: EdgeId = current?;
+            current = self.edges[id.0].next;
+            Some(id)
+        })
+    }
+
+    pub fn neighbors(&self, from: NodeId)
+        -> impl Iterator<Item = NodeId> + '_
+    {
+        self.edges_from(from).map(|id| self.edges[id.0].to)
+    }
+}
+
+fn cities() -> (Graph<&'static str, u32>, [NodeId; 5]) {
+    let mut graph: Graph<&str, u32> = Graph::new();
+    let [rome, milan, turin, naples, bari] =
+        ["rome", "milan", "turin", "naples", "bari"]
+            .map(|city| graph.add_node(city));
+    graph.add_edge(rome, milan, 570);
+    graph.add_edge(rome, naples, 230);
+    graph.add_edge(milan, turin, 140);
+    graph.add_edge(naples, bari, 260);
+    graph.add_edge(turin, rome, 670);           // a cycle: just a number
+    (graph, [rome, milan, turin, naples, bari])
+}
+
+runnable!(nodes_and_edges, {
+    let (graph, [rome, milan, ..]) = cities();
+    assert_eq!(graph.node_count(), 5);
+    assert_eq!(*graph.node(rome), "rome");
+    let names: Vec<&str> =
+        graph.neighbors(rome).map(|id| *graph.node(id)).collect();
+    assert_eq!(names, ["naples", "milan"]);     // most recent first
+    let distances: Vec<u32> =
+        graph.edges_from(rome).map(|id| graph.edge(id).data).collect();
+    assert_eq!(distances, [230, 570]);
+    assert_eq!(graph.edges_from(milan).count(), 1);
+    // graph.node(EdgeId(0));
+    // ^ Error: mismatched types: expected `NodeId`, found `EdgeId`
+});
+
+/// ## Breadth-First Search
+/// The visited set is a `Vec<bool>` indexed like the nodes: no hashing, and
+/// no need for node identity beyond the index.
+impl<N, E> Graph<N, E> {
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited: Vec<bool> = vec![false; self.nodes.len()];
+        let mut queue: VecDeque<NodeId> = VecDeque::from([start]);
+        let mut order: Vec<NodeId> = Vec::new();
+        visited[start.0] = true;
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for next in self.neighbors(node) {
+                if !std::mem::replace(&mut visited[next.0], true) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// ## Depth-First Search
+    /// The same loop with a stack instead of a queue. An explicit stack
+    /// avoids the stack overflows of a recursive DFS on long paths (see
+    /// recursion.rs).
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited: Vec<bool> = vec![false; self.nodes.len()];
+        let mut stack: Vec<NodeId> = vec![start];
+        let mut order: Vec<NodeId> = Vec::new();
+        while let Some(node) = stack.pop() {
+            if std::mem::replace(&mut visited[node.0], true) { continue; }
+            order.push(node);
+            stack.extend(self.neighbors(node));
+        }
+        order
+    }
+}
+
+runnable!(traversals, {
+    let (graph, [rome, milan, turin, naples, bari]) = cities();
+    assert_eq!(graph.bfs(rome), [rome, naples, milan, bari, turin]);
+    assert_eq!(graph.dfs(rome), [rome, milan, turin, naples, bari]);
+    assert_eq!(graph.bfs(bari), [bari]);
+
+    // A path of 100_000 nodes: no recursion, no overflow
+    let mut path: Graph<(), ()> = Graph::new();
+    let mut last: NodeId = path.add_node(());
+    for _ in 0..100_000 {
+        let next: NodeId = path.add_node(());
+        path.add_edge(last, next, ());
+        last = next;
+    }
+    assert_eq!(path.dfs(NodeId(0)).len(), 100_001);
+});
+
+/// ## No Borrow-Checker Fights
+/// The problems of the `Rc<RefCell>` graph (see shared_graph.rs) go away:
+/// - mutating a node while iterating over edges: the ids are `Copy`, so
+///   collect them, end the borrow of the graph, and mutate through `&mut`.
+///   Forgetting to do so is a compile error, not a `BorrowMutError` panic
+/// - cycles: an edge to an ancestor is just a number, so there is nothing
+///   to leak, and no `Weak` to upgrade
+/// - the whole graph is a single value: `Clone`, `Send` and dropped at once,
+///   without walking reference counts
+///
+/// The price: ids are not tied to their graph. An id from another graph
+/// indexes the wrong node or panics, and removing nodes would invalidate
+/// ids unless slots are never reused (this graph, like the arena in
+/// patterns/arena.rs, never removes anything).
+runnable!(no_borrow_checker_fights, {
+    let mut graph: Graph<u32, ()> = Graph::new();
+    let hub: NodeId = graph.add_node(0);
+    for _ in 0..3 {
+        let leaf: NodeId = graph.add_node(0);
+        graph.add_edge(hub, leaf, ());
+        graph.add_edge(leaf, hub, ());          // cycles everywhere
+    }
+
+    // for next in graph.neighbors(hub) { *graph.node_mut(next) += 1; }
+    // ^ Error: cannot borrow `graph` as mutable because it is also borrowed
+    //          as immutable
+    let neighbors: Vec<NodeId> = graph.neighbors(hub).collect();
+    for next in neighbors {
+        *graph.node_mut(next) += 1;
+        *graph.node_mut(hub) += 1;              // no RefCell, no panic
+    }
+    assert_eq!(*graph.node(hub), 3);
+    assert!(graph.neighbors(hub).all(|leaf| *graph.node(leaf) == 1));
+
+    // Plain data: `Sync`, so other threads can borrow it
+    let reached: usize =
+        std::thread::scope(|scope| scope.spawn(|| graph.bfs(hub).len()).join())
+            .unwrap();
+    assert_eq!(reached, 4);
+});
@@ -0,0 +1,97 @@
+/// # Higher-Ranked Trait Bounds (HRTB)
+/// A lifetime parameter `'a` on a function or a struct is chosen by the
+/// caller: inside the definition, `'a` is one fixed (unknown) lifetime.
+///
+/// Sometimes a bound must hold for *every* lifetime instead, e.g. a closure
+/// which must accept references of any lifetime, including references to
+/// local variables that the caller cannot even name. This is expressed with
+/// a higher-ranked trait bound: `for<'a> Bound<'a>`, read "for all 'a".
+fn higher_ranked_bounds() {}
+
+/// ## Inferred HRTB
+/// Most of the time, HRTB are inferred by lifetime elision in `Fn` bounds:
+/// `Fn(&str) -> &str` is sugar for `for<'a> Fn(&'a str) -> &'a str`.
+fn apply_elided<F>(f: F, input: &str) -> usize
+where F: Fn(&str) -> &str {
+    f(input).len()
+}
+fn apply_explicit<F>(f: F, input: &str) -> usize
+where F: for<'a> Fn(&'a str) -> &'a str {
+    f(input).len()
+}
+
+runnable!(inferred_hrtb, {
+    // Closures passed directly to the function infer their signature from
+    // the bound, so they become higher-ranked as well
+    println!("elided: {}", apply_elided(|s| s.trim(), "  hello  "));
+    println!("explicit: {}", apply_explicit(|s| s.trim(), "  hello  "));
+
+    // let trim = |s: &str| -> &str { s.trim() };
+    // println!("{}", apply_elided(trim, "  hello  "));
+    // ^ Error: lifetime may not live long enough (a closure stored in a
+    //          variable does not infer a higher-ranked return type)
+    fn trim(s: &str) -> &str { s.trim() }  // functions are always fine
+    println!("function: {}", apply_elided(trim, "  hello  "));
+});
+
+/// ## Required HRTB
+/// When the lifetime is declared as a parameter, the closure only accepts
+/// references with that lifetime, which the caller picks. The definition
+/// cannot pass it references to its own local variables, because they do
+/// not live for the (arbitrarily long) lifetime chosen by the caller.
+struct Naive<'a, F: Fn(&'a str) -> &'a str> {
+    f: F,
+    marker: std::marker::PhantomData<&'a ()>,
+}
+impl<'a, F: Fn(&'a str) -> &'a str> Naive<'a, F> {
+    fn apply(&self, input: &'a str) -> usize { (self.f)(input).len() }
+    // fn apply_uppercase(&self, input: &str) -> usize {
+    //     let owned: String = input.to_uppercase();
+    //     (self.f)(&owned).len()
+    // }
+    // ^ Error: `owned` does not live long enough (it must live for 'a)
+}
+
+/// Storing the closure with a higher-ranked bound fixes the problem: the
+/// closure works for any lifetime, so it also works for local variables.
+struct Transformer<F> where F: for<'a> Fn(&'a str) -> &'a str { f: F }
+
+impl<F> Transformer<F> where F: for<'a> Fn(&'a str) -> &'a str {
+    fn apply_uppercase(&self, input: &str) -> usize {
+        let owned: String = input.to_uppercase();
+        (self.f)(&owned).len()    // borrow of a local variable: fine
+    }
+}
+
+runnable!(required_hrtb, {
+    fn trim(s: &str) -> &str { s.trim() }
+    let naive = Naive { f: trim, marker: std::marker::PhantomData };
+    println!("naive: {}", naive.apply("  naive  "));
+    let transformer = Transformer { f: trim };
+    println!("transformer: {}", transformer.apply_uppercase("  hrtb  "));
+    assert_eq!(transformer.apply_uppercase("  hrtb  "), 4);
+});
+
+/// ## HRTB beyond Closures
+/// Elision only works for `Fn` traits: for other traits, HRTB must always be
+/// written explicitly. A common example is requiring that any borrow of a
+/// collection can be iterated.
+fn sum_twice<C>(collection: C) -> i32
+where for<'a> &'a C: IntoIterator<Item = &'a i32> {
+    // `collection` is a local variable: its borrows have lifetimes that the
+    // caller cannot name
+    let first: i32 = (&collection).into_iter().sum();
+    let second: i32 = (&collection).into_iter().sum();
+    first + second
+}
+// fn sum_twice<'a, C>(collection: C) -> i32
+// where &'a C: IntoIterator<Item = &'a i32> {
+//     (&collection).into_iter().sum()
+// }
+// ^ Error: `collection` does not live long enough (it must live for 'a)
+
+runnable!(hrtb_beyond_closures, {
+    println!("vec: {}", sum_twice(vec![1, 2, 3]));
+    println!("set: {}", sum_twice(std::collections::BTreeSet::from([1, 2])));
+    assert_eq!(sum_twice([1, 2, 3]), 12);
+});
\ No newline at end of file
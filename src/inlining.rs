@@ -0,0 +1,114 @@
+/// # Inlining (~ copy-pasting the callee)
+/// Inlining replaces a call with the body of the called function. The call
+/// itself is cheap, but inlining is what enables most other optimizations:
+/// once the body sits inside the caller's loop, constants propagate into
+/// it, redundant work disappears, and the loop can be vectorized.
+///
+/// The compiler (LLVM, in the end) decides on its own which calls to
+/// inline. Three attributes let the programmer weigh in:
+/// - `#[inline]`: a hint, which mostly matters across crates (see below)
+/// - `#[inline(always)]`: a stronger hint, rarely needed
+/// - `#[inline(never)]`: keep the call, e.g. for a cold path, or to see the
+///   function in a profiler
+use std::hint::black_box;
+
+fn inlining() {}
+
+/// ## Three Tiny Functions
+/// The same function, with each attribute, and without any.
+#[inline(always)]
+fn scale_always(value: u64) -> u64 { value * 3 + 1 }
+
+#[inline(never)]
+fn scale_never(value: u64) -> u64 { value * 3 + 1 }
+
+fn scale_default(value: u64) -> u64 { value * 3 + 1 }
+
+/// ## A Hot Loop
+/// Each call sits in the loop body, once per element. Generic over the
+/// function (see monomorphization.rs), so each loop is compiled separately
+/// around a static call.
+fn sum_scaled(values: &[u64], scale: impl Fn(u64) -> u64) -> u64 {
+    values.iter().fold(0, |total, value| total.wrapping_add(scale(*value)))
+}
+
+runnable!(three_tiny_functions, {
+    let values: Vec<u64> = (0..100).collect();
+    let expected: u64 = sum_scaled(&values, |value| value * 3 + 1);
+    assert_eq!(sum_scaled(&values, scale_always), expected);
+    assert_eq!(sum_scaled(&values, scale_never), expected);
+    assert_eq!(sum_scaled(&values, scale_default), expected);
+});
+
+/// ## Measurements
+/// In debug builds (`cargo test`), nothing is inlined except
+/// `#[inline(always)]` functions, and every other call in the loop (`fold`,
+/// the closure, `wrapping_add`) remains: the three versions are within
+/// noise of each other.
+///
+/// In release builds (`cargo test --release inlining -- --nocapture`),
+/// `scale_default` is inlined without any hint, because it is tiny: it runs
+/// as fast as `scale_always`, the loop being vectorized in both cases. Only
+/// `scale_never` is several times slower: a real call per element, and no
+/// vectorization across it.
+///
+/// `black_box` hides the input from the optimizer, which would otherwise
+/// compute the sum once, out of the timing loop (see black_box.rs).
+runnable!(measurements, {
+    let values: Vec<u64> = (0..100_000).collect();
+    let build: &str = if cfg!(debug_assertions) { "debug" } else { "release" };
+    println!("{} build", build);
+    let always = benchmark!("#[inline(always)]", 50,
+        sum_scaled(black_box(&values), scale_always));
+    let never = benchmark!("#[inline(never)]", 50,
+        sum_scaled(black_box(&values), scale_never));
+    let default = benchmark!("no attribute", 50,
+        sum_scaled(black_box(&values), scale_default));
+    for (label, average) in [("#[inline(never)]", never), ("none", default)] {
+        println!("{}: {:.2}x #[inline(always)]", label,
+            average.as_secs_f64() / always.as_secs_f64().max(1e-9));
+    }
+});
+
+/// ## When the Attributes Matter
+/// Within a crate, the compiler sees every function body, and its own
+/// heuristics (based on the size of the callee, and the number of callers)
+/// are usually better than manual hints.
+///
+/// Across crates, a non-generic function is compiled once, in its own
+/// crate: other crates only see its signature, so they cannot inline it,
+/// however small it is (unless link-time optimization, `lto = true`, is
+/// enabled). `#[inline]` makes the body available to other crates, which
+/// is why the standard library marks small functions such as `Vec::len`
+/// with it. Generic functions need no hint: they are compiled in the crate
+/// which instantiates them (see monomorphization.rs).
+///
+/// `#[inline(always)]` forces the inlining, even of large functions into
+/// many callers: more code, longer compile times, and possibly a slower
+/// program, as hot loops stop fitting in the instruction cache. It is worth
+/// it only when measured.
+///
+/// ## Cold Paths
+/// `#[inline(never)]` and `#[cold]` keep rarely executed code, such as error
+/// reporting, out of the hot function: the hot path stays small, and the
+/// compiler lays out the code for the likely branch.
+#[cold]
+#[inline(never)]
+fn out_of_range(value: u64, limit: u64) -> String {
+    format!("{} is out of range (limit: {})", value, limit)
+}
+
+fn checked_total(values: &[u64], limit: u64) -> Result<u64, String> {
+    let mut total: u64 = 0;
+    for value in values {
+        if *value > limit { return Err(out_of_range(*value, limit)); }
+        total += scale_default(*value);
+    }
+    Ok(total)
+}
+
+runnable!(cold_paths, {
+    assert_eq!(checked_total(&[1, 2, 3], 10), Ok(4 + 7 + 10));
+    assert_eq!(checked_total(&[1, 20], 10),
+        Err("20 is out of range (limit: 10)".to_string()));
+});
@@ -0,0 +1,139 @@
+/// # Interior Mutability Costs
+/// Every wrapper allowing mutation through `&T` (see unsafe_code/
+/// unsafe_cell.rs) pays for its guarantees:
+/// - `&mut T`: nothing, checked at compile time
+/// - `Cell<T>`: nothing either, but values are copied in and out, and it is
+///   not `Sync`
+/// - `RefCell<T>`: a borrow counter, checked (and updated) at each borrow
+/// - `Mutex<T>`/`RwLock<T>`: an atomic operation to lock and unlock, plus
+///   waiting when another thread holds the lock
+/// - `AtomicU64`: a single atomic instruction, but only for integers and
+///   booleans, and contended cache lines are slow when shared by threads
+///
+/// The benchmarks below put numbers on these costs. Tests are not
+/// optimized (see `benchmark!` in util.rs): the ratios matter more than the
+/// absolute numbers. With `cargo test --release`, the `&mut`, `Cell` and
+/// `RefCell` loops are folded into a single addition, while the atomic
+/// operations of the others must all happen: the gap becomes enormous.
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+
+fn interior_mutability() {}
+
+const INCREMENTS: u64 = 100_000;
+const THREADS: u64 = 4;
+
+/// ## Single-Threaded
+fn increment_mut(counter: &mut u64) {
+    for _ in 0..INCREMENTS { *counter += 1; }
+}
+fn increment_cell(counter: &Cell<u64>) {
+    for _ in 0..INCREMENTS { counter.set(counter.get() + 1); }
+}
+fn increment_ref_cell(counter: &RefCell<u64>) {
+    for _ in 0..INCREMENTS { *counter.borrow_mut() += 1; }
+}
+fn increment_mutex(counter: &Mutex<u64>) {
+    for _ in 0..INCREMENTS { *counter.lock().unwrap() += 1; }
+}
+fn increment_rw_lock(counter: &RwLock<u64>) {
+    for _ in 0..INCREMENTS { *counter.write().unwrap() += 1; }
+}
+fn increment_atomic(counter: &AtomicU64) {
+    for _ in 0..INCREMENTS { counter.fetch_add(1, Ordering::Relaxed); }
+}
+
+runnable!(single_threaded, {
+    let mut plain: u64 = 0;
+    let cell: Cell<u64> = Cell::new(0);
+    let ref_cell: RefCell<u64> = RefCell::new(0);
+    let mutex: Mutex<u64> = Mutex::new(0);
+    let rw_lock: RwLock<u64> = RwLock::new(0);
+    let atomic: AtomicU64 = AtomicU64::new(0);
+
+    let base = benchmark!("&mut", 5, increment_mut(&mut plain));
+    let results = [
+        ("Cell", benchmark!("Cell", 5, increment_cell(&cell))),
+        ("RefCell", benchmark!("RefCell", 5, increment_ref_cell(&ref_cell))),
+        ("Mutex", benchmark!("Mutex", 5, increment_mutex(&mutex))),
+        ("RwLock", benchmark!("RwLock", 5, increment_rw_lock(&rw_lock))),
+        ("AtomicU64", benchmark!("AtomicU64", 5, increment_atomic(&atomic))),
+    ];
+    for (label, average) in results {
+        println!("{}: {:.1}x &mut", label,
+            average.as_secs_f64() / base.as_secs_f64().max(1e-9));
+    }
+    let total: u64 = 5 * INCREMENTS;
+    assert_eq!([plain, cell.get(), *ref_cell.borrow()], [total; 3]);
+    assert_eq!(*mutex.lock().unwrap(), total);
+    assert_eq!(*rw_lock.read().unwrap(), total);
+    assert_eq!(atomic.load(Ordering::Relaxed), total);
+});
+
+/// ## Multi-Threaded
+/// `Cell` and `RefCell` are not `Sync`: they cannot be shared by threads at
+/// all. The equivalent of `&mut` is to give each thread its own counter,
+/// and to sum them at the end: no sharing, no synchronization.
+fn per_thread_counters() -> u64 {
+    let handles: Vec<thread::JoinHandle<u64>> = (0..THREADS)
+        .map(|_| thread::spawn(|| {
+            let mut counter: u64 = 0;
+            increment_mut(&mut counter);
+            counter
+        }))
+        .collect();
+    handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+}
+
+/// Runs `increment` on a shared value from every thread.
+fn shared_counter<T: Send + Sync + 'static>(
+    value: T, increment: fn(&T)
+) -> Arc<T> {
+    let shared: Arc<T> = Arc::new(value);
+    let handles: Vec<thread::JoinHandle<()>> = (0..THREADS)
+        .map(|_| {
+            let shared: Arc<T> = Arc::clone(&shared);
+            thread::spawn(move || increment(&shared))
+        })
+        .collect();
+    for handle in handles { handle.join().unwrap(); }
+    shared
+}
+
+runnable!(multi_threaded, {
+    // let cell: Arc<Cell<u64>> = Arc::new(Cell::new(0));
+    // thread::spawn(move || cell.set(1));
+    // ^ Error: `Cell<u64>` cannot be shared between threads safely
+    let base = benchmark!("per-thread counters", 3,
+        assert_eq!(per_thread_counters(), THREADS * INCREMENTS));
+    let results = [
+        ("Mutex", benchmark!("Mutex", 3,
+            shared_counter(Mutex::new(0), increment_mutex))),
+        ("RwLock", benchmark!("RwLock", 3,
+            shared_counter(RwLock::new(0), increment_rw_lock))),
+        ("AtomicU64", benchmark!("AtomicU64", 3,
+            shared_counter(AtomicU64::new(0), increment_atomic))),
+    ];
+    for (label, average) in results {
+        println!("{}: {:.1}x per-thread counters", label,
+            average.as_secs_f64() / base.as_secs_f64().max(1e-9));
+    }
+    let total = shared_counter(AtomicU64::new(0), increment_atomic);
+    assert_eq!(total.load(Ordering::Relaxed), THREADS * INCREMENTS);
+});
+
+/// ## Which Wrapper?
+/// - single owner, or a clear borrowing structure: `&mut T`
+/// - single thread, `Copy` values: `Cell<T>` (as cheap as `&mut`)
+/// - single thread, other values: `RefCell<T>` (a counter per borrow, and a
+///   panic if the rules are broken at runtime)
+/// - several threads, integers or flags: atomics
+/// - several threads, other values: `Mutex<T>`, or `RwLock<T>` when reads
+///   dominate (it is not faster for writes)
+///
+/// In every case, contention is the real cost: threads incrementing the
+/// same counter are slower than a single thread. Splitting the work (one
+/// counter per thread, merged at the end) beats any wrapper.
+fn which_wrapper() {}
\ No newline at end of file
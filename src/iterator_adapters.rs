@@ -0,0 +1,160 @@
+/// # Iterator Adapters from Scratch
+/// `map`, `filter` and `take` do not compute anything: each returns a small
+/// struct wrapping the previous iterator (and the closure, or the count),
+/// which implements `Iterator` itself. Work happens only when the outermost
+/// adapter is asked for its `next` item, which asks the one it wraps, and
+/// so on: iterators are `lazy` and `pull-based`.
+///
+/// This lesson rewrites the three adapters, to show what the standard
+/// library hides behind `Map`, `Filter` and `Take`.
+use std::cell::Cell;
+
+fn iterator_adapters() {}
+
+/// ## Adapter Structs
+/// Each adapter owns the iterator it wraps. The closures are generic
+/// parameters, not trait objects: every closure has its own type, so each
+/// chain is a distinct, fully monomorphized type (see generics.rs).
+pub struct MyMap<I, F> {
+    inner: I,
+    function: F,
+}
+
+pub struct MyFilter<I, P> {
+    inner: I,
+    predicate: P,
+}
+
+pub struct MyTake<I> {
+    inner: I,
+    remaining: usize,
+}
+
+/// ## `Iterator` Implementations
+/// `next` is the only required method. `size_hint` is optional, but lets
+/// `collect` allocate the right capacity up front.
+impl<B, I: Iterator, F: FnMut(I::Item) -> B> Iterator for MyMap<I, F> {
+    type Item = B;
+    fn next(&mut self) -> Option<B> {
+        self.inner.next().map(&mut self.function)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()          // one output per input
+    }
+}
+
+impl<I: Iterator, P: FnMut(&I::Item) -> bool> Iterator for MyFilter<I, P> {
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        // Pull until an item passes: a single `next` may consume many items
+        while let Some(item) = self.inner.next() {
+            if (self.predicate)(&item) { return Some(item); }
+        }
+        None
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)   // anywhere between none and all
+    }
+}
+
+impl<I: Iterator> Iterator for MyTake<I> {
+    type Item = I::Item;
+    fn next(&mut self) -> Option<I::Item> {
+        if self.remaining == 0 { return None; }  // never touches `inner`
+        self.remaining -= 1;
+        self.inner.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        let upper: usize = upper.map_or(self.remaining, |upper| {
+            upper.min(self.remaining)
+        });
+        (lower.min(self.remaining), Some(upper))
+    }
+}
+
+/// ## The Extension Trait
+/// The standard adapters are provided methods of `Iterator`. Outside the
+/// standard library, an extension trait with a blanket impl gives the same
+/// method syntax (see patterns/extension_traits.rs).
+pub trait MyIteratorExt: Iterator + Sized {
+    fn my_map<B, F: FnMut(Self::Item) -> B>(self, function: F)
+        -> MyMap<Self, F>
+    {
+        MyMap { inner: self, function }
+    }
+
+    fn my_filter<P: FnMut(&Self::Item) -> bool>(self, predicate: P)
+        -> MyFilter<Self, P>
+    {
+        MyFilter { inner: self, predicate }
+    }
+
+    fn my_take(self, count: usize) -> MyTake<Self> {
+        MyTake { inner: self, remaining: count }
+    }
+}
+
+impl<I: Iterator> MyIteratorExt for I {}
+
+runnable!(adapters, {
+    let chain = (1..=10).my_map(|x: u32| x * x).my_filter(|x| x % 2 == 1);
+    // The type of `chain` spells out the whole pipeline:
+    // MyFilter<MyMap<RangeInclusive<u32>, {closure}>, {closure}>
+    assert_eq!(chain.my_take(3).collect::<Vec<u32>>(), [1, 9, 25]);
+
+    let words: Vec<String> = ["a", "bb", "ccc"].iter()
+        .my_map(|word| word.to_uppercase())
+        .collect();
+    assert_eq!(words, ["A", "BB", "CCC"]);
+    assert_eq!((0..100).my_take(5).size_hint(), (5, Some(5)));
+    assert_eq!((0..100).my_filter(|_| true).size_hint(), (0, Some(100)));
+});
+
+/// ## Laziness
+/// Building a chain runs no closure. Consuming it runs each closure only as
+/// many times as needed: `my_take(2)` stops pulling after two items, so the
+/// rest of the (infinite!) input is never mapped.
+runnable!(laziness, {
+    let calls: Cell<u32> = Cell::new(0);
+    let chain = (1..)
+        .my_map(|x: u64| { calls.set(calls.get() + 1); x * 10 })
+        .my_filter(|x| x % 20 == 0)
+        .my_take(2);
+    assert_eq!(calls.get(), 0);                 // nothing ran yet
+
+    let values: Vec<u64> = chain.collect();
+    assert_eq!(values, [20, 40]);
+    assert_eq!(calls.get(), 4);                 // 10, 20, 30, 40: then stop
+
+    // An adapter which is never consumed does nothing (std warns about it)
+    let _unused = (1..4).my_map(|x: u32| calls.set(calls.get() + x));
+    assert_eq!(calls.get(), 4);
+});
+
+/// ## Same Behaviour as the Standard Library
+/// The same pipelines, built with both sets of adapters, over several
+/// inputs: the outputs and the order in which closures run are identical.
+runnable!(same_behaviour_as_std, {
+    for input in [vec![], vec![7], (0..50).collect::<Vec<i32>>()] {
+        for count in [0, 1, 3, 100] {
+            let trace: std::cell::RefCell<Vec<String>> = Default::default();
+            let log = |event: String| trace.borrow_mut().push(event);
+
+            let mine: Vec<i32> = input.iter()
+                .my_map(|x| { log(format!("map {}", x)); x * 3 })
+                .my_filter(|x| { log(format!("filter {}", x)); x % 2 == 0 })
+                .my_take(count)
+                .collect();
+            let my_trace: Vec<String> = trace.take();
+
+            let theirs: Vec<i32> = input.iter()
+                .map(|x| { log(format!("map {}", x)); x * 3 })
+                .filter(|x| { log(format!("filter {}", x)); x % 2 == 0 })
+                .take(count)
+                .collect();
+            assert_eq!(mine, theirs);
+            assert_eq!(my_trace, trace.take());
+        }
+    }
+});
@@ -0,0 +1,155 @@
+/// # Memory Layout
+/// Every type in Rust has a `size` (the number of bytes it occupies) and an
+/// `alignment` (the address of its values must be a multiple of it). Both are
+/// known at compile-time and can be inspected through `std::mem`:
+/// - `size_of::<T>()`: the size in bytes of `T`, including padding
+/// - `align_of::<T>()`: the alignment in bytes of `T`
+///
+/// Note: the size of a type is always a multiple of its alignment, so that
+///       arrays `[T; N]` can place their elements one after the other.
+use std::mem::{align_of, size_of};
+use std::num::NonZeroU8;
+
+fn layout() {}
+
+/// ## Primitives and Tuples
+/// Primitives are aligned to their own size. Tuples are aligned to the
+/// largest alignment of their elements, and padded accordingly.
+runnable!(primitives_and_tuples, {
+    println!("u8:  size={} align={}", size_of::<u8>(), align_of::<u8>());
+    println!("u32: size={} align={}", size_of::<u32>(), align_of::<u32>());
+    println!("u64: size={} align={}", size_of::<u64>(), align_of::<u64>());
+    println!("():  size={} align={}", size_of::<()>(), align_of::<()>());
+
+    // (u8, u32) = 1 byte + 3 bytes of padding + 4 bytes
+    assert_eq!(size_of::<(u8, u32)>(), 8);
+    assert_eq!(align_of::<(u8, u32)>(), 4);
+    // (u8, u8, u16) = 1 byte + 1 byte + 2 bytes, no padding needed
+    assert_eq!(size_of::<(u8, u8, u16)>(), 4);
+    println!("(u8, u32):     size={}", size_of::<(u8, u32)>());
+    println!("(u8, u8, u16): size={}", size_of::<(u8, u8, u16)>());
+
+    // References and boxes to sized types are as big as a pointer
+    assert_eq!(size_of::<&u8>(), size_of::<usize>());
+    assert_eq!(size_of::<Box<u64>>(), size_of::<usize>());
+    // References to unsized types are `fat pointers` (pointer + length)
+    assert_eq!(size_of::<&[u8]>(), 2 * size_of::<usize>());
+    assert_eq!(size_of::<&str>(), 2 * size_of::<usize>());
+});
+
+/// ## Enums and Niche Optimization
+/// An enum needs to store a `discriminant` (also called `tag`) to remember
+/// which variant it holds, in addition to the largest of its variants.
+///
+/// However, if one of the variants contains some invalid bit-patterns (a
+/// `niche`), the compiler can use them to encode the discriminant, avoiding
+/// any overhead. For example:
+/// - `&T` and `Box<T>` can never be null, so `None` is encoded as null
+/// - `NonZeroU8` can never be zero, so `None` is encoded as zero
+/// - `bool` only uses the values 0 and 1, so `None` is encoded as 2
+runnable!(enums_and_niches, {
+    enum Tagged { A(u32), B(u8) }
+    // 4 bytes for the largest variant + 1 byte of tag + 3 bytes of padding
+    assert_eq!(size_of::<Tagged>(), 8);
+    println!("Tagged: size={}", size_of::<Tagged>());
+
+    // No niche: `Option` needs an extra tag (padded to the alignment)
+    assert_eq!(size_of::<Option<u8>>(), 2);
+    assert_eq!(size_of::<Option<u32>>(), 8);
+
+    // Niche: `Option` is as big as the wrapped type
+    assert_eq!(size_of::<Option<&u8>>(), size_of::<&u8>());
+    assert_eq!(size_of::<Option<Box<u8>>>(), size_of::<Box<u8>>());
+    assert_eq!(size_of::<Option<NonZeroU8>>(), size_of::<NonZeroU8>());
+    assert_eq!(size_of::<Option<bool>>(), size_of::<bool>());
+    println!("Option<u8>:        size={}", size_of::<Option<u8>>());
+    println!("Option<&u8>:       size={}", size_of::<Option<&u8>>());
+    println!("Option<NonZeroU8>: size={}", size_of::<Option<NonZeroU8>>());
+
+    // let zero: NonZeroU8 = NonZeroU8::new(0).unwrap();
+    // ^ Error (at runtime): `new` returns `None` for a zero value
+    let one: NonZeroU8 = NonZeroU8::new(1).unwrap();
+    println!("one: {}", one);
+});
+
+/// ## Field Reordering and `repr(C)`
+/// By default (`repr(Rust)`), the compiler is free to reorder the fields of a
+/// struct to minimize padding. Using `#[repr(C)]`, the fields are laid out in
+/// declaration order, as a C compiler would do. This is required when sharing
+/// structures with foreign code (see unsafe_code.rs).
+runnable!(repr_c, {
+    struct Reordered { a: u8, b: u32, c: u8 }
+    #[repr(C)]
+    struct Ordered { a: u8, b: u32, c: u8 }
+
+    // Reordered: [b b b b][a][c][_ _]     = 8 bytes
+    // Ordered:   [a][_ _ _][b b b b][c][_ _ _] = 12 bytes
+    assert_eq!(size_of::<Reordered>(), 8);
+    assert_eq!(size_of::<Ordered>(), 12);
+    println!("Reordered: size={}", size_of::<Reordered>());
+    println!("Ordered:   size={}", size_of::<Ordered>());
+
+    /// ### Field Offsets
+    /// The offset of a field can be read with `std::mem::offset_of!`.
+    use std::mem::offset_of;
+    println!(
+        "Ordered offsets: a={} b={} c={}",
+        offset_of!(Ordered, a),
+        offset_of!(Ordered, b),
+        offset_of!(Ordered, c),
+    );
+    assert_eq!(offset_of!(Ordered, b), 4);
+});
+
+/// ## Packed Structs
+/// Using `#[repr(packed)]`, the compiler removes all padding, setting the
+/// alignment of the struct to 1. This saves memory, but fields may not be
+/// aligned anymore: creating a reference to an unaligned field is undefined
+/// behavior, so the compiler forbids it.
+runnable!(repr_packed, {
+    #[repr(C, packed)]
+    struct Packed { a: u8, b: u32 }
+    assert_eq!(size_of::<Packed>(), 5);
+    assert_eq!(align_of::<Packed>(), 1);
+
+    let packed: Packed = Packed { a: 1, b: 2 };
+    // let b: &u32 = &packed.b;
+    // ^ Error: reference to packed field is unaligned
+
+    // Fields must be copied out (by value) before being used
+    let b: u32 = packed.b;
+    println!("Packed: size={} b={}", size_of::<Packed>(), b);
+
+    // Or read through a raw pointer, explicitly allowing unaligned reads
+    let b_ptr: *const u32 = std::ptr::addr_of!(packed.b);
+    let b: u32 = unsafe { b_ptr.read_unaligned() };
+    println!("Packed: b (unaligned read)={}", b);
+});
+
+/// ## Padding Visualization
+/// The padding of a `repr(C)` struct can be visualized by marking which bytes
+/// are covered by a field. Each byte is printed as the name of the field it
+/// belongs to, or `_` if it is padding.
+fn visualize(size: usize, fields: &[(char, usize, usize)]) -> String {
+    let mut bytes: Vec<char> = vec!['_'; size];
+    for &(name, offset, field_size) in fields {
+        for byte in &mut bytes[offset..offset + field_size] { *byte = name; }
+    }
+    bytes.into_iter().collect()
+}
+
+runnable!(padding_visualization, {
+    use std::mem::offset_of;
+
+    #[repr(C)]
+    struct Padded { a: u8, b: u16, c: u8, d: u64 }
+
+    let picture: String = visualize(size_of::<Padded>(), &[
+        ('a', offset_of!(Padded, a), size_of::<u8>()),
+        ('b', offset_of!(Padded, b), size_of::<u16>()),
+        ('c', offset_of!(Padded, c), size_of::<u8>()),
+        ('d', offset_of!(Padded, d), size_of::<u64>()),
+    ]);
+    println!("Padded: [{}]", picture);
+    assert_eq!(picture, "a_bbc___dddddddd");
+});
\ No newline at end of file
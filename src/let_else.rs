@@ -0,0 +1,186 @@
+/// # Let-Else and Early Returns (~ Swift `guard let`)
+/// `let PATTERN = EXPRESSION else { DIVERGING };` destructures a value with
+/// a refutable pattern: if the pattern matches, its bindings are available
+/// in the rest of the block; otherwise, the `else` block runs, and must
+/// diverge (`return`, `break`, `continue`, `panic!`, see never_type.rs).
+///
+/// It is the tool for `guard-style` code: check the preconditions first,
+/// leave early when they fail, and keep the happy path unindented.
+use std::collections::HashMap;
+
+fn let_else() {}
+
+/// ## Early-Return Extraction
+fn port(settings: &HashMap<&str, &str>) -> Result<u16, String> {
+    let Some(text) = settings.get("port") else {
+        return Err("missing port".to_string());
+    };
+    let Ok(port) = text.parse::<u16>() else {
+        return Err(format!("invalid port: {}", text));
+    };
+    Ok(port)
+}
+
+runnable!(early_return_extraction, {
+    let mut settings: HashMap<&str, &str> = HashMap::new();
+    assert_eq!(port(&settings), Err("missing port".to_string()));
+    settings.insert("port", "http");
+    assert_eq!(port(&settings), Err("invalid port: http".to_string()));
+    settings.insert("port", "8080");
+    assert_eq!(port(&settings), Ok(8080));
+
+    // let Some(text) = settings.get("host") else { println!("no host"); };
+    // ^ Error: `else` clause of `let...else` does not diverge
+});
+
+/// ## Inside Loops
+/// In a loop, `continue` skips the invalid items, `break` stops at the
+/// first one.
+runnable!(inside_loops, {
+    let mut sum: u32 = 0;
+    for line in ["1", "two", "3", "", "5"] {
+        let Ok(number) = line.parse::<u32>() else { continue };
+        sum += number;
+    }
+    assert_eq!(sum, 9);
+});
+
+/// ## Nested `if let`
+/// Before `let-else`, the same extraction needed one level of nesting per
+/// step: the happy path ends up at the bottom-right of the function, far
+/// from the error it would return.
+#[derive(Debug, PartialEq)]
+struct Request<'a> {
+    method: &'a str,
+    path: &'a str,
+}
+
+fn parse_nested(line: &str) -> Option<Request> {
+    if let Some((method, rest)) = line.split_once(' ') {
+        if let Some((path, version)) = rest.split_once(' ') {
+            if version == "HTTP/1.1" {
+                if path.starts_with('/') {
+                    return Some(Request { method, path });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The same parser, flattened.
+fn parse_flat(line: &str) -> Option<Request> {
+    let Some((method, rest)) = line.split_once(' ') else { return None };
+    let Some((path, "HTTP/1.1")) = rest.split_once(' ') else { return None };
+    if !path.starts_with('/') {
+        return None;
+    }
+    Some(Request { method, path })
+}
+
+/// When the failure cases need no distinct handling, `?` on `Option` is
+/// even shorter (see errors.rs).
+fn parse_with_question_mark(line: &str) -> Option<Request> {
+    let (method, rest) = line.split_once(' ')?;
+    let (path, version) = rest.split_once(' ')?;
+    (version == "HTTP/1.1" && path.starts_with('/'))
+        .then_some(Request { method, path })
+}
+
+runnable!(flattening_nested_code, {
+    for line in ["GET /index.html HTTP/1.1", "GET index HTTP/1.1",
+                 "POST /form HTTP/2", "garbage"] {
+        assert_eq!(parse_nested(line), parse_flat(line));
+        assert_eq!(parse_nested(line), parse_with_question_mark(line));
+    }
+    assert_eq!(parse_flat("GET / HTTP/1.1"),
+        Some(Request { method: "GET", path: "/" }));
+});
+
+/// ## Let-Chains
+/// `if let ... && condition && let ...` chains several patterns and
+/// conditions in a single `if`, without nesting. They are only available
+/// in edition 2024, while this crate uses edition 2021:
+/// ```
+/// if let Some((method, rest)) = line.split_once(' ')
+///     && let Some((path, version)) = rest.split_once(' ')
+///     && version == "HTTP/1.1"
+/// {
+///     return Some(Request { method, path });
+/// }
+/// // ^ Error: let chains are only allowed in Rust 2024 or later
+/// ```
+/// In edition 2021, the alternatives are nesting, `let-else`, or matching a
+/// tuple of the values at once.
+fn parse_tuple(line: &str) -> Option<Request> {
+    let (method, rest) = line.split_once(' ')?;
+    match (rest.split_once(' '), method) {
+        (Some((path @ ("/" | "/index.html"), "HTTP/1.1")), "GET") => {
+            Some(Request { method, path })
+        }
+        _ => None,
+    }
+}
+
+runnable!(let_chains_alternatives, {
+    assert!(parse_tuple("GET / HTTP/1.1").is_some());
+    assert!(parse_tuple("POST / HTTP/1.1").is_none());
+});
+
+/// ## Refactoring Nested Matches
+/// A `match` per step, each with its own error, becomes a sequence of
+/// `let-else` statements, each handling its failure on the spot.
+#[derive(Debug, PartialEq)]
+enum ConfigError {
+    Missing(&'static str),
+    NotANumber(String),
+    OutOfRange(u32),
+}
+
+fn workers_nested(settings: &HashMap<&str, &str>) -> Result<u32, ConfigError> {
+    match settings.get("workers") {
+        Some(text) => match text.parse::<u32>() {
+            Ok(count) => match count {
+                1..=64 => Ok(count),
+                _ => Err(ConfigError::OutOfRange(count)),
+            },
+            Err(_) => Err(ConfigError::NotANumber(text.to_string())),
+        },
+        None => Err(ConfigError::Missing("workers")),
+    }
+}
+
+fn workers_flat(settings: &HashMap<&str, &str>) -> Result<u32, ConfigError> {
+    let Some(text) = settings.get("workers") else {
+        return Err(ConfigError::Missing("workers"));
+    };
+    let Ok(count) = text.parse::<u32>() else {
+        return Err(ConfigError::NotANumber(text.to_string()));
+    };
+    let 1..=64 = count else {
+        return Err(ConfigError::OutOfRange(count));
+    };
+    Ok(count)
+}
+
+runnable!(refactoring_nested_matches, {
+    let mut settings: HashMap<&str, &str> = HashMap::new();
+    for value in [None, Some("many"), Some("0"), Some("8")] {
+        if let Some(value) = value {
+            settings.insert("workers", value);
+        }
+        assert_eq!(workers_nested(&settings), workers_flat(&settings));
+    }
+    assert_eq!(workers_flat(&settings), Ok(8));
+});
+
+/// ## Limits
+/// The `else` block cannot see the value which failed to match: when the
+/// error needs it (e.g. the `Err` of a `Result`), use `match` or `map_err`
+/// with `?` instead.
+/// ```
+/// let Ok(port) = text.parse::<u16>() else {
+///     return Err(error);  // `error` does not exist here
+/// };
+/// ```
+fn limits() {}
\ No newline at end of file
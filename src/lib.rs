@@ -0,0 +1,141 @@
+//! # Rust Playground (library target)
+//! Besides the binary (`src/main.rs`), the package has a library target,
+//! named `rust_plauground` too. The binary and the integration tests (in
+//! `tests/`) are separate crates, depending on it like on any other library:
+//! lessons use it when the difference between the crate defining an API and
+//! its downstream crates matters (see non_exhaustive.rs and
+//! patterns/sealed.rs), and for doctests, which only run for libraries (see
+//! compile_errors.rs).
+
+pub mod compile_errors;
+pub mod doctests;
+
+/// ## Non-Exhaustive Enums
+/// A logging library which may add levels (e.g. `Debug`, `Trace`) in a
+/// future minor version.
+///
+/// Downstream crates (including doctests) must match it with a wildcard:
+/// ```compile_fail
+/// use rust_plauground::LogLevel;
+/// fn label(level: LogLevel) -> &'static str {
+///     match level {
+///         LogLevel::Error => "error",
+///         LogLevel::Warning => "warning",
+///         LogLevel::Info => "info",
+///     }
+///     // ^ Error: non-exhaustive patterns: `_` not covered
+/// }
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Warning,
+    Info,
+}
+
+impl LogLevel {
+    /// Inside the defining crate, the enum is exhaustive: no wildcard is
+    /// needed, so adding a variant shows every match to update.
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warning => "warning",
+            LogLevel::Info => "info",
+        }
+    }
+}
+
+/// ## Non-Exhaustive Structs
+/// A configuration which may gain fields in a future minor version.
+///
+/// Downstream crates can read its public fields, but cannot build it with a
+/// struct expression, nor destructure it without `..`:
+/// ```compile_fail
+/// use rust_plauground::{LoggerConfig, LogLevel};
+/// let config = LoggerConfig { level: LogLevel::Info, colored: false };
+/// // ^ Error: cannot create non-exhaustive struct using struct expression
+/// ```
+/// They go through the constructors instead:
+/// ```
+/// use rust_plauground::{LoggerConfig, LogLevel};
+/// let config = LoggerConfig::new(LogLevel::Info).colored(true);
+/// let LoggerConfig { level, .. } = config;
+/// assert_eq!(level, LogLevel::Info);
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoggerConfig {
+    pub level: LogLevel,
+    pub colored: bool,
+}
+
+impl LoggerConfig {
+    pub fn new(level: LogLevel) -> LoggerConfig {
+        LoggerConfig { level, colored: false }
+    }
+
+    pub fn colored(mut self, colored: bool) -> LoggerConfig {
+        self.colored = colored;
+        self
+    }
+}
+
+impl Default for LoggerConfig {
+    fn default() -> LoggerConfig {
+        LoggerConfig::new(LogLevel::Warning)
+    }
+}
+
+/// ## Non-Exhaustive Variants
+/// `#[non_exhaustive]` also applies to a single variant: downstream crates
+/// cannot build it, and must match it with `..`.
+/// ```compile_fail
+/// use rust_plauground::{Event, LogLevel};
+/// let event = Event::Message { level: LogLevel::Info, text: String::new() };
+/// // ^ Error: cannot create non-exhaustive variant using struct expression
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    #[non_exhaustive]
+    Message { level: LogLevel, text: String },
+    Flush,
+}
+
+impl Event {
+    pub fn message(level: LogLevel, text: &str) -> Event {
+        Event::Message { level, text: text.to_string() }
+    }
+}
+
+/// ## Sealed Traits
+/// The shapes of patterns/sealed.rs: `Shape` requires `Sealed`, which lives
+/// in a private module, so downstream crates can use `Shape` but not
+/// implement it (checked by the doctests of compile_errors.rs).
+pub trait Shape: private::Sealed {
+    fn area(&self) -> f64;
+    fn describe(&self) -> String {
+        format!("shape with area {:.2}", self.area())
+    }
+}
+
+mod private {
+    /// The supertrait is public (so it can appear in a public bound), but it
+    /// lives in a private module, so it cannot be named outside this crate.
+    pub trait Sealed {}
+}
+
+pub struct Square(pub f64);
+pub struct Circle(pub f64);
+
+// Only this crate can implement `Sealed`, so only this crate can implement
+// `Shape`
+impl private::Sealed for Square {}
+impl private::Sealed for Circle {}
+
+impl Shape for Square {
+    fn area(&self) -> f64 { self.0 * self.0 }
+}
+impl Shape for Circle {
+    fn area(&self) -> f64 { std::f64::consts::PI * self.0 * self.0 }
+}
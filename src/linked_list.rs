@@ -0,0 +1,282 @@
+/// # Linked Lists (~ the hard way)
+/// In most languages, a linked list is the first data structure one writes.
+/// In Rust, it is a classic exercise for a different reason: it exercises
+/// ownership at every step (who owns each node, how to move nodes in and
+/// out of `Option`s, how long borrows of a node last).
+///
+/// Note: in practice, `Vec` and `VecDeque` are faster for almost everything
+///       (contiguous memory, no allocation per element), and
+///       `std::collections::LinkedList` exists for the remaining cases.
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+fn linked_list() {}
+
+/// ## A Singly-Linked Stack
+/// Each node owns the next one through a `Box`: the list is a chain of
+/// unique owners. `Option<Box<Node<T>>>` is as big as a pointer, `None`
+/// being the null pointer (see layout.rs).
+pub struct Stack<T> {
+    head: Link<T>,
+    length: usize,
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    next: Link<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Stack<T> {
+        Stack { head: None, length: 0 }
+    }
+
+    /// `Option::take` moves the old head out, leaving `None` in its place:
+    /// `self.head` cannot be left empty, even for a moment.
+    pub fn push(&mut self, value: T) {
+        let next: Link<T> = self.head.take();
+        let node: Box<Node<T>> = Box::new(Node { value, next });
+        self.head = Some(node);
+        self.length += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.head.take().map(|node| {
+            self.head = node.next;      // move the rest of the list back
+            self.length -= 1;
+            node.value                  // the box is freed here
+        })
+    }
+
+    /// `as_ref`/`as_mut` turn `&Option<Box<Node>>` into `Option<&Box<Node>>`,
+    /// borrowing the node instead of moving it out of the list.
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.value)
+    }
+
+    pub fn len(&self) -> usize { self.length }
+}
+
+runnable!(singly_linked_stack, {
+    let mut stack: Stack<String> = Stack::new();
+    assert_eq!(stack.pop(), None);
+    stack.push("a".to_string());
+    stack.push("b".to_string());
+    assert_eq!(stack.peek().map(String::as_str), Some("b"));
+    stack.peek_mut().unwrap().push('!');
+    assert_eq!(stack.pop().as_deref(), Some("b!"));
+    assert_eq!(stack.len(), 1);
+    // stack.head = stack.head.unwrap().next;
+    // ^ Error: cannot move out of type `Stack<String>`, which implements the
+    //          `Drop` trait (and inside `&mut self` methods: cannot move out
+    //          of `self.head` which is behind a mutable reference)
+});
+
+/// ## Iterators
+/// Three iterators, as for every collection in `std`:
+/// - `IntoIter`: owns the stack, and pops its values
+/// - `Iter`: borrows a node at a time, yielding `&T`
+/// - `IterMut`: the same with `&mut T`. `take` moves the link out of the
+///   iterator, so that each node is mutably borrowed only once
+pub struct IntoIter<T>(Stack<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> { self.0.pop() }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();   // `&Box<Node>` to `&Node`
+            &node.value
+        })
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref_mut();
+            &mut node.value
+        })
+    }
+}
+
+impl<T> Stack<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.head.as_deref_mut() }
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> { IntoIter(self) }
+}
+
+runnable!(iterators, {
+    let mut stack: Stack<u32> = Stack::new();
+    for value in 1..=3 { stack.push(value); }
+    assert_eq!(stack.iter().collect::<Vec<&u32>>(), [&3, &2, &1]);
+    for value in stack.iter_mut() { *value *= 10; }
+    assert_eq!(stack.into_iter().collect::<Vec<u32>>(), [30, 20, 10]);
+});
+
+/// ## Recursive Drop
+/// Without a `Drop` implementation, dropping the stack drops the head box,
+/// which drops the next box, and so on: one nested call per node. A long
+/// list overflows the stack of the thread:
+/// ```
+/// thread 'linked_list::recursive_drop' has overflowed its stack
+/// fatal runtime error: stack overflow
+/// ```
+/// The fix drops the nodes in a loop, detaching each one from its
+/// successor first, so that dropping a node never recurses.
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        let mut link: Link<T> = self.head.take();
+        while let Some(mut node) = link {
+            link = node.next.take();
+            // <-- `node` is dropped here, with an empty `next`
+        }
+    }
+}
+
+runnable!(recursive_drop, {
+    let mut stack: Stack<u32> = Stack::new();
+    for value in 0..1_000_000 { stack.push(value); }
+    drop(stack);                        // fine, thanks to the loop
+});
+
+/// ## Teaser: an Unsafe Doubly-Linked Deque
+/// In a doubly-linked list, each node has two "owners" (its previous and
+/// next nodes): `Box` cannot express it. Safe alternatives use
+/// `Rc<RefCell<Node>>` with `Weak` back-links (see shared_graph.rs), at the
+/// cost of counters and runtime checks. `std::collections::LinkedList` uses
+/// raw pointers instead, and so does this deque (see unsafe_code/non_null.rs).
+///
+/// Mixing the two does not work: a raw pointer into a node still owned by a
+/// `Box` is invalidated when the `Box` moves (the aliasing rules treat a
+/// `Box` like a `&mut`). So every link is a `NonNull`, each node is leaked
+/// with `Box::into_raw` when pushed, and taken back with `Box::from_raw`
+/// exactly once: when popped, or when the deque is dropped.
+///
+/// Invariants: `head` and `tail` are both `None` or both `Some`; each node
+/// is reachable from `head` through `next`, and from `tail` through `prev`.
+///
+/// Note: check unsafe code like this one under Miri, which detects leaks,
+///       double frees and aliasing violations:
+///       `cargo +nightly miri test unsafe_doubly_linked_teaser`.
+pub struct Deque<T> {
+    head: Option<NonNull<DoubleNode<T>>>,
+    tail: Option<NonNull<DoubleNode<T>>>,
+    /// The deque owns its nodes, and their values (see drop_check.rs).
+    marker: PhantomData<Box<DoubleNode<T>>>,
+}
+
+struct DoubleNode<T> {
+    value: T,
+    prev: Option<NonNull<DoubleNode<T>>>,
+    next: Option<NonNull<DoubleNode<T>>>,
+}
+
+impl<T> Deque<T> {
+    pub fn new() -> Deque<T> {
+        Deque { head: None, tail: None, marker: PhantomData }
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let node = Box::new(DoubleNode { value, prev: self.tail, next: None });
+        let node: NonNull<DoubleNode<T>> = NonNull::from(Box::leak(node));
+        match self.tail {
+            // SAFETY: `tail` points to a live node, owned by the deque.
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let node = Box::new(DoubleNode { value, prev: None, next: self.head });
+        let node: NonNull<DoubleNode<T>> = NonNull::from(Box::leak(node));
+        match self.head {
+            // SAFETY: `head` points to a live node, owned by the deque.
+            Some(head) => unsafe { (*head.as_ptr()).prev = Some(node) },
+            None => self.tail = Some(node),
+        }
+        self.head = Some(node);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|head| {
+            // SAFETY: `head` was leaked by a push, and is unlinked below:
+            // nothing can reach it after this `Box` takes it back.
+            let node: Box<DoubleNode<T>> =
+                unsafe { Box::from_raw(head.as_ptr()) };
+            self.head = node.next;
+            match self.head {
+                // SAFETY: the new head is a live node of the deque.
+                Some(next) => unsafe { (*next.as_ptr()).prev = None },
+                None => self.tail = None,
+            }
+            node.value
+        })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|tail| {
+            // SAFETY: as in `pop_front`, from the other end.
+            let node: Box<DoubleNode<T>> =
+                unsafe { Box::from_raw(tail.as_ptr()) };
+            self.tail = node.prev;
+            match self.tail {
+                // SAFETY: the new tail is a live node of the deque.
+                Some(prev) => unsafe { (*prev.as_ptr()).next = None },
+                None => self.head = None,
+            }
+            node.value
+        })
+    }
+}
+
+/// The nodes are not owned by any `Box` while in the deque: without this
+/// impl, they would leak.
+impl<T> Drop for Deque<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+runnable!(unsafe_doubly_linked_teaser, {
+    let mut deque: Deque<String> = Deque::new();
+    deque.push_back("b".to_string());
+    deque.push_front("a".to_string());
+    deque.push_back("c".to_string());
+    assert_eq!(deque.pop_front().as_deref(), Some("a"));
+    assert_eq!(deque.pop_back().as_deref(), Some("c"));
+    assert_eq!(deque.pop_back().as_deref(), Some("b"));
+    assert_eq!(deque.pop_front(), None);
+    deque.push_front("d".to_string());      // both ends were reset
+    assert_eq!(deque.pop_back().as_deref(), Some("d"));
+    deque.push_back("e".to_string());       // freed by `Drop`
+});
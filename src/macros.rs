@@ -180,4 +180,97 @@ mod module {
         my_macro1!();
         my_macro3!();
     });
+}
+
+/// ## Hygiene
+/// Macros by example are `hygienic`: each identifier remembers where it was
+/// written, and it is resolved there. Local variables (and labels) created
+/// inside a macro cannot be seen by the caller, and the macro cannot see the
+/// local variables of the caller, even if they have the same name.
+///
+/// Hygiene only applies to local variables, labels and `$crate`: items like
+/// functions, structs or modules are resolved at the call site (see `new_fn!`
+/// above, which defines a function usable by the caller).
+pub mod hygiene {
+    macro_rules! declare_secret { () => { let secret: u32 = 42; }; }
+    macro_rules! read_secret { () => { secret }; }
+
+    /// `value` inside the macro and `value` at the call site are two
+    /// different variables: the macro cannot accidentally capture or shadow
+    /// the variables of its caller.
+    macro_rules! square {
+        ($exp: expr) => ({ let value = $exp; value * value });
+    }
+
+    runnable!(hygienic_identifiers, {
+        declare_secret!();
+        // println!("{}", secret);
+        // ^ Error: cannot find value `secret` in this scope
+
+        let secret: u32 = 7;
+        // println!("{}", read_secret!());
+        // ^ Error: cannot find value `secret` in this scope
+        //   (the `secret` of the caller is not the one of the macro)
+
+        let value: u32 = 3;
+        println!("square of value + 1: {}", square!(value + 1));  // 16
+    });
+
+    /// ### Deliberately Unhygienic Macros
+    /// To let the caller use an identifier introduced by the macro, the
+    /// caller must provide it as an `ident` parameter: the identifier then
+    /// belongs to the call site.
+    macro_rules! declare {
+        ($name: ident = $value: expr) => { let $name = $value; };
+    }
+    macro_rules! with_counter {
+        ($counter: ident, $body: block) => ({
+            let mut $counter: u32 = 0;
+            for _ in 0..3 { $body }
+            $counter
+        });
+    }
+
+    runnable!(unhygienic_identifiers, {
+        declare!(answer = 42);
+        println!("answer: {}", answer);     // visible: named by the caller
+
+        let count: u32 = with_counter!(calls, { calls += 1; });
+        println!("count: {}", count);
+    });
+
+    /// ### $crate
+    /// Hygiene does not cover paths: a path in a macro is resolved where the
+    /// macro is expanded. A macro exported to other crates (with
+    /// `#[macro_export]`, see util.rs) that refers to `crate::...` would then
+    /// point to the crate of the caller, where the item does not exist.
+    ///
+    /// `$crate` is a special metavariable that always expands to the crate
+    /// defining the macro, making paths work from any crate. The rest of the
+    /// path is still resolved from the crate of the caller, so each module
+    /// along it must be public: here `macros` (see main.rs) and `hygiene`.
+    pub fn elapsed_millis(start: std::time::Instant) -> u128 {
+        start.elapsed().as_millis()
+    }
+
+    #[macro_export] macro_rules! measure {
+        ($exp: expr) => ({
+            let start = ::std::time::Instant::now();
+            let result = $exp;
+            // crate::macros::hygiene::elapsed_millis(start)
+            // ^ Would only work inside this crate
+            let millis = $crate::macros::hygiene::elapsed_millis(start);
+            println!("{} took {} ms", stringify!($exp), millis);
+            result
+        });
+    }
+
+    /// The same applies to the standard library: `std::...` is resolved at
+    /// the call site, where a local module named `std` would hide it, while
+    /// `::std::...` always refers to the standard library. This is why
+    /// `runnable!` and `benchmark!` (see util.rs) use absolute paths.
+    runnable!(crate_metavariable, {
+        let sum: u64 = measure!((1..=1_000u64).sum::<u64>());
+        println!("sum: {}", sum);
+    });
 }
\ No newline at end of file
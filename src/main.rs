@@ -5,31 +5,95 @@
 // Use other modules so that they are compiled
 // Create modules for each file in the crate `src`, so they are compiled
 #[macro_use] pub mod util;
+mod advanced_macros;
+mod allocator;
 mod annotations;
 mod assignments;
+mod associated_consts;
+mod async_await;
+#[cfg(feature = "tokio")] mod async_tokio;
+mod binary_search_tree;
+mod black_box;
+mod blanket_impls;
+mod boolean_patterns;
+mod bounds_checks;
+mod build_scripts;
 mod cargo;
 mod closures;
 mod crates;
 mod documentation;
+mod drop_check;
+mod dyn_error;
+mod dyn_internals;
+mod embedded_assets;
 mod enums;
 mod errors;
 mod expressions;
+mod features;
+mod ffi_callbacks;
+mod ffi_strings;
+mod fixtures;
+mod function_pointers;
 mod functions;
+mod futures;
+mod fuzzing;
 mod generics;
+mod graph;
+mod higher_ranked_bounds;
 mod imports;
-mod macros;
+mod inlining;
+mod interior_mutability;
+mod iterator_adapters;
+mod layout;
+mod let_else;
+mod linked_list;
+pub mod macros;
+mod marker_traits;
+mod maybe_uninit;
 mod methods;
 mod modules;
+mod monomorphization;
+mod native_linking;
+mod nested_patterns;
+mod never_type;
+mod no_std;
+mod non_exhaustive;
+mod object_safety;
+mod opaque_handles;
+mod operators;
 mod ownership;
+mod parser;
 mod pattern_matching;
+mod patterns;
+mod pinning;
 mod primitives;
 mod printing;
+mod proc_macros;
+mod reactor;
+mod reborrowing;
+mod recursion;
 mod references;
+mod repr_transparent;
+mod reverse_ffi;
+mod self_referential;
+mod shared_graph;
+mod simd;
+mod state_machines;
+mod static_assertions;
+mod streams;
 mod structures;
+mod target_config;
+mod testing;
 mod unit_testing;
+mod trait_upcasting;
 mod traits;
+mod transmute;
 mod types;
+mod unions;
 mod unsafe_code;
+mod variance;
+mod wasm;
+mod zero_cost;
 // -----------------------------------------------------------------------------
 
 /// # Entry Point (Main Function)
@@ -38,6 +102,7 @@ mod unsafe_code;
 /// - `rustc **/main.rs` and then execute the output binary `**/main`
 /// - Clicking on `Run` in VsCode with Rust-Analyzer on top of a `main` function
 fn main() {
+    print!("{}", embedded_assets::BANNER);
     println!("Hello, world!");
     println!("I'm a Rustacean");
 
@@ -47,4 +112,18 @@ fn main() {
     let program: &String = &args[0];
     let program_args: &[String] = &args[1..];
     println!("Running {:?} with arguments {:?}", program, program_args);
-}
+
+    /// ## Running All the Runnables
+    /// `cargo run -- --run-all` runs every runnable through the custom test
+    /// harness (see tests/custom_harness.rs), with the features of this
+    /// binary (see features.rs).
+    if program_args.iter().any(|arg| arg == "--run-all") {
+        let cargo: String = std::env::var("CARGO").unwrap_or("cargo".into());
+        let status = std::process::Command::new(&cargo)
+            .args(["test", "--test", "custom_harness", "--features"])
+            .arg(features::enabled_features().join(","))
+            .status()
+            .expect("cannot run cargo");
+        if !status.success() { std::process::exit(1); }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+/// # Marker Traits and Auto Traits
+/// A `marker trait` is a trait with no methods: implementing it does not add
+/// any behaviour to a type, it only states a property of that type. Markers
+/// are used as bounds, so that the compiler checks that property for us.
+///
+/// Some markers of the standard library are `auto traits`: the compiler
+/// implements them automatically for every type whose fields all implement
+/// them. The most common are:
+/// - `Send`: values of the type can be moved to another thread
+/// - `Sync`: values of the type can be shared between threads (i.e. `&T` is
+///   `Send`)
+/// - `Unpin`: values of the type can be moved after being pinned (see
+///   pinning.rs)
+///
+/// Other markers, like `Copy`, `Eq` or `Sized`, are not auto traits: they
+/// must be derived or implemented explicitly (`Sized` is implied instead).
+use std::cell::Cell;
+use std::marker::{PhantomData, PhantomPinned};
+use std::rc::Rc;
+
+fn marker_traits() {}
+
+/// ## Custom Marker Traits
+/// A marker trait can encode a capability of a type. Here, only the types
+/// implementing `Trusted` can be executed without being sanitized first.
+trait Trusted {}
+
+struct Literal(&'static str);
+struct UserInput(String);
+struct Sanitized(String);
+
+impl Trusted for Literal {}
+impl Trusted for Sanitized {}
+
+trait Query { fn sql(&self) -> &str; }
+impl Query for Literal { fn sql(&self) -> &str { self.0 } }
+impl Query for UserInput { fn sql(&self) -> &str { &self.0 } }
+impl Query for Sanitized { fn sql(&self) -> &str { &self.0 } }
+
+fn execute<Q: Query + Trusted>(query: &Q) -> String {
+    format!("executing `{}`", query.sql())
+}
+fn sanitize(input: UserInput) -> Sanitized {
+    Sanitized(input.0.replace(';', ""))
+}
+
+runnable!(custom_marker_traits, {
+    println!("{}", execute(&Literal("SELECT * FROM users")));
+
+    let input: UserInput = UserInput(String::from("1; DROP TABLE users"));
+    // execute(&input);
+    // ^ Error: the trait bound `UserInput: Trusted` is not satisfied
+    println!("{}", execute(&sanitize(input)));
+});
+
+/// ## Auto Traits
+/// Auto traits propagate through the fields of a type: a struct is `Send` if
+/// all of its fields are `Send`, without writing anything.
+struct Plain { id: u32, name: String }
+struct Shared { counter: Rc<u32> }  // `Rc` is neither `Send` nor `Sync`
+struct Mutable { value: Cell<u32> }  // `Cell` is `Send`, but not `Sync`
+
+runnable!(auto_traits, {
+    // Compile-time assertions that `Plain` implements the markers (see
+    // static_assertions.rs)
+    static_assert_impl!(Plain: Send + Sync + Unpin);
+
+    // static_assert_impl!(Shared: Send);
+    // ^ Error: `Rc<u32>` cannot be sent between threads safely
+    //   note: required because it appears within the type `Shared`
+    static_assert_impl!(Mutable: Send);
+    // static_assert_impl!(Mutable: Sync);
+    // ^ Error: `Cell<u32>` cannot be shared between threads safely
+
+    // The compiler checks auto traits where they matter, e.g. `thread::spawn`
+    // requires its closure (and so everything it captures) to be `Send`
+    let plain: Plain = Plain { id: 1, name: String::from("plain") };
+    let handle = std::thread::spawn(move || plain.name.len());
+    println!("length computed in another thread: {}", handle.join().unwrap());
+});
+
+/// ## Opting Out of Auto Traits
+/// Opting out with `impl !Send for T {}` (a `negative implementation`) is
+/// still unstable. On stable Rust, a type opts out by containing a field
+/// that does not implement the auto trait, usually a zero-sized
+/// `PhantomData` (see generics.rs):
+/// - `PhantomData<*const ()>`: neither `Send` nor `Sync` (raw pointers
+///   implement neither)
+/// - `PhantomData<Cell<()>>`: `Send`, but not `Sync`
+/// - `PhantomPinned`: not `Unpin`
+///
+/// Useful e.g. for a handle that must stay on the thread that created it.
+struct ThreadBound { id: u32, _not_send: PhantomData<*const ()> }
+struct NotUnpin { id: u32, _pinned: PhantomPinned }
+
+// impl !Send for ThreadBound {}
+// ^ Error: negative trait bounds are not fully implemented; use marker
+//          types for now
+
+runnable!(opting_out_of_auto_traits, {
+    // static_assert_impl!(ThreadBound: Send);
+    // ^ Error: `*const ()` cannot be sent between threads safely
+    // static_assert_impl!(NotUnpin: Unpin);
+    // ^ Error: `PhantomPinned` cannot be unpinned
+    assert_eq!(std::mem::size_of::<ThreadBound>(), std::mem::size_of::<u32>());
+
+    let handle: ThreadBound = ThreadBound { id: 7, _not_send: PhantomData };
+    // std::thread::spawn(move || handle.id);
+    // ^ Error: `*const ()` cannot be sent between threads safely
+    println!("handle {} stays on this thread", handle.id);
+});
+
+/// ## Opting Back In
+/// The opposite is possible too: a type containing a raw pointer can promise
+/// to be thread-safe with an `unsafe impl` (see unsafe_code.rs). It is unsafe
+/// because the compiler cannot check the promise: the author must guarantee
+/// that the pointed data is never accessed concurrently without sync.
+struct StaticMessage { ptr: *const u8, len: usize }
+
+// Safety: the pointer always comes from a `&'static str`, which is immutable
+// and valid for the whole execution
+unsafe impl Send for StaticMessage {}
+unsafe impl Sync for StaticMessage {}
+
+impl StaticMessage {
+    fn new(message: &'static str) -> Self {
+        StaticMessage { ptr: message.as_ptr(), len: message.len() }
+    }
+    fn get(&self) -> &'static str {
+        unsafe {
+            let bytes = std::slice::from_raw_parts(self.ptr, self.len);
+            std::str::from_utf8_unchecked(bytes)
+        }
+    }
+}
+
+runnable!(opting_back_in, {
+    static_assert_impl!(StaticMessage: Send);
+    let message: StaticMessage = StaticMessage::new("hello from a raw pointer");
+    let handle = std::thread::spawn(move || message.get().len());
+    println!("length: {}", handle.join().unwrap());
+});
\ No newline at end of file
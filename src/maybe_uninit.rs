@@ -0,0 +1,139 @@
+/// # Uninitialized Memory (~ C locals without initializer)
+/// Safe Rust never lets a program read uninitialized memory: every variable
+/// must be assigned before use. Sometimes, initializing twice is wasteful
+/// (large buffers filled right after) or impossible (arrays of non-`Copy`
+/// values built element by element, out-parameters of C functions).
+///
+/// `std::mem::MaybeUninit<T>` is a wrapper which may or may not hold a valid
+/// `T`, with the same size and alignment as `T`. The compiler assumes nothing
+/// about its content, until `assume_init` promises that it is initialized.
+use std::mem::MaybeUninit;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn maybe_uninit() {}
+
+/// ## Write, Then Assume Init
+/// Writing is safe. Reading the value back is `unsafe`: the caller promises
+/// that the memory has been initialized with a valid `T`.
+runnable!(write_then_assume_init, {
+    let mut slot: MaybeUninit<u64> = MaybeUninit::uninit();
+    slot.write(42);
+    // SAFETY: `slot` was initialized by `write` just above.
+    let value: u64 = unsafe { slot.assume_init() };
+    assert_eq!(value, 42);
+    assert_eq!(std::mem::size_of::<MaybeUninit<u64>>(), 8);
+});
+
+/// ## Out-Parameters
+/// C functions often initialize a value through a pointer given by the
+/// caller. `as_mut_ptr` provides that pointer without initializing the value
+/// first (see unsafe_code.rs for FFI).
+#[derive(Debug, PartialEq)]
+struct Config { retries: u32, verbose: bool }
+
+/// Like a C function `void load_config(struct Config *out)`.
+unsafe fn load_config(out: *mut Config) {
+    out.write(Config { retries: 3, verbose: true });  // no read, no drop
+}
+
+runnable!(out_parameters, {
+    let mut config: MaybeUninit<Config> = MaybeUninit::uninit();
+    // SAFETY: the pointer is valid for writes and properly aligned.
+    unsafe { load_config(config.as_mut_ptr()); }
+    // SAFETY: `load_config` always initializes the whole value.
+    let config: Config = unsafe { config.assume_init() };
+    assert_eq!(config, Config { retries: 3, verbose: true });
+});
+
+/// ## Arrays, Element by Element
+/// An array of `MaybeUninit<T>` can be created without initializing its
+/// elements (`MaybeUninit<T>` is not `Copy`, so the repeated element must be
+/// a `const`). Each element is then written, and the whole array is
+/// reinterpreted as `[T; N]` once it is complete.
+///
+/// If producing an element panics, the elements already written must still
+/// be dropped: a guard takes care of it, since `MaybeUninit` never drops its
+/// content (it would only leak, which is safe but wasteful).
+fn init_array<T, const N: usize>(mut f: impl FnMut(usize) -> T) -> [T; N] {
+    struct Guard<'a, T> { elements: &'a mut [MaybeUninit<T>], length: usize }
+    impl<T> Drop for Guard<'_, T> {
+        fn drop(&mut self) {
+            for element in &mut self.elements[..self.length] {
+                // SAFETY: the first `length` elements were initialized.
+                unsafe { element.assume_init_drop(); }
+            }
+        }
+    }
+
+    let mut array: [MaybeUninit<T>; N] = [const { MaybeUninit::uninit() }; N];
+    let mut guard: Guard<T> = Guard { elements: &mut array, length: 0 };
+    for i in 0..N {
+        guard.elements[i].write(f(i));
+        guard.length += 1;
+    }
+    std::mem::forget(guard);  // every element is now owned by the result
+    // SAFETY: all `N` elements were initialized, and `MaybeUninit<T>` has the
+    // same layout as `T` (`transmute` cannot be used with a generic `N`).
+    unsafe { (&array as *const [MaybeUninit<T>; N] as *const [T; N]).read() }
+}
+
+runnable!(arrays_element_by_element, {
+    let names: [String; 4] = init_array(|i| format!("item {}", i));
+    assert_eq!(names, ["item 0", "item 1", "item 2", "item 3"]);
+    // The safe alternative (which uses the same technique internally)
+    let same: [String; 4] = std::array::from_fn(|i| format!("item {}", i));
+    assert_eq!(names, same);
+});
+
+/// Elements are small and few under Miri (`cargo miri test`), an interpreter
+/// detecting undefined behaviour, which is much slower than native code.
+const ARRAY_LENGTH: usize = if cfg!(miri) { 8 } else { 256 };
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+struct Tracked;
+impl Drop for Tracked {
+    fn drop(&mut self) { DROPPED.fetch_add(1, Ordering::SeqCst); }
+}
+
+runnable!(arrays_drop_on_panic, {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        init_array::<Tracked, ARRAY_LENGTH>(|i| {
+            if i == ARRAY_LENGTH / 2 { panic!("failed at {}", i); }
+            Tracked
+        })
+    }));
+    assert!(result.is_err());
+    // The elements written before the panic were dropped by the guard
+    assert_eq!(DROPPED.load(Ordering::SeqCst), ARRAY_LENGTH / 2);
+});
+
+/// ## Undefined Behaviour
+/// Creating an invalid value is undefined behaviour immediately, even if the
+/// value is never read: the compiler relies on the validity of every value
+/// (e.g. a `bool` is `0` or `1`, a reference is never null).
+/// ```
+/// let flag: bool = unsafe { MaybeUninit::uninit().assume_init() };
+/// // ^ UB: the memory may hold any byte
+/// let number: u32 = unsafe { MaybeUninit::uninit().assume_init() };
+/// // ^ UB: even for integers, uninitialized is not "any number"
+/// ```
+/// The deprecated `std::mem::uninitialized` did exactly this, which is why
+/// it was replaced by `MaybeUninit`. The compiler warns about the obvious
+/// cases:
+/// ```
+/// let flag: bool = unsafe { std::mem::uninitialized() };
+/// // ^ Warning: the type `bool` does not permit being left uninitialized
+/// let reference: &u8 = unsafe { std::mem::zeroed() };
+/// // ^ Warning: the type `&u8` does not permit zero-initialization
+/// // ^ Panic: attempted to zero-initialize type `&u8`, which is invalid
+/// ```
+/// Only `std::mem::zeroed` is fine for types where all zeros is valid
+/// (integers, floats, raw pointers, `Option<&T>`).
+runnable!(undefined_behaviour, {
+    // SAFETY: all zeros is a valid `u32` and a valid `Option<&u8>` (`None`).
+    let number: u32 = unsafe { std::mem::zeroed() };
+    let nothing: Option<&u8> = unsafe { std::mem::zeroed() };
+    assert_eq!((number, nothing), (0, None));
+});
\ No newline at end of file
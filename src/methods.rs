@@ -1,4 +1,5 @@
 /// # Implementations (~ Java Methods)
+#[derive(Debug)]
 struct Point {
     /// ## Fields
     x: f64,
@@ -75,4 +76,12 @@ runnable!(methods, {
     let mut point_mut: Point = point_mut.translate(10.0, 5.0);
     point_mut.translate_mutable(0.0, 5.0);
     point_mut.destroy1();
-});
\ No newline at end of file
+});
+
+/// ## Snapshots
+/// The `Debug` output of a point, checked against src/snapshots (see
+/// testing/snapshots.rs): adding or renaming a field breaks it.
+runnable!(point_snapshot, {
+    let point: Point = Point::ORIGIN.translate(1.5, -2.0).translate_x(0.5);
+    assert_debug_snapshot!("methods_point", point);
+});
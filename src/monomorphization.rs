@@ -0,0 +1,165 @@
+/// # Monomorphization vs Dynamic Dispatch (~ stamping vs sharing)
+/// A generic function is not compiled once: it is a template, stamped out
+/// once for every list of type arguments it is used with (see generics.rs).
+/// Each copy is specialized, so calls are static and can be inlined, but
+/// each copy must also be optimized, and ends up in the binary.
+///
+/// A function taking trait objects is compiled once, whatever the types
+/// behind the pointers: less code, at the cost of a call through a vtable
+/// (see dyn_internals.rs) and of the optimizations it prevents.
+use crate::util::TestDir;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+fn monomorphization() {}
+
+/// ## One Copy per Type
+/// `describe::<u8>` and `describe::<char>` are two different functions,
+/// with two different addresses. `describe_dyn` is a single function, for
+/// every type.
+pub fn describe<T: Debug>(values: &[T]) -> String {
+    let items: Vec<String> =
+        values.iter().map(|value| format!("{:?}", value)).collect();
+    format!("{} values: {}", values.len(), items.join(", "))
+}
+
+pub fn describe_dyn(values: &[&dyn Debug]) -> String {
+    let items: Vec<String> =
+        values.iter().map(|value| format!("{:?}", value)).collect();
+    format!("{} values: {}", values.len(), items.join(", "))
+}
+
+runnable!(one_copy_per_type, {
+    assert_eq!(describe(&[1u8, 2]), "2 values: 1, 2");
+    assert_eq!(describe(&['a', 'b']), "2 values: 'a', 'b'");
+    assert_eq!(describe_dyn(&[&1u8, &'b']), "2 values: 1, 'b'");
+
+    let for_u8: fn(&[u8]) -> String = describe::<u8>;
+    let for_char: fn(&[char]) -> String = describe::<char>;
+    assert_ne!(for_u8 as usize, for_char as usize);
+});
+
+/// ## Measuring the Bloat
+/// Two generated programs print the values of the same 60 types: the first
+/// through a generic function (60 copies), the second through a trait
+/// object (one copy). The runnable compiles both with `rustc` (or the
+/// compiler in the `RUSTC` environment variable), and compares compile
+/// times and binary sizes, without and with optimizations.
+///
+/// Typical results: the generic program compiles slower, and its binary is
+/// larger. Optimizations shrink the difference in size, because the copies
+/// get simplified, but widen the difference in compile time (often two to
+/// three times as long): every copy is optimized separately. How much
+/// smaller depends on the inlining decisions of the toolchain, so only the
+/// unoptimized sizes are compared.
+///
+/// Four full builds are slow: the runnable only exists with the
+/// `extra-lessons` feature (see features.rs), e.g.
+/// `cargo test --features extra-lessons measuring_the_bloat`.
+const TYPES: usize = 60;
+
+fn generated_program(generic: bool) -> String {
+    let mut source: String = String::from("use std::fmt::Debug;\n");
+    for index in 0..TYPES {
+        source += &format!("#[derive(Debug)] struct S{}(u32);\n", index);
+    }
+    source += if generic {
+        "#[inline(never)] fn describe<T: Debug>(values: &[T]) -> String {\n"
+    } else {
+        "#[inline(never)] fn describe(values: &[&dyn Debug]) -> String {\n"
+    };
+    source += "    let mut text = String::new();\n";
+    source += "    for value in values {\n";
+    source += "        text += &format!(\"{:?}, \", value);\n";
+    source += "        if text.len() > 1000 { text.truncate(10); }\n";
+    source += "    }\n";
+    source += "    text\n}\n";
+    source += "fn main() {\n";
+    for index in 0..TYPES {
+        source += &if generic {
+            format!("    println!(\"{{}}\", describe(&[S{0}(1), S{0}(2)]));\n",
+                index)
+        } else {
+            format!(
+                "    println!(\"{{}}\", describe(&[&S{0}(1), &S{0}(2)]));\n",
+                index)
+        };
+    }
+    source + "}\n"
+}
+
+/// Compile `source` to `output`, returning the compile time and the size of
+/// the binary, or `None` if the compiler is missing or fails.
+fn compile(source: &Path, output: &Path, opt_level: u32)
+    -> Option<(Duration, u64)>
+{
+    let rustc: String =
+        std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let start: Instant = Instant::now();
+    let status = Command::new(rustc)
+        .args(["-A", "warnings", "-C", "strip=symbols"])
+        .arg("-C").arg(format!("opt-level={}", opt_level))
+        .arg("-o").arg(output)
+        .arg(source)
+        .status()
+        .ok()?;
+    let elapsed: Duration = start.elapsed();
+    if !status.success() { return None; }
+    Some((elapsed, std::fs::metadata(output).ok()?.len()))
+}
+
+#[cfg(feature = "extra-lessons")]
+runnable!(measuring_the_bloat, {
+    let directory: TestDir = TestDir::new("monomorphization");
+    for opt_level in [0, 2] {
+        let mut sizes: Vec<u64> = Vec::new();
+        for (name, generic) in [("generic", true), ("dyn", false)] {
+            let source: PathBuf = directory.join(&format!("{}.rs", name));
+            let output: PathBuf = directory.join(&format!("{}-bin", name));
+            std::fs::write(&source, generated_program(generic)).unwrap();
+            let Some((elapsed, size)) = compile(&source, &output, opt_level)
+            else {
+                println!("cannot run rustc: skipping the measurements");
+                return;
+            };
+            println!("opt-level={} {:>7}: compiled in {:?}, {} bytes",
+                opt_level, name, elapsed, size);
+            sizes.push(size);
+        }
+        if opt_level == 0 {
+            assert!(sizes[0] > sizes[1]);   // the copies take room
+        }
+    }
+});
+
+/// ## The Trade-Off
+/// - Generics: static calls, inlining and specialized code (see
+///   zero_cost.rs), paid in compile time and binary size, per type.
+/// - Trait objects: one copy, heterogeneous collections and types chosen at
+///   runtime (see patterns/strategy.rs), paid with an indirect call per
+///   method, and no inlining across it.
+///
+/// Most code should stay generic: a handful of instantiations cost little.
+/// Bloat becomes a problem with large generic functions used with many
+/// types, such as deeply nested iterator or future types, or libraries
+/// whose every public function is generic.
+///
+/// ## Keeping Generics Thin
+/// A common middle ground, used throughout the standard library (e.g.
+/// `std::fs::read`): a thin generic wrapper converts its argument, then
+/// calls a non-generic inner function which holds the actual work. Only the
+/// conversion is copied per type.
+pub fn word_count<S: AsRef<str>>(text: S) -> usize {
+    fn inner(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+    inner(text.as_ref())
+}
+
+runnable!(keeping_generics_thin, {
+    assert_eq!(word_count("one two"), 2);                   // &str
+    assert_eq!(word_count(String::from("one two three")), 3); // String
+    assert_eq!(word_count(Box::<str>::from("")), 0);        // Box<str>
+});
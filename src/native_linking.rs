@@ -0,0 +1,71 @@
+/// # Native Libraries (~ linking C code into a program)
+/// The FFI section of unsafe_code.rs links libraries already installed on
+/// the system (`libm`). A crate can also ship its own C code: the build
+/// script compiles it, then tells Cargo how to link it (see build.rs and
+/// build_scripts.rs):
+/// - `cargo:rustc-link-search=native=DIR`: where to look for libraries
+/// - `cargo:rustc-link-lib=static=NAME`: link `libNAME.a` into the binary
+///   (`dylib=NAME` for a shared library, loaded at runtime instead)
+///
+/// A static library is copied into the final binary, which then needs no
+/// file at runtime. Crates wrapping a C library this way are conventionally
+/// named `<name>-sys`, and usually compile it with the `cc` crate, which
+/// knows the compilers and flags of every platform.
+///
+/// This lesson needs a C compiler: run it with
+/// `cargo test --features native-c native_linking`.
+fn native_linking() {}
+
+/// ## Declaring the C Functions
+/// The declarations mirror `native/checksum.c`. No `#[link]` attribute is
+/// needed: the build script already passed the library to the linker.
+#[cfg(feature = "native-c")]
+mod native {
+    extern "C" {
+        pub fn native_fletcher32(data: *const u8, length: usize) -> u32;
+        pub fn native_reverse(buffer: *mut u8, length: usize);
+    }
+
+    /// Safe wrappers: slices always provide a valid pointer and length.
+    pub fn fletcher32(data: &[u8]) -> u32 {
+        // SAFETY: `data` is valid for `data.len()` bytes.
+        unsafe { native_fletcher32(data.as_ptr(), data.len()) }
+    }
+
+    pub fn reverse(buffer: &mut [u8]) {
+        // SAFETY: `buffer` is valid and exclusively borrowed for its length.
+        unsafe { native_reverse(buffer.as_mut_ptr(), buffer.len()) }
+    }
+}
+
+/// The same checksum in Rust, to compare with the C version.
+fn fletcher32(data: &[u8]) -> u32 {
+    let (mut sum1, mut sum2): (u32, u32) = (0, 0);
+    for word in data.chunks(2) {
+        let high: u8 = *word.get(1).unwrap_or(&0);
+        let word: u32 = word[0] as u32 | (high as u32) << 8;
+        sum1 = (sum1 + word) % 65535;
+        sum2 = (sum2 + sum1) % 65535;
+    }
+    sum2 << 16 | sum1
+}
+
+/// ## Calling the Static Library
+#[cfg(feature = "native-c")]
+runnable!(calling_the_static_library, {
+    for text in ["abcde", "abcdef", "abcdefgh", ""] {
+        assert_eq!(native::fletcher32(text.as_bytes()),
+            fletcher32(text.as_bytes()));
+    }
+    assert_eq!(native::fletcher32(b"abcde"), 0xf04f_c729);
+
+    let mut buffer: [u8; 5] = *b"hello";
+    native::reverse(&mut buffer);
+    assert_eq!(&buffer, b"olleh");
+});
+
+#[cfg(not(feature = "native-c"))]
+runnable!(calling_the_static_library_disabled, {
+    println!("Run `cargo test --features native-c` to link the C library");
+    assert_eq!(fletcher32(b"abcde"), 0xf04f_c729);  // the Rust version only
+});
\ No newline at end of file
@@ -0,0 +1,180 @@
+/// # Or-Patterns and Nested Destructuring
+/// Patterns compose: any subpattern can itself be a destructuring, an
+/// alternative, a range, or a binding (see pattern_matching.rs for the
+/// basics). This lesson pushes these combinations further.
+fn nested_patterns() {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(i64),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Parenthesis(char),
+}
+
+/// ## Or-Patterns Inside Variants
+/// `|` can appear anywhere in a pattern, not only between match arms: inside
+/// a variant, a tuple, or a slice.
+fn is_additive(token: &Token) -> bool {
+    matches!(token, Token::Plus | Token::Minus)
+}
+
+fn is_bracket(token: &Token) -> bool {
+    matches!(token, Token::Parenthesis('(' | ')'))  // inside the variant
+}
+
+fn is_small(pair: (i64, i64)) -> bool {
+    matches!(pair, (0 | 1 | 2, 0 | 1 | 2))          // inside the tuple
+}
+
+runnable!(or_patterns_inside_variants, {
+    assert!(is_additive(&Token::Minus));
+    assert!(!is_additive(&Token::Star));
+    assert!(is_bracket(&Token::Parenthesis(')')));
+    assert!(!is_bracket(&Token::Parenthesis('[')));
+    assert!(is_small((1, 2)));
+    assert!(!is_small((1, 3)));
+});
+
+/// ## Bindings Across Alternatives
+/// A name bound in one alternative must be bound in all of them, with the
+/// same type (and binding mode).
+/// ```
+/// match token {
+///     Token::Number(value) | Token::Plus => ...,
+///     // ^ Error: variable `value` is not bound in all patterns
+/// }
+/// ```
+/// `name @ (A | B)` binds the whole value matched by any alternative.
+#[derive(Debug, PartialEq)]
+enum Shape {
+    Square(u32),
+    Rectangle(u32, u32),
+    Circle(u32),
+}
+
+fn width(shape: &Shape) -> u32 {
+    match shape {
+        // `side` is bound (as `&u32`) by both alternatives
+        Shape::Square(side) | Shape::Rectangle(side, _) => *side,
+        Shape::Circle(radius) => radius * 2,
+    }
+}
+
+fn precedence(token: &Token) -> Option<(u8, &Token)> {
+    match token {
+        operator @ (Token::Star | Token::Slash) => Some((2, operator)),
+        operator @ (Token::Plus | Token::Minus) => Some((1, operator)),
+        _ => None,
+    }
+}
+
+runnable!(bindings_across_alternatives, {
+    assert_eq!(width(&Shape::Square(3)), 3);
+    assert_eq!(width(&Shape::Rectangle(4, 1)), 4);
+    assert_eq!(width(&Shape::Circle(2)), 4);
+    assert_eq!(precedence(&Token::Slash), Some((2, &Token::Slash)));
+    assert_eq!(precedence(&Token::Number(1)), None);
+
+    // Alternatives bind the same name to the same type only
+    // match shape { Shape::Square(side) | Shape::Circle(side) => ... }
+    // ^ OK: both are `u32`
+    // match result { Ok(value) | Err(value) => ... }
+    // ^ Error (for `Result<u8, u16>`): mismatched types, expected `u8`, found
+    //   `u16`
+});
+
+/// ## Deeply Nested Destructuring
+/// A single pattern can look through several layers of types, here
+/// `Option<Result<(A, B), E>>`, binding only the parts it needs.
+fn summary(lookup: Option<Result<(&str, u32), String>>) -> String {
+    match lookup {
+        None => "not requested".to_string(),
+        Some(Err(error)) => format!("failed: {}", error),
+        Some(Ok((name, 0))) => format!("{} is empty", name),
+        Some(Ok((name, count @ 1..=9))) => format!("{} has {}", name, count),
+        Some(Ok((name, _))) => format!("{} is large", name),
+    }
+}
+
+runnable!(deeply_nested_destructuring, {
+    assert_eq!(summary(None), "not requested");
+    assert_eq!(summary(Some(Err("timeout".to_string()))), "failed: timeout");
+    assert_eq!(summary(Some(Ok(("inbox", 0)))), "inbox is empty");
+    assert_eq!(summary(Some(Ok(("inbox", 3)))), "inbox has 3");
+    assert_eq!(summary(Some(Ok(("inbox", 30)))), "inbox is large");
+
+    // Or-patterns combine with nesting: the two failure cases at once
+    let lookup: Option<Result<(&str, u32), String>> = None;
+    let failed: bool = matches!(lookup, None | Some(Err(_)));
+    assert!(failed);
+});
+
+/// ## Slice and Struct Patterns Combined
+/// Slice patterns match on the length and on the elements, which can be
+/// structs, enums, or literals, and bind the rest of the slice with `@ ..`.
+#[derive(Debug)]
+struct Point { x: i32, y: i32 }
+
+fn describe_path(path: &[Point]) -> String {
+    match path {
+        [] => "empty".to_string(),
+        [Point { x: 0, y: 0 }] => "origin only".to_string(),
+        [Point { x: 0, y: 0 }, .., Point { x: 0, y: 0 }] => {
+            "loop through the origin".to_string()
+        }
+        [first, rest @ ..] if rest.iter().all(|point| point.y == first.y) => {
+            format!("horizontal at y={}", first.y)
+        }
+        [Point { x, .. }, .., Point { x: last_x, .. }] if x == last_x => {
+            format!("returns to x={}", x)
+        }
+        [_, middle @ .., _] => format!("{} inner points", middle.len()),
+        // Never reached (the guard above holds for a single point), but
+        // guards are ignored when checking exhaustiveness
+        [_] => "single point".to_string(),
+    }
+}
+
+/// A tiny evaluator: slices of tokens, nested variants and alternatives all
+/// at once.
+fn evaluate(tokens: &[Token]) -> Option<i64> {
+    match tokens {
+        [Token::Number(value)] => Some(*value),
+        [Token::Parenthesis('('), inner @ .., Token::Parenthesis(')')] => {
+            evaluate(inner)
+        }
+        [Token::Number(a), operator @ (Token::Plus | Token::Minus),
+         rest @ ..] => {
+            let b: i64 = evaluate(rest)?;
+            Some(if *operator == Token::Plus { a + b } else { a - b })
+        }
+        _ => None,
+    }
+}
+
+runnable!(slice_and_struct_patterns, {
+    let origin = || Point { x: 0, y: 0 };
+    assert_eq!(describe_path(&[]), "empty");
+    assert_eq!(describe_path(&[origin()]), "origin only");
+    assert_eq!(describe_path(&[origin(), Point { x: 1, y: 1 }, origin()]),
+        "loop through the origin");
+    assert_eq!(describe_path(&[Point { x: 1, y: 5 }, Point { x: 9, y: 5 }]),
+        "horizontal at y=5");
+    assert_eq!(describe_path(&[Point { x: 2, y: 0 }, Point { x: 2, y: 1 }]),
+        "returns to x=2");
+    assert_eq!(describe_path(&[origin(), Point { x: 1, y: 1 },
+        Point { x: 2, y: 4 }]), "1 inner points");
+    assert_eq!(describe_path(&[Point { x: 4, y: 4 }]), "horizontal at y=4");
+
+    let tokens: Vec<Token> = vec![
+        Token::Parenthesis('('), Token::Number(5), Token::Minus,
+        Token::Number(2), Token::Plus, Token::Number(1),
+        Token::Parenthesis(')'),
+    ];
+    assert_eq!(evaluate(&tokens), Some(5 - (2 + 1)));  // right-associative
+    assert_eq!(evaluate(&[Token::Identifier("x".to_string())]), None);
+});
\ No newline at end of file
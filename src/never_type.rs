@@ -0,0 +1,135 @@
+/// # Never Type (~ Scala Nothing, TypeScript never)
+/// `!` (the `never` type, or bottom type) is the type of expressions that
+/// never produce a value: `panic!`, `loop {}` without `break`, `return`,
+/// `break`, `continue`, `std::process::exit` (see primitives.rs and
+/// errors.rs). As no value of type `!` can exist, it can be coerced to any
+/// other type.
+///
+/// Using `!` as a type is only stable in return position: elsewhere (e.g.
+/// `Result<T, !>`), `std::convert::Infallible` and empty enums play its role.
+use std::convert::Infallible;
+use std::str::FromStr;
+
+fn never_type() {}
+
+/// ## Diverging Expressions
+/// `return`, `break`, `continue` and `panic!` have type `!`, which is why
+/// they fit in any branch, whatever the type of the other branches.
+fn parse_all(texts: &[&str]) -> Option<Vec<u32>> {
+    let mut numbers: Vec<u32> = Vec::new();
+    for text in texts {
+        let number: u32 = match text.parse() {
+            Ok(number) => number,
+            Err(_) if text.is_empty() => continue,  // `!` coerced to `u32`
+            Err(_) => return None,                   // same
+        };
+        numbers.push(number);
+    }
+    Some(numbers)
+}
+
+runnable!(diverging_expressions, {
+    let parsed: Option<Vec<u32>> = parse_all(&["1", "", "3"]);
+    assert_eq!(parsed, Some(vec![1, 3]));
+    assert_eq!(parse_all(&["1", "x"]), None);
+    let count: usize = match parsed {
+        Some(numbers) => numbers.len(),
+        None => unreachable!(),  // `!` coerced to `usize`
+    };
+});
+
+/// ## Functions Returning `!`
+/// A `diverging function` never returns: it panics, exits the process, or
+/// loops forever (e.g. a server loop). Calls to it are `!` too, so the
+/// compiler knows that the code after them is unreachable.
+fn fail(reason: &str) -> ! {
+    panic!("fatal error: {}", reason)
+}
+
+fn port_from(setting: Option<&str>) -> u16 {
+    match setting {
+        Some(text) => text.parse().unwrap_or_else(|_| fail("invalid port")),
+        None => 8080,
+    }
+}
+
+runnable!(functions_returning_never, {
+    assert_eq!(port_from(Some("3000")), 3000);
+    assert_eq!(port_from(None), 8080);
+    assert!(std::panic::catch_unwind(|| port_from(Some("x"))).is_err());
+});
+
+/// ## Infallible
+/// `std::convert::Infallible` is an enum without variants: no value of it
+/// can ever be built. It is the error type of conversions which cannot fail,
+/// e.g. `String::from_str`, and every `TryFrom` implied by a `From` (see
+/// blanket_impls.rs).
+/// ```
+/// let bottom: Result<u8, !> = Ok(1);
+/// // ^ Error: the `!` type is experimental
+/// ```
+#[derive(Debug, PartialEq)]
+struct Celsius(f64);
+#[derive(Debug, PartialEq)]
+struct Kelvin(f64);
+
+impl TryFrom<Kelvin> for Celsius {
+    type Error = Infallible;  // any temperature in Kelvin has a Celsius value
+    fn try_from(kelvin: Kelvin) -> Result<Celsius, Infallible> {
+        Ok(Celsius(kelvin.0 - 273.15))
+    }
+}
+
+runnable!(infallible, {
+    let converted: Result<u16, Infallible> = u16::try_from(7u8);  // From
+    let parsed: Result<String, Infallible> = String::from_str("text");
+    let celsius: Result<Celsius, Infallible> =
+        Celsius::try_from(Kelvin(273.15));
+    assert_eq!(celsius, Ok(Celsius(0.0)));
+});
+
+/// ## Unwrapping `Result<T, Infallible>`
+/// The `Err` case cannot happen, and the compiler knows it: the value can be
+/// extracted without `unwrap` (which would suggest a possible panic).
+runnable!(unwrapping_infallible_results, {
+    let result: Result<u16, Infallible> = u16::try_from(7u8);
+    // An irrefutable pattern: `Err(_)` cannot match any value
+    let Ok(number) = result;
+    assert_eq!(number, 7);
+
+    // An empty match on the error, of type `Infallible`, coerces to any type
+    let text: String = String::from_str("text")
+        .unwrap_or_else(|never: Infallible| match never {});
+    assert_eq!(text, "text");
+});
+
+/// ## Empty Enums
+/// An enum without variants is a user-defined never type. Matching on it
+/// needs no arms: the match is exhaustive, and has type `!`. It can mark
+/// impossible states in generic code, e.g. a message type for a channel
+/// which never receives anything.
+#[derive(Debug)]
+enum Void {}
+
+/// From a value that cannot exist, anything can be produced.
+fn absurd<T>(void: Void) -> T {
+    match void {}
+}
+
+enum Message<Extra> { Text(String), Extra(Extra) }
+
+/// With `Extra = Void`, the `Extra` variant cannot be built, so the match
+/// below only really handles `Text`.
+fn render(message: Message<Void>) -> String {
+    match message {
+        Message::Text(text) => text,
+        Message::Extra(void) => absurd(void),
+    }
+}
+
+runnable!(empty_enums, {
+    assert_eq!(render(Message::Text("hi".to_string())), "hi");
+    assert_eq!(std::mem::size_of::<Void>(), 0);
+    assert_eq!(std::mem::size_of::<Message<Void>>(),
+        std::mem::size_of::<String>());  // no room needed for `Extra`
+});
\ No newline at end of file
@@ -0,0 +1,105 @@
+/// # No Standard Library (~ freestanding C)
+/// `#![no_std]` at the top of a crate root stops it from linking `std` (see
+/// annotations.rs), for targets without an operating system: embedded
+/// systems, kernels, bootloaders. The crate then only has access to:
+/// - `core`: the platform-independent part of `std` (primitives, `Option`,
+///   `Result`, iterators, `fmt`, slices, ...), which needs no allocator and
+///   no operating system
+/// - `alloc` (opt-in, with `extern crate alloc;`): the heap-allocated types
+///   (`Box`, `Vec`, `String`, `Rc`, `BTreeMap`, ...)
+///
+/// Most of `std` is a re-export of these two crates: `std::option::Option`
+/// is `core::option::Option`, `std::vec::Vec` is `alloc::vec::Vec`.
+///
+/// The workspace member `playground-no-std` is a complete `no_std` library.
+/// It cannot be a dependency of this crate: it defines its own panic handler,
+/// which would clash with the one of `std`.
+fn no_std() {}
+
+extern crate alloc;
+
+/// ## What Disappears Without `std`
+/// - Printing: `println!`, `eprintln!`, `dbg!` (there may be no console), so
+///   output goes through `core::fmt::Write` into a buffer or a device
+/// - `format!`: it is in `alloc`, like `String` and `vec!`
+/// - The global allocator: a `no_std` binary using `alloc` must provide one
+///   with `#[global_allocator]` (e.g. from a crate managing a static buffer)
+/// - `HashMap` and `HashSet`: their default hasher needs random seeds from
+///   the operating system (`BTreeMap` and `BTreeSet` remain in `alloc`)
+/// - Threads, files, networking, processes, environment variables, time
+/// - Unwinding: a panic cannot be caught, and the `#[panic_handler]` must
+///   decide what to do (halt, reset, log on a serial port, ...)
+/// - The entry point: `fn main` is called by the runtime of `std`, so a
+///   freestanding binary needs `#![no_main]` and a target-specific entry
+///   point, and must build with `panic = "abort"` in its profile
+fn what_disappears() {}
+
+runnable!(core_and_alloc_paths, {
+    // The same types, reached through `core` and `alloc`
+    let maybe: core::option::Option<u8> = Some(42);
+    let boxed: alloc::boxed::Box<u8> = alloc::boxed::Box::new(maybe.unwrap());
+    let list: alloc::vec::Vec<u8> = alloc::vec![1, 2, 3];
+    let sorted: alloc::collections::BTreeMap<&str, u8> =
+        [("b", 2), ("a", 1)].into_iter().collect();
+    println!("{} {:?} {:?}", boxed, list, sorted);
+    let same: std::vec::Vec<u8> = list;  // `std` re-exports `alloc`
+    assert_eq!(core::mem::size_of::<u32>(), std::mem::size_of::<u32>());
+});
+
+/// ## Formatting Without `println!`
+/// `write!` works with any type implementing `core::fmt::Write`: a fixed
+/// buffer, a serial port, a screen. No allocation is needed.
+struct Buffer {
+    bytes: [u8; 64],
+    length: usize,
+}
+
+impl core::fmt::Write for Buffer {
+    fn write_str(&mut self, text: &str) -> core::fmt::Result {
+        let end: usize = self.length + text.len();
+        if end > self.bytes.len() { return Err(core::fmt::Error); }
+        self.bytes[self.length..end].copy_from_slice(text.as_bytes());
+        self.length = end;
+        Ok(())
+    }
+}
+
+runnable!(formatting_without_println, {
+    use core::fmt::Write;
+    let mut buffer: Buffer = Buffer { bytes: [0; 64], length: 0 };
+    write!(buffer, "uptime: {} s, load: {:.2}", 3600, 0.25).unwrap();
+    let text: &str = core::str::from_utf8(&buffer.bytes[..buffer.length])
+        .unwrap();
+    println!("{}", text);  // only to show it: `println!` is not in `core`
+    assert_eq!(text, "uptime: 3600 s, load: 0.25");
+});
+
+/// ## Panic Handler
+/// A `no_std` binary must define exactly one panic handler for the whole
+/// program (see `playground-no-std/src/lib.rs`):
+/// ```
+/// #[panic_handler]
+/// fn panic(info: &core::panic::PanicInfo) -> ! {
+///     loop { core::hint::spin_loop(); }
+/// }
+/// ```
+/// Defining one in a crate linked with `std` fails:
+/// ```
+/// #[panic_handler]
+/// fn my_panic(info: &core::panic::PanicInfo) -> ! { loop {} }
+/// // ^ Error: found duplicate lang item `panic_impl`
+/// ```
+fn panic_handler() {}
+
+/// ## Writing `no_std`-Friendly Libraries
+/// Many libraries support both worlds: `no_std` by default, with a `std`
+/// feature adding what needs the operating system (see features.rs).
+/// ```
+/// #![cfg_attr(not(feature = "std"), no_std)]
+///
+/// #[cfg(feature = "std")]
+/// impl std::error::Error for MyError {}
+/// ```
+/// Using `core::` and `alloc::` paths instead of `std::` is enough for most
+/// code to compile in both cases.
+fn no_std_friendly_libraries() {}
\ No newline at end of file
@@ -0,0 +1,88 @@
+/// # Non-Exhaustive Types (~ Java sealed hierarchies, inverted)
+/// Adding a variant to a public enum, or a field to a public struct, breaks
+/// every downstream crate which matches all the variants, or builds the
+/// struct with a struct expression. Library authors keep room to grow with
+/// `#[non_exhaustive]`: outside the defining crate,
+/// - matches on a non-exhaustive enum need a wildcard arm
+/// - non-exhaustive structs (and variants) cannot be built with a struct
+///   expression, and their patterns need `..`
+///
+/// Inside the defining crate, the attribute has no effect. This is why the
+/// types of this lesson live in the library target of the playground (see
+/// src/lib.rs): this binary, like the integration tests (see
+/// tests/non_exhaustive.rs), is a downstream crate.
+use rust_plauground::{Event, LogLevel, LoggerConfig};
+
+fn non_exhaustive() {}
+
+/// ## Non-Exhaustive Enums
+/// The wildcard arm handles the variants added by future versions.
+fn prefix(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Error => "[E]",
+        LogLevel::Warning => "[W]",
+        LogLevel::Info => "[I]",
+        _ => "[?]",
+    }
+}
+// fn prefix(level: LogLevel) -> &'static str {
+//     match level {
+//         LogLevel::Error => "[E]",
+//         LogLevel::Warning => "[W]",
+//         LogLevel::Info => "[I]",
+//     }
+// }
+// ^ Error: non-exhaustive patterns: `_` not covered
+
+runnable!(non_exhaustive_enums, {
+    assert_eq!(prefix(LogLevel::Warning), "[W]");
+    assert_eq!(LogLevel::Warning.label(), "warning");  // exhaustive inside
+
+    // The standard library does the same, e.g. with `std::io::ErrorKind`
+    let kind: std::io::ErrorKind = std::io::ErrorKind::NotFound;
+    let retry: bool = matches!(
+        kind, std::io::ErrorKind::Interrupted | std::io::ErrorKind::TimedOut
+    );
+    assert!(!retry);
+});
+
+/// ## Non-Exhaustive Structs
+/// Fields stay readable (and writable, if public), but values come from the
+/// constructors of the library, which can initialize future fields.
+runnable!(non_exhaustive_structs, {
+    // let config: LoggerConfig =
+    //     LoggerConfig { level: LogLevel::Info, colored: true };
+    // ^ Error: cannot create non-exhaustive struct using struct expression
+    let mut config: LoggerConfig = LoggerConfig::new(LogLevel::Info);
+    config.colored = true;
+    assert_eq!(config, LoggerConfig::new(LogLevel::Info).colored(true));
+
+    // let LoggerConfig { level, colored } = config;
+    // ^ Error: `..` required with struct marked as non-exhaustive
+    let LoggerConfig { level, .. } = config;
+    assert_eq!(level, LogLevel::Info);
+    assert_eq!(LoggerConfig::default().level, LogLevel::Warning);
+});
+
+/// ## Non-Exhaustive Variants
+runnable!(non_exhaustive_variants, {
+    // let event: Event =
+    //     Event::Message { level: LogLevel::Info, text: "hi".to_string() };
+    // ^ Error: cannot create non-exhaustive variant using struct expression
+    let event: Event = Event::message(LogLevel::Info, "hi");
+    let text: &str = match &event {
+        Event::Message { text, .. } => text,  // `..` is required
+        Event::Flush => "",
+    };
+    assert_eq!(text, "hi");
+});
+
+/// ## Trade-Offs
+/// `#[non_exhaustive]` moves the cost of a new variant from a compile error
+/// (downstream code must be updated) to a runtime fallback (the wildcard arm
+/// silently handles it). Use it for types expected to grow, like error kinds
+/// and configurations, not for closed sets like `Ordering` or `Option`.
+///
+/// On private types, or within an application, it only gets in the way:
+/// exhaustive matches are what make adding a variant safe.
+fn trade_offs() {}
\ No newline at end of file
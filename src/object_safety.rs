@@ -0,0 +1,173 @@
+/// # Object Safety (a.k.a. Dyn Compatibility)
+/// Only some traits can be used as trait objects (see traits.rs): a trait is
+/// `object safe` (recently renamed `dyn compatible`) if every method can be
+/// called through a vtable (see dyn_internals.rs), without knowing the
+/// concrete type behind the pointer.
+///
+/// Each rule is shown with an example which does not compile. This module is
+/// part of the binary, where doctests do not run: the examples are checked
+/// by `compile_fail` doctests in the library target (see compile_errors.rs).
+///
+/// See: https://doc.rust-lang.org/reference/items/traits.html#dyn-compatibility
+fn object_safety() {}
+
+/// ## Generic Methods
+/// A generic method has one implementation per type argument (see
+/// generics.rs), so it cannot fit in a vtable with a fixed number of entries.
+/// ```text
+/// trait Logger {
+///     fn log<T: std::fmt::Debug>(&self, value: T);
+/// }
+/// fn use_logger(logger: &dyn Logger) {}
+/// // ^ Error: the trait `Logger` is not dyn compatible
+/// //   note: ...because method `log` has generic type parameters
+/// ```
+/// Taking a trait object instead of a generic parameter keeps the method
+/// dispatchable, at the cost of a second dynamic dispatch.
+trait Logger {
+    fn log(&self, value: &dyn std::fmt::Debug) -> String;
+}
+
+struct Prefixed(&'static str);
+impl Logger for Prefixed {
+    fn log(&self, value: &dyn std::fmt::Debug) -> String {
+        format!("{} {:?}", self.0, value)
+    }
+}
+
+runnable!(generic_methods, {
+    let logger: &dyn Logger = &Prefixed("[info]");
+    println!("{}", logger.log(&42));
+    println!("{}", logger.log(&"text"));
+});
+
+/// ## Returning or Taking Self
+/// `Self` is the concrete type, which is unknown behind a trait object: a
+/// method returning `Self` (or taking it by value, other than as receiver)
+/// cannot be called on it.
+/// ```text
+/// trait Shape {
+///     fn area(&self) -> f64;
+///     fn scaled(&self, factor: f64) -> Self;
+/// }
+/// fn use_shape(shape: &dyn Shape) {}
+/// // ^ Error: the trait `Shape` is not dyn compatible
+/// //   note: ...because method `scaled` references the `Self` type in its
+/// //         return type
+/// ```
+/// Static methods (without a `self` receiver) have the same problem: there
+/// is no value to read the vtable from.
+/// ```text
+/// trait Shape {
+///     fn unit() -> Self;
+/// }
+/// fn use_shape(shape: &dyn Shape) {}
+/// // ^ Error: the trait `Shape` is not dyn compatible
+/// //   note: ...because associated function `unit` has no `self` parameter
+/// ```
+fn returning_self() {}
+
+/// ## The `where Self: Sized` Escape Hatch
+/// Trait objects are unsized (`dyn Shape: !Sized`), so a method bound by
+/// `where Self: Sized` can never be called on a trait object. The compiler
+/// then excludes it from the vtable, and the rest of the trait stays
+/// object safe. The method is still callable on concrete types.
+trait Shape {
+    fn area(&self) -> f64;
+    fn scaled(&self, factor: f64) -> Self where Self: Sized;
+    fn unit() -> Self where Self: Sized;
+}
+
+#[derive(Debug)]
+struct Square(f64);
+impl Shape for Square {
+    fn area(&self) -> f64 { self.0 * self.0 }
+    fn scaled(&self, factor: f64) -> Self { Square(self.0 * factor) }
+    fn unit() -> Self { Square(1.0) }
+}
+
+runnable!(where_self_sized, {
+    let shape: &dyn Shape = &Square(2.0);
+    println!("area: {}", shape.area());
+    // shape.scaled(2.0);
+    // ^ Error: the `scaled` method cannot be invoked on a trait object
+
+    let square: Square = Square::unit().scaled(3.0);
+    println!("{:?} area: {}", square, square.area());
+});
+
+/// ## Default Methods with `where Self: Sized`
+/// The same bound works on default methods: a default method may use
+/// generics or `Self` freely, as long as it opts out of the vtable. This is
+/// how `Iterator` stays object safe while providing dozens of generic
+/// adapters (`map`, `filter`, ...), all bound by `Self: Sized`.
+trait Counter {
+    fn next(&mut self) -> Option<u32>;
+    fn sum_mapped<F: Fn(u32) -> u32>(mut self, f: F) -> u32
+    where Self: Sized {
+        let mut sum: u32 = 0;
+        while let Some(value) = self.next() { sum += f(value); }
+        sum
+    }
+}
+
+struct UpTo { current: u32, limit: u32 }
+impl Counter for UpTo {
+    fn next(&mut self) -> Option<u32> {
+        if self.current >= self.limit { return None; }
+        self.current += 1;
+        Some(self.current)
+    }
+}
+
+runnable!(default_methods, {
+    let mut counter: Box<dyn Counter> = Box::new(UpTo { current: 0, limit: 3 });
+    println!("first: {:?}", counter.next());
+
+    let up_to: UpTo = UpTo { current: 0, limit: 3 };
+    println!("sum of squares: {}", up_to.sum_mapped(|x| x * x));
+});
+
+/// Without the bound, the default method makes the whole trait unusable as
+/// an object:
+/// ```text
+/// trait Counter {
+///     fn next(&mut self) -> Option<u32>;
+///     fn sum_mapped<F: Fn(u32) -> u32>(&self, f: F) -> u32 { 0 }
+/// }
+/// fn use_counter(counter: &dyn Counter) {}
+/// // ^ Error: the trait `Counter` is not dyn compatible
+/// //   note: ...because method `sum_mapped` has generic type parameters
+/// ```
+fn default_methods_without_bound() {}
+
+/// ## Associated Consts
+/// Associated consts are not stored in the vtable, so a trait declaring one
+/// is never object safe (and `where Self: Sized` cannot be used on consts).
+/// ```text
+/// trait Unit {
+///     const SYMBOL: &'static str;
+///     fn amount(&self) -> f64;
+/// }
+/// fn use_unit(unit: &dyn Unit) {}
+/// // ^ Error: the trait `Unit` is not dyn compatible
+/// //   note: ...because it contains associated const `SYMBOL`
+/// ```
+/// A method returning the value keeps the trait object safe.
+trait Unit {
+    fn symbol(&self) -> &'static str;
+    fn amount(&self) -> f64;
+}
+
+struct Meters(f64);
+impl Unit for Meters {
+    fn symbol(&self) -> &'static str { "m" }
+    fn amount(&self) -> f64 { self.0 }
+}
+
+runnable!(associated_consts, {
+    let units: Vec<Box<dyn Unit>> = vec![Box::new(Meters(3.5))];
+    for unit in units {
+        println!("{}{}", unit.amount(), unit.symbol());
+    }
+});
\ No newline at end of file
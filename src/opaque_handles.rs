@@ -0,0 +1,107 @@
+/// # Opaque Handles (~ incomplete C types)
+/// C libraries often hide their data structures behind a `handle`: the
+/// header only declares the type, without its fields, and every operation is
+/// a function taking a pointer to it (`FILE *` and `fopen`/`fclose` are the
+/// classic example).
+/// ```c
+/// typedef struct PlaygroundCounter PlaygroundCounter;  // no fields
+/// PlaygroundCounter *playground_counter_new(uint64_t step);
+/// uint64_t playground_counter_increment(PlaygroundCounter *counter);
+/// void playground_counter_free(PlaygroundCounter *counter);
+/// ```
+/// This lesson writes bindings to this API of `playground-ffi` (see
+/// reverse_ffi.rs) the way real bindings are written: a raw `-sys` layer
+/// mirroring the header, and a safe wrapper on top of it.
+use std::marker::{PhantomData, PhantomPinned};
+use std::ptr::NonNull;
+
+fn opaque_handles() {}
+
+/// ## Raw Bindings
+/// The opaque type is modeled as a struct that cannot be constructed,
+/// moved meaningfully or sized by Rust code:
+/// - `_data: [u8; 0]`: a private, zero-sized field, so no value can be
+///   built outside of this module, and `#[repr(C)]` keeps it FFI-safe
+/// - `_marker`: `*mut u8` removes `Send` and `Sync`, and `PhantomPinned`
+///   removes `Unpin` (see pinning.rs), since nothing is known about the
+///   real data
+///
+/// Only pointers to it are ever used. (The unstable `extern type` feature
+/// expresses the same idea directly.)
+mod sys {
+    use super::*;
+
+    #[repr(C)]
+    pub struct PlaygroundCounter {
+        _data: [u8; 0],
+        _marker: PhantomData<(*mut u8, PhantomPinned)>,
+    }
+
+    // The functions are resolved by the linker, like functions of a C
+    // library (here, they come from the `playground-ffi` dependency)
+    extern "C" {
+        pub fn playground_counter_new(step: u64) -> *mut PlaygroundCounter;
+        pub fn playground_counter_increment(
+            counter: *mut PlaygroundCounter
+        ) -> u64;
+        pub fn playground_counter_free(counter: *mut PlaygroundCounter);
+    }
+}
+
+runnable!(raw_bindings, {
+    // SAFETY: the handle is checked for null, used while alive, and freed
+    // exactly once. Nothing enforces it: each call site must get it right.
+    unsafe {
+        let handle: *mut sys::PlaygroundCounter =
+            sys::playground_counter_new(2);
+        assert!(!handle.is_null());
+        sys::playground_counter_increment(handle);
+        assert_eq!(sys::playground_counter_increment(handle), 4);
+        sys::playground_counter_free(handle);
+    }
+    assert_eq!(std::mem::size_of::<sys::PlaygroundCounter>(), 0);
+});
+
+/// ## Safe Wrapper
+/// The wrapper owns the handle: it is created by the constructor, freed by
+/// `Drop`, and cannot be used after being freed, or freed twice (see
+/// ownership.rs). Methods take `&mut self` where the C function mutates the
+/// data, so the borrow checker also rules out data races.
+///
+/// The wrapper is not `Send` (because of the raw pointer): it would only be
+/// sound to implement it if the C library documents that a handle can be
+/// used from another thread.
+pub struct Counter {
+    handle: NonNull<sys::PlaygroundCounter>,
+}
+
+impl Counter {
+    /// `None` if the library could not create the counter (null handle).
+    pub fn new(step: u64) -> Option<Counter> {
+        // SAFETY: no precondition.
+        let handle: *mut sys::PlaygroundCounter =
+            unsafe { sys::playground_counter_new(step) };
+        NonNull::new(handle).map(|handle| Counter { handle })
+    }
+
+    pub fn increment(&mut self) -> u64 {
+        // SAFETY: the handle is valid until `drop`, and `&mut self` makes
+        // this the only use of it.
+        unsafe { sys::playground_counter_increment(self.handle.as_ptr()) }
+    }
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        // SAFETY: the handle is valid, and this is its last use.
+        unsafe { sys::playground_counter_free(self.handle.as_ptr()); }
+    }
+}
+
+runnable!(safe_wrapper, {
+    let mut counter: Counter = Counter::new(5).expect("counter created");
+    counter.increment();
+    assert_eq!(counter.increment(), 10);
+    // drop(counter); counter.increment();
+    // ^ Error: borrow of moved value: `counter`
+});  // `playground_counter_free` is called here, by `Drop`
\ No newline at end of file
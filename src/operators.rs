@@ -0,0 +1,184 @@
+/// # Operator Overloading (~ C++ Operator Overloading)
+/// Operators are syntactic sugar for the methods of the traits in `std::ops`
+/// (see the operator overloading section of traits.rs): `a + b` is
+/// `Add::add(a, b)`, `-a` is `Neg::neg(a)`, `a[i]` is `*Index::index(&a, i)`
+/// and so on. Implementing those traits makes the operators available for
+/// custom types.
+///
+/// Operators cannot be created, only overloaded, and their precedence cannot
+/// be changed.
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Not, Sub};
+
+fn operators() {}
+
+/// A vector in the plane. It is deliberately not `Copy`, to show what
+/// happens to operands that are moved by operators.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vector2 { pub x: f64, pub y: f64 }
+
+impl Vector2 {
+    pub fn new(x: f64, y: f64) -> Self { Vector2 { x, y } }
+}
+
+/// ## Binary Operators
+/// The traits of binary operators are generic over the type of the right
+/// operand (`Rhs`, defaulting to `Self`) and declare the type of the result
+/// (`Output`), which can be anything.
+impl Add for Vector2 {
+    type Output = Vector2;
+    fn add(self, rhs: Vector2) -> Vector2 {
+        Vector2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+impl Sub for Vector2 {
+    type Output = Vector2;
+    fn sub(self, rhs: Vector2) -> Vector2 {
+        Vector2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+/// Scalar product: `Vector2 * f64`
+impl Mul<f64> for Vector2 {
+    type Output = Vector2;
+    fn mul(self, rhs: f64) -> Vector2 {
+        Vector2::new(self.x * rhs, self.y * rhs)
+    }
+}
+/// `f64 * Vector2` needs its own implementation, on the foreign type `f64`:
+/// allowed by the orphan rule (see patterns/newtype.rs) because `Vector2` is
+/// local
+impl Mul<Vector2> for f64 {
+    type Output = Vector2;
+    fn mul(self, rhs: Vector2) -> Vector2 { rhs * self }
+}
+/// Dot product: `Vector2 * Vector2`, with a different `Output`
+impl Mul for Vector2 {
+    type Output = f64;
+    fn mul(self, rhs: Vector2) -> f64 { self.x * rhs.x + self.y * rhs.y }
+}
+
+runnable!(binary_operators, {
+    let a: Vector2 = Vector2::new(1.0, 2.0);
+    let b: Vector2 = Vector2::new(3.0, 4.0);
+    let sum: Vector2 = a.clone() + b.clone();
+    let difference: Vector2 = a.clone() - b.clone();
+    println!("a + b = {:?}\na - b = {:?}", sum, difference);
+    println!("a * 2 = {:?}\n2 * a = {:?}", a.clone() * 2.0, 2.0 * a.clone());
+    let dot: f64 = a * b;
+    println!("a . b = {}", dot);
+    // println!("{:?}", a);
+    // ^ Error: borrow of moved value: `a` (`mul` takes `self` by value)
+});
+
+/// ## Unary Operators
+/// `Neg` (`-v`) and `Not` (`!v`) take a single operand. Overloads should
+/// keep the intuitive meaning of the operator: here `!v` is the vector
+/// rotated by 90 degrees, which is a stretch and would deserve a named
+/// method in real code.
+impl Neg for Vector2 {
+    type Output = Vector2;
+    fn neg(self) -> Vector2 { Vector2::new(-self.x, -self.y) }
+}
+impl Not for Vector2 {
+    type Output = Vector2;
+    fn not(self) -> Vector2 { Vector2::new(-self.y, self.x) }
+}
+
+runnable!(unary_operators, {
+    let v: Vector2 = Vector2::new(1.0, 2.0);
+    println!("-v = {:?}", -v.clone());
+    println!("!v = {:?}", !v.clone());
+    assert_eq!(!v.clone() * v, 0.0);  // perpendicular vectors
+});
+
+/// ## Compound Assignment Operators
+/// `+=`, `-=`, ... have their own traits (`AddAssign`, `SubAssign`, ...),
+/// taking `&mut self`: they modify the left operand in place, and are not
+/// derived from `Add` automatically.
+impl AddAssign for Vector2 {
+    fn add_assign(&mut self, rhs: Vector2) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+runnable!(compound_assignment_operators, {
+    let mut position: Vector2 = Vector2::new(0.0, 0.0);
+    let velocity: Vector2 = Vector2::new(0.5, 1.0);
+    for _ in 0..4 { position += velocity.clone(); }
+    println!("position: {:?}", position);
+    // position -= velocity;
+    // ^ Error: binary assignment operation `-=` cannot be applied to type
+    //          `Vector2` (`SubAssign` is not implemented)
+});
+
+/// ## Indexing
+/// `Index` returns a reference to an element (`v[i]` is `*v.index(i)`), and
+/// `IndexMut` a mutable one. Out of bounds indices should panic, like they
+/// do for slices.
+impl Index<usize> for Vector2 {
+    type Output = f64;
+    fn index(&self, index: usize) -> &f64 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("index out of bounds: {} >= 2", index),
+        }
+    }
+}
+impl IndexMut<usize> for Vector2 {
+    fn index_mut(&mut self, index: usize) -> &mut f64 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("index out of bounds: {} >= 2", index),
+        }
+    }
+}
+
+runnable!(indexing, {
+    let mut v: Vector2 = Vector2::new(1.0, 2.0);
+    v[1] = 5.0;
+    println!("v[0] = {}, v[1] = {}", v[0], v[1]);
+});
+
+/// ## Values vs References
+/// Operators take their operands by value, so for non-`Copy` types every
+/// operation consumes its operands, forcing callers to `.clone()` values they
+/// still need (see the runnables above). Implementing the operators for
+/// references removes those clones: `&a + &b` borrows both operands.
+impl Add<&Vector2> for &Vector2 {
+    type Output = Vector2;
+    fn add(self, rhs: &Vector2) -> Vector2 {
+        Vector2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+impl Mul<f64> for &Vector2 {
+    type Output = Vector2;
+    fn mul(self, rhs: f64) -> Vector2 {
+        Vector2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+runnable!(values_vs_references, {
+    let a: Vector2 = Vector2::new(1.0, 2.0);
+    let b: Vector2 = Vector2::new(3.0, 4.0);
+    let sum: Vector2 = &a + &b;
+    let scaled: Vector2 = &a * 3.0;
+    println!("a: {:?} b: {:?} sum: {:?} scaled: {:?}", a, b, sum, scaled);
+    // Mixed forms need their own implementations
+    // let mixed = &a + b;
+    // ^ Error: mismatched types: expected `&Vector2`, found `Vector2`
+});
+
+/// ## Guidelines
+/// - For small `Copy` types (like a `Vector2` of two `f64`), implementing the
+///   operators for values is enough: operands are copied, never moved
+/// - For types owning heap memory (matrices, big integers, strings), also
+///   implement the operators for references, so that callers can avoid
+///   cloning; the value versions can then reuse the memory of an operand
+/// - The four combinations (`T + T`, `T + &T`, `&T + T`, `&T + &T`) are
+///   usually generated with a macro (see macros.rs), as the standard library
+///   does for the primitive types
+/// - Implement compound assignments (`AddAssign`, ...) alongside the binary
+///   operators, as users expect both
+fn guidelines() {}
\ No newline at end of file
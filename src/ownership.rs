@@ -367,4 +367,240 @@ runnable!(lifetime_elision, {
         // fn method6(&self, a: &str) -> &str { a }
         // ^ Error: output expected to have lifetime 's
     }
-});
\ No newline at end of file
+});
+
+/// ### `T: 'static` vs `&'static T`
+/// The two look alike, but mean different things:
+/// - `&'static T` is a reference valid until the end of the program: it
+///   points to data which is never freed (a literal, a `static`, leaked
+///   memory)
+/// - `T: 'static` is a bound on a type: `T` contains no borrow shorter than
+///   `'static`. Any owned type satisfies it (`String`, `Vec<u8>`, `Box<T>`),
+///   even though its values are freed as usual. Only types holding
+///   non-`'static` references fail it.
+///
+/// In other words, `T: 'static` means "can be kept for as long as needed",
+/// not "lives forever".
+mod static_bounds {
+    use std::error::Error;
+    use std::fmt::Debug;
+    use std::thread;
+
+    fn keep_forever<T: 'static>(value: T) -> T { value }
+
+    runnable!(static_bound_on_owned_types, {
+        let owned: String = String::from("owned");
+        let kept: String = keep_forever(owned);  // `String: 'static`
+        drop(kept);                               // ...and freed anyway
+
+        let literal: &'static str = "literal";    // `&'static str: 'static`
+        keep_forever(literal);
+
+        let local: String = String::from("local");
+        // keep_forever(&local);
+        // ^ Error: `local` does not live long enough (`&'a String: 'static`
+        //          requires `'a: 'static`)
+        // let forever: &'static String = &local;
+        // ^ Error: `local` does not live long enough
+    });
+
+    /// #### `thread::spawn`
+    /// `thread::spawn` requires `F: 'static` (and so its captures): the
+    /// thread may outlive the caller. Owned values can be moved in, borrowed
+    /// ones cannot (see `thread::scope` in closures.rs for borrowing).
+    fn print_in_thread<T: Debug + Send + 'static>(value: T) {
+        thread::spawn(move || println!("in thread: {:?}", value))
+            .join()
+            .unwrap();
+    }
+
+    runnable!(thread_spawn_bound, {
+        print_in_thread(vec![1, 2, 3]);           // owned: fine
+        print_in_thread("literal");               // `&'static str`: fine
+        let local: Vec<u8> = vec![1, 2, 3];
+        // print_in_thread(&local);
+        // ^ Error: `local` does not live long enough
+        print_in_thread(local.clone());
+    });
+
+    /// #### `Box<dyn Error>`
+    /// A trait object has an implicit lifetime bound: in a return type,
+    /// `Box<dyn Error>` means `Box<dyn Error + 'static>` (see errors.rs). So
+    /// errors borrowing their input cannot be boxed this way, while errors
+    /// owning a copy of it can.
+    #[derive(Debug)]
+    struct InvalidInput<'a>(&'a str);
+    #[derive(Debug)]
+    struct OwnedInvalidInput(String);
+
+    impl std::fmt::Display for InvalidInput<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "invalid input: {}", self.0)
+        }
+    }
+    impl std::fmt::Display for OwnedInvalidInput {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "invalid input: {}", self.0)
+        }
+    }
+    impl Error for InvalidInput<'_> {}
+    impl Error for OwnedInvalidInput {}
+
+    fn validate(input: &str) -> Result<(), Box<dyn Error>> {
+        // return Err(Box::new(InvalidInput(input)));
+        // ^ Error: lifetime may not live long enough (returning this value
+        //          requires that `'1` must outlive `'static`)
+        if input.is_empty() { return Ok(()); }
+        Err(Box::new(OwnedInvalidInput(input.to_string())))
+    }
+
+    /// A lifetime parameter relaxes the bound, as with closures.
+    fn validate_borrowed<'a>(input: &'a str)
+        -> Result<(), Box<dyn Error + 'a>> {
+        if input.is_empty() { return Ok(()); }
+        Err(Box::new(InvalidInput(input)))
+    }
+
+    runnable!(boxed_errors, {
+        let input: String = String::from("x");
+        let error: Box<dyn Error> = validate(&input).unwrap_err();
+        drop(input);
+        println!("{}", error);                    // owns its data: still valid
+
+        let input: String = String::from("y");
+        let error: Box<dyn Error + '_> = validate_borrowed(&input).unwrap_err();
+        println!("{}", error);                    // must be used before `input`
+    });
+
+    /// #### Obtaining a True `&'static T`
+    /// `Box::leak` gives up ownership of a heap value, returning a reference
+    /// valid for the rest of the program: the memory is never freed. It fits
+    /// values created once at startup and used everywhere (see `OnceLock` for
+    /// a safe lazy alternative).
+    struct Config { name: String }
+
+    fn load_config() -> &'static Config {
+        let config: Box<Config> = Box::new(Config { name: "app".to_string() });
+        Box::leak(config)
+    }
+
+    runnable!(leaking_a_box, {
+        let config: &'static Config = load_config();
+        let handle = thread::spawn(move || config.name.len());  // `'static`
+        assert_eq!(handle.join().unwrap(), 3);
+
+        let name: &'static str = String::from("leaked").leak();
+        assert_eq!(name, "leaked");
+    });
+}
+
+/// ## Non-Lexical Lifetimes
+/// Before Rust 2018, a borrow lasted until the end of the lexical scope (the
+/// block) of the reference. Since then, the borrow checker works on the
+/// control flow of the function (`non-lexical lifetimes`, or NLL): a borrow
+/// lasts until the last use of the reference, on each path of execution.
+///
+/// Many programs rejected by the old rules are accepted now. The examples
+/// below all compile: the comments explain why the old borrow checker
+/// refused them.
+mod non_lexical_lifetimes {
+    use std::collections::HashMap;
+
+    /// ### Borrows End at Their Last Use
+    runnable!(borrows_end_at_last_use, {
+        let mut numbers: Vec<u8> = vec![1, 2, 3];
+        let first: &u8 = &numbers[0];
+        println!("first: {}", first);
+        // <-- `numbers` is no longer borrowed (no further use of `first`)
+        //     Before NLL: Error, `first` borrows `numbers` until the end of
+        //     the block
+        numbers.push(4);
+        // println!("first: {}", first);
+        // ^ Error: cannot borrow `numbers` as mutable because it is also
+        //          borrowed as immutable (`first` would still be in use)
+        assert_eq!(numbers.len(), 4);
+    });
+
+    /// ### Borrows on Separate Paths
+    /// A borrow used in one branch does not extend to the others.
+    fn increment_or_insert(counts: &mut HashMap<String, u32>, key: &str) {
+        match counts.get_mut(key) {
+            Some(count) => *count += 1,
+            // <-- `counts` is not borrowed on this path (`count` does not
+            //     exist here)
+            //     Before NLL: Error, the borrow of `get_mut` lasts for the
+            //     whole `match`
+            None => { counts.insert(key.to_string(), 1); }
+        }
+    }
+
+    runnable!(borrows_on_separate_paths, {
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        increment_or_insert(&mut counts, "a");
+        increment_or_insert(&mut counts, "a");
+        assert_eq!(counts["a"], 2);
+    });
+
+    /// ### Limits of NLL
+    /// When a borrow is returned from the function on one path, NLL still
+    /// considers it alive on the other paths: the next version of the borrow
+    /// checker (`Polonius`) accepts this code.
+    /// ```
+    /// fn get_or_insert(map: &mut HashMap<u32, String>, key: u32)
+    ///     -> &mut String {
+    ///     match map.get_mut(&key) {
+    ///         Some(value) => value,        // returned: borrowed for `'1`
+    ///         None => {
+    ///             map.insert(key, String::new());
+    ///             // ^ Error: cannot borrow `*map` as mutable more than once
+    ///             //          at a time
+    ///             map.get_mut(&key).unwrap()
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    /// The workaround checks first, then borrows (or uses the `entry` API).
+    fn get_or_insert(map: &mut HashMap<u32, String>, key: u32)
+        -> &mut String {
+        if !map.contains_key(&key) {
+            map.insert(key, String::new());
+        }
+        map.get_mut(&key).unwrap()
+    }
+
+    runnable!(limits_of_nll, {
+        let mut map: HashMap<u32, String> = HashMap::new();
+        get_or_insert(&mut map, 1).push('a');
+        get_or_insert(&mut map, 1).push('b');
+        map.entry(2).or_default().push('c');
+        assert_eq!((map[&1].as_str(), map[&2].as_str()), ("ab", "c"));
+    });
+
+    /// ### Two-Phase Borrows
+    /// In `numbers.push(numbers.len())`, `push` needs `&mut numbers`, taken
+    /// before evaluating the arguments, which read `numbers`. A mutable borrow
+    /// created by a method call (`autoref`) is only `reserved` until the
+    /// call happens: until then, shared borrows are still allowed.
+    runnable!(two_phase_borrows, {
+        let mut numbers: Vec<usize> = vec![0];
+        numbers.push(numbers.len());
+        // <-- `&mut numbers` is reserved, `numbers.len()` reads it, then the
+        //     mutable borrow is activated by the call
+        //     Without two-phase borrows: Error, `numbers` is borrowed as
+        //     mutable by `push`
+
+        let reference: &mut Vec<usize> = &mut numbers;
+        reference.push(reference.len());     // same through a reference
+        assert_eq!(numbers, [0, 1, 2]);
+
+        // Vec::push(&mut numbers, numbers.len());
+        // ^ Error: cannot borrow `numbers` as immutable because it is also
+        //          borrowed as mutable (an explicit `&mut` is not two-phase)
+        // numbers.push({ numbers.push(1); 2 });
+        // ^ Error: cannot borrow `numbers` as mutable more than once at a
+        //          time (only shared borrows are allowed while reserved)
+        let length: usize = numbers.len();
+        Vec::push(&mut numbers, length);
+        assert_eq!(numbers, [0, 1, 2, 3]);
+    });
+}
\ No newline at end of file
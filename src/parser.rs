@@ -0,0 +1,213 @@
+/// # Recursive Descent Parsing
+/// A parser turns text into a tree: here, arithmetic expressions such as
+/// `2 * (x + 1)` into an `Expr`, a recursive enum (see
+/// binary_search_tree.rs). Each rule of the grammar becomes a function,
+/// calling the functions of its subrules:
+/// ```text
+/// expression = term (("+" | "-") term)*
+/// term       = factor (("*" | "/") factor)*
+/// factor     = number | identifier | "-" factor | "(" expression ")"
+/// ```
+/// Precedence follows from the nesting: a `term` binds tighter than an
+/// `expression`, so `1 + 2 * 3` is parsed as `1 + (2 * 3)`.
+///
+/// Other lessons walk this tree (see patterns/visitor.rs).
+use std::fmt;
+
+fn parser() {}
+
+/// ## The Syntax Tree
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp { Add, Sub, Mul, Div }
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Number(i64),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+}
+
+impl Expr {
+    pub fn binary(left: Expr, op: BinaryOp, right: Expr) -> Expr {
+        Expr::Binary(Box::new(left), op, Box::new(right))
+    }
+}
+
+/// ## Errors
+/// A parser must reject any input without panicking, and say where it
+/// stopped: the position is a byte offset into the input.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// ## The Parser
+/// The parser only keeps its input and a position in it: each function
+/// consumes what it recognizes, and leaves the position after it.
+///
+/// It also counts how deep the tree being built is. Each level of nesting
+/// is a level of recursion, in the parser and in every function walking the
+/// tree (including `drop`): without a limit, an input such as `((((...`
+/// overflows the stack, which aborts the whole process (see fuzzing.rs).
+const MAX_DEPTH: usize = 256;
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, ParseError> {
+        Err(ParseError { position: self.position, message: message.into() })
+    }
+
+    /// The next non-whitespace character, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        let rest: &str = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+        self.input[self.position..].chars().next()
+    }
+
+    /// Consume the peeked character: symbols are ASCII, a single byte.
+    fn skip(&mut self) { self.position += 1; }
+
+    /// Go one level deeper in the tree, or fail if it is too deep.
+    fn nest(&mut self) -> Result<(), ParseError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return self.error("expression nested too deeply");
+        }
+        Ok(())
+    }
+
+    /// Consume characters while they satisfy `predicate`, returning them.
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest: &'a str = &self.input[self.position..];
+        let length: usize = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    /// A chain such as `1 + 2 + 3` is parsed by a loop, but builds a tree
+    /// one level deeper per operator: each one counts as nesting.
+    fn expression(&mut self) -> Result<Expr, ParseError> {
+        let depth: usize = self.depth;
+        let mut left: Expr = self.term()?;
+        loop {
+            let op: BinaryOp = match self.peek() {
+                Some('+') => BinaryOp::Add,
+                Some('-') => BinaryOp::Sub,
+                _ => { self.depth = depth; return Ok(left); }
+            };
+            self.skip();
+            self.nest()?;
+            left = Expr::binary(left, op, self.term()?);  // left-associative
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, ParseError> {
+        let depth: usize = self.depth;
+        let mut left: Expr = self.factor()?;
+        loop {
+            let op: BinaryOp = match self.peek() {
+                Some('*') => BinaryOp::Mul,
+                Some('/') => BinaryOp::Div,
+                _ => { self.depth = depth; return Ok(left); }
+            };
+            self.skip();
+            self.nest()?;
+            left = Expr::binary(left, op, self.factor()?);
+        }
+    }
+
+    fn factor(&mut self) -> Result<Expr, ParseError> {
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => {
+                let start: usize = self.position;
+                let digits: &str = self.take_while(|c| c.is_ascii_digit());
+                match digits.parse::<i64>() {
+                    Ok(value) => Ok(Expr::Number(value)),
+                    Err(_) => Err(ParseError {
+                        position: start,
+                        message: "number too large".into(),
+                    }),
+                }
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name: &str =
+                    self.take_while(|c| c.is_alphanumeric() || c == '_');
+                Ok(Expr::Variable(name.to_string()))
+            }
+            Some('-') => {
+                self.skip();
+                self.nest()?;
+                let operand: Expr = self.factor()?;
+                self.depth -= 1;
+                Ok(Expr::Negate(Box::new(operand)))
+            }
+            Some('(') => {
+                self.skip();
+                self.nest()?;
+                let inner: Expr = self.expression()?;
+                self.depth -= 1;
+                match self.peek() {
+                    Some(')') => { self.skip(); Ok(inner) }
+                    _ => self.error("expected `)`"),
+                }
+            }
+            Some(_) => self.error("expected an expression"),
+            None => self.error("unexpected end of input"),
+        }
+    }
+}
+
+/// Parse a whole expression: trailing input is an error, not ignored.
+pub fn parse(input: &str) -> Result<Expr, ParseError> {
+    let mut parser: Parser = Parser { input, position: 0, depth: 0 };
+    let expr: Expr = parser.expression()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(_) => parser.error("expected an operator"),
+    }
+}
+
+runnable!(parsing, {
+    use BinaryOp::*;
+    let (n, b) = (Expr::Number, Expr::binary);  // variants are functions too
+    assert_eq!(parse("42"), Ok(n(42)));
+    assert_eq!(parse(" 1 + 2 * 3 "), Ok(b(n(1), Add, b(n(2), Mul, n(3)))));
+    assert_eq!(parse("(1 + 2) * 3"), Ok(b(b(n(1), Add, n(2)), Mul, n(3))));
+    assert_eq!(parse("8 - 4 - 2"), Ok(b(b(n(8), Sub, n(4)), Sub, n(2))));
+    assert_eq!(parse("-x"),
+        Ok(Expr::Negate(Box::new(Expr::Variable("x".into())))));
+});
+
+/// The shape of a whole tree, checked against src/snapshots (see
+/// testing/snapshots.rs): easier to read than nested constructors.
+runnable!(syntax_tree_snapshot, {
+    assert_debug_snapshot!("parser_syntax_tree", parse("-(x + 2) * 3 - y"));
+});
+
+/// Each kind of error is a row of a table, and a test of its own (see
+/// `test_cases!` in util.rs): `parse_errors::unclosed_parenthesis`, ...
+test_cases!(parse_errors, |input: &str| parse(input).unwrap_err().to_string(), [
+    (empty_input, "", "at 0: unexpected end of input"),
+    (missing_operand, "1 +", "at 3: unexpected end of input"),
+    (unclosed_parenthesis, "(1 + 2", "at 6: expected `)`"),
+    (missing_operator, "1 2", "at 2: expected an operator"),
+    (unexpected_character, "2 * ?", "at 4: expected an expression"),
+    (number_too_large, "99999999999999999999", "at 0: number too large"),
+    (nested_too_deeply, &"(".repeat(MAX_DEPTH + 1),
+        "at 257: expression nested too deeply"),
+]);
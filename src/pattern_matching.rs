@@ -124,4 +124,242 @@ runnable!(while_let, {
         }
     }
     println!("while_let: {:?}", option);
-});
\ No newline at end of file
+});
+
+/// ## Binding Modes (Match Ergonomics)
+/// A binding in a pattern either moves (or copies) the matched value, or
+/// borrows it. Since Rust 2018, the `default binding mode` is chosen by the
+/// compiler: when a non-reference pattern (e.g. `Some(x)`) meets a reference,
+/// the reference is skipped and the bindings inside become references too.
+mod binding_modes {
+    /// ### Matching References without `ref`
+    /// Matching `&Option<String>` against `Some(text)` binds `text: &String`:
+    /// the `Option` is never moved out of the reference.
+    fn length(name: &Option<String>) -> usize {
+        match name {
+            Some(text) => text.len(),  // `text: &String`
+            None => 0,
+        }
+    }
+
+    /// The same applies to `&mut`, binding mutable references.
+    fn shout(name: &mut Option<String>) {
+        if let Some(text) = name {     // `text: &mut String`
+            text.push('!');
+        }
+    }
+
+    runnable!(matching_references_without_ref, {
+        let mut name: Option<String> = Some("ferris".to_string());
+        assert_eq!(length(&name), 6);
+        shout(&mut name);
+        assert_eq!(name.as_deref(), Some("ferris!"));
+
+        // Nested references and tuples work the same way
+        let pairs: Vec<(String, u32)> = vec![("a".to_string(), 1)];
+        for (key, value) in &pairs {   // `key: &String`, `value: &u32`
+            assert_eq!((key.as_str(), *value), ("a", 1));
+        }
+    });
+
+    /// ### Pre-2018 Rust, Side by Side
+    /// Before match ergonomics, the pattern had to mirror the reference with
+    /// `&`, and borrow the bindings explicitly with `ref`/`ref mut`.
+    /// ```
+    /// match name {                   // name: &Option<String>
+    ///     &Some(ref text) => text.len(),
+    ///     &None => 0,
+    /// }
+    /// // or, dereferencing first
+    /// match *name {
+    ///     Some(ref text) => text.len(),
+    ///     None => 0,
+    /// }
+    /// ```
+    /// Both forms still compile, but moving out of the reference does not:
+    /// ```
+    /// match name {
+    ///     &Some(text) => text.len(),
+    ///     &None => 0,
+    /// }
+    /// // ^ Error: cannot move out of a shared reference
+    /// ```
+    fn length_pre_2018(name: &Option<String>) -> usize {
+        match *name {
+            Some(ref text) => text.len(),
+            None => 0,
+        }
+    }
+
+    runnable!(pre_2018_side_by_side, {
+        let name: Option<String> = Some("ferris".to_string());
+        assert_eq!(length_pre_2018(&name), length(&name));
+    });
+
+    /// ### When `ref` and `ref mut` Are Still Needed
+    /// When matching a value (not a reference), bindings move by default.
+    /// `ref`/`ref mut` borrow instead, keeping the value usable afterwards,
+    /// without having to match on `&value`/`&mut value`.
+    runnable!(when_ref_is_still_needed, {
+        let mut name: Option<String> = Some("ferris".to_string());
+        // match name { Some(text) => text.len(), None => 0 };
+        // println!("{:?}", name);
+        // ^ Error: borrow of partially moved value: `name`
+        let length: usize = match name {
+            Some(ref text) => text.len(),
+            None => 0,
+        };
+        if let Some(ref mut text) = name {
+            text.push('!');
+        }
+        assert_eq!((length, name.as_deref()), (6, Some("ferris!")));
+
+        // Mixing moves and borrows of different fields of a value
+        let pair: (String, String) = ("kept".to_string(), "moved".to_string());
+        let (ref kept, moved) = pair;
+        assert_eq!((kept.as_str(), pair.0.as_str()), ("kept", "kept"));
+        assert_eq!(moved, "moved");
+
+        // A `&` reference cannot be mutated through a binding
+        // let readonly: &Option<String> = &name;
+        // if let Some(text) = readonly { text.push('!'); }
+        // ^ Error: cannot borrow `*text` as mutable, as it is behind a `&`
+        //          reference
+    });
+
+    /// ### `@`-Bindings with Subpatterns
+    /// `name @ pattern` binds the whole matched value, while the subpattern
+    /// can still bind its parts. With the default binding mode, both are
+    /// references; when matching a value, they cannot both move it.
+    #[derive(Debug, PartialEq)]
+    enum Shape {
+        Circle { radius: u32 },
+        Label(String),
+    }
+
+    fn describe(shape: &Shape) -> String {
+        match shape {
+            // `circle: &Shape`, `radius: &u32`
+            circle @ Shape::Circle { radius: ..=9 } => {
+                format!("small {:?}", circle)
+            }
+            Shape::Circle { radius: radius @ 10.. } => {
+                format!("circle of radius {}", radius)
+            }
+            label @ Shape::Label(text) if text.is_empty() => {
+                format!("empty {:?}", label)
+            }
+            Shape::Label(text) => format!("label {}", text),
+        }
+    }
+
+    runnable!(at_bindings_with_subpatterns, {
+        let small: Shape = Shape::Circle { radius: 2 };
+        assert_eq!(describe(&small), "small Circle { radius: 2 }");
+        assert_eq!(describe(&Shape::Circle { radius: 12 }),
+            "circle of radius 12");
+        assert_eq!(describe(&Shape::Label(String::new())),
+            "empty Label(\"\")");
+
+        let label: Shape = Shape::Label("hi".to_string());
+        // match label { whole @ Shape::Label(text) => (), _ => () }
+        // ^ Error: use of partially moved value: `label`
+        match label {
+            ref whole @ Shape::Label(ref text) => {
+                assert_eq!((whole, text.as_str()),
+                    (&Shape::Label("hi".to_string()), "hi"));
+            }
+            _ => unreachable!(),
+        }
+    });
+}
+
+/// ## Refutable and Irrefutable Patterns
+/// A pattern is `irrefutable` if it matches every possible value of its type
+/// (e.g. `x`, `(a, b)`, `Point { x, y }`, `[first, second]` on an array of 2
+/// elements), and `refutable` otherwise (e.g. `Some(x)`, `0..=9`, `[a, b]`
+/// on a slice).
+///
+/// Some positions need a match no matter what: they only accept irrefutable
+/// patterns. Others exist to handle failures: they accept refutable patterns.
+mod refutability {
+    struct Point { x: i32, y: i32 }
+
+    /// ### Irrefutable Positions
+    /// `let` statements, function and closure parameters, and `for` loops.
+    fn norm(Point { x, y }: &Point) -> i32 {  // destructuring a parameter
+        x.abs() + y.abs()
+    }
+    // fn first(Some(value): Option<u8>) -> u8 { value }
+    // ^ Error: refutable pattern in function argument: pattern `None` not
+    //          covered
+
+    runnable!(irrefutable_positions, {
+        let (name, age): (&str, u8) = ("ferris", 8);
+        let Point { x, y }: Point = Point { x: 3, y: -4 };
+        let [first, second]: [u8; 2] = [1, 2];
+        assert_eq!(norm(&Point { x, y }), 7);
+
+        let swap = |(a, b): (u8, u8)| (b, a);
+        assert_eq!(swap((first, second)), (2, 1));
+        for (index, letter) in ['a', 'b'].iter().enumerate() {
+            assert_eq!(index, (*letter as u8 - b'a') as usize);
+        }
+
+        let option: Option<u8> = Some(1);
+        // let Some(value) = option;
+        // ^ Error: refutable pattern in local binding: pattern `None` not
+        //          covered
+        // let unwrap = |Some(value): Option<u8>| value;
+        // ^ Error: refutable pattern in closure argument: pattern `None` not
+        //          covered
+        // for Some(value) in vec![Some(1), None] {}
+        // ^ Error: refutable pattern in `for` loop binding: pattern `None`
+        //          not covered
+        // let [a, b] = &[1, 2][..];
+        // ^ Error: refutable pattern in local binding: patterns `&[]`,
+        //          `&[_]` and `&[_, _, _, ..]` not covered
+    });
+
+    /// ### Refutable Positions
+    /// `if let`, `while let`, `let-else` (see let_else.rs) and `match` arms.
+    /// A `match` must be exhaustive as a whole, but each arm may be
+    /// refutable.
+    runnable!(refutable_positions, {
+        let option: Option<u8> = Some(1);
+        if let Some(value) = option {
+            assert_eq!(value, 1);
+        }
+        let mut stack: Vec<u8> = vec![1, 2];
+        while let Some(top) = stack.pop() {
+            assert!(top <= 2);
+        }
+        let Some(value) = option else { return };
+        let slice: &[u8] = &[1, 2];
+        let kind: &str = match slice {
+            [] => "empty",
+            [_] => "one",
+            [_, ..] => "many",
+        };
+        assert_eq!(kind, "many");
+    });
+
+    /// ### Irrefutable Patterns in Refutable Positions
+    /// This compiles, but the test can never fail: the compiler warns about
+    /// it, as it likely is a mistake (e.g. a binding instead of a constant).
+    /// ```
+    /// if let value = option {}
+    /// // ^ Warning: irrefutable `if let` pattern
+    /// while let _ = option {}
+    /// // ^ Warning: irrefutable `while let` pattern
+    /// ```
+    /// A `match` whose single arm is irrefutable is accepted without warnings
+    /// (and `let` is usually clearer).
+    runnable!(irrefutable_patterns_in_refutable_positions, {
+        let pair: (u8, u8) = (1, 2);
+        let sum: u8 = match pair {
+            (a, b) => a + b,
+        };
+        assert_eq!(sum, 3);
+    });
+}
\ No newline at end of file
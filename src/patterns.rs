@@ -0,0 +1,44 @@
+/// # Design Patterns
+/// Many classic design patterns rely on inheritance, garbage collection or
+/// shared mutable state, so they cannot be ported to Rust as they are.
+/// Instead, Rust has its own idioms, built on ownership, traits and enums.
+///
+/// Each submodule in the `patterns` folder covers one of these idioms (see
+/// modules.rs for how directory modules work).
+fn patterns() {}
+
+/// ## Builders
+mod builder;
+
+/// ## Newtypes
+mod newtype;
+
+/// ## Sealed Traits
+mod sealed;
+
+/// ## Extension Traits
+mod extension_traits;
+
+/// ## Arenas
+mod arena;
+
+/// ## Visitors
+pub mod visitor;
+
+/// ## Enum State Machines
+mod enum_state;
+
+/// ## Commands
+mod command;
+
+/// ## Observers
+mod observer;
+
+/// ## Strategies
+mod strategy;
+
+/// ## Dependency Injection
+mod dependency_injection;
+
+/// ## Retries
+mod retry;
@@ -0,0 +1,216 @@
+/// # Arena Pattern
+/// An `arena` owns many values of the same type, and hands out lightweight
+/// handles to them. Values refer to each other through these handles, not
+/// through owning pointers: a tree with parent links, or a graph with
+/// cycles, needs neither `Rc` nor `RefCell`, and does not fight the borrow
+/// checker (see shared_graph.rs and self_referential.rs).
+///
+/// Deallocation is centralized: everything is freed at once when the arena
+/// is dropped, which is also cheaper than freeing values one by one.
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+
+fn arena() {}
+
+/// ## Index Handles
+/// A `Vec`-backed arena: a handle is an index into the vector, typed with
+/// `PhantomData` so that a handle to a `T` cannot be used on an arena of
+/// `U` (see patterns/newtype.rs).
+pub struct Id<T> {
+    index: usize,
+    _type: PhantomData<fn() -> T>,   // `Id<T>` neither owns nor borrows a `T`
+}
+
+// Derives would require `T: Copy`: the handle is copyable whatever `T` is.
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self { *self }
+}
+impl<T> Copy for Id<T> {}
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool { self.index == other.index }
+}
+impl<T> std::fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Id({})", self.index)
+    }
+}
+
+pub struct Arena<T> {
+    values: Vec<T>,
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Arena<T> { Arena { values: Vec::new() } }
+
+    pub fn alloc(&mut self, value: T) -> Id<T> {
+        self.values.push(value);
+        Id { index: self.values.len() - 1, _type: PhantomData }
+    }
+
+    pub fn len(&self) -> usize { self.values.len() }
+}
+
+/// Handles are checked by `Vec` indexing: they are always in bounds, as
+/// values are never removed.
+impl<T> std::ops::Index<Id<T>> for Arena<T> {
+    type Output = T;
+    fn index(&self, id: Id<T>) -> &T { &self.values[id.index] }
+}
+impl<T> std::ops::IndexMut<Id<T>> for Arena<T> {
+    fn index_mut(&mut self, id: Id<T>) -> &mut T {
+        &mut self.values[id.index]
+    }
+}
+
+/// ### A Tree with Parent Links
+/// With `Box`, a child cannot point back to its parent (the parent owns
+/// it). With handles, links go both ways.
+struct Directory {
+    name: String,
+    parent: Option<Id<Directory>>,
+    children: Vec<Id<Directory>>,
+}
+
+fn mkdir(arena: &mut Arena<Directory>, parent: Option<Id<Directory>>,
+    name: &str) -> Id<Directory> {
+    let id: Id<Directory> = arena.alloc(Directory {
+        name: name.to_string(),
+        parent,
+        children: Vec::new(),
+    });
+    if let Some(parent) = parent {
+        arena[parent].children.push(id);
+    }
+    id
+}
+
+fn path(arena: &Arena<Directory>, mut id: Id<Directory>) -> String {
+    let mut parts: Vec<&str> = vec![&arena[id].name];
+    while let Some(parent) = arena[id].parent {
+        parts.push(&arena[parent].name);
+        id = parent;
+    }
+    parts.reverse();
+    parts.join("/")
+}
+
+runnable!(index_handles, {
+    let mut arena: Arena<Directory> = Arena::new();
+    let root: Id<Directory> = mkdir(&mut arena, None, "");
+    let home: Id<Directory> = mkdir(&mut arena, Some(root), "home");
+    let ferris: Id<Directory> = mkdir(&mut arena, Some(home), "ferris");
+    mkdir(&mut arena, Some(root), "tmp");
+
+    assert_eq!(path(&arena, ferris), "/home/ferris");
+    assert_eq!(arena[root].children.len(), 2);
+    arena[ferris].name = String::from("crab");  // mutation through `&mut`
+    assert_eq!(path(&arena, ferris), "/home/crab");
+    assert_eq!(arena.len(), 4);
+});
+
+/// ## Reference-Returning Arena
+/// Handles need the arena at hand to be dereferenced. An arena can instead
+/// return plain references, valid as long as the arena itself, from a
+/// shared `&self`: values can then point to each other directly, even in
+/// cycles (through `Cell`), all with the same lifetime `'a`.
+///
+/// This needs `unsafe`: the values must never move while references to
+/// them exist, so they are stored in chunks which are never reallocated.
+/// When a chunk is full, a new one is allocated next to it, instead of
+/// growing it. The invariants:
+/// 1. a value is pushed into a chunk only if its capacity allows it, so the
+///    buffer of a chunk never moves (moving the chunk `Vec` itself, when
+///    `chunks` grows, does not move its buffer)
+/// 2. values are never removed or mutated through `chunks` before the
+///    arena is dropped, so the returned references stay valid
+///
+/// Returning `&mut T` would also be sound (each value is handed out once),
+/// but `&T` with `Cell` fields is enough here.
+///
+/// The `typed-arena` and `bumpalo` crates implement this pattern.
+pub struct RefArena<T> {
+    chunks: RefCell<Vec<Vec<T>>>,
+}
+
+impl<T> RefArena<T> {
+    const FIRST_CHUNK_CAPACITY: usize = 4;
+
+    pub fn new() -> RefArena<T> {
+        let chunk: Vec<T> = Vec::with_capacity(Self::FIRST_CHUNK_CAPACITY);
+        RefArena { chunks: RefCell::new(vec![chunk]) }
+    }
+
+    pub fn alloc(&self, value: T) -> &T {
+        let mut chunks = self.chunks.borrow_mut();
+        let last: &Vec<T> = chunks.last().unwrap();
+        if last.len() == last.capacity() {
+            let capacity: usize = last.capacity() * 2;
+            chunks.push(Vec::with_capacity(capacity));
+        }
+        let chunk: &mut Vec<T> = chunks.last_mut().unwrap();
+        chunk.push(value);                  // no reallocation (invariant 1)
+        let value: *const T = chunk.last().unwrap();
+        // SAFETY: the value never moves (1), is never mutated through
+        // `chunks` (2), and lives as long as `self`: the reference is valid
+        // for `&self`.
+        unsafe { &*value }
+    }
+
+    pub fn len(&self) -> usize {
+        self.chunks.borrow().iter().map(Vec::len).sum()
+    }
+}
+
+/// ### A Graph with Direct References
+/// Nodes borrow each other for `'a`, the lifetime of the arena: the arena
+/// is borrowed (shared) while the graph exists, and frees every node at
+/// once when dropped.
+struct Node<'a> {
+    name: &'static str,
+    next: Cell<Option<&'a Node<'a>>>,
+}
+
+runnable!(reference_returning_arena, {
+    let arena: RefArena<Node> = RefArena::new();
+    let new = |name: &'static str| -> &Node {
+        arena.alloc(Node { name, next: Cell::new(None) })
+    };
+    let a: &Node = new("a");
+    let b: &Node = new("b");
+    let c: &Node = new("c");
+    a.next.set(Some(b));
+    b.next.set(Some(c));
+    c.next.set(Some(a));                    // a cycle, without `Rc`
+
+    let mut names: String = String::new();
+    let mut current: &Node = a;
+    for _ in 0..6 {
+        names.push_str(current.name);
+        current = current.next.get().unwrap();
+    }
+    assert_eq!(names, "abcabc");
+
+    for _ in 0..10 { new("filler"); }       // new chunks: old nodes stay put
+    assert_eq!(a.next.get().unwrap().name, "b");
+    assert_eq!(arena.len(), 13);
+});
+
+/// ## Centralized Deallocation
+/// Dropping the arena drops every value, whatever the links between them.
+struct Tracked<'a>(&'a Cell<u32>);
+
+impl Drop for Tracked<'_> {
+    fn drop(&mut self) { self.0.set(self.0.get() + 1); }
+}
+
+runnable!(centralized_deallocation, {
+    let drops: Cell<u32> = Cell::new(0);
+    {
+        let mut arena: Arena<Tracked> = Arena::new();
+        for _ in 0..3 { arena.alloc(Tracked(&drops)); }
+        let references: RefArena<Tracked> = RefArena::new();
+        for _ in 0..5 { references.alloc(Tracked(&drops)); }
+        assert_eq!(drops.get(), 0);
+    }   // <-- both arenas are dropped here, with all their values
+    assert_eq!(drops.get(), 8);
+});
\ No newline at end of file
@@ -0,0 +1,175 @@
+/// # Builder Pattern
+/// Rust has no named or default arguments, and no constructor overloading.
+/// When a type has many optional settings, a `builder` collects them step by
+/// step, then creates the value with `build()`.
+///
+/// Builders come in two flavours, depending on how their setters take `self`:
+/// - `consuming` builders (`self -> Self`): setters take the builder by value
+///   and return it, so calls can be chained into a single expression
+/// - `mutating` builders (`&mut self -> &mut Self`): setters modify the
+///   builder in place, so it can also be configured conditionally
+use std::time::Duration;
+
+fn builder() {}
+
+#[derive(Debug, PartialEq)]
+pub struct ServerConfig {
+    host: String,
+    port: u16,
+    workers: usize,
+    timeout: Duration,
+}
+
+/// ## Defaults
+/// Implementing `Default` gives each setting a fallback value. Builders can
+/// start from it, so that users only set what they care about.
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            host: String::from("127.0.0.1"),
+            port: 8080,
+            workers: 4,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// ## Validation
+/// `build()` is the single place where the settings are checked together,
+/// returning a `Result` instead of an invalid `ServerConfig`.
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    EmptyHost,
+    ReservedPort(u16),
+    NoWorkers,
+}
+
+fn validate(config: ServerConfig) -> Result<ServerConfig, ConfigError> {
+    if config.host.is_empty() { return Err(ConfigError::EmptyHost); }
+    if config.port < 1024 {
+        return Err(ConfigError::ReservedPort(config.port));
+    }
+    if config.workers == 0 { return Err(ConfigError::NoWorkers); }
+    Ok(config)
+}
+
+/// ## Consuming Builder
+/// `#[must_use]` makes the compiler warn when the returned builder is
+/// discarded: with a consuming builder, `builder.port(80);` alone would
+/// silently throw the setting (and the builder) away.
+#[derive(Default)]
+#[must_use = "builders do nothing unless `build()` is called"]
+pub struct ConsumingBuilder { config: ServerConfig }
+
+impl ConsumingBuilder {
+    pub fn new() -> Self { Self::default() }
+    pub fn host(mut self, host: &str) -> Self {
+        self.config.host = String::from(host);
+        self
+    }
+    pub fn port(mut self, port: u16) -> Self { self.config.port = port; self }
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.config.workers = workers;
+        self
+    }
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+    pub fn build(self) -> Result<ServerConfig, ConfigError> {
+        validate(self.config)   // moves the settings out: no clone needed
+    }
+}
+
+runnable!(consuming_builder, {
+    let config: Result<ServerConfig, ConfigError> = ConsumingBuilder::new()
+        .host("0.0.0.0")
+        .port(3000)
+        .workers(8)
+        .build();
+    println!("config: {:?}", config);
+    assert!(config.is_ok());
+
+    // Conditional configuration requires re-binding the builder
+    let verbose: bool = true;
+    let mut builder: ConsumingBuilder = ConsumingBuilder::new();
+    if verbose { builder = builder.timeout(Duration::from_secs(60)); }
+    println!("conditional: {:?}", builder.build());
+
+    // let builder = ConsumingBuilder::new();
+    // builder.port(80);
+    // ^ Warning: unused `ConsumingBuilder` that must be used
+    // builder.build();
+    // ^ Error: use of moved value `builder`
+
+    let invalid = ConsumingBuilder::new().port(80).build();
+    println!("invalid: {:?}", invalid);
+    assert_eq!(invalid, Err(ConfigError::ReservedPort(80)));
+});
+
+/// ## Mutating Builder
+/// Setters borrow the builder mutably, so the builder can be reused to build
+/// many values. The price is that `build()` only borrows the builder, so it
+/// must clone the settings out of it.
+#[derive(Default)]
+pub struct MutatingBuilder { config: ServerConfig }
+
+impl MutatingBuilder {
+    pub fn new() -> Self { Self::default() }
+    pub fn host(&mut self, host: &str) -> &mut Self {
+        self.config.host = String::from(host);
+        self
+    }
+    pub fn port(&mut self, port: u16) -> &mut Self {
+        self.config.port = port;
+        self
+    }
+    pub fn workers(&mut self, workers: usize) -> &mut Self {
+        self.config.workers = workers;
+        self
+    }
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.config.timeout = timeout;
+        self
+    }
+    pub fn build(&self) -> Result<ServerConfig, ConfigError> {
+        validate(ServerConfig {
+            host: self.config.host.clone(),  // settings must be cloned
+            ..self.config
+        })
+    }
+}
+
+runnable!(mutating_builder, {
+    let mut builder: MutatingBuilder = MutatingBuilder::new();
+    builder.host("0.0.0.0").workers(2);
+
+    // Conditional configuration is natural
+    let verbose: bool = true;
+    if verbose { builder.timeout(Duration::from_secs(60)); }
+
+    // The same builder can build many values
+    let first: ServerConfig = builder.port(3000).build().unwrap();
+    let second: ServerConfig = builder.port(3001).build().unwrap();
+    println!("first: {:?}\nsecond: {:?}", first, second);
+
+    // let config = MutatingBuilder::new().port(3000).build();
+    // ^ Fine: the temporary builder lives until the end of the statement
+    // let builder = MutatingBuilder::new().port(3000);
+    // builder.build();
+    // ^ Error: temporary value dropped while borrowed (`port` returns a
+    //          reference to the temporary builder)
+
+    let invalid = MutatingBuilder::new().workers(0).build();
+    assert_eq!(invalid, Err(ConfigError::NoWorkers));
+});
+
+/// ## Trade-Offs
+/// - Consuming builders allow one-liners and move settings into the result
+///   without cloning, but make conditional configuration awkward
+/// - Mutating builders make conditional configuration and reuse easy, but
+///   `build()` must clone, and chains starting from a temporary cannot be
+///   stored in a variable
+/// - In both cases, `Default` keeps the builder short, and validating in
+///   `build()` keeps invalid values from ever existing
+fn trade_offs() {}
\ No newline at end of file
@@ -0,0 +1,176 @@
+/// # Command Pattern
+/// A command turns an action into a value: it can be stored, undone and
+/// redone. Each command owns whatever data it needs to revert itself, e.g.
+/// the text it deleted, so the history is a plain list of owned values,
+/// `Vec<Box<dyn Command>>`: different command types, behind one trait object
+/// (see traits.rs).
+fn command() {}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Document {
+    pub text: String,
+}
+
+/// ## Dyn-Compatible Commands
+/// To be usable as `dyn Command`, the trait must be dyn-compatible (see
+/// object_safety.rs): no generic methods, no `Self` in argument or return
+/// types, no `Clone` supertrait. Methods taking `&mut self` let commands
+/// remember what `execute` did, for `undo`.
+pub trait Command {
+    fn execute(&mut self, doc: &mut Document);
+    fn undo(&mut self, doc: &mut Document);
+    fn name(&self) -> String;
+}
+
+pub struct Insert {
+    pub at: usize,
+    pub text: String,
+}
+
+impl Command for Insert {
+    fn execute(&mut self, doc: &mut Document) {
+        doc.text.insert_str(self.at, &self.text);
+    }
+    fn undo(&mut self, doc: &mut Document) {
+        doc.text.replace_range(self.at..self.at + self.text.len(), "");
+    }
+    fn name(&self) -> String { format!("insert {:?}", self.text) }
+}
+
+/// Only `execute` knows the deleted text: the command captures it, taking
+/// ownership of the `String` removed from the document.
+pub struct Delete {
+    pub range: std::ops::Range<usize>,
+    deleted: Option<String>,
+}
+
+impl Delete {
+    pub fn new(range: std::ops::Range<usize>) -> Delete {
+        Delete { range, deleted: None }
+    }
+}
+
+impl Command for Delete {
+    fn execute(&mut self, doc: &mut Document) {
+        self.deleted = Some(doc.text.drain(self.range.clone()).collect());
+    }
+    fn undo(&mut self, doc: &mut Document) {
+        let deleted: String = self.deleted.take().expect("undo before execute");
+        doc.text.insert_str(self.range.start, &deleted);
+    }
+    fn name(&self) -> String { format!("delete {:?}", self.range) }
+}
+
+/// ## History with Redo
+/// Executed commands move into `done`. Undoing moves the last one into
+/// `undone`, redoing moves it back: the boxes move, the commands are never
+/// cloned. A new command clears `undone`, as in any editor.
+#[derive(Default)]
+pub struct Editor {
+    pub doc: Document,
+    done: Vec<Box<dyn Command>>,
+    undone: Vec<Box<dyn Command>>,
+}
+
+impl Editor {
+    pub fn run(&mut self, mut command: Box<dyn Command>) {
+        command.execute(&mut self.doc);
+        self.done.push(command);
+        self.undone.clear();
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(mut command) = self.done.pop() else { return false };
+        command.undo(&mut self.doc);
+        self.undone.push(command);
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(mut command) = self.undone.pop() else { return false };
+        command.execute(&mut self.doc);
+        self.done.push(command);
+        true
+    }
+
+    pub fn history(&self) -> Vec<String> {
+        self.done.iter().map(|command| command.name()).collect()
+    }
+}
+
+runnable!(history_with_redo, {
+    let mut editor: Editor = Editor::default();
+    editor.run(Box::new(Insert { at: 0, text: "hello world".into() }));
+    editor.run(Box::new(Delete::new(5..11)));
+    editor.run(Box::new(Insert { at: 5, text: ", rust".into() }));
+    assert_eq!(editor.doc.text, "hello, rust");
+    assert_eq!(editor.history(),
+        ["insert \"hello world\"", "delete 5..11", "insert \", rust\""]);
+
+    assert!(editor.undo());
+    assert!(editor.undo());
+    assert_eq!(editor.doc.text, "hello world");  // the deleted text is back
+    assert!(editor.redo());
+    assert_eq!(editor.doc.text, "hello");
+
+    editor.run(Box::new(Insert { at: 5, text: "!".into() }));
+    assert!(!editor.redo());                     // the redo stack is gone
+    assert!(editor.undo() && editor.undo() && editor.undo());
+    assert!(!editor.undo());
+    assert_eq!(editor.doc, Document::default());
+});
+
+/// ## Composite and Closure Commands
+/// A macro is a command made of commands, undone in reverse order. Any pair
+/// of closures is a command too: a generic struct implements the trait for
+/// every closure type, and boxing erases the type (see closures.rs).
+pub struct Macro(pub Vec<Box<dyn Command>>);
+
+impl Command for Macro {
+    fn execute(&mut self, doc: &mut Document) {
+        self.0.iter_mut().for_each(|command| command.execute(doc));
+    }
+    fn undo(&mut self, doc: &mut Document) {
+        self.0.iter_mut().rev().for_each(|command| command.undo(doc));
+    }
+    fn name(&self) -> String { format!("macro of {}", self.0.len()) }
+}
+
+pub struct FnCommand<E, U> {
+    pub execute: E,
+    pub undo: U,
+}
+
+impl<E, U> Command for FnCommand<E, U>
+where
+    E: FnMut(&mut Document),
+    U: FnMut(&mut Document),
+{
+    fn execute(&mut self, doc: &mut Document) { (self.execute)(doc) }
+    fn undo(&mut self, doc: &mut Document) { (self.undo)(doc) }
+    fn name(&self) -> String { "closure".to_string() }
+}
+
+runnable!(composite_and_closure_commands, {
+    let mut editor: Editor = Editor::default();
+    editor.doc.text.push_str("rust");
+    let suffix: String = "!".repeat(3);
+    let length: usize = suffix.len();
+    let exclaim = FnCommand {
+        execute: move |doc: &mut Document| doc.text.push_str(&suffix),
+        undo: move |doc: &mut Document| {
+            doc.text.truncate(doc.text.len() - length)
+        },
+    };
+    // println!("{}", suffix);
+    // ^ Error: borrow of moved value: `suffix` (the command owns it now)
+    editor.run(Box::new(Macro(vec![
+        Box::new(Insert { at: 0, text: "hello ".into() }),
+        Box::new(exclaim),
+        Box::new(Insert { at: 0, text: "oh, ".into() }),
+    ])));
+    assert_eq!(editor.doc.text, "oh, hello rust!!!");
+    assert_eq!(editor.history(), ["macro of 3"]);
+    assert!(editor.undo());
+    assert_eq!(editor.doc.text, "rust");
+});
@@ -0,0 +1,177 @@
+/// # Dependency Injection
+/// A component which reads the time or writes files directly is hard to
+/// test: its output changes every second, and tests leave files behind.
+/// Instead, the component receives its dependencies, described by traits,
+/// when it is constructed (`constructor injection`). Production code passes
+/// the real implementations, tests pass fakes.
+///
+/// Rust needs no framework for this: a generic struct, or a struct holding
+/// trait objects, is enough.
+use crate::util::TestDir;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn dependency_injection() {}
+
+/// ## Dependencies as Traits
+pub trait Clock {
+    /// Seconds since the Unix epoch.
+    fn now(&self) -> u64;
+}
+
+pub trait Storage {
+    fn save(&mut self, name: &str, content: &str) -> io::Result<()>;
+    fn load(&self, name: &str) -> io::Result<String>;
+}
+
+/// ## Real Implementations
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+}
+
+pub struct FileStorage {
+    pub directory: PathBuf,
+}
+
+impl Storage for FileStorage {
+    fn save(&mut self, name: &str, content: &str) -> io::Result<()> {
+        std::fs::write(self.directory.join(name), content)
+    }
+    fn load(&self, name: &str) -> io::Result<String> {
+        std::fs::read_to_string(self.directory.join(name))
+    }
+}
+
+/// ## The Component
+/// Generic over its dependencies: no runtime cost, and the fakes never end
+/// up in the production binary. The bounds are only on the `impl`, so that
+/// the struct itself stays easy to name (see generics.rs).
+pub struct ReportGenerator<C, S> {
+    clock: C,
+    storage: S,
+}
+
+impl<C: Clock, S: Storage> ReportGenerator<C, S> {
+    pub fn new(clock: C, storage: S) -> ReportGenerator<C, S> {
+        ReportGenerator { clock, storage }
+    }
+
+    /// Write a report of the sales, named after the current time, and
+    /// return its name.
+    pub fn generate(&mut self, sales: &[(&str, u32)]) -> io::Result<String> {
+        let now: u64 = self.clock.now();
+        let name: String = format!("report-{}.txt", now);
+        let total: u32 = sales.iter().map(|(_, amount)| amount).sum();
+        let mut content: String = format!("generated at {}\n", now);
+        for (item, amount) in sales {
+            content += &format!("{}: {}\n", item, amount);
+        }
+        content += &format!("total: {}\n", total);
+        self.storage.save(&name, &content)?;
+        Ok(name)
+    }
+
+    pub fn storage(&self) -> &S { &self.storage }
+}
+
+runnable!(real_dependencies, {
+    let directory: TestDir = TestDir::new("dependency-injection");
+    let storage: FileStorage =
+        FileStorage { directory: directory.path().to_path_buf() };
+    let mut generator = ReportGenerator::new(SystemClock, storage);
+    let name: String = generator.generate(&[("apples", 3)]).unwrap();
+    let report: String = generator.storage().load(&name).unwrap();
+    assert!(report.ends_with("apples: 3\ntotal: 3\n"));
+});
+
+/// ## Trait Objects
+/// When the dependencies are chosen at runtime, or the generic parameters
+/// spread through every type holding a `ReportGenerator`, boxing the
+/// dependencies works too: `Box<dyn Clock>` implements `Clock` through this
+/// forwarding impl, so the same generic struct accepts it.
+impl<T: Clock + ?Sized> Clock for Box<T> {
+    fn now(&self) -> u64 { (**self).now() }
+}
+
+impl<T: Storage + ?Sized> Storage for Box<T> {
+    fn save(&mut self, name: &str, content: &str) -> io::Result<()> {
+        (**self).save(name, content)
+    }
+    fn load(&self, name: &str) -> io::Result<String> { (**self).load(name) }
+}
+
+pub type DynReportGenerator =
+    ReportGenerator<Box<dyn Clock>, Box<dyn Storage>>;
+
+/// ## Fakes
+/// Unit tests replace the clock with a fixed one, and the file system with
+/// a `HashMap`: tests become deterministic, fast, and can simulate failures
+/// which are hard to trigger for real (a full disk). Mocks go further, and
+/// check how the component called its dependencies (see testing/mocks.rs).
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct FixedClock(u64);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> u64 { self.0 }
+    }
+
+    #[derive(Default)]
+    struct MemoryStorage {
+        files: HashMap<String, String>,
+        full: bool,
+    }
+
+    impl Storage for MemoryStorage {
+        fn save(&mut self, name: &str, content: &str) -> io::Result<()> {
+            if self.full {
+                return Err(io::Error::new(io::ErrorKind::Other, "disk full"));
+            }
+            self.files.insert(name.to_string(), content.to_string());
+            Ok(())
+        }
+        fn load(&self, name: &str) -> io::Result<String> {
+            self.files.get(name).cloned()
+                .ok_or(io::Error::from(io::ErrorKind::NotFound))
+        }
+    }
+
+    #[test]
+    fn test_report_content() {
+        let storage: MemoryStorage = MemoryStorage::default();
+        let mut generator = ReportGenerator::new(FixedClock(1_000), storage);
+        let sales: [(&str, u32); 2] = [("apples", 3), ("pears", 4)];
+        assert_eq!(generator.generate(&sales).unwrap(), "report-1000.txt");
+        assert_eq!(
+            generator.storage().load("report-1000.txt").unwrap(),
+            "generated at 1000\napples: 3\npears: 4\ntotal: 7\n"
+        );
+    }
+
+    #[test]
+    fn test_storage_failure() {
+        let storage = MemoryStorage { full: true, ..MemoryStorage::default() };
+        let mut generator = ReportGenerator::new(FixedClock(0), storage);
+        let error: io::Error = generator.generate(&[]).unwrap_err();
+        assert_eq!(error.to_string(), "disk full");
+        assert!(generator.storage().files.is_empty());
+    }
+
+    #[test]
+    fn test_dyn_dependencies() {
+        let mut generator: DynReportGenerator = ReportGenerator::new(
+            Box::new(FixedClock(42)),
+            Box::new(MemoryStorage::default()),
+        );
+        assert_eq!(generator.generate(&[]).unwrap(), "report-42.txt");
+        assert!(generator.storage().load("report-42.txt").is_ok());
+    }
+}
@@ -0,0 +1,179 @@
+/// # Enum State Machines
+/// A state machine is an enum with one variant per state, each holding only
+/// the data meaningful in that state, and a transition function consuming
+/// the current state and an event to produce the next one. Invalid
+/// combinations of data cannot be represented: a machine which is not
+/// dispensing has no change to give back.
+///
+/// Compare with the suspended functions of state_machines.rs, where the
+/// compiler derives the states from the code.
+fn enum_state() {}
+
+pub const PRICE: u32 = 150;
+
+/// ## States and Events
+/// A vending machine selling a single item, at `PRICE` cents.
+/// ```text
+///            Coin               Select (credit >= PRICE)
+///   Idle ----------> Paying ---------------------------> Dispensing
+///    ^  ^             |  ^ Coin                              |
+///    |  '--- Cancel --'  '--'                                |
+///    |                                                 Take  |
+///    '----------------------------(stock left)---------------+
+///                                                            | Take
+///   Empty <----------------------------(no stock left)-------'
+///     '--- Restock ---> Idle
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Machine {
+    Idle { stock: u32 },
+    Paying { stock: u32, credit: u32 },
+    Dispensing { stock: u32, change: u32 },
+    Empty,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Coin(u32),
+    Select,
+    Cancel,
+    Take,
+    Restock(u32),
+}
+
+/// A rejected event hands the state back: `try_step` consumed it.
+#[derive(Debug, PartialEq)]
+pub struct Rejected {
+    pub state: Machine,
+    pub event: Event,
+}
+
+/// ## Exhaustive Transitions
+/// `match` on the pair `(state, event)`. There is no final `_` arm: every
+/// state lists the events it rejects, so adding a state or an event makes
+/// the match non-exhaustive, and the compiler points at the transitions to
+/// decide (see pattern_matching.rs). Guarded arms do not count towards
+/// exhaustiveness, hence the unguarded `Select` arm after the guarded one.
+/// Counters are added with `checked_add`: an event which would overflow
+/// them is rejected, like any other illegal transition.
+impl Machine {
+    pub fn try_step(self, event: Event) -> Result<Machine, Rejected> {
+        use Event::*;
+        use Machine::*;
+        let next: Machine = match (self, event) {
+            (Idle { stock }, Coin(cents)) => Paying { stock, credit: cents },
+            (Idle { stock }, Restock(items)) =>
+                match stock.checked_add(items) {
+                    Some(stock) => Idle { stock },
+                    None => return self.reject(event),
+                },
+            (Idle { .. }, Select | Cancel | Take) => return self.reject(event),
+
+            (Paying { stock, credit }, Coin(cents)) =>
+                match credit.checked_add(cents) {
+                    Some(credit) => Paying { stock, credit },
+                    None => return self.reject(event),
+                },
+            (Paying { stock: stock @ 1.., credit }, Select) if credit >= PRICE =>
+                Dispensing { stock: stock - 1, change: credit - PRICE },
+            (Paying { stock, .. }, Cancel) => Idle { stock },
+            (Paying { .. }, Select | Take | Restock(_)) =>
+                return self.reject(event),
+
+            (Dispensing { stock: 0, .. }, Take) => Empty,
+            (Dispensing { stock, .. }, Take) => Idle { stock },
+            (Dispensing { .. }, Coin(_) | Select | Cancel | Restock(_)) =>
+                return self.reject(event),
+
+            (Empty, Restock(items)) if items > 0 => Idle { stock: items },
+            (Empty, Coin(_) | Select | Cancel | Take | Restock(_)) =>
+                return self.reject(event),
+        };
+        Ok(next)
+    }
+
+    fn reject(self, event: Event) -> Result<Machine, Rejected> {
+        Err(Rejected { state: self, event })
+    }
+
+    /// ## Transitions by Value
+    /// `step(self, ..) -> Self` takes the state by value: the old state
+    /// cannot be used after the transition, and its data moves into the new
+    /// one without cloning. Rejected events leave the state unchanged.
+    pub fn step(self, event: Event) -> Machine {
+        self.try_step(event).unwrap_or_else(|rejected| rejected.state)
+    }
+}
+
+runnable!(a_purchase, {
+    let machine: Machine = Machine::Idle { stock: 1 };
+    let machine: Machine = [Event::Coin(100), Event::Coin(100), Event::Select]
+        .into_iter()
+        .fold(machine, Machine::step);
+    assert_eq!(machine, Machine::Dispensing { stock: 0, change: 50 });
+    assert_eq!(machine.step(Event::Take), Machine::Empty);
+    assert_eq!(Machine::Empty.step(Event::Restock(5)),
+        Machine::Idle { stock: 5 });
+});
+
+/// ## Rejecting Illegal Transitions
+/// Every combination of a sample of states and events is tried: exactly the
+/// ones in the table are accepted, everything else is rejected, with the
+/// state handed back untouched.
+runnable!(rejecting_illegal_transitions, {
+    use Event::*;
+    use Machine::*;
+    let states: [Machine; 7] = [
+        Idle { stock: 2 },
+        Paying { stock: 2, credit: 100 },           // not enough credit
+        Paying { stock: 2, credit: 200 },
+        Dispensing { stock: 1, change: 50 },
+        Empty,
+        Idle { stock: u32::MAX },                   // full: restocks overflow
+        Paying { stock: 2, credit: u32::MAX },      // coins overflow
+    ];
+    let events: [Event; 6] =
+        [Coin(50), Select, Cancel, Take, Restock(3), Restock(0)];
+    let legal: [(Machine, Event, Machine); 17] = [
+        (states[0], Coin(50), Paying { stock: 2, credit: 50 }),
+        (states[0], Restock(3), Idle { stock: 5 }),
+        (states[0], Restock(0), Idle { stock: 2 }),
+        (states[1], Coin(50), Paying { stock: 2, credit: 150 }),
+        (states[1], Cancel, Idle { stock: 2 }),
+        (states[2], Coin(50), Paying { stock: 2, credit: 250 }),
+        (states[2], Select, Dispensing { stock: 1, change: 50 }),
+        (states[2], Cancel, Idle { stock: 2 }),
+        (states[3], Take, Idle { stock: 1 }),
+        (Dispensing { stock: 0, change: 0 }, Take, Empty),
+        (Empty, Restock(3), Idle { stock: 3 }),
+        (Paying { stock: 3, credit: 150 }, Select,
+            Dispensing { stock: 2, change: 0 }),
+        (states[5], Coin(50), Paying { stock: u32::MAX, credit: 50 }),
+        (states[5], Restock(0), Idle { stock: u32::MAX }),
+        (states[6], Select,
+            Dispensing { stock: 1, change: u32::MAX - PRICE }),
+        (states[6], Cancel, Idle { stock: 2 }),
+        (Idle { stock: 2 }, Coin(u32::MAX),
+            Paying { stock: 2, credit: u32::MAX }),
+    ];
+    for (state, event, next) in legal {
+        assert_eq!(state.try_step(event), Ok(next), "{:?} {:?}", state, event);
+    }
+    let paying: Machine = Machine::Idle { stock: 2 }.step(Coin(u32::MAX));
+    assert_eq!(paying.try_step(Coin(1)),
+        Err(Rejected { state: paying, event: Coin(1) }));
+    for state in states {
+        for event in events {
+            let accepted: bool = legal.iter()
+                .any(|(from, on, _)| (*from, *on) == (state, event));
+            match state.try_step(event) {
+                Ok(_) => assert!(accepted, "accepted {:?} {:?}", state, event),
+                Err(rejected) => {
+                    assert!(!accepted, "rejected {:?} {:?}", state, event);
+                    assert_eq!(rejected, Rejected { state, event });
+                    assert_eq!(state.step(event), state);
+                }
+            }
+        }
+    }
+});
@@ -0,0 +1,89 @@
+/// # Extension Traits
+/// Methods cannot be added to a type defined in another crate (e.g. `str` or
+/// `Iterator`), but a local trait can be implemented for it: its methods then
+/// become callable on that type, as if they were its own.
+///
+/// ## Naming Conventions
+/// Extension traits are named after the type or trait they extend, with an
+/// `Ext` suffix (e.g. `StrExt`, `IteratorExt`, `FutureExt`). They are usually
+/// not meant to be implemented by users, but only imported.
+fn extension_traits() {}
+
+/// ## Extending a Type
+/// `StrExt` is implemented for `str` only, adding string utilities.
+pub trait StrExt {
+    /// Prefix every line with `spaces` spaces.
+    fn indent(&self, spaces: usize) -> String;
+    /// Check whether the string reads the same backwards.
+    fn is_palindrome(&self) -> bool;
+}
+
+impl StrExt for str {
+    fn indent(&self, spaces: usize) -> String {
+        let padding: String = " ".repeat(spaces);
+        self.lines()
+            .map(|line| format!("{}{}", padding, line))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+    fn is_palindrome(&self) -> bool {
+        self.chars().eq(self.chars().rev())
+    }
+}
+
+runnable!(extending_a_type, {
+    let code: &str = "fn main() {\n    println!(\"hi\");\n}";
+    println!("{}", code.indent(4));
+    assert_eq!("a\nb".indent(2), "  a\n  b");
+
+    // `String` derefs to `str`, so it gets the methods too
+    let word: String = String::from("racecar");
+    println!("{} is a palindrome: {}", word, word.is_palindrome());
+});
+
+/// ## Extending a Trait
+/// A `blanket implementation` (`impl<I: Iterator> IteratorExt for I`) extends
+/// every type implementing a trait, including types that do not exist yet.
+/// Requiring `Iterator` as a supertrait makes its methods available in the
+/// default methods of the extension.
+pub trait IteratorExt: Iterator {
+    /// Shorthand for `.collect::<Vec<_>>()`.
+    fn collect_vec(self) -> Vec<Self::Item> where Self: Sized {
+        self.collect()
+    }
+    /// Count the items satisfying a predicate.
+    fn count_where<P>(self, predicate: P) -> usize
+    where Self: Sized, P: FnMut(&Self::Item) -> bool {
+        self.filter(predicate).count()
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+runnable!(extending_a_trait, {
+    let squares: Vec<u32> = (1..=4).map(|x| x * x).collect_vec();
+    println!("squares: {:?}", squares);
+    assert_eq!(squares, vec![1, 4, 9, 16]);
+
+    let evens: usize = squares.into_iter().count_where(|x| x % 2 == 0);
+    println!("evens: {}", evens);
+});
+
+/// ## Import Requirements
+/// Trait methods are only callable when the trait is in scope. This keeps
+/// extensions opt-in, but it also means that users must import them (which
+/// is why libraries often re-export them in a `prelude` module).
+mod elsewhere {
+    runnable!(extension_not_imported, {
+        // println!("{}", "text".indent(2));
+        // ^ Error: no method named `indent` found for reference `&str`
+        //   help: trait `StrExt` which provides `indent` is implemented but
+        //         not in scope; perhaps you want to import it
+
+        use super::StrExt;  // import the trait, not the method
+        println!("{}", "text".indent(2));
+
+        // Disambiguation also works without importing (see traits.rs)
+        println!("{:?}", super::IteratorExt::collect_vec("ab".chars()));
+    });
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+/// # Newtype Pattern
+/// A `newtype` is a tuple struct with a single field, wrapping an existing
+/// type into a new, distinct one (see structures.rs). It has no runtime cost:
+/// the wrapper has the same size as the wrapped value.
+///
+/// ## Orphan Rule
+/// A trait can be implemented for a type only if either the trait or the type
+/// is defined in the current crate. This guarantees that two crates can never
+/// provide conflicting implementations of the same trait for the same type.
+/// ```
+/// impl<T: Display> Display for Vec<T> { ... }
+/// // ^ Error: only traits defined in the current crate can be implemented
+/// //          for types defined outside of the crate
+/// ```
+/// Wrapping the foreign type into a local newtype works around the rule,
+/// because the newtype is defined in the current crate.
+use std::fmt::{self, Display};
+use std::ops::Deref;
+
+fn newtype() {}
+
+pub struct Wrapper<T>(Vec<T>);
+
+impl<T: Display> Display for Wrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, item) in self.0.iter().enumerate() {
+            if i > 0 { write!(f, " | ")?; }
+            write!(f, "{}", item)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// ## Conveniences
+/// - `From` lets users create the newtype with `.into()`
+/// - `Deref` exposes the read-only methods of the wrapped type (see
+///   references.rs), so the wrapper can be used almost like a `Vec<T>`
+impl<T> From<Vec<T>> for Wrapper<T> {
+    fn from(vec: Vec<T>) -> Self { Wrapper(vec) }
+}
+impl<T> Deref for Wrapper<T> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Vec<T> { &self.0 }
+}
+
+runnable!(newtype_display, {
+    let wrapper: Wrapper<u8> = vec![1, 2, 3].into();
+    println!("wrapper: {}", wrapper);
+    assert_eq!(wrapper.to_string(), "[1 | 2 | 3]");
+
+    // Methods of `Vec<T>` through `Deref`
+    println!("len: {} first: {:?}", wrapper.len(), wrapper.first());
+    let slice: &[u8] = &wrapper;  // deref coercion: &Wrapper -> &Vec -> &[u8]
+
+    // wrapper.push(4);
+    // ^ Error: cannot borrow data in dereference of `Wrapper<u8>` as mutable
+    //          (`DerefMut` is not implemented)
+});
+
+/// ## Newtypes for Type Safety
+/// Newtypes also prevent mixing up values with the same representation, at
+/// no cost at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Meters(f64);
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Seconds(f64);
+
+fn speed(distance: Meters, time: Seconds) -> f64 { distance.0 / time.0 }
+
+runnable!(newtype_safety, {
+    let distance: Meters = Meters(100.0);
+    let time: Seconds = Seconds(9.58);
+    println!("speed: {:.2} m/s", speed(distance, time));
+    // speed(time, distance);
+    // ^ Error: expected `Meters`, found `Seconds`
+    assert_eq!(std::mem::size_of::<Meters>(), std::mem::size_of::<f64>());
+});
+
+/// ## Costs
+/// - The newtype does not inherit any trait implementation of the wrapped
+///   type: `Debug`, `Clone`, `PartialEq`, ... must be derived or implemented
+///   again (e.g. `Wrapper<T>` above is not `Debug`)
+/// - Mutating methods must be forwarded by hand, or exposed through
+///   `DerefMut`, which gives up any invariant the newtype wanted to protect
+/// - Implementing `Deref` for non-pointer types is often considered an
+///   anti-pattern, because methods appear on the wrapper implicitly
+/// - Users must convert between the newtype and the wrapped type (`From`,
+///   `.0`, `into_inner()`)
+impl<T> Wrapper<T> {
+    fn push(&mut self, item: T) { self.0.push(item); }  // forwarded method
+    fn into_inner(self) -> Vec<T> { self.0 }
+}
+
+runnable!(newtype_costs, {
+    let mut wrapper: Wrapper<&str> = Wrapper(vec!["a"]);
+    wrapper.push("b");
+    // println!("{:?}", wrapper);
+    // ^ Error: `Wrapper<&str>` doesn't implement `Debug`
+    let inner: Vec<&str> = wrapper.into_inner();
+    println!("inner: {:?}", inner);
+});
\ No newline at end of file
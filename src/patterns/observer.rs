@@ -0,0 +1,153 @@
+/// # Observer Pattern
+/// A publisher notifies a list of subscribers when something happens. In a
+/// garbage-collected language, the publisher keeps references to its
+/// subscribers, which often keep a reference to the publisher (to
+/// unsubscribe, or to read its state): a cycle, collected anyway.
+///
+/// In Rust, the same design with `Rc` leaks (see shared_graph.rs), and
+/// needs `RefCell` to mutate subscribers through shared pointers. The usual
+/// fixes:
+/// - the publisher holds `Weak` pointers: subscribers are owned elsewhere,
+///   and unsubscribe by being dropped
+/// - no pointers at all: events are sent through a channel, and subscribers
+///   receive them whenever they want
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+fn observer() {}
+
+pub trait Observer {
+    fn notify(&mut self, event: &str);
+}
+
+#[derive(Default)]
+pub struct Log {
+    pub events: Vec<String>,
+}
+
+impl Observer for Log {
+    fn notify(&mut self, event: &str) { self.events.push(event.to_string()); }
+}
+
+/// ## Naive Observers Create Cycles
+/// A subscriber keeping its publisher alive, while the publisher keeps the
+/// subscriber alive: neither count ever reaches zero.
+struct NaivePublisher {
+    observers: Vec<Rc<RefCell<dyn Observer>>>,
+}
+
+struct Unsubscriber {
+    publisher: Option<Rc<RefCell<NaivePublisher>>>,    // to unsubscribe later
+}
+
+impl Observer for Unsubscriber {
+    fn notify(&mut self, _event: &str) {}
+}
+
+runnable!(naive_observers_create_cycles, {
+    let publisher = Rc::new(RefCell::new(NaivePublisher { observers: vec![] }));
+    let subscriber = Rc::new(RefCell::new(Unsubscriber { publisher: None }));
+    subscriber.borrow_mut().publisher = Some(Rc::clone(&publisher));
+    publisher.borrow_mut().observers.push(subscriber.clone());
+    let weak: Weak<RefCell<NaivePublisher>> = Rc::downgrade(&publisher);
+    drop((publisher, subscriber));
+    assert!(weak.upgrade().is_some());          // leaked: still alive
+});
+
+/// ## Weak Subscribers
+/// The publisher does not own its subscribers: notifying upgrades each
+/// `Weak`, and skips (then forgets) the ones already dropped. Unsized
+/// coercion turns an `Rc<RefCell<Log>>` into an `Rc<RefCell<dyn Observer>>`
+/// (see dyn_internals.rs), before downgrading it.
+#[derive(Default)]
+pub struct Publisher {
+    observers: Vec<Weak<RefCell<dyn Observer>>>,
+}
+
+impl Publisher {
+    pub fn subscribe(&mut self, observer: &Rc<RefCell<dyn Observer>>) {
+        self.observers.push(Rc::downgrade(observer));
+    }
+
+    /// Notify every live subscriber, pruning the dead ones on the way.
+    /// Returns the number of subscribers notified.
+    pub fn publish(&mut self, event: &str) -> usize {
+        self.observers.retain(|observer| match observer.upgrade() {
+            Some(observer) => { observer.borrow_mut().notify(event); true }
+            None => false,
+        });
+        self.observers.len()
+    }
+}
+
+runnable!(weak_subscribers, {
+    let mut publisher: Publisher = Publisher::default();
+    let first: Rc<RefCell<Log>> = Rc::new(RefCell::new(Log::default()));
+    let second: Rc<RefCell<Log>> = Rc::new(RefCell::new(Log::default()));
+    publisher.subscribe(&(first.clone() as Rc<RefCell<dyn Observer>>));
+    publisher.subscribe(&(second.clone() as Rc<RefCell<dyn Observer>>));
+
+    assert_eq!(publisher.publish("started"), 2);
+    drop(second);                               // unsubscribes
+    assert_eq!(publisher.publish("stopped"), 1);
+    assert_eq!(first.borrow().events, ["started", "stopped"]);
+});
+
+/// A subscriber may not publish while being notified: the publisher is
+/// already borrowed (`&mut self`), and the compiler rejects any path back
+/// to it. With `Rc<RefCell<Publisher>>`, the same re-entrance compiles, and
+/// panics at runtime with a `BorrowMutError`.
+///
+/// Likewise, a subscriber notified while borrowed elsewhere panics: keep
+/// borrows of subscribers short, as in shared_graph.rs.
+runnable!(borrowed_subscribers, {
+    let mut publisher: Publisher = Publisher::default();
+    let log: Rc<RefCell<Log>> = Rc::new(RefCell::new(Log::default()));
+    publisher.subscribe(&(log.clone() as Rc<RefCell<dyn Observer>>));
+    let reading = log.borrow();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        publisher.publish("event")
+    }));
+    assert!(result.is_err());                   // already borrowed
+});
+
+/// ## Channels Instead of Pointers
+/// With channels (as in reactor.rs), the publisher only holds
+/// `Sender`s: it owns no subscriber, so no cycle is possible. Subscribers
+/// read events when they want, from any thread, and unsubscribe by dropping
+/// their `Receiver` (sending then fails, and the sender is pruned).
+///
+/// The trade-off: events are delivered later, not during `publish`, and
+/// must be owned values (`String`, not `&str`) since they outlive the call.
+#[derive(Default)]
+pub struct ChannelPublisher {
+    senders: Vec<Sender<String>>,
+}
+
+impl ChannelPublisher {
+    pub fn subscribe(&mut self) -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        self.senders.push(sender);
+        receiver
+    }
+
+    pub fn publish(&mut self, event: &str) -> usize {
+        self.senders.retain(|sender| sender.send(event.to_string()).is_ok());
+        self.senders.len()
+    }
+}
+
+runnable!(channels_instead_of_pointers, {
+    let mut publisher: ChannelPublisher = ChannelPublisher::default();
+    let first: Receiver<String> = publisher.subscribe();
+    let second: Receiver<String> = publisher.subscribe();
+    assert_eq!(publisher.publish("started"), 2);
+    drop(second);
+    assert_eq!(publisher.publish("stopped"), 1);
+
+    let events: Vec<String> = std::thread::spawn(move || {
+        first.try_iter().collect()              // the events queued so far
+    }).join().unwrap();
+    assert_eq!(events, ["started", "stopped"]);
+});
@@ -0,0 +1,200 @@
+/// # Retry with Backoff
+/// Network calls fail transiently: retrying often succeeds. Retrying
+/// immediately, though, hammers a server which is already struggling, and
+/// clients failing together retry together. The usual fix is to wait longer
+/// after each failure (`exponential backoff`), by a random amount
+/// (`jitter`), so that clients spread out.
+///
+/// The retry loop is generic over the operation (any closure returning a
+/// `Result`), and receives its clock and random numbers from the caller, so
+/// that tests never actually sleep (see patterns/dependency_injection.rs).
+use crate::util::Rng;
+use std::time::Duration;
+
+fn retry_lesson() {}
+
+/// ## Policies
+#[derive(Clone, Debug, PartialEq)]
+pub enum Backoff {
+    /// The same delay after every failure.
+    Fixed(Duration),
+    /// `initial`, then twice as long after each failure, up to `max`.
+    Exponential { initial: Duration, max: Duration },
+    /// A random delay between zero and the exponential one (`full jitter`).
+    Jittered { initial: Duration, max: Duration },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Policy {
+    /// The total number of calls, including the first one.
+    pub attempts: u32,
+    pub backoff: Backoff,
+}
+
+impl Backoff {
+    /// The delay after the failure of attempt `attempt` (starting from 0).
+    pub fn delay(&self, attempt: u32, rng: &mut Rng) -> Duration {
+        let exponential = |initial: Duration, max: Duration| {
+            initial.saturating_mul(2u32.saturating_pow(attempt)).min(max)
+        };
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, max } => exponential(initial, max),
+            Backoff::Jittered { initial, max } =>
+                exponential(initial, max).mul_f64(rng.next_f64()),
+        }
+    }
+}
+
+/// ## The Retry Loop
+/// Waiting is a dependency: production code sleeps the thread, tests record
+/// the delays instead.
+pub trait Sleep {
+    fn sleep(&mut self, duration: Duration);
+}
+
+pub struct ThreadSleep;
+
+impl Sleep for ThreadSleep {
+    fn sleep(&mut self, duration: Duration) { std::thread::sleep(duration) }
+}
+
+/// Call `operation` until it succeeds, or until the attempts run out, and
+/// return its last result: the caller sees the last error, not a wrapper.
+pub fn retry_with<T, E>(
+    policy: &Policy,
+    sleep: &mut impl Sleep,
+    rng: &mut Rng,
+    mut operation: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut attempt: u32 = 0;
+    loop {
+        match operation() {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt + 1 >= policy.attempts => return Err(error),
+            Err(_) => {
+                sleep.sleep(policy.backoff.delay(attempt, rng));
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// The convenient entry point, with the real dependencies.
+pub fn retry<T, E>(
+    policy: &Policy,
+    operation: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let seed: u64 = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(1, |time| time.subsec_nanos() as u64);
+    retry_with(policy, &mut ThreadSleep, &mut Rng::new(seed), operation)
+}
+
+/// ## A Flaky Operation
+/// A fake server which fails a given number of times before answering. The
+/// closure borrows it mutably: `FnMut`, not `Fn` (see closures.rs).
+struct FlakyServer {
+    failures_left: u32,
+    calls: u32,
+}
+
+impl FlakyServer {
+    fn get(&mut self) -> Result<&'static str, String> {
+        self.calls += 1;
+        if self.failures_left == 0 { return Ok("200 OK"); }
+        self.failures_left -= 1;
+        Err(format!("503 Service Unavailable (call {})", self.calls))
+    }
+}
+
+runnable!(a_flaky_operation, {
+    let policy: Policy = Policy {
+        attempts: 4,
+        backoff: Backoff::Jittered {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(5),
+        },
+    };
+    let mut server: FlakyServer = FlakyServer { failures_left: 2, calls: 0 };
+    assert_eq!(retry(&policy, || server.get()), Ok("200 OK"));
+    assert_eq!(server.calls, 3);
+
+    let mut server: FlakyServer = FlakyServer { failures_left: 9, calls: 0 };
+    assert_eq!(retry(&policy, || server.get()),
+        Err("503 Service Unavailable (call 4)".to_string()));
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the delays, without waiting.
+    #[derive(Default)]
+    struct FakeSleep(Vec<Duration>);
+
+    impl Sleep for FakeSleep {
+        fn sleep(&mut self, duration: Duration) { self.0.push(duration); }
+    }
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|value| Duration::from_millis(*value)).collect()
+    }
+
+    fn delays(backoff: Backoff, failures: u32) -> Vec<Duration> {
+        let policy: Policy = Policy { attempts: 10, backoff };
+        let mut server = FlakyServer { failures_left: failures, calls: 0 };
+        let mut sleep: FakeSleep = FakeSleep::default();
+        let result = retry_with(&policy, &mut sleep, &mut Rng::new(7), || {
+            server.get()
+        });
+        assert!(result.is_ok());
+        sleep.0
+    }
+
+    #[test]
+    fn test_fixed_backoff() {
+        let backoff = Backoff::Fixed(Duration::from_millis(100));
+        assert_eq!(delays(backoff, 3), millis(&[100, 100, 100]));
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(1000),
+        };
+        let doubling = millis(&[100, 200, 400, 800, 1000, 1000]);
+        assert_eq!(delays(backoff, 6), doubling);
+    }
+
+    #[test]
+    fn test_jittered_backoff() {
+        let backoff = Backoff::Jittered {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(1000),
+        };
+        let jittered: Vec<Duration> = delays(backoff.clone(), 6);
+        let ceilings = millis(&[100, 200, 400, 800, 1000, 1000]);
+        for (delay, ceiling) in jittered.iter().zip(&ceilings) {
+            assert!(delay <= ceiling);
+        }
+        assert_ne!(jittered, ceilings);
+        assert_eq!(jittered, delays(backoff, 6));   // same seed, same delays
+    }
+
+    #[test]
+    fn test_gives_up() {
+        let backoff: Backoff = Backoff::Fixed(Duration::ZERO);
+        let policy: Policy = Policy { attempts: 3, backoff };
+        let mut sleep: FakeSleep = FakeSleep::default();
+        let mut calls: u32 = 0;
+        let result: Result<(), u32> =
+            retry_with(&policy, &mut sleep, &mut Rng::new(1), || {
+                calls += 1;
+                Err(calls)
+            });
+        assert_eq!(result, Err(3));                 // the last error
+        assert_eq!(sleep.0.len(), 2);               // no sleep after the last
+    }
+}
@@ -0,0 +1,55 @@
+/// # Sealed Traits
+/// A public trait can be implemented by anyone. Sometimes a library wants
+/// users to *use* a trait (as a bound, or calling its methods) without being
+/// able to *implement* it, e.g. to add methods later without breaking anyone,
+/// or because the library relies on knowing every implementation.
+///
+/// The standard technique is a `private supertrait`: the public trait requires
+/// a trait that lives in a private module. Downstream crates can name the
+/// public trait, but not the supertrait, so they cannot implement either.
+///
+/// The traits and the shapes of this lesson live in the library target of
+/// the playground (see src/lib.rs): this binary is a downstream crate, like
+/// any user of the library, and the doctests of the library check the seal
+/// (see compile_errors.rs).
+/// ```text
+/// struct Triangle;
+/// impl Shape for Triangle { fn area(&self) -> f64 { 0.0 } }
+/// // ^ Error: the trait bound `Triangle: Sealed` is not satisfied
+/// ```
+use rust_plauground::{Circle, Shape, Square};
+
+fn sealed() {}
+
+/// ## Using a Sealed Trait
+/// Sealing does not restrict users: the trait can still be used in bounds,
+/// as a trait object and for calling its methods.
+fn total_area(shapes: &[&dyn Shape]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+runnable!(sealed_traits, {
+    let square: Square = Square(2.0);
+    let circle: Circle = Circle(1.0);
+    println!("{}", square.describe());
+    println!("{}", circle.describe());
+    println!("total: {:.2}", total_area(&[&square, &circle]));
+});
+
+/// ## Partially Sealed Traits
+/// A variant of the technique seals a single method instead of the whole
+/// trait, by giving it a parameter of a type that cannot be named outside
+/// the crate. Users can implement the trait, but not override that method.
+mod token { pub struct Token; }
+
+pub trait Plugin {
+    fn name(&self) -> &str;
+    /// Only callable (and overridable) from inside this crate
+    fn internal_id(&self, _: token::Token) -> u64 { self.name().len() as u64 }
+}
+
+runnable!(partially_sealed_traits, {
+    struct Logger;
+    impl Plugin for Logger { fn name(&self) -> &str { "logger" } }
+    println!("id: {}", Logger.internal_id(token::Token));
+});
\ No newline at end of file
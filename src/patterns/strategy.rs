@@ -0,0 +1,152 @@
+/// # Strategy Pattern
+/// A strategy is a piece of behaviour chosen by the user of a component:
+/// here, the order in which a sorter sorts. Rust offers three ways to pass
+/// behaviour around, with different costs:
+/// - a generic parameter `S: Strategy`: resolved at compile time
+/// - a trait object `Box<dyn Strategy>`: resolved at runtime
+/// - a closure `Fn(&u32, &u32) -> Ordering`: no trait to declare at all
+use std::cmp::Ordering;
+
+fn strategy() {}
+
+pub trait Strategy {
+    fn compare(&self, a: &u32, b: &u32) -> Ordering;
+}
+
+pub struct Ascending;
+pub struct Descending;
+/// A stateful strategy: numbers closest to a target first.
+pub struct ClosestTo(pub u32);
+
+impl Strategy for Ascending {
+    fn compare(&self, a: &u32, b: &u32) -> Ordering { a.cmp(b) }
+}
+impl Strategy for Descending {
+    fn compare(&self, a: &u32, b: &u32) -> Ordering { b.cmp(a) }
+}
+impl Strategy for ClosestTo {
+    fn compare(&self, a: &u32, b: &u32) -> Ordering {
+        a.abs_diff(self.0).cmp(&b.abs_diff(self.0)).then(a.cmp(b))
+    }
+}
+
+/// ## Generic Strategy
+/// The compiler generates one copy of `sort` per strategy type
+/// (monomorphization, see generics.rs): each copy calls `compare` directly,
+/// and can inline it into the sorting loop. The strategy is part of the
+/// type: `Sorter<Ascending>` and `Sorter<Descending>` are different types,
+/// which cannot be stored in the same `Vec`, nor swapped at runtime.
+pub struct Sorter<S: Strategy> {
+    pub strategy: S,
+}
+
+impl<S: Strategy> Sorter<S> {
+    pub fn sort(&self, values: &mut [u32]) {
+        values.sort_by(|a, b| self.strategy.compare(a, b));
+    }
+}
+
+/// ## Trait Object Strategy
+/// A single copy of `sort`, calling `compare` through the vtable (see
+/// dyn_internals.rs): an indirect call per comparison, which cannot be
+/// inlined. In exchange, the strategy can be chosen at runtime, e.g. from a
+/// configuration file, and sorters with different strategies share a type.
+pub struct DynSorter {
+    pub strategy: Box<dyn Strategy>,
+}
+
+impl DynSorter {
+    pub fn sort(&self, values: &mut [u32]) {
+        values.sort_by(|a, b| self.strategy.compare(a, b));
+    }
+
+    pub fn from_config(name: &str) -> Option<DynSorter> {
+        let strategy: Box<dyn Strategy> = match name {
+            "ascending" => Box::new(Ascending),
+            "descending" => Box::new(Descending),
+            _ => return None,
+        };
+        Some(DynSorter { strategy })
+    }
+}
+
+/// ## Closure Strategy
+/// A one-method trait is just a function signature: closures need no
+/// declaration, and capture their state (the target of `ClosestTo`) from
+/// their environment (see closures.rs). `impl Fn` is generic, like `S`;
+/// `Box<dyn Fn>` would be dynamic, like `Box<dyn Strategy>`.
+pub fn sort_with(
+    values: &mut [u32],
+    compare: impl Fn(&u32, &u32) -> Ordering,
+) {
+    values.sort_by(compare);
+}
+
+runnable!(three_strategies, {
+    let numbers: [u32; 6] = [5, 1, 9, 3, 7, 4];
+    let sorted = |sort: &dyn Fn(&mut [u32])| {
+        let mut values: [u32; 6] = numbers;
+        sort(&mut values);
+        values
+    };
+
+    let by_generic = sorted(&|values| {
+        Sorter { strategy: ClosestTo(6) }.sort(values)
+    });
+    let by_dyn = sorted(&|values| {
+        DynSorter { strategy: Box::new(ClosestTo(6)) }.sort(values)
+    });
+    let target: u32 = 6;
+    let by_closure = sorted(&|values| sort_with(values, |a, b| {
+        a.abs_diff(target).cmp(&b.abs_diff(target)).then(a.cmp(b))
+    }));
+    assert_eq!(by_generic, [5, 7, 4, 3, 9, 1]);
+    assert_eq!(by_generic, by_dyn);
+    assert_eq!(by_generic, by_closure);
+
+    // Only trait objects can be chosen at runtime, and mixed in a `Vec`
+    let sorters: Vec<DynSorter> = ["ascending", "descending"].iter()
+        .filter_map(|name| DynSorter::from_config(name))
+        .collect();
+    assert_eq!(sorted(&|values| sorters[1].sort(values)), [9, 7, 5, 4, 3, 1]);
+    assert!(DynSorter::from_config("random").is_none());
+    // let sorters = vec![Sorter { strategy: Ascending },
+    //                    Sorter { strategy: Descending }];
+    // ^ Error: mismatched types: expected `Sorter<Ascending>`, found
+    //          `Sorter<Descending>`
+});
+
+/// ## Speed and Code Size
+/// - generics and `impl Fn` are usually the fastest: the comparison is
+///   inlined into the sort. But each strategy adds a full copy of the
+///   sorting code to the binary: with many strategies (or many generic
+///   functions), compile times and binary size grow
+/// - `dyn` has one copy of the code, whatever the number of strategies,
+///   and pays an indirect call per comparison (a few nanoseconds, more when
+///   it prevents vectorization)
+///
+/// A common compromise: a generic public API forwarding to a non-generic
+/// inner function, where most of the code lives. Measured without
+/// optimizations (see util.rs), the gap is smaller than in a release build.
+runnable!(speed_and_code_size, {
+    let numbers: Vec<u32> =
+        (0..10_000u32).map(|i| i.wrapping_mul(2_654_435_761) % 1000).collect();
+    let generic = benchmark!("generic", 20, {
+        let mut values: Vec<u32> = numbers.clone();
+        Sorter { strategy: Ascending }.sort(&mut values);
+        values
+    });
+    let dynamic = benchmark!("dyn", 20, {
+        let mut values: Vec<u32> = numbers.clone();
+        DynSorter { strategy: Box::new(Ascending) }.sort(&mut values);
+        values
+    });
+    let closure = benchmark!("closure", 20, {
+        let mut values: Vec<u32> = numbers.clone();
+        sort_with(&mut values, u32::cmp);
+        values
+    });
+    println!("dyn: {:.2}x generic, closure: {:.2}x generic",
+        dynamic.as_secs_f64() / generic.as_secs_f64().max(1e-9),
+        closure.as_secs_f64() / generic.as_secs_f64().max(1e-9));
+});
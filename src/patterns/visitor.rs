@@ -0,0 +1,257 @@
+/// # Visitor Pattern
+/// In object-oriented languages, a visitor adds operations to a class
+/// hierarchy without modifying its classes: each class has an `accept`
+/// method calling back the right `visit` method of the visitor (`double
+/// dispatch`). In Rust, the hierarchy is usually an enum, and a `match`
+/// already dispatches on the variant: the pattern splits into
+/// - a walker: the traversal is written once, and visitors only provide
+///   hooks for the nodes they care about (as in `syn::visit`)
+/// - a fold: each operation is a bottom-up function from children results
+///   to a parent result, driven by a single `match`
+///
+/// Both are shown here on the expression tree of parser.rs.
+use crate::parser::{parse, BinaryOp, Expr};
+use std::collections::BTreeSet;
+
+fn visitor() {}
+
+/// ## Walker with Trait Objects
+/// Every hook has an empty default: a visitor implements only the ones it
+/// needs. The walker recurses, and calls the hooks in pre-order.
+pub trait Visit {
+    fn number(&mut self, _value: i64) {}
+    fn variable(&mut self, _name: &str) {}
+    fn negate(&mut self) {}
+    fn binary(&mut self, _op: BinaryOp) {}
+}
+
+/// The walker takes `&mut dyn Visit`: a single compiled copy serves every
+/// visitor, and visitors can be chosen at runtime (see dyn_internals.rs).
+pub fn walk(expr: &Expr, visitor: &mut dyn Visit) {
+    match expr {
+        Expr::Number(value) => visitor.number(*value),
+        Expr::Variable(name) => visitor.variable(name),
+        Expr::Negate(operand) => {
+            visitor.negate();
+            walk(operand, visitor);
+        }
+        Expr::Binary(left, op, right) => {
+            visitor.binary(*op);
+            walk(left, visitor);
+            walk(right, visitor);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Variables(BTreeSet<String>);
+
+impl Visit for Variables {
+    fn variable(&mut self, name: &str) { self.0.insert(name.to_string()); }
+}
+
+#[derive(Default)]
+struct OperatorCount(usize);
+
+impl Visit for OperatorCount {
+    fn negate(&mut self) { self.0 += 1; }
+    fn binary(&mut self, _op: BinaryOp) { self.0 += 1; }
+}
+
+/// Several visitors can share one traversal, behind a visitor forwarding
+/// every hook to a list of trait objects.
+struct Many<'a>(Vec<&'a mut dyn Visit>);
+
+impl Visit for Many<'_> {
+    fn number(&mut self, value: i64) {
+        self.0.iter_mut().for_each(|visitor| visitor.number(value));
+    }
+    fn variable(&mut self, name: &str) {
+        self.0.iter_mut().for_each(|visitor| visitor.variable(name));
+    }
+    fn negate(&mut self) {
+        self.0.iter_mut().for_each(|visitor| visitor.negate());
+    }
+    fn binary(&mut self, op: BinaryOp) {
+        self.0.iter_mut().for_each(|visitor| visitor.binary(op));
+    }
+}
+
+runnable!(walker_with_trait_objects, {
+    let expr: Expr = parse("x * (y - x) + -z / 2").unwrap();
+    let mut variables: Variables = Variables::default();
+    walk(&expr, &mut variables);
+    assert_eq!(variables.0.into_iter().collect::<Vec<_>>(), ["x", "y", "z"]);
+
+    let mut variables: Variables = Variables::default();
+    let mut operators: OperatorCount = OperatorCount::default();
+    walk(&expr, &mut Many(vec![&mut variables, &mut operators]));
+    assert_eq!((variables.0.len(), operators.0), (3, 5));
+});
+
+/// ## Fold with Match
+/// A fold computes a value for each node from the values of its children:
+/// the recursion, and the single `match`, live in `fold`. The associated
+/// type lets each operation choose its result: a number, a `Result`, a
+/// `String`, a new tree...
+pub trait Fold {
+    type Output;
+    fn number(&mut self, value: i64) -> Self::Output;
+    fn variable(&mut self, name: &str) -> Self::Output;
+    fn negate(&mut self, operand: Self::Output) -> Self::Output;
+    fn binary(&mut self, left: Self::Output, op: BinaryOp, right: Self::Output)
+        -> Self::Output;
+}
+
+/// Generic over the folder: monomorphized, so every call is static (see
+/// generics.rs). A fold returning values cannot go behind `dyn` anyway,
+/// unless its `Output` is fixed (see object_safety.rs).
+pub fn fold<F: Fold>(expr: &Expr, folder: &mut F) -> F::Output {
+    match expr {
+        Expr::Number(value) => folder.number(*value),
+        Expr::Variable(name) => folder.variable(name),
+        Expr::Negate(operand) => {
+            let operand: F::Output = fold(operand, folder);
+            folder.negate(operand)
+        }
+        Expr::Binary(left, op, right) => {
+            let left: F::Output = fold(left, folder);
+            let right: F::Output = fold(right, folder);
+            folder.binary(left, *op, right)
+        }
+    }
+}
+
+/// Evaluation, with the values of the variables. Errors propagate from the
+/// children with `?`, and overflows are errors rather than panics (see
+/// primitives.rs).
+struct Evaluator<'a> { variables: &'a [(&'a str, i64)] }
+
+impl Fold for Evaluator<'_> {
+    type Output = Result<i64, String>;
+    fn number(&mut self, value: i64) -> Result<i64, String> { Ok(value) }
+    fn variable(&mut self, name: &str) -> Result<i64, String> {
+        self.variables.iter()
+            .find(|(variable, _)| *variable == name)
+            .map(|(_, value)| *value)
+            .ok_or(format!("unknown variable `{}`", name))
+    }
+    fn negate(&mut self, operand: Result<i64, String>) -> Result<i64, String> {
+        operand?.checked_neg().ok_or("overflow".to_string())
+    }
+    fn binary(
+        &mut self,
+        left: Result<i64, String>,
+        op: BinaryOp,
+        right: Result<i64, String>,
+    ) -> Result<i64, String> {
+        let (left, right): (i64, i64) = (left?, right?);
+        let result: Option<i64> = match op {
+            BinaryOp::Add => left.checked_add(right),
+            BinaryOp::Sub => left.checked_sub(right),
+            BinaryOp::Mul => left.checked_mul(right),
+            BinaryOp::Div if right == 0 => {
+                return Err("division by zero".to_string());
+            }
+            BinaryOp::Div => left.checked_div(right),
+        };
+        result.ok_or("overflow".to_string())
+    }
+}
+
+/// Printing, fully parenthesized, so that a parsed tree, once printed,
+/// parses back into the same tree.
+pub struct Printer;
+
+impl Fold for Printer {
+    type Output = String;
+    fn number(&mut self, value: i64) -> String { value.to_string() }
+    fn variable(&mut self, name: &str) -> String { name.to_string() }
+    fn negate(&mut self, operand: String) -> String { format!("-{}", operand) }
+    fn binary(&mut self, left: String, op: BinaryOp, right: String) -> String {
+        let symbol: char = match op {
+            BinaryOp::Add => '+',
+            BinaryOp::Sub => '-',
+            BinaryOp::Mul => '*',
+            BinaryOp::Div => '/',
+        };
+        format!("({} {} {})", left, symbol, right)
+    }
+}
+
+runnable!(fold_with_match, {
+    let expr: Expr = parse("x * (y - x) + -z / 2").unwrap();
+    let variables: [(&str, i64); 3] = [("x", 3), ("y", 5), ("z", 4)];
+    let mut evaluator = Evaluator { variables: &variables };
+    assert_eq!(fold(&expr, &mut evaluator), Ok(3 * (5 - 3) + -4 / 2));
+    let mut evaluator = Evaluator { variables: &variables[..1] };
+    assert_eq!(fold(&expr, &mut evaluator), Err("unknown variable `y`".into()));
+
+    let printed: String = fold(&expr, &mut Printer);
+    assert_eq!(printed, "((x * (y - x)) + (-z / 2))");
+    assert_eq!(parse(&printed), Ok(expr));
+});
+
+/// ## Plain Functions
+/// Often, no trait is needed at all: an operation is a recursive function
+/// with a `match`, and can return whatever shape it needs. Constant folding
+/// rebuilds the tree, replacing the operations on constants by their result.
+/// Like the `Evaluator`, it checks for overflows (`-i64::MIN` does not fit
+/// in an `i64`), and leaves the operations which overflow for runtime.
+fn simplify(expr: &Expr) -> Expr {
+    match expr {
+        Expr::Negate(operand) => match simplify(operand) {
+            Expr::Number(value) => match value.checked_neg() {
+                Some(negated) => Expr::Number(negated),
+                None => Expr::Negate(Box::new(Expr::Number(value))),
+            },
+            operand => Expr::Negate(Box::new(operand)),
+        },
+        Expr::Binary(left, op, right) => {
+            match (simplify(left), *op, simplify(right)) {
+                (Expr::Number(0), BinaryOp::Add, other)
+                | (other, BinaryOp::Add | BinaryOp::Sub, Expr::Number(0))
+                | (Expr::Number(1), BinaryOp::Mul, other)
+                | (other, BinaryOp::Mul | BinaryOp::Div, Expr::Number(1)) => {
+                    other
+                }
+                (left, op, right) => {
+                    let folded: Expr = Expr::binary(left, op, right);
+                    match fold(&folded, &mut Evaluator { variables: &[] }) {
+                        Ok(value) => Expr::Number(value),
+                        Err(_) => folded,
+                    }
+                }
+            }
+        }
+        leaf => leaf.clone(),
+    }
+}
+
+runnable!(plain_functions, {
+    let simplified = |input: &str| {
+        fold(&simplify(&parse(input).unwrap()), &mut Printer)
+    };
+    assert_eq!(simplified("2 * 3 + x"), "(6 + x)");
+    assert_eq!(simplified("(x + 0) * (4 - 3)"), "x");
+    assert_eq!(simplified("-(1 + 1) * y"), "(-2 * y)");
+    assert_eq!(simplified("1 / 0"), "(1 / 0)");       // left for runtime
+    assert_eq!(simplified("-(0 - 9223372036854775807 - 1)"),
+        "--9223372036854775808");                      // also left
+});
+
+/// ## Extensibility Trade-Offs (the Expression Problem)
+/// - a new operation (type checking, compiling...) is a new `Visit` or
+///   `Fold` implementation, or a new function: `Expr` and the existing
+///   operations are untouched
+/// - a new variant (e.g. `Expr::Call`) must be handled everywhere. With a
+///   `match`, the compiler lists every place to update (non-exhaustive
+///   patterns), in `walk`, `fold`, `simplify`. Hooks with empty defaults
+///   are convenient, but a visitor which forgets a new hook still compiles,
+///   and silently ignores the new nodes
+///
+/// Object-oriented hierarchies have the opposite trade-off: a new class is
+/// local, a new operation touches every class. Trait objects give Rust that
+/// side too (see traits.rs): a `trait Node` implemented by one struct per
+/// kind of node makes new kinds cheap and new operations expensive.
+fn extensibility_trade_offs() {}
@@ -0,0 +1,170 @@
+/// # Pinning
+/// In Rust, any value can be moved to a different memory location (e.g. when
+/// it is passed by value or returned). Usually this is fine, but some values
+/// contain pointers into themselves (`self-referential` values): moving them
+/// would leave those pointers dangling.
+///
+/// The futures generated by `async fn` are often self-referential, because a
+/// borrow of a local variable can be kept across an `.await`. For this reason,
+/// `Future::poll` takes `self: Pin<&mut Self>`: a pointer guaranteeing that the
+/// future will never be moved again (see async_await.rs).
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+fn pinning() {}
+
+/// ## The Self-Referential Problem
+/// `SelfRef` stores a pointer to its own `value` field. After a move, the
+/// field is at a new address, but the pointer still points to the old one.
+///
+/// Note: here we only compare addresses. Dereferencing the stale pointer
+///       would be undefined behavior.
+struct SelfRef {
+    value: String,
+    pointer_to_value: *const String,
+}
+
+impl SelfRef {
+    fn new(value: &str) -> SelfRef {
+        SelfRef {
+            value: String::from(value),
+            pointer_to_value: std::ptr::null(),
+        }
+    }
+    fn init(&mut self) { self.pointer_to_value = &self.value; }
+    fn is_consistent(&self) -> bool {
+        std::ptr::eq(self.pointer_to_value, &self.value)
+    }
+}
+
+runnable!(self_referential_problem, {
+    let mut a: SelfRef = SelfRef::new("a");
+    a.init();
+    assert!(a.is_consistent());
+
+    let moved: Box<SelfRef> = Box::new(a);  // move `a` into the heap
+    println!("consistent after move: {}", moved.is_consistent());
+    assert!(!moved.is_consistent());
+    // ^ `pointer_to_value` still points to where `a` was on the stack
+});
+
+/// ## Unpin
+/// `Unpin` is an `auto trait`: the compiler implements it for every type
+/// whose fields are all `Unpin`, which is almost every type. For an `Unpin`
+/// type, pinning has no effect: it can be freely moved in and out of a `Pin`.
+///
+/// To opt out of `Unpin`, a type can contain a `PhantomPinned` marker. Once
+/// such a type is pinned, safe code can never move it again.
+struct Pinned {
+    value: String,
+    pointer_to_value: *const String,
+    _pinned: PhantomPinned,
+}
+
+impl Pinned {
+    /// ### Pin<Box<T>>
+    /// `Box::pin` allocates the value in the heap and pins it there. The
+    /// address of the value is stable, so the self-reference can be set up
+    /// after pinning, and will stay valid for the lifetime of the box.
+    fn new(value: &str) -> Pin<Box<Pinned>> {
+        let mut pinned: Pin<Box<Pinned>> = Box::pin(Pinned {
+            value: String::from(value),
+            pointer_to_value: std::ptr::null(),
+            _pinned: PhantomPinned,
+        });
+        // SAFETY: we only write a field; the value is never moved out
+        let this: &mut Pinned = unsafe { pinned.as_mut().get_unchecked_mut() };
+        this.pointer_to_value = &this.value;
+        pinned
+    }
+    fn value(self: Pin<&Self>) -> &str {
+        // SAFETY: the value is pinned, so `pointer_to_value` is still valid
+        unsafe { &*self.pointer_to_value }
+    }
+}
+
+runnable!(pin_box, {
+    let pinned: Pin<Box<Pinned>> = Pinned::new("pinned");
+    println!("value: {}", pinned.as_ref().value());
+
+    // let unpinned: Pinned = *Pin::into_inner(pinned);
+    // ^ Error: `PhantomPinned` cannot be unpinned (`Pinned` is not `Unpin`)
+
+    // Moving the `Pin<Box<_>>` moves the pointer, not the pinned value
+    let moved: Pin<Box<Pinned>> = pinned;
+    println!("value after move: {}", moved.as_ref().value());
+});
+
+/// ## Pin<&mut T>
+/// Values can also be pinned on the stack with the `std::pin::pin!` macro. The
+/// macro takes ownership of the value, so the original variable cannot be
+/// used (and moved) anymore.
+runnable!(pin_mut, {
+    fn print_len(value: Pin<&mut String>) { println!("len: {}", value.len()); }
+
+    let pinned: Pin<&mut String> = std::pin::pin!(String::from("stack"));
+    print_len(pinned);
+
+    /// For `Unpin` types, `Pin::new` is safe and `Pin::get_mut` gives back a
+    /// plain `&mut T`: pinning an `Unpin` type does not restrict anything.
+    let mut string: String = String::from("unpin");
+    let mut pinned: Pin<&mut String> = Pin::new(&mut string);
+    pinned.push('!');
+    let unpinned: &mut String = Pin::get_mut(pinned);
+    std::mem::take(unpinned);  // moving out is fine: `String` is `Unpin`
+
+    // let pinned: Pin<&mut Pinned> = Pin::new(&mut not_unpin);
+    // ^ Error: `Pin::new` requires `Pinned: Unpin`
+});
+
+/// ## Pin Projection
+/// Given a `Pin<&mut Wrapper>`, we often need to access its fields. Each field
+/// must be either:
+/// - `structurally pinned`: accessed as `Pin<&mut Field>`; the field must
+///   never be moved out (e.g. an inner future being polled)
+/// - `not structurally pinned`: accessed as a plain `&mut Field`; the field
+///   can be moved freely (e.g. a counter)
+///
+/// Crates like `pin-project` generate this code. By hand, it requires unsafe
+/// code with the following invariants:
+/// 1. a pinned field is never moved out of a `&mut Wrapper`
+/// 2. `Wrapper` does not implement `Drop` moving its pinned fields
+/// 3. `Wrapper` is `Unpin` only if its pinned fields are `Unpin` (this is
+///    what the auto trait does by default)
+/// 4. `Wrapper` is not `repr(packed)`, which may move fields to align them
+struct Counted<F> {
+    inner: F,      // structurally pinned
+    polls: usize,  // not structurally pinned
+}
+
+impl<F> Counted<F> {
+    fn project(self: Pin<&mut Self>) -> (Pin<&mut F>, &mut usize) {
+        // SAFETY: `inner` is never moved out of `Counted` (invariants 1-4)
+        unsafe {
+            let this: &mut Counted<F> = self.get_unchecked_mut();
+            (Pin::new_unchecked(&mut this.inner), &mut this.polls)
+        }
+    }
+}
+
+impl<F: std::future::Future> std::future::Future for Counted<F> {
+    type Output = (F::Output, usize);
+    fn poll(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context,
+    ) -> std::task::Poll<Self::Output> {
+        let (inner, polls) = self.project();
+        *polls += 1;
+        inner.poll(cx).map(|output| (output, *polls))
+    }
+}
+
+runnable!(pin_projection, {
+    use crate::async_await::block_on;
+    use crate::futures::yield_now;
+
+    let future = async { yield_now().await; yield_now().await; "done" };
+    let (output, polls) = block_on(Counted { inner: future, polls: 0 });
+    println!("output: {} polls: {}", output, polls);
+    assert_eq!(polls, 3);
+});
\ No newline at end of file
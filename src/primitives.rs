@@ -39,7 +39,7 @@ const FALSE: bool = false;
 const UNIT: () = ();
 
 /// ## Nothing
-/// The bottom type
+/// The bottom type (see never_type.rs)
 fn nothing() -> ! { panic!("An exception has been thrown!") }
 fn nothing_1() -> ! { loop {} }
 fn nothing_2() -> ! { std::process::exit(0) }
@@ -0,0 +1,266 @@
+/// # Procedural Macros
+/// Declarative macros (see macros.rs) transform tokens by pattern matching.
+/// `Procedural macros` are instead Rust functions, run by the compiler, that
+/// take a stream of tokens and return a new one: they can parse and generate
+/// any code, with the full power of the language.
+///
+/// There are three kinds of procedural macros:
+/// - `custom derives`: `#[derive(MyTrait)]`, adding items after a type
+/// - `attribute macros`: `#[my_attribute]`, replacing the item they annotate
+/// - `function-like macros`: `my_macro!(...)`, replacing the invocation
+///
+/// They must be defined in a separate crate of type `proc-macro`: in this
+/// playground, the workspace member `playground-derive` (see Cargo.toml).
+/// Their syntax trees are usually parsed with the crate `syn` and generated
+/// with the crate `quote`.
+use playground_derive::{csv_row, timed, Describe, Error};
+
+fn proc_macros() {}
+
+/// ## Custom Derives
+/// `#[derive(Describe)]` (see playground-derive/src/lib.rs) reads the fields
+/// of a struct and generates an `impl` block with `field_names()` and
+/// `describe()`.
+#[derive(Describe)]
+struct User {
+    id: u64,
+    name: String,
+    emails: Vec<String>,
+}
+
+/// The macro expands (`cargo expand` shows the expansion) to:
+/// ```
+/// impl User {
+///     pub fn field_names() -> &'static [&'static str] {
+///         &["id", "name", "emails"]
+///     }
+///     pub fn describe() -> &'static str {
+///         "User { id: u64, name: String, emails: Vec<String> }"
+///     }
+/// }
+/// ```
+runnable!(custom_derives, {
+    println!("{}", User::describe());
+    println!("fields: {:?}", User::field_names());
+    assert_eq!(User::field_names(), ["id", "name", "emails"]);
+});
+
+/// ### Tuple, Unit and Generic Structs
+/// The macro handles every kind of struct, forwarding the generic parameters
+/// of the type to the generated `impl` block.
+#[derive(Describe)]
+struct Pair<T: Clone>(T, T);
+#[derive(Describe)]
+struct Marker;
+#[derive(Describe)]
+struct Borrowed<'a> { text: &'a str }
+
+runnable!(other_structs, {
+    println!("{}", Pair::<u8>::describe());
+    println!("{}", Marker::describe());
+    assert_eq!(Marker::field_names(), [] as [&str; 0]);
+    println!("{}", Borrowed::describe());
+    assert_eq!(Pair::<u8>::field_names(), ["0", "1"]);
+});
+
+/// ## Errors
+/// A procedural macro reports errors by generating a `compile_error!`
+/// invocation, attached to the span (the source location) of the offending
+/// tokens, so that the compiler points at the right place.
+// #[derive(Describe)]
+// enum Shape { Circle, Square }
+//      ^^^^^ Error: `Describe` can only be derived for structs
+
+/// ## Attribute Macros
+/// `#[timed]` (see playground-derive/src/lib.rs) replaces the function it
+/// annotates with a copy whose body is wrapped in the timing logic of
+/// `runnable!` (see util.rs). The expansion of `timed_sum` looks like:
+/// ```
+/// fn timed_sum(values: &[u64]) -> u64 {
+///     let function_name = "timed_sum";
+///     println!("{} [start]", function_name);
+///     let start_time = ::std::time::Instant::now();
+///     let result = (move || { values.iter().sum() })();
+///     println!("{} [end]: took {} ms...", ...);
+///     result
+/// }
+/// ```
+#[timed]
+fn timed_sum(values: &[u64]) -> u64 {
+    values.iter().sum()
+}
+
+#[timed]
+fn timed_search(values: &[u64], target: u64) -> Option<usize> {
+    for (i, &value) in values.iter().enumerate() {
+        if value == target { return Some(i); }  // still timed
+    }
+    None
+}
+
+/// Attributes compose: `#[test]` is kept on the generated function, so
+/// `#[timed]` alone can replace `runnable!`.
+#[test]
+#[timed]
+fn attribute_macros() {
+    let values: Vec<u64> = (1..=1_000).collect();
+    println!("sum: {}", timed_sum(&values));
+    println!("position of 500: {:?}", timed_search(&values, 500));
+}
+
+// #[timed(millis)]
+// fn with_arguments() {}
+// ^ Error: `timed` does not take any argument
+
+/// ### Attribute Macros vs Declarative Macros
+/// `runnable!(name, { ... })` and `#[test] #[timed] fn name() { ... }` produce
+/// the same test, but:
+/// - `#[timed]` keeps the function written as a regular function: arguments,
+///   return types, visibility and doc comments all work, and editors can
+///   analyze the body as usual, while `runnable!` only supports tests without
+///   arguments nor return values
+/// - `runnable!` needs no separate crate and no dependency (`syn` and `quote`
+///   slow down the first build), and it is expanded without running code
+/// - Declarative macros can only match the tokens they are given, while
+///   procedural macros can inspect them (e.g. `#[timed]` reads the name of
+///   the function, where `runnable!` needs it as an argument)
+
+/// ## Function-Like Macros
+/// `csv_row!` (see playground-derive/src/lib.rs) parses a CSV row while
+/// compiling, and expands to a tuple whose types depend on the content of
+/// the row: the parsing costs nothing at runtime, and the compiler checks
+/// how the values are used.
+runnable!(function_like_macros, {
+    let (id, price, available, name) = csv_row!("42, 9.99, true, keyboard");
+    // ^ Expanded to: (42i64, 9.99f64, true, "keyboard",)
+    println!("#{} {} costs {} (available: {})", id, name, price, available);
+
+    let total: f64 = price * id as f64;
+    println!("total: {:.2}", total);
+
+    let single: (&str,) = csv_row!("alone");
+    println!("single: {:?}", single);
+});
+
+/// ### Compile-Time Errors
+/// Malformed input never reaches runtime: the macro turns it into compile
+/// errors, pointing at the offending tokens.
+// csv_row!(42);
+//          ^^ Error: expected string literal
+// csv_row!("1,,3");
+//          ^^^^^^ Error: empty cell in column 2
+// csv_row!("1, nan, 1e400");
+//          ^^^^^^^^^^^^^^^ Error: not a finite number in column 2
+// let (id, name): (u8, &str) = csv_row!("42, keyboard");
+//                              ^^^^^^^^^^^^^^^^^^^^^^^^ Error: mismatched
+//                              types: expected `u8`, found `i64`
+fn compile_time_errors() {}
+
+/// ## Hygiene and Paths
+/// Unlike declarative macros, procedural macros are unhygienic by default:
+/// the generated code is resolved as if it was written at the call site.
+/// Generated code should therefore use absolute paths (e.g. `::std::...`) to
+/// avoid picking up local items with the same names.
+fn hygiene() {}
+
+/// ## Derived Errors
+/// `#[derive(Error)]` (see playground-derive/src/lib.rs) generates the
+/// `Display`, `Error` and `From` impls written by hand in errors.rs, from
+/// attributes: `#[error("...")]` gives the message of each variant, where
+/// `{0}` or `{name}` refers to a field, and `#[from]` marks a field as the
+/// source of the error, convertible with `?`.
+#[derive(Debug, Error)]
+enum DownloadError {
+    #[error("invalid url `{0}`")]
+    InvalidUrl(String),
+    #[error("server answered {status}: {reason}")]
+    Http { status: u16, reason: String },
+    #[error("timed out")]
+    Timeout,
+    #[error("invalid content length")]
+    Length(#[from] std::num::ParseIntError),
+}
+
+#[derive(Debug, Error)]
+#[error("gave up after {attempts} attempts")]
+struct GaveUp { attempts: u32 }
+
+fn content_length(header: &str) -> Result<u64, DownloadError> {
+    let value: &str = header.strip_prefix("Content-Length: ")
+        .ok_or_else(|| DownloadError::Http {
+            status: 411,
+            reason: "length required".to_string(),
+        })?;
+    Ok(value.parse()?)                  // `From<ParseIntError>` is derived
+}
+
+runnable!(derived_errors, {
+    use std::error::Error;
+    let http = DownloadError::Http { status: 404, reason: "not found".into() };
+    assert_eq!(http.to_string(), "server answered 404: not found");
+    assert_eq!(DownloadError::InvalidUrl("ftp:".into()).to_string(),
+        "invalid url `ftp:`");
+    assert!(DownloadError::Timeout.source().is_none());
+    assert_eq!(GaveUp { attempts: 3 }.to_string(), "gave up after 3 attempts");
+
+    assert_eq!(content_length("Content-Length: 42").unwrap(), 42);
+    let error: DownloadError =
+        content_length("Content-Length: big").unwrap_err();
+    assert_eq!(error.to_string(), "invalid content length");
+    assert!(error.source().unwrap().is::<std::num::ParseIntError>());
+});
+
+// #[derive(Error)]
+// enum Missing { Variant }
+//                ^^^^^^^ Error: missing `#[error("...")]` attribute
+// #[derive(Error)]
+// enum TooMany { #[error("two")] Two(#[from] u8, u8) }
+//                ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ Error: `#[from]` must
+//                be on the only field of a variant
+
+/// ### Derived Errors vs Declarative Macros
+/// A declarative macro can generate the same impls, but only for the shapes
+/// its patterns foresee: here, variants with exactly one field, used as the
+/// only argument of the message.
+macro_rules! error_enum {
+    ($name: ident {
+        $($variant: ident($field: ty) => $message: literal,)*
+    }) => {
+        #[derive(Debug)]
+        enum $name { $($variant($field),)* }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match self {
+                    $($name::$variant(value) => write!(f, $message, value),)*
+                }
+            }
+        }
+
+        impl ::std::error::Error for $name {}
+    };
+}
+
+error_enum!(DeclaredError {
+    InvalidUrl(String) => "invalid url `{}`",
+    Length(std::num::ParseIntError) => "invalid content length: {}",
+});
+
+/// The derive macro reads the attributes and the shape of each variant, so:
+/// - variants may have no field, several, or named ones
+/// - messages refer to fields by name or position, in any order
+/// - `#[from]` picks which field is the source, and generates `From`: the
+///   declarative version has no `source`, and no `From`, since its syntax
+///   has no way to mark a field (and adding one grows every pattern)
+///
+/// In exchange, the declarative macro needs no extra crate, and its whole
+/// definition fits on a screen.
+runnable!(derived_errors_vs_declarative_macros, {
+    use std::error::Error;
+    let error = DeclaredError::Length("big".parse::<u64>().unwrap_err());
+    assert_eq!(error.to_string(),
+        "invalid content length: invalid digit found in string");
+    assert!(error.source().is_none());
+    assert_eq!(DeclaredError::InvalidUrl("ftp:".into()).to_string(),
+        DownloadError::InvalidUrl("ftp:".into()).to_string());
+});
@@ -0,0 +1,111 @@
+/// # Reactors
+/// An executor polls futures, but something must wake them up when they can
+/// make progress again. In real runtimes this is the job of a `reactor`: a
+/// component waiting on external events (sockets, timers, ...) on behalf of
+/// the futures, and waking their `Waker`s when the events occur.
+///
+/// Here we implement a timer reactor: a background thread storing wakers
+/// together with their deadlines, and waking each of them when its deadline
+/// passes. Pending futures cost nothing while they wait (no busy-polling).
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::task::Waker;
+use std::thread;
+use std::time::Instant;
+
+fn reactor() {}
+
+/// ## Timers
+/// A timer pairs a deadline with the waker to wake when it passes. Timers are
+/// ordered by deadline only, so that the reactor can keep them in a heap.
+struct Timer { deadline: Instant, waker: Waker }
+
+impl PartialEq for Timer {
+    fn eq(&self, other: &Self) -> bool { self.deadline == other.deadline }
+}
+impl Eq for Timer {}
+impl PartialOrd for Timer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Timer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.deadline.cmp(&other.deadline)
+    }
+}
+
+/// ## Reactor Thread
+/// The reactor thread receives new timers through a channel. While waiting
+/// for new timers, it sleeps until the earliest deadline (`recv_timeout`),
+/// then wakes all the timers that have expired.
+fn run(timers: Receiver<Timer>) {
+    // `Reverse` turns the max-heap into a min-heap (earliest deadline first)
+    let mut heap: BinaryHeap<Reverse<Timer>> = BinaryHeap::new();
+    loop {
+        let received: Result<Timer, RecvTimeoutError> = match heap.peek() {
+            Some(Reverse(timer)) => timers.recv_timeout(
+                timer.deadline.saturating_duration_since(Instant::now())
+            ),
+            None => timers.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(timer) => heap.push(Reverse(timer)),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+        while let Some(Reverse(timer)) = heap.peek() {
+            if timer.deadline > Instant::now() { break; }
+            let Reverse(timer) = heap.pop().unwrap();
+            timer.waker.wake();
+        }
+    }
+}
+
+/// ## Registration
+/// The reactor is started lazily the first time a timer is registered, and
+/// it is shared by all the futures in the program.
+static REACTOR: OnceLock<Mutex<Sender<Timer>>> = OnceLock::new();
+
+pub(crate) fn register(deadline: Instant, waker: Waker) {
+    let sender = REACTOR.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name(String::from("timer-reactor"))
+            .spawn(move || run(receiver))
+            .expect("failed to spawn the timer reactor");
+        Mutex::new(sender)
+    });
+    sender.lock().unwrap().send(Timer { deadline, waker }).unwrap();
+}
+
+/// ## Counting Wakeups
+/// With the reactor, a `Delay` is polled only twice: once to register its
+/// timer, and once after being woken by the reactor (see futures.rs).
+runnable!(reactor_wakeups, {
+    use crate::async_await::block_on;
+    use crate::futures::Delay;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    struct CountPolls<F> { inner: F, polls: u32 }
+    impl<F: Future<Output = ()> + Unpin> Future for CountPolls<F> {
+        type Output = u32;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<u32> {
+            self.polls += 1;
+            match Pin::new(&mut self.inner).poll(cx) {
+                Poll::Ready(()) => Poll::Ready(self.polls),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    let delay: Delay = Delay::new(Duration::from_millis(20));
+    let polls: u32 = block_on(CountPolls { inner: delay, polls: 0 });
+    println!("polls: {}", polls);
+    assert!(polls <= 3);  // a spurious unpark may cause an extra poll
+});
\ No newline at end of file
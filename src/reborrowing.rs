@@ -0,0 +1,127 @@
+/// # Reborrowing
+/// `&mut T` is not `Copy`: there can only be one usable mutable reference at
+/// a time. Yet a `&mut T` can be passed to several functions in a row. This
+/// works through `reborrowing`: `&mut *reference` creates a new, shorter
+/// mutable borrow of the same data, through the original reference. While
+/// the reborrow is in use, the original reference is frozen; afterwards, it
+/// is usable again.
+///
+/// The same exists for shared references (`&*reference`), but as `&T` is
+/// `Copy`, it rarely matters.
+fn reborrowing() {}
+
+fn append(numbers: &mut Vec<u8>, value: u8) {
+    numbers.push(value);
+}
+
+/// ## Implicit Reborrows
+/// When a `&mut T` is passed where a `&mut T` is expected, the compiler
+/// inserts `&mut *` automatically: the reference is reborrowed, not moved.
+runnable!(implicit_reborrows, {
+    let mut numbers: Vec<u8> = Vec::new();
+    let reference: &mut Vec<u8> = &mut numbers;
+    append(reference, 1);        // `append(&mut *reference, 1)`
+    append(reference, 2);        // `reference` is still usable
+    reference.push(3);           // method calls reborrow too
+    assert_eq!(numbers, [1, 2, 3]);
+
+    // An explicit reborrow, frozen while `shorter` is in use
+    let reference: &mut Vec<u8> = &mut numbers;
+    let shorter: &mut Vec<u8> = &mut *reference;
+    shorter.push(4);
+    // reference.push(0);
+    // shorter.push(5);
+    // ^ Error: cannot borrow `*reference` as mutable more than once at a time
+    reference.push(5);           // `shorter` is done: `reference` is back
+    assert_eq!(numbers, [1, 2, 3, 4, 5]);
+});
+
+/// ## When a Manual `&mut *` Is Needed
+/// The implicit reborrow only happens when the expected type is known to be
+/// a `&mut` reference. Otherwise, the reference is moved:
+/// - generic parameters (`T` could be anything, so `&mut Vec<u8>` is moved)
+/// - `let` bindings without a type annotation
+/// - `for` loops, which call `IntoIterator::into_iter` on the value
+fn consume<T>(value: T) {}
+
+runnable!(manual_reborrows, {
+    let mut numbers: Vec<u8> = vec![1, 2];
+    let reference: &mut Vec<u8> = &mut numbers;
+
+    consume(&mut *reference);    // reborrow: `reference` stays usable
+    // consume(reference);
+    // reference.push(3);
+    // ^ Error: borrow of moved value: `reference`
+
+    let moved = &mut *reference; // without `&mut *`, `reference` is moved
+    moved.push(3);
+    let annotated: &mut Vec<u8> = reference;  // an annotation reborrows too
+    annotated.push(4);
+
+    for number in &mut *reference {  // `for number in reference` would move
+        *number *= 10;
+    }
+    reference.push(50);
+    assert_eq!(numbers, [10, 20, 30, 40, 50]);
+});
+
+/// ### Reborrowing Through `Option`
+/// `Option<&mut T>` is not reborrowed automatically either: `as_deref_mut`
+/// (or `as_mut` with a `map`) creates a shorter `Option<&mut T>` each time.
+fn push_if_any(numbers: Option<&mut Vec<u8>>, value: u8) {
+    if let Some(numbers) = numbers {
+        numbers.push(value);
+    }
+}
+
+runnable!(reborrowing_through_option, {
+    let mut numbers: Vec<u8> = Vec::new();
+    let mut target: Option<&mut Vec<u8>> = Some(&mut numbers);
+    push_if_any(target.as_deref_mut(), 1);
+    push_if_any(target.as_deref_mut(), 2);
+    // push_if_any(target, 1);
+    // push_if_any(target, 2);
+    // ^ Error: use of moved value: `target`
+    assert_eq!(numbers, [1, 2]);
+});
+
+/// ## Returning References from `&mut self` Methods
+/// A method taking `&mut self` and returning a reference reborrows `self`
+/// for as long as the result is in use: the object stays mutably borrowed,
+/// even if the result is a shared reference. The signature cannot express
+/// "mutable during the call, shared afterwards".
+struct Cache {
+    items: Vec<String>,
+    hits: u32,
+}
+
+impl Cache {
+    fn lookup(&mut self, index: usize) -> &String {
+        self.hits += 1;
+        &self.items[index]
+    }
+
+    /// Splitting the work avoids the problem: mutate first, then borrow.
+    fn record_hit(&mut self) { self.hits += 1; }
+    fn get(&self, index: usize) -> &String { &self.items[index] }
+}
+
+runnable!(returning_references_from_mut_self, {
+    let mut cache: Cache = Cache {
+        items: vec!["a".to_string(), "b".to_string()],
+        hits: 0,
+    };
+    let first: &String = cache.lookup(0);
+    // let second: &String = cache.lookup(1);
+    // println!("{} {}", first, second);
+    // ^ Error: cannot borrow `cache` as mutable more than once at a time
+    // println!("{} {}", first, cache.hits);
+    // ^ Error: cannot borrow `cache.hits` as immutable because it is also
+    //          borrowed as mutable
+    assert_eq!(first, "a");      // the last use of `first`
+
+    cache.record_hit();
+    cache.record_hit();
+    let (first, second): (&String, &String) = (cache.get(0), cache.get(1));
+    assert_eq!((first.as_str(), second.as_str(), cache.hits), ("a", "b", 3));
+});
\ No newline at end of file
@@ -0,0 +1,135 @@
+/// # Recursion (~ Haskell Recursion, without Tail Call Optimization)
+/// A function can call itself, as long as the recursion eventually reaches a
+/// base case. Each call pushes a new frame on the stack of the thread, and
+/// Rust does not guarantee tail call optimization: a recursion deep enough
+/// overflows the stack, which aborts the whole process.
+///
+/// This lesson compares different implementations of the Fibonacci sequence
+/// (see `benchmark!` in util.rs).
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+fn recursion() {}
+
+/// ## Naive Recursion
+/// The direct translation of the definition recomputes the same values over
+/// and over: `fib(n)` makes an exponential number of calls.
+fn fib_naive(n: u64) -> u64 {
+    match n {
+        0 | 1 => n,
+        _ => fib_naive(n - 1) + fib_naive(n - 2),
+    }
+}
+
+runnable!(naive_recursion, {
+    let fibs: Vec<u64> = (0..10).map(fib_naive).collect();
+    println!("fib(0..10): {:?}", fibs);
+    assert_eq!(fib_naive(20), 6765);
+});
+
+/// ## Memoization
+/// Caching the results already computed makes each value computed once.
+///
+/// ### Cache as a Parameter
+/// The simplest way is to pass the cache down the recursive calls.
+fn fib_memo(n: u64, memo: &mut HashMap<u64, u64>) -> u64 {
+    if n <= 1 { return n; }
+    if let Some(&cached) = memo.get(&n) { return cached; }
+    let result: u64 = fib_memo(n - 1, memo) + fib_memo(n - 2, memo);
+    memo.insert(n, result);
+    result
+}
+
+/// ### Cache in a RefCell
+/// Hiding the cache in a struct keeps the signature clean. A `RefCell` (see
+/// ownership.rs) lets a method taking `&self` update the cache. The borrow
+/// of the cache must be released before recursing: holding it across the
+/// recursive calls would panic with `already borrowed`.
+struct Fibonacci { memo: RefCell<HashMap<u64, u64>> }
+
+impl Fibonacci {
+    fn new() -> Self { Fibonacci { memo: RefCell::new(HashMap::new()) } }
+    fn get(&self, n: u64) -> u64 {
+        if n <= 1 { return n; }
+        if let Some(&cached) = self.memo.borrow().get(&n) { return cached; }
+        // <-- the shared borrow ends with the `if let`
+        let result: u64 = self.get(n - 1) + self.get(n - 2);
+        self.memo.borrow_mut().insert(n, result);
+        result
+    }
+}
+
+runnable!(memoization, {
+    let mut memo: HashMap<u64, u64> = HashMap::new();
+    println!("fib(90) = {}", fib_memo(90, &mut memo));
+    println!("cached values: {}", memo.len());
+
+    let fibonacci: Fibonacci = Fibonacci::new();
+    println!("fib(90) = {}", fibonacci.get(90));
+    assert_eq!(fibonacci.get(20), fib_naive(20));
+});
+
+/// ## Iteration
+/// Recursion can always be rewritten as a loop (with an explicit stack, if
+/// needed). For Fibonacci, two variables are enough: constant memory, no
+/// cache and no risk of overflowing the stack.
+fn fib_iterative(n: u64) -> u64 {
+    let (mut current, mut next): (u64, u64) = (0, 1);
+    for _ in 0..n {
+        (current, next) = (next, current + next);
+    }
+    current
+}
+
+runnable!(iteration, {
+    println!("fib(90) = {}", fib_iterative(90));
+    assert!((0..25).all(|n| fib_iterative(n) == fib_naive(n)));
+});
+
+/// ## Benchmarks
+/// The naive version grows exponentially, the others linearly. Note that the
+/// memoized versions are benchmarked with a fresh cache at each iteration.
+runnable!(benchmarks, {
+    let n: u64 = 20;
+    let naive = benchmark!("naive", 20, fib_naive(n));
+    let memo = benchmark!("memoized (parameter)", 20,
+        fib_memo(n, &mut HashMap::new()));
+    let refcell = benchmark!("memoized (RefCell)", 20,
+        Fibonacci::new().get(n));
+    let iterative = benchmark!("iterative", 20, fib_iterative(n));
+    println!("naive / iterative: {:.0}x",
+        naive.as_secs_f64() / iterative.as_secs_f64().max(1e-9));
+});
+
+/// ## Stack Depth
+/// Every call uses some stack space, so the maximum recursion depth depends
+/// on the size of the stack: the main thread usually gets 8 MiB, spawned
+/// threads (including tests) 2 MiB. A thread with a bigger stack can be
+/// spawned when deep recursion is expected.
+fn depth(n: u64) -> u64 {
+    if n == 0 { 0 } else { 1 + depth(n - 1) }  // not a tail call
+}
+
+runnable!(stack_depth, {
+    println!("depth: {}", depth(10_000));
+
+    let handle = std::thread::Builder::new()
+        .stack_size(256 * 1024 * 1024)
+        .spawn(|| depth(1_000_000))
+        .unwrap();
+    println!("depth with a bigger stack: {}", handle.join().unwrap());
+});
+
+/// ## Stack Overflow
+/// Overflowing the stack is not a panic: the process is aborted with
+/// `thread '...' has overflowed its stack`, taking every other test down
+/// with it. For this reason, the demonstration only runs when the variable
+/// `PLAYGROUND_STACK_OVERFLOW` is set, e.g.
+/// `PLAYGROUND_STACK_OVERFLOW=1 cargo test stack_overflow`.
+runnable!(stack_overflow, {
+    if std::env::var("PLAYGROUND_STACK_OVERFLOW").is_err() {
+        println!("skipped: set PLAYGROUND_STACK_OVERFLOW to run it");
+        return;
+    }
+    println!("depth: {}", depth(u64::MAX));
+});
\ No newline at end of file
@@ -0,0 +1,112 @@
+/// # Transparent Representation
+/// A newtype (see patterns/newtype.rs) is a struct with a single field,
+/// adding meaning and type safety on top of an existing type. With the
+/// default representation, nothing guarantees that it is laid out or passed
+/// to functions like its field, even if it usually is in practice.
+///
+/// `#[repr(transparent)]` guarantees it: the wrapper has exactly the same
+/// size, alignment and ABI (calling convention) as its single non-zero-sized
+/// field. The wrapper and the field can then be used interchangeably at the
+/// binary level: through pointer casts, and across FFI boundaries.
+use std::marker::PhantomData;
+use std::num::NonZeroU32;
+use std::ptr::NonNull;
+
+fn repr_transparent() {}
+
+/// ## Layout Compatibility
+/// The layouts are checked at compile time with `static_assert_layout!` (see
+/// util.rs and static_assertions.rs).
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Meters(f64);
+
+static_assert_layout!(Meters, f64);
+static_assert_layout!([Meters; 4], [f64; 4]);
+
+/// Only one field may have a non-zero size: zero-sized fields, like markers,
+/// are allowed alongside it.
+/// ```
+/// #[repr(transparent)]
+/// struct Pair(u32, u32);
+/// // ^ Error: transparent struct needs at most one field with non-trivial
+/// //          size or alignment, but has 2
+/// ```
+#[repr(transparent)]
+pub struct Id<T> {
+    value: u32,
+    _kind: PhantomData<T>,  // zero-sized: `Id<User>` and `Id<Order>` differ
+}
+
+struct User;
+static_assert_layout!(Id<User>, u32);
+
+runnable!(layout_compatibility, {
+    // A slice of wrappers can be viewed as a slice of the inner type
+    let distances: Vec<Meters> = vec![Meters(1.5), Meters(2.0), Meters(0.5)];
+    // SAFETY: `Meters` is `repr(transparent)` over `f64`, so the slices have
+    // the same layout, and the lifetime is bound to `distances`.
+    let raw: &[f64] = unsafe {
+        std::slice::from_raw_parts(distances.as_ptr() as *const f64,
+            distances.len())
+    };
+    assert_eq!(raw.iter().sum::<f64>(), 4.0);
+
+    let id: Id<User> = Id { value: 7, _kind: PhantomData };
+    assert_eq!(std::mem::size_of_val(&id), 4);
+});
+
+/// ## FFI
+/// A `repr(transparent)` wrapper is passed exactly like its field, so it can
+/// appear in the signature of a C function, adding type safety on the Rust
+/// side only: here, the C function `double sqrt(double)` is declared as
+/// taking and returning `Meters`.
+#[cfg_attr(target_os = "linux", link(name = "m"))]
+extern "C" {
+    #[link_name = "sqrt"]
+    fn sqrt_meters(area: Meters) -> Meters;
+}
+
+runnable!(ffi, {
+    // SAFETY: `sqrt` has no preconditions, and `Meters` has the ABI of
+    // `f64` (without `repr(transparent)`, this declaration would be wrong).
+    let side: Meters = unsafe { sqrt_meters(Meters(16.0)) };
+    assert_eq!(side, Meters(4.0));
+});
+
+/// ## Niches
+/// `Option<&T>`, `Option<Box<T>>`, `Option<NonNull<T>>` and
+/// `Option<NonZeroU32>` are guaranteed to be as big as their content, with
+/// `None` represented by zero (a null pointer): they can be used in FFI
+/// signatures for nullable C pointers (see layout.rs and
+/// unsafe_code/non_null.rs).
+///
+/// A `repr(transparent)` wrapper keeps the niche of its field, and with it
+/// this guarantee.
+#[repr(transparent)]
+pub struct Handle(NonNull<u8>);
+
+#[repr(transparent)]
+pub struct Port(NonZeroU32);
+
+static_assert_layout!(Option<Handle>, *mut u8);
+static_assert_layout!(Option<Port>, u32);
+
+/// A callback for C code, receiving a nullable pointer: null is `None`.
+extern "C" fn describe_handle(handle: Option<&Handle>) -> u32 {
+    match handle {
+        Some(_) => 1,
+        None => 0,
+    }
+}
+
+runnable!(niches, {
+    let mut byte: u8 = 0;
+    let handle: Handle = Handle(NonNull::from(&mut byte));
+    assert_eq!(describe_handle(Some(&handle)), 1);
+    assert_eq!(describe_handle(None), 0);
+    // `None` is the all-zeros value
+    // SAFETY: `Option<Port>` has the layout of `u32`.
+    let none: Option<Port> = unsafe { std::mem::transmute(0u32) };
+    assert!(none.is_none());
+});
\ No newline at end of file
@@ -0,0 +1,95 @@
+/// # Reverse FFI (~ writing a C library)
+/// FFI also works the other way around: a Rust library can expose functions
+/// to C (or to any language able to call C, like Python or Java), see
+/// unsafe_code.rs for calling C from Rust.
+///
+/// The workspace member `playground-ffi` is such a library:
+/// - `crate-type = ["cdylib"]` in its `Cargo.toml` builds a C-compatible
+///   shared library (`libplayground_ffi.so`, `.dylib` or `.dll`), and
+///   `"staticlib"` would build a static one (`.a` or `.lib`)
+/// - `include/playground_ffi.h` declares its functions for C, by hand (tools
+///   like `cbindgen` generate headers from the Rust code)
+/// - `tests/c_program.rs` compiles and runs a C program using it
+///   (`cargo test -p playground-ffi`)
+///
+/// It is also built as an `rlib`, so this lesson calls its C API from Rust,
+/// exactly as a C program would.
+use playground_ffi::{
+    playground_add, playground_counter_free, playground_counter_increment,
+    playground_counter_new, playground_distance, playground_divide,
+    playground_midpoint, Counter, Point, PLAYGROUND_NULL_POINTER,
+    PLAYGROUND_OK, PLAYGROUND_PANIC,
+};
+use std::ptr;
+
+fn reverse_ffi() {}
+
+/// ## Exporting Functions
+/// ```
+/// #[no_mangle]                  // keep the symbol name `playground_add`
+/// pub extern "C" fn playground_add(a: i32, b: i32) -> i32 { ... }
+/// ```
+/// The exported symbols of the shared library can be listed with
+/// `nm -D --defined-only target/debug/libplayground_ffi.so`.
+runnable!(exporting_functions, {
+    assert_eq!(playground_add(40, 2), 42);
+    assert_eq!(playground_add(i32::MAX, 1), i32::MIN);  // no panic
+});
+
+/// ## Sharing Types with `#[repr(C)]`
+/// The layout of a Rust struct is unspecified: the compiler may reorder its
+/// fields (see layout.rs). `#[repr(C)]` follows the rules of C instead, so
+/// that both sides agree on the size, alignment and offset of each field.
+/// Only FFI-safe types should be used in signatures: integers, floats, raw
+/// pointers, `#[repr(C)]` types, `Option<&T>` and function pointers (not
+/// `String`, `Vec`, `&str`, trait objects or tuples).
+runnable!(sharing_types_with_repr_c, {
+    let origin: Point = Point { x: 0.0, y: 0.0 };
+    let point: Point = Point { x: 3.0, y: 4.0 };
+    assert_eq!(playground_distance(origin, point), 5.0);
+    assert_eq!(std::mem::size_of::<Point>(), 16);  // 2 * sizeof(double)
+
+    let mut middle: Point = origin;
+    // SAFETY: all pointers are valid (or null, which is checked).
+    unsafe {
+        assert!(playground_midpoint(&origin, &point, &mut middle));
+        assert!(!playground_midpoint(&origin, ptr::null(), &mut middle));
+    }
+    assert_eq!(middle, Point { x: 1.5, y: 2.0 });
+});
+
+/// ## Ownership Across the Boundary
+/// A value allocated by Rust is handed to C as a raw pointer
+/// (`Box::into_raw`), and must come back to Rust to be freed
+/// (`Box::from_raw`): each allocator frees only what it allocated. C sees the
+/// type as opaque (`typedef struct PlaygroundCounter PlaygroundCounter;`),
+/// so it can only use it through the exported functions.
+runnable!(ownership_across_the_boundary, {
+    let counter: *mut Counter = playground_counter_new(10);
+    // SAFETY: `counter` comes from `playground_counter_new`, and is freed
+    // exactly once, after its last use.
+    unsafe {
+        playground_counter_increment(counter);
+        assert_eq!(playground_counter_increment(counter), 20);
+        playground_counter_free(counter);
+        playground_counter_free(ptr::null_mut());  // like C `free(NULL)`
+    }
+});
+
+/// ## Panics at the Boundary
+/// Unwinding out of an `extern "C"` function would corrupt the C stack, so
+/// a panic reaching the boundary aborts the process instead. Exported
+/// functions catch panics with `std::panic::catch_unwind`, and turn them
+/// into error codes. (The `extern "C-unwind"` ABI allows unwinding through C
+/// frames, for C code compiled to support it.)
+runnable!(panics_at_the_boundary, {
+    let mut quotient: i32 = 0;
+    // SAFETY: `quotient` is valid for writes (or the pointer is null).
+    unsafe {
+        assert_eq!(playground_divide(7, 2, &mut quotient), PLAYGROUND_OK);
+        assert_eq!(playground_divide(1, 0, &mut quotient), PLAYGROUND_PANIC);
+        assert_eq!(playground_divide(1, 1, ptr::null_mut()),
+            PLAYGROUND_NULL_POINTER);
+    }
+    assert_eq!(quotient, 3);
+});
\ No newline at end of file
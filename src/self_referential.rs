@@ -0,0 +1,228 @@
+/// # Self-Referential Structs
+/// A struct cannot simply hold both some data and a reference into that
+/// data: moving the struct would move the data, and invalidate the
+/// reference (see pinning.rs for what happens at the memory level). The
+/// borrow checker has no lifetime for "as long as the struct itself", so it
+/// rejects every attempt to build one.
+///
+/// In practice, this comes up when parsing: keeping the input text together
+/// with the tokens borrowed from it.
+use std::ops::Range;
+use std::pin::Pin;
+use std::rc::Rc;
+
+fn self_referential() {}
+
+/// ## The Attempt
+/// ```
+/// struct Parsed<'a> {
+///     text: String,
+///     first_word: &'a str,         // meant to borrow from `text`
+/// }
+///
+/// fn parse<'a>(text: String) -> Parsed<'a> {
+///     let first_word: &str = text.split(' ').next().unwrap();
+///     Parsed { text, first_word }
+///     // ^ Error: cannot move out of `text` because it is borrowed
+///     // ^ Error: cannot return value referencing function parameter `text`
+/// }
+///
+/// fn parse_in_place<'a>(text: String) -> Parsed<'a> {
+///     let mut parsed = Parsed { text, first_word: "" };
+///     parsed.first_word = &parsed.text;
+///     parsed
+///     // ^ Error: cannot move out of `parsed` because it is borrowed
+///     // ^ Error: cannot return value referencing local data `parsed.text`
+/// }
+/// ```
+/// A value borrowing itself can be built in a local variable, but then it
+/// stays borrowed for its whole life: it cannot be moved, returned, or even
+/// mutated anymore, which makes it useless.
+fn the_attempt() {}
+
+/// ## Workaround: Indices
+/// Store positions into the data instead of references. Borrowing happens
+/// on access, through `&self`, so the struct moves freely. This is the
+/// simplest and most common solution (see also patterns/arena.rs).
+struct IndexedText {
+    text: String,
+    words: Vec<Range<usize>>,
+}
+
+impl IndexedText {
+    fn new(text: String) -> IndexedText {
+        let mut words: Vec<Range<usize>> = Vec::new();
+        let mut start: Option<usize> = None;
+        for (index, character) in text.char_indices() {
+            match (start, character.is_whitespace()) {
+                (None, false) => start = Some(index),
+                (Some(from), true) => {
+                    words.push(from..index);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(from) = start {
+            words.push(from..text.len());
+        }
+        IndexedText { text, words }
+    }
+
+    fn word(&self, index: usize) -> Option<&str> {
+        self.words.get(index).map(|range| &self.text[range.clone()])
+    }
+}
+
+runnable!(workaround_indices, {
+    let parsed: IndexedText =
+        IndexedText::new("self referential  structs".to_string());
+    let moved: Box<IndexedText> = Box::new(parsed);   // moves are fine
+    assert_eq!(moved.word(0), Some("self"));
+    assert_eq!(moved.word(2), Some("structs"));
+    assert_eq!(moved.word(3), None);
+});
+
+/// ## Workaround: Shared Ownership
+/// With `Rc`, the pieces own (a share of) their data instead of borrowing
+/// it: nothing refers to the struct itself. It costs an allocation per
+/// piece here; alternatively, all pieces can share one `Rc<str>` plus a
+/// range each.
+struct SharedText {
+    text: Rc<str>,
+    first_word: Rc<str>,
+}
+
+impl SharedText {
+    fn new(text: &str) -> SharedText {
+        let first_word: &str = text.split_whitespace().next().unwrap_or("");
+        SharedText { text: Rc::from(text), first_word: Rc::from(first_word) }
+    }
+}
+
+runnable!(workaround_shared_ownership, {
+    let shared: SharedText = SharedText::new("shared ownership");
+    let first_word: Rc<str> = Rc::clone(&shared.first_word);
+    drop(shared);                                     // `first_word` survives
+    assert_eq!(&*first_word, "shared");
+});
+
+/// ## Workaround: Unsafe, with Invariants
+/// The data is put behind a pointer, so that moving the struct does not move
+/// the data, and the reference into it is stored as a raw pointer. The
+/// pointer to the data cannot be a `Box`: moving a `Box` asserts that it is
+/// the only way to reach its content, which invalidates every pointer taken
+/// from it before (Miri reports it, see unsafe_code/raw_pointers.rs). Crates
+/// like `ouroboros` and `self_cell` take the same precautions, with an
+/// aliasable box or raw pointers. The struct is sound as long as its
+/// invariants hold:
+/// 1. `text` is never mutated nor replaced, so the heap buffer never moves,
+///    and it is only freed by `Drop`
+/// 2. `first_word` is never exposed with a longer lifetime than `&self`
+///
+/// The fields are private, so that only this module can break them.
+mod owned_words {
+    use std::ptr::NonNull;
+
+    pub struct OwnedText {
+        text: NonNull<str>,          // owned: from `Box::into_raw`
+        first_word: *const str,      // points into `text`
+    }
+
+    impl OwnedText {
+        pub fn new(text: &str) -> OwnedText {
+            let text: NonNull<str> =
+                NonNull::from(Box::leak(Box::<str>::from(text)));
+            // SAFETY: `text` was just allocated, and nothing else uses it.
+            let first_word: &str = unsafe { text.as_ref() }
+                .split_whitespace().next().unwrap_or("");
+            OwnedText { text, first_word }
+        }
+
+        pub fn first_word(&self) -> &str {
+            // SAFETY: `first_word` points into `text`, alive and unchanged
+            // as long as `self` (1), and borrowed for as long as `&self` (2).
+            unsafe { &*self.first_word }
+        }
+
+        pub fn text(&self) -> &str {
+            // SAFETY: as in `first_word`.
+            unsafe { self.text.as_ref() }
+        }
+    }
+
+    impl Drop for OwnedText {
+        fn drop(&mut self) {
+            // SAFETY: `text` comes from `Box::into_raw` (via `Box::leak`),
+            // and is freed only here, once (1).
+            drop(unsafe { Box::from_raw(self.text.as_ptr()) });
+        }
+    }
+}
+
+runnable!(workaround_unsafe, {
+    use owned_words::OwnedText;
+    let owned: OwnedText = OwnedText::new("hand written");
+    let moved: Vec<OwnedText> = vec![owned];          // moves are fine
+    assert_eq!(moved[0].first_word(), "hand");
+    assert_eq!(moved[0].text(), "hand written");
+});
+
+/// ## Workaround: Pinning
+/// When the data cannot live behind a separate pointer (e.g. the state of a
+/// future, see async_await.rs), the whole value is pinned instead: `Pin`
+/// guarantees that it will never move again, so a raw pointer into it stays
+/// valid. See pinning.rs for `PhantomPinned` and pin projections.
+///
+/// Here the text is stored inline, in a fixed-size buffer: moving the struct
+/// would move the bytes, and leave `first_word` dangling.
+const CAPACITY: usize = 32;
+
+struct PinnedText {
+    buffer: [u8; CAPACITY],
+    length: usize,
+    first_word: *const str,      // points into `buffer`, once pinned
+    _pinned: std::marker::PhantomPinned,
+}
+
+impl PinnedText {
+    fn new(text: &str) -> Pin<Box<PinnedText>> {
+        assert!(text.len() <= CAPACITY, "text too long");
+        let mut buffer: [u8; CAPACITY] = [0; CAPACITY];
+        buffer[..text.len()].copy_from_slice(text.as_bytes());
+        let mut boxed: Pin<Box<PinnedText>> = Box::pin(PinnedText {
+            buffer,
+            length: text.len(),
+            first_word: "",
+            _pinned: std::marker::PhantomPinned,
+        });
+        // SAFETY: the value is not moved out of the pin, only one field is
+        // written.
+        unsafe {
+            let this: &mut PinnedText = boxed.as_mut().get_unchecked_mut();
+            let first_word: *const str = this.text().split_whitespace()
+                .next().unwrap_or("");
+            this.first_word = first_word;
+        }
+        boxed
+    }
+
+    fn text(&self) -> &str {
+        // Only a whole `&str` is ever copied into the buffer
+        std::str::from_utf8(&self.buffer[..self.length]).unwrap()
+    }
+
+    fn first_word(self: Pin<&Self>) -> &str {
+        // SAFETY: the value is pinned, so `buffer` has not moved since the
+        // pointer was taken, and it is never mutated.
+        unsafe { &*self.get_ref().first_word }
+    }
+}
+
+runnable!(workaround_pinning, {
+    let pinned: Pin<Box<PinnedText>> = PinnedText::new("pinned value");
+    let moved: Vec<Pin<Box<PinnedText>>> = vec![pinned];  // moves the box
+    assert_eq!(moved[0].as_ref().first_word(), "pinned");
+    // let unpinned: PinnedText = *moved.into_iter().next().unwrap();
+    // ^ Error: cannot move out of a `Pin` (`PinnedText` is `!Unpin`)
+});
@@ -0,0 +1,223 @@
+/// # Shared Mutable Graphs (~ object graphs in garbage-collected languages)
+/// In Java or Python, a social network is a set of objects pointing to each
+/// other, mutated through any of these pointers. In Rust, a value has a
+/// single owner, and mutation requires exclusive access: such a graph needs
+/// - `Rc<T>`: shared ownership, counting the owners (see generics.rs)
+/// - `RefCell<T>`: mutation through shared references, with borrows checked
+///   at runtime (see unsafe_code/unsafe_cell.rs)
+/// - `Weak<T>`: a non-owning pointer, which does not keep its target alive,
+///   to break cycles
+///
+/// This lesson builds a small social graph with them, and shows where this
+/// design hurts.
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::rc::{Rc, Weak};
+
+fn shared_graph() {}
+
+/// ## Nodes
+/// Each person is owned by the network (`Rc`), and refers to other people
+/// through `Weak` edges: a `follows` edge and its `followed_by` back-edge.
+#[derive(Debug)]
+struct Person {
+    name: String,
+    follows: Vec<Weak<RefCell<Person>>>,
+    followed_by: Vec<Weak<RefCell<Person>>>,
+}
+
+type PersonRef = Rc<RefCell<Person>>;
+
+struct Network {
+    people: Vec<PersonRef>,
+}
+
+impl Network {
+    fn new() -> Network { Network { people: Vec::new() } }
+
+    fn add(&mut self, name: &str) -> PersonRef {
+        let person: PersonRef = Rc::new(RefCell::new(Person {
+            name: name.to_string(),
+            follows: Vec::new(),
+            followed_by: Vec::new(),
+        }));
+        self.people.push(Rc::clone(&person));
+        person
+    }
+
+    fn find(&self, name: &str) -> Option<PersonRef> {
+        self.people.iter().find(|person| person.borrow().name == name).cloned()
+    }
+}
+
+/// Each `borrow_mut` ends at the end of its statement, so the two people can
+/// be mutated one after the other (even if they are the same person).
+fn follow(follower: &PersonRef, followed: &PersonRef) {
+    follower.borrow_mut().follows.push(Rc::downgrade(followed));
+    followed.borrow_mut().followed_by.push(Rc::downgrade(follower));
+}
+
+fn names(people: &[Weak<RefCell<Person>>]) -> Vec<String> {
+    people.iter()
+        .filter_map(Weak::upgrade)              // skips people already gone
+        .map(|person| person.borrow().name.clone())
+        .collect()
+}
+
+fn sample_network() -> Network {
+    let mut network: Network = Network::new();
+    let ann: PersonRef = network.add("ann");
+    let bob: PersonRef = network.add("bob");
+    let cid: PersonRef = network.add("cid");
+    let dan: PersonRef = network.add("dan");
+    follow(&ann, &bob);
+    follow(&bob, &ann);                         // a cycle: fine with `Weak`
+    follow(&bob, &cid);
+    follow(&cid, &dan);
+    network
+}
+
+runnable!(nodes, {
+    let network: Network = sample_network();
+    let bob: PersonRef = network.find("bob").unwrap();
+    assert_eq!(names(&bob.borrow().follows), ["ann", "cid"]);
+    assert_eq!(names(&bob.borrow().followed_by), ["ann"]);
+    assert_eq!(Rc::strong_count(&bob), 2);      // `network` and `bob`
+    assert_eq!(Rc::weak_count(&bob), 3);        // edges pointing to bob
+});
+
+/// ## Traversal
+/// Visiting everyone reachable from a person, breadth-first. Nodes are
+/// identified by address (`Rc::as_ptr`): names need not be unique.
+fn reachable(start: &PersonRef) -> Vec<String> {
+    let mut visited: HashSet<*const RefCell<Person>> = HashSet::new();
+    let mut queue: VecDeque<PersonRef> = VecDeque::from([Rc::clone(start)]);
+    let mut order: Vec<String> = Vec::new();
+    while let Some(person) = queue.pop_front() {
+        if !visited.insert(Rc::as_ptr(&person)) { continue; }
+        let person = person.borrow();           // shared borrow, until `}`
+        order.push(person.name.clone());
+        queue.extend(person.follows.iter().filter_map(Weak::upgrade));
+    }
+    order
+}
+
+runnable!(traversal, {
+    let network: Network = sample_network();
+    let ann: PersonRef = network.find("ann").unwrap();
+    assert_eq!(reachable(&ann), ["ann", "bob", "cid", "dan"]);
+    let dan: PersonRef = network.find("dan").unwrap();
+    assert_eq!(reachable(&dan), ["dan"]);
+});
+
+/// ## Mutation During Iteration
+/// The borrow rules still apply, only checked at runtime: iterating over
+/// the edges of a person keeps it borrowed, and a `borrow_mut` of the same
+/// person meanwhile panics (`already borrowed: BorrowMutError`). The
+/// compiler cannot see it, since both go through shared references.
+fn follow_back_all(person: &PersonRef) {
+    for follower in person.borrow().followed_by.iter() {
+        let follower: PersonRef = follower.upgrade().unwrap();
+        follow(person, &follower);              // `person.borrow_mut()`!
+    }
+}
+
+/// The fix: collect what is needed, end the borrow, then mutate.
+fn follow_back_all_fixed(person: &PersonRef) {
+    let followers: Vec<PersonRef> = person.borrow().followed_by.iter()
+        .filter_map(Weak::upgrade)
+        .collect();                             // the borrow ends here
+    for follower in &followers {
+        follow(person, follower);
+    }
+}
+
+runnable!(mutation_during_iteration, {
+    let network: Network = sample_network();
+    let cid: PersonRef = network.find("cid").unwrap();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        follow_back_all(&cid)
+    }));
+    assert!(result.is_err());                   // panicked: already borrowed
+
+    // `try_borrow_mut` reports the conflict instead of panicking
+    let reading = cid.borrow();
+    assert!(cid.try_borrow_mut().is_err());
+    drop(reading);
+
+    follow_back_all_fixed(&cid);
+    assert_eq!(names(&cid.borrow().follows), ["dan", "bob"]);
+});
+
+/// ## Leaks with Strong Cycles
+/// Had the edges been `Rc`s, two people following each other would keep
+/// each other alive forever: their counts never reach zero, and they are
+/// never dropped, even after the network is gone. `Weak` edges avoid this.
+struct Leaky {
+    friend: RefCell<Option<Rc<Leaky>>>,
+    drops: Rc<RefCell<u32>>,
+}
+
+impl Drop for Leaky {
+    fn drop(&mut self) { *self.drops.borrow_mut() += 1; }
+}
+
+runnable!(leaks_with_strong_cycles, {
+    let drops: Rc<RefCell<u32>> = Rc::new(RefCell::new(0));
+    {
+        let new = || Rc::new(Leaky {
+            friend: RefCell::new(None),
+            drops: Rc::clone(&drops),
+        });
+        let (a, b): (Rc<Leaky>, Rc<Leaky>) = (new(), new());
+        *a.friend.borrow_mut() = Some(Rc::clone(&b));
+        *b.friend.borrow_mut() = Some(Rc::clone(&a));
+        assert_eq!(Rc::strong_count(&a), 2);
+    }   // <-- `a` and `b` go out of scope, but their counts only drop to 1
+    assert_eq!(*drops.borrow(), 0);             // leaked
+
+    let network: Network = sample_network();
+    let weak: Weak<RefCell<Person>> = Rc::downgrade(&network.people[0]);
+    drop(network);
+    assert!(weak.upgrade().is_none());          // freed despite the cycle
+});
+
+/// ## Comparison with Indices
+/// The same graph can be stored as plain data: people in a `Vec`, edges as
+/// indices into it (see graph.rs for a full version).
+/// - no `Rc`, `RefCell` or `Weak`: no reference counting, no runtime borrow
+///   checks, no possible panics or leaks
+/// - mutation during iteration is a compile error instead of a panic, and
+///   is solved the same way (collect indices first)
+/// - removing a node leaves stale indices, like dangling `Weak`s, unless
+///   indices are never reused (or carry a generation)
+/// - the whole graph is borrowed at once: a single `&mut` allows mutating
+///   everything, and the graph can be cloned or sent to another thread
+struct IndexedNetwork {
+    names: Vec<String>,
+    follows: Vec<Vec<usize>>,
+}
+
+impl IndexedNetwork {
+    fn reachable(&self, start: usize) -> Vec<&str> {
+        let mut visited: Vec<bool> = vec![false; self.names.len()];
+        let mut queue: VecDeque<usize> = VecDeque::from([start]);
+        let mut order: Vec<&str> = Vec::new();
+        while let Some(person) = queue.pop_front() {
+            if std::mem::replace(&mut visited[person], true) { continue; }
+            order.push(&self.names[person]);
+            queue.extend(&self.follows[person]);
+        }
+        order
+    }
+}
+
+runnable!(comparison_with_indices, {
+    let network: IndexedNetwork = IndexedNetwork {
+        names: ["ann", "bob", "cid", "dan"].map(String::from).to_vec(),
+        follows: vec![vec![1], vec![0, 2], vec![3], vec![]],
+    };
+    assert_eq!(network.reachable(0), ["ann", "bob", "cid", "dan"]);
+    assert_eq!(network.reachable(0), reachable(&sample_network().people[0]));
+});
\ No newline at end of file
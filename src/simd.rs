@@ -0,0 +1,204 @@
+/// # SIMD (~ C intrinsics from immintrin.h / arm_neon.h)
+/// `SIMD` (Single Instruction, Multiple Data) instructions process several
+/// values at once, stored side by side in wide registers (`lanes`): 4 `f32`
+/// in a 128-bit register, 8 in a 256-bit one.
+///
+/// `std::arch` exposes the intrinsics of each architecture, with the same
+/// names as in C. They are only available on their architecture (hence
+/// `cfg(target_arch)`, see target_config.rs), and many require CPU features
+/// which may be missing at runtime:
+/// - `SSE2` (x86_64) and `NEON` (aarch64) are part of the baseline of their
+///   architecture, so they can always be used
+/// - newer extensions (e.g. `AVX`) are detected at runtime with
+///   `is_x86_feature_detected!`, and used in functions compiled with
+///   `#[target_feature(enable = "...")]`, which are `unsafe` to call
+///
+/// The compiler also vectorizes simple loops on its own (auto-vectorization),
+/// in optimized builds: explicit intrinsics are for what it cannot infer.
+fn simd() {}
+
+/// ## Scalar Baselines
+pub fn dot_scalar(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+pub fn find_byte_scalar(haystack: &[u8], needle: u8) -> Option<usize> {
+    haystack.iter().position(|&byte| byte == needle)
+}
+
+/// ## x86_64: SSE2 and AVX
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::{dot_scalar, find_byte_scalar};
+    use std::arch::x86_64::*;
+
+    pub fn dot_sse2(a: &[f32], b: &[f32]) -> f32 {
+        assert_eq!(a.len(), b.len());
+        let chunks: usize = a.len() / 4;
+        // SAFETY: SSE2 is always available on x86_64, every load reads 4
+        // `f32` inside the slices (`loadu` accepts unaligned pointers), and
+        // `lanes` has room for 4 `f32`.
+        let sum: f32 = unsafe {
+            let mut sum: __m128 = _mm_setzero_ps();
+            for i in 0..chunks {
+                let x: __m128 = _mm_loadu_ps(a.as_ptr().add(4 * i));
+                let y: __m128 = _mm_loadu_ps(b.as_ptr().add(4 * i));
+                sum = _mm_add_ps(sum, _mm_mul_ps(x, y));  // 4 at once
+            }
+            let mut lanes: [f32; 4] = [0.0; 4];
+            _mm_storeu_ps(lanes.as_mut_ptr(), sum);
+            lanes.iter().sum()
+        };
+        sum + dot_scalar(&a[4 * chunks..], &b[4 * chunks..])  // remainder
+    }
+
+    /// SAFETY: the CPU must support AVX.
+    #[target_feature(enable = "avx")]
+    pub unsafe fn dot_avx(a: &[f32], b: &[f32]) -> f32 {
+        assert_eq!(a.len(), b.len());
+        let chunks: usize = a.len() / 8;
+        let mut sum: __m256 = _mm256_setzero_ps();
+        for i in 0..chunks {
+            let x: __m256 = _mm256_loadu_ps(a.as_ptr().add(8 * i));
+            let y: __m256 = _mm256_loadu_ps(b.as_ptr().add(8 * i));
+            sum = _mm256_add_ps(sum, _mm256_mul_ps(x, y));  // 8 at once
+        }
+        let mut lanes: [f32; 8] = [0.0; 8];
+        _mm256_storeu_ps(lanes.as_mut_ptr(), sum);
+        lanes.iter().sum::<f32>()
+            + dot_scalar(&a[8 * chunks..], &b[8 * chunks..])
+    }
+
+    /// Compare 16 bytes at once: `movemask` packs the 16 comparison results
+    /// into the bits of an integer, so the first match is its lowest set bit.
+    pub fn find_byte_sse2(haystack: &[u8], needle: u8) -> Option<usize> {
+        let chunks: usize = haystack.len() / 16;
+        for i in 0..chunks {
+            // SAFETY: SSE2 is always available on x86_64, and the 16 bytes
+            // from `16 * i` are inside `haystack`.
+            let mask: i32 = unsafe {
+                let pointer: *const __m128i =
+                    haystack.as_ptr().add(16 * i) as *const __m128i;
+                let bytes: __m128i = _mm_loadu_si128(pointer);
+                let needles: __m128i = _mm_set1_epi8(needle as i8);
+                _mm_movemask_epi8(_mm_cmpeq_epi8(bytes, needles))
+            };
+            if mask != 0 {
+                return Some(16 * i + mask.trailing_zeros() as usize);
+            }
+        }
+        find_byte_scalar(&haystack[16 * chunks..], needle)
+            .map(|position| 16 * chunks + position)
+    }
+}
+
+/// ## aarch64: NEON
+#[cfg(target_arch = "aarch64")]
+mod arm {
+    use super::{dot_scalar, find_byte_scalar};
+    use std::arch::aarch64::*;
+
+    pub fn dot_neon(a: &[f32], b: &[f32]) -> f32 {
+        assert_eq!(a.len(), b.len());
+        let chunks: usize = a.len() / 4;
+        // SAFETY: NEON is always available on aarch64, and every load reads
+        // 4 `f32` inside the slices.
+        let sum: f32 = unsafe {
+            let mut sum: float32x4_t = vdupq_n_f32(0.0);
+            for i in 0..chunks {
+                let x: float32x4_t = vld1q_f32(a.as_ptr().add(4 * i));
+                let y: float32x4_t = vld1q_f32(b.as_ptr().add(4 * i));
+                sum = vfmaq_f32(sum, x, y);  // sum + x * y, 4 at once
+            }
+            vaddvq_f32(sum)  // add the 4 lanes together
+        };
+        sum + dot_scalar(&a[4 * chunks..], &b[4 * chunks..])
+    }
+
+    /// NEON has no `movemask`: the chunk containing a match is found with
+    /// SIMD, then the exact position with a scalar search.
+    pub fn find_byte_neon(haystack: &[u8], needle: u8) -> Option<usize> {
+        let chunks: usize = haystack.len() / 16;
+        for i in 0..chunks {
+            // SAFETY: NEON is always available on aarch64, and the 16 bytes
+            // from `16 * i` are inside `haystack`.
+            let found: bool = unsafe {
+                let bytes: uint8x16_t = vld1q_u8(haystack.as_ptr().add(16 * i));
+                vmaxvq_u8(vceqq_u8(bytes, vdupq_n_u8(needle))) != 0
+            };
+            if found {
+                return find_byte_scalar(&haystack[16 * i..16 * i + 16], needle)
+                    .map(|position| 16 * i + position);
+            }
+        }
+        find_byte_scalar(&haystack[16 * chunks..], needle)
+            .map(|position| 16 * chunks + position)
+    }
+}
+
+/// ## Dispatch
+/// Pick the best implementation for the target, and for the CPU at runtime,
+/// falling back to the scalar version on other architectures.
+pub fn dot(a: &[f32], b: &[f32]) -> f32 {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx") {
+            // SAFETY: AVX support was just checked.
+            return unsafe { x86::dot_avx(a, b) };
+        }
+        x86::dot_sse2(a, b)
+    }
+    #[cfg(target_arch = "aarch64")]
+    { arm::dot_neon(a, b) }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    { dot_scalar(a, b) }
+}
+
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    { x86::find_byte_sse2(haystack, needle) }
+    #[cfg(target_arch = "aarch64")]
+    { arm::find_byte_neon(haystack, needle) }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    { find_byte_scalar(haystack, needle) }
+}
+
+runnable!(dot_product, {
+    // Small integers: the sums are exact, whatever the order of additions
+    let a: Vec<f32> = (0..1003).map(|i| (i % 7) as f32).collect();
+    let b: Vec<f32> = (0..1003).map(|i| (i % 5) as f32).collect();
+    assert_eq!(dot(&a, &b), dot_scalar(&a, &b));
+    assert_eq!(dot(&a[..3], &b[..3]), dot_scalar(&a[..3], &b[..3]));
+    #[cfg(target_arch = "x86_64")]
+    println!("AVX available: {}", is_x86_feature_detected!("avx"));
+});
+
+runnable!(byte_search, {
+    let text: &[u8] = b"SIMD searches sixteen bytes at a time!";
+    for needle in [b'S', b'x', b'!', b'?'] {
+        assert_eq!(find_byte(text, needle), find_byte_scalar(text, needle));
+    }
+    assert_eq!(find_byte(text, b'!'), Some(text.len() - 1));
+});
+
+/// ## Benchmarks
+/// Tests are not optimized (see `benchmark!` in util.rs): the scalar loops
+/// are not auto-vectorized, but each intrinsic also becomes a function call,
+/// which can eat the whole gain (the byte search is often no faster). The
+/// numbers only give a rough idea of the speedup of optimized code.
+runnable!(benchmarks, {
+    let a: Vec<f32> = (0..4096).map(|i| (i % 7) as f32).collect();
+    let b: Vec<f32> = (0..4096).map(|i| (i % 5) as f32).collect();
+    let scalar = benchmark!("dot (scalar)", 200, dot_scalar(&a, &b));
+    let simd = benchmark!("dot (SIMD)", 200, dot(&a, &b));
+    println!("dot speedup: {:.1}x",
+        scalar.as_secs_f64() / simd.as_secs_f64().max(1e-9));
+
+    let mut haystack: Vec<u8> = vec![b'.'; 64 * 1024];
+    *haystack.last_mut().unwrap() = b'!';
+    let scalar = benchmark!("find (scalar)", 50,
+        find_byte_scalar(&haystack, b'!'));
+    let simd = benchmark!("find (SIMD)", 50, find_byte(&haystack, b'!'));
+    println!("find speedup: {:.1}x",
+        scalar.as_secs_f64() / simd.as_secs_f64().max(1e-9));
+});
\ No newline at end of file
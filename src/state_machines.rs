@@ -0,0 +1,174 @@
+/// # State Machines
+/// A function that can be suspended and resumed (a `generator`, or the body
+/// of an `async fn`) must remember where it stopped and the values of its
+/// local variables. The compiler achieves this by turning the function into
+/// an enum: one variant per suspension point, holding the variables that are
+/// still alive at that point (see enums.rs). Resuming the function is then a
+/// `match` on the current state (see pattern_matching.rs).
+///
+/// Writing these state machines by hand shows what `async fn` and generators
+/// are compiled into (see futures.rs).
+fn state_machines() {}
+
+/// ## Generators
+/// A generator yields many values before returning a final one. Each call to
+/// `resume` runs the generator until its next `yield` (or its `return`).
+///
+/// The following generator, written in pseudo-Rust:
+/// ```
+/// || {
+///     let mut n = 3;
+///     while n > 0 { yield n; n -= 1; }
+///     return "liftoff";
+/// }
+/// ```
+/// is compiled into something similar to `Countdown`.
+#[derive(Debug, PartialEq)]
+enum GeneratorState<Y, R> { Yielded(Y), Complete(R) }
+
+enum Countdown {
+    Start,
+    Suspended { n: u32 },  // the only live variable at the `yield` is `n`
+    Done,
+}
+
+impl Countdown {
+    fn resume(&mut self) -> GeneratorState<u32, &'static str> {
+        // Each arm runs the code between two suspension points
+        let n: u32 = match *self {
+            Countdown::Start => 3,
+            Countdown::Suspended { n } => n - 1,
+            Countdown::Done => panic!("generator resumed after completion"),
+        };
+        if n > 0 {
+            *self = Countdown::Suspended { n };
+            GeneratorState::Yielded(n)
+        } else {
+            *self = Countdown::Done;
+            GeneratorState::Complete("liftoff")
+        }
+    }
+}
+
+runnable!(generators, {
+    let mut countdown: Countdown = Countdown::Start;
+    assert_eq!(countdown.resume(), GeneratorState::Yielded(3));
+    assert_eq!(countdown.resume(), GeneratorState::Yielded(2));
+    assert_eq!(countdown.resume(), GeneratorState::Yielded(1));
+    assert_eq!(countdown.resume(), GeneratorState::Complete("liftoff"));
+    // countdown.resume();
+    // ^ Error (at runtime): generator resumed after completion
+});
+
+/// ## Generators as Iterators
+/// A generator yielding values and completing with `()` is an iterator: the
+/// `Iterator` trait is itself a tiny state machine protocol.
+impl Iterator for Countdown {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> {
+        match self {
+            Countdown::Done => None,
+            _ => match self.resume() {
+                GeneratorState::Yielded(n) => Some(n),
+                GeneratorState::Complete(_) => None,
+            },
+        }
+    }
+}
+
+runnable!(generators_as_iterators, {
+    let numbers: Vec<u32> = Countdown::Start.collect();
+    println!("countdown: {:?}", numbers);
+    assert_eq!(numbers, vec![3, 2, 1]);
+});
+
+/// ## Resumable Parser
+/// Input often arrives in chunks (e.g. from a socket), and a token may be
+/// split across two chunks. A resumable parser consumes whatever input is
+/// available, then suspends, remembering in its state what it was doing.
+///
+/// This parser reads `key=value;` records. Its states are the points where
+/// it can be suspended, holding the partial data parsed so far:
+/// ```
+/// Key { key }  --'='-->  Value { key, value }  --';'-->  Key { key: "" }
+/// ```
+#[derive(Debug)]
+enum ParserState {
+    Key { key: String },
+    Value { key: String, value: String },
+    Failed(String),
+}
+
+struct Parser { state: ParserState, records: Vec<(String, String)> }
+
+impl Parser {
+    fn new() -> Parser {
+        let state: ParserState = ParserState::Key { key: String::new() };
+        Parser { state, records: vec![] }
+    }
+
+    /// Feed a new chunk of input to the parser, resuming from its state.
+    fn feed(&mut self, chunk: &str) {
+        for c in chunk.chars() {
+            // Take the current state by value, to move its data into the next
+            let state = std::mem::replace(
+                &mut self.state, ParserState::Failed(String::new())
+            );
+            self.state = match (state, c) {
+                (ParserState::Key { key }, '=') if !key.is_empty() =>
+                    ParserState::Value { key, value: String::new() },
+                (ParserState::Key { mut key }, c) if c.is_alphanumeric() => {
+                    key.push(c);
+                    ParserState::Key { key }
+                }
+                (ParserState::Value { key, value }, ';') => {
+                    self.records.push((key, value));
+                    ParserState::Key { key: String::new() }
+                }
+                (ParserState::Value { key, mut value }, c) if c != '=' => {
+                    value.push(c);
+                    ParserState::Value { key, value }
+                }
+                (ParserState::Failed(error), _) => ParserState::Failed(error),
+                (state, c) => ParserState::Failed(
+                    format!("unexpected {:?} in state {:?}", c, state)
+                ),
+            };
+        }
+    }
+}
+
+runnable!(resumable_parser, {
+    let mut parser: Parser = Parser::new();
+    // The record `name=Ferris;` is split across three chunks
+    for chunk in ["na", "me=Fer", "ris;age=", "8;"] {
+        parser.feed(chunk);
+        println!("after {:?}: {:?}", chunk, parser.state);
+    }
+    assert_eq!(parser.records, vec![
+        (String::from("name"), String::from("Ferris")),
+        (String::from("age"), String::from("8")),
+    ]);
+
+    let mut parser: Parser = Parser::new();
+    parser.feed("=oops;");
+    println!("failed: {:?}", parser.state);
+    assert!(matches!(parser.state, ParserState::Failed(_)));
+});
+
+/// ## Async Functions as State Machines
+/// An `async fn` is compiled in the same way: each `.await` is a suspension
+/// point, and `poll` plays the role of `resume`. The future returned by an
+/// `async fn` is as large as its largest state, which can be observed with
+/// `size_of_val` (see layout.rs).
+runnable!(async_state_size, {
+    async fn small() -> u8 { 0 }
+    async fn large() -> u8 {
+        let buffer: [u8; 1024] = [0; 1024];  // alive across the `.await`
+        small().await;
+        buffer[0]
+    }
+    println!("small: {} bytes", std::mem::size_of_val(&small()));
+    println!("large: {} bytes", std::mem::size_of_val(&large()));
+    assert!(std::mem::size_of_val(&large()) >= 1024);
+});
\ No newline at end of file
@@ -0,0 +1,101 @@
+/// # Static Assertions (~ C++ static_assert)
+/// Some properties of a program can be checked by the compiler instead of a
+/// test: the size of a type, the value of a constant, the traits implemented
+/// by a type. A failing `static assertion` stops the compilation, so the
+/// property holds in every build, and costs nothing at runtime.
+use std::cell::Cell;
+use std::mem::{align_of, size_of};
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn static_assertions() {}
+
+/// ## compile_error!
+/// `compile_error!("message")` fails the compilation with the given message
+/// when it is expanded. In a macro, it turns unsupported inputs into clear
+/// errors, instead of confusing errors in the expanded code.
+macro_rules! unsigned {
+    (8) => (u8);
+    (16) => (u16);
+    (32) => (u32);
+    (64) => (u64);
+    ($bits: tt) => (compile_error!(concat!(
+        "unsupported width: ", stringify!($bits), " (expected 8, 16, 32 or 64)"
+    )));
+}
+
+runnable!(compile_error_in_macros, {
+    let byte: unsigned!(8) = 255;
+    let word: unsigned!(32) = 65_536;
+    println!("byte: {} word: {}", byte, word);
+    // let odd: unsigned!(12) = 0;
+    // ^ Error: unsupported width: 12 (expected 8, 16, 32 or 64)
+
+    // `cfg!` and `compile_error!` can also reject unsupported configurations
+    // #[cfg(not(any(unix, windows)))]
+    // compile_error!("this playground only supports unix and windows");
+});
+
+/// ## Constant Assertions
+/// `assert!` can be used in constant expressions (see associated_consts.rs).
+/// Evaluating it in an unnamed constant `const _: () = assert!(...);` checks
+/// a condition at compile time, anywhere an item is allowed: a failure is a
+/// compile error.
+///
+/// A common use is to pin down the layout of types shared with other
+/// languages or written to disk (see layout.rs), so that an accidental
+/// change of a field is noticed immediately.
+#[repr(C)]
+struct Header {
+    magic: [u8; 4],
+    version: u16,
+    flags: u16,
+    length: u32,
+}
+
+const _: () = assert!(size_of::<Header>() == 12);
+const _: () = assert!(align_of::<Header>() == 4);
+const _: () = assert!(size_of::<Option<Box<Header>>>() == size_of::<usize>());
+
+const MAX_CONNECTIONS: usize = 64;
+const BUFFER_SIZE: usize = 4096;
+const _: () = assert!(
+    BUFFER_SIZE.is_power_of_two(),
+    "BUFFER_SIZE must be a power of two"
+);
+const _: () = assert!(MAX_CONNECTIONS * BUFFER_SIZE <= 1 << 20);
+
+// const _: () = assert!(size_of::<Header>() == 10, "unexpected header size");
+// ^ Error: evaluation panicked: unexpected header size
+
+runnable!(constant_assertions, {
+    println!("header: {} bytes, aligned to {}",
+        size_of::<Header>(), align_of::<Header>());
+    // Unlike a static assertion, a runtime `assert!` only fails when (and
+    // if) this line runs
+    assert_eq!(size_of::<Header>(), 12);
+});
+
+/// ## Asserting Trait Implementations
+/// `static_assert_impl!` (see util.rs) checks that a type implements some
+/// traits, by calling a generic function bound by those traits inside an
+/// unnamed constant. It is mostly used for auto traits (see
+/// marker_traits.rs), which a type can gain or lose silently when one of its
+/// fields changes. Similarly, `static_assert_layout!` checks that two types
+/// have the same size and alignment (see repr_transparent.rs).
+struct Job { id: u64, payload: Vec<u8>, owner: Arc<str> }
+
+static_assert_impl!(Job: Send + Sync);
+static_assert_impl!(Arc<Job>: Send + Sync + Clone);
+static_assert_impl!(str: Send + Sync);  // unsized types are supported
+// static_assert_impl!(Rc<Job>: Send);
+// ^ Error: `Rc<Job>` cannot be sent between threads safely
+// static_assert_impl!(Cell<u8>: Sync);
+// ^ Error: `Cell<u8>` cannot be shared between threads safely
+
+runnable!(asserting_trait_implementations, {
+    // The assertions above are checked even if this function never runs
+    let job: Job = Job { id: 1, payload: vec![1, 2, 3], owner: Arc::from("a") };
+    let handle = std::thread::spawn(move || job.payload.len());
+    println!("payload length: {}", handle.join().unwrap());
+});
\ No newline at end of file
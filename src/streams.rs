@@ -0,0 +1,190 @@
+/// # Async Streams (~ Async Iterators)
+/// A `Stream` is to `Future` what `Iterator` is to a single value: it
+/// produces many values over time, asynchronously.
+///
+/// `std` does not provide a `Stream` trait yet (the `futures` crate defines the
+/// one used by the ecosystem), so here we define a minimal one. Its only
+/// method mirrors `Future::poll`, returning:
+/// - `Poll::Ready(Some(item))`: the next item is available
+/// - `Poll::Ready(None)`: the stream is exhausted
+/// - `Poll::Pending`: the next item is not available yet
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use crate::async_await::{block_on, join};
+use crate::futures::{yield_now, Delay};
+
+fn streams() {}
+
+/// ## Stream Trait
+pub(crate) trait Stream {
+    type Item;
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Self::Item>>;
+}
+
+/// ## Consuming Streams
+/// `next` turns a stream into a future resolving to its next item, so that
+/// streams can be consumed with `while let Some(x) = stream.next().await`.
+pub(crate) trait StreamExt: Stream {
+    fn next(&mut self) -> Next<'_, Self> where Self: Unpin {
+        Next { stream: self }
+    }
+}
+impl<S: Stream + ?Sized> StreamExt for S {}
+
+pub(crate) struct Next<'a, S: ?Sized> { stream: &'a mut S }
+
+impl<S: Stream + Unpin + ?Sized> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(cx)
+    }
+}
+
+/// ## Interval Stream
+/// An interval yields a tick every `period`, for a given number of ticks. It
+/// is built on top of `Delay`, which is woken by the timer reactor.
+pub(crate) struct Interval { period: Duration, delay: Delay, remaining: u32 }
+
+impl Interval {
+    pub(crate) fn new(period: Duration, ticks: u32) -> Interval {
+        Interval { period, delay: Delay::new(period), remaining: ticks }
+    }
+}
+
+impl Stream for Interval {
+    type Item = u32;
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<u32>> {
+        if self.remaining == 0 { return Poll::Ready(None); }
+        match Pin::new(&mut self.delay).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(()) => {
+                self.remaining -= 1;
+                self.delay = Delay::new(self.period);
+                Poll::Ready(Some(self.remaining))
+            }
+        }
+    }
+}
+
+runnable!(interval_stream, {
+    let ticks: Vec<u32> = block_on(async {
+        let mut interval: Interval = Interval::new(Duration::from_millis(5), 3);
+        let mut ticks: Vec<u32> = Vec::new();
+        while let Some(remaining) = interval.next().await {
+            println!("tick, {} remaining", remaining);
+            ticks.push(remaining);
+        }
+        ticks
+    });
+    assert_eq!(ticks, vec![2, 1, 0]);
+});
+
+/// ## Channel Stream
+/// An unbounded channel whose receiving half is a stream. The sender pushes
+/// items into a shared queue and wakes the receiver, which stores its waker
+/// whenever it finds the queue empty. The stream ends when all the senders
+/// have been dropped.
+struct Shared<T> { queue: VecDeque<T>, waker: Option<Waker>, senders: usize }
+
+pub(crate) struct Sender<T> { shared: Arc<Mutex<Shared<T>>> }
+pub(crate) struct Receiver<T> { shared: Arc<Mutex<Shared<T>>> }
+
+pub(crate) fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let shared: Arc<Mutex<Shared<T>>> = Arc::new(Mutex::new(Shared {
+        queue: VecDeque::new(), waker: None, senders: 1,
+    }));
+    (Sender { shared: shared.clone() }, Receiver { shared })
+}
+
+impl<T> Sender<T> {
+    pub(crate) fn send(&self, item: T) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.queue.push_back(item);
+        if let Some(waker) = shared.waker.take() { waker.wake(); }
+    }
+}
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().unwrap().senders += 1;
+        Sender { shared: self.shared.clone() }
+    }
+}
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.senders -= 1;
+        // The receiver must be woken to notice that the stream has ended
+        if shared.senders == 0 {
+            if let Some(waker) = shared.waker.take() { waker.wake(); }
+        }
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<T>> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.queue.pop_front() {
+            Some(item) => Poll::Ready(Some(item)),
+            None if shared.senders == 0 => Poll::Ready(None),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+runnable!(channel_stream, {
+    let (sender, mut receiver) = channel::<String>();
+
+    let producer = async move {
+        for i in 0..3 {
+            sender.send(format!("message {}", i));
+            yield_now().await;
+        }
+        // <-- Here `sender` is dropped, ending the stream
+    };
+    let consumer = async move {
+        let mut received: Vec<String> = Vec::new();
+        while let Some(message) = receiver.next().await {
+            println!("received: {}", message);
+            received.push(message);
+        }
+        received
+    };
+    let ((), received) = block_on(join(producer, consumer));
+    assert_eq!(received.len(), 3);
+});
+
+/// ## Streams across Threads
+/// Since the channel wakes the receiver through its `Waker`, the sender can
+/// also live on another thread.
+runnable!(threaded_channel_stream, {
+    let (sender, mut receiver) = channel::<u32>();
+    let producer = std::thread::spawn(move || {
+        for i in 1..=3 {
+            std::thread::sleep(Duration::from_millis(5));
+            sender.send(i);
+        }
+    });
+    let sum: u32 = block_on(async {
+        let mut sum: u32 = 0;
+        while let Some(i) = receiver.next().await { sum += i; }
+        sum
+    });
+    producer.join().unwrap();
+    println!("sum: {}", sum);
+    assert_eq!(sum, 6);
+});
\ No newline at end of file
@@ -48,6 +48,7 @@ runnable!(destructuring, {
     println!("id={} age={}", x, y);
 
     /// ### Let-Else - Handling Destructuring Failures
+    /// See let_else.rs for early returns with `let-else`.
     let person: Person = Person { id: 2, age: 20 };
     let Person { id: 3, age: person_age } = person else {
         panic!("Pattern match failed on variable '{person:?}': id mismatch");
@@ -0,0 +1,127 @@
+/// # Target-Specific Code
+/// The same crate can be compiled for many targets: operating systems,
+/// architectures, pointer widths. The compiler sets a configuration option
+/// for each property of the target (see the conditional compilation section
+/// of annotations.rs), so that code can adapt to it:
+/// - `#[cfg(...)]` keeps or removes the following item
+/// - `#[cfg_attr(condition, attribute)]` applies an attribute conditionally
+/// - `cfg!(...)` evaluates a condition to a `bool` at compile time
+///
+/// The options of a target can be listed with
+/// `rustc --print cfg --target <triple>`.
+fn target_config() {}
+
+/// ## Operating Systems
+/// Exactly one of these functions is compiled. The last one covers every
+/// other system, so that the crate compiles everywhere.
+#[cfg(target_os = "linux")]
+fn temp_dir_hint() -> &'static str { "/tmp (or $TMPDIR)" }
+#[cfg(target_os = "macos")]
+fn temp_dir_hint() -> &'static str { "$TMPDIR (e.g. /var/folders/...)" }
+#[cfg(target_os = "windows")]
+fn temp_dir_hint() -> &'static str { "%TEMP% (e.g. C:\\Users\\...\\Temp)" }
+#[cfg(not(any(
+    target_os = "linux", target_os = "macos", target_os = "windows"
+)))]
+fn temp_dir_hint() -> &'static str { "unknown" }
+
+/// Families group similar systems (`unix` is a shorthand for
+/// `target_family = "unix"`, which includes Linux and macOS).
+#[cfg(unix)]
+const PATH_SEPARATOR: char = '/';
+#[cfg(windows)]
+const PATH_SEPARATOR: char = '\\';
+#[cfg(not(any(unix, windows)))]
+const PATH_SEPARATOR: char = '/';
+
+runnable!(operating_systems, {
+    println!("temporary files: {}", temp_dir_hint());
+    println!("path separator: {:?}", PATH_SEPARATOR);
+    println!("standard library says: {:?}", std::env::temp_dir());
+    assert_eq!(PATH_SEPARATOR, std::path::MAIN_SEPARATOR);
+});
+
+/// ## Pointer Width
+/// `target_pointer_width` is the size of `usize` and of pointers, in bits.
+/// Code relying on a specific width should state it, instead of silently
+/// truncating values on smaller targets.
+#[cfg(target_pointer_width = "64")]
+type HalfWord = u32;
+#[cfg(target_pointer_width = "32")]
+type HalfWord = u16;
+#[cfg(target_pointer_width = "16")]
+type HalfWord = u8;
+
+const _: () = assert!(
+    2 * std::mem::size_of::<HalfWord>() == std::mem::size_of::<usize>()
+);  // see static_assertions.rs
+
+// #[cfg(not(target_pointer_width = "64"))]
+// compile_error!("this crate requires a 64-bit target");
+
+runnable!(pointer_width, {
+    let bits: usize = usize::BITS as usize;
+    println!("usize: {} bits, half word: {} bits",
+        bits, 8 * std::mem::size_of::<HalfWord>());
+    // On 32-bit targets, this conversion would lose the upper bits
+    let big: u64 = 1 << 40;
+    match usize::try_from(big) {
+        Ok(value) => println!("{} fits in a usize", value),
+        Err(error) => println!("{} does not fit in a usize: {}", big, error),
+    }
+});
+
+/// ## Conditional Attributes
+/// `cfg_attr` only applies an attribute when a condition holds. Common uses
+/// are deriving traits only for tests (so they do not weigh on normal builds)
+/// and enabling lints or attributes for specific targets.
+#[cfg_attr(test, derive(Debug, PartialEq))]
+struct Settings { threads: usize }
+
+/// Attributes can also depend on the target, e.g. to link a system library
+/// only where it exists (see unsafe_code.rs)
+#[cfg_attr(target_os = "linux", link(name = "m"))]
+extern "C" { fn cbrt(x: f64) -> f64; }
+
+runnable!(conditional_attributes, {
+    // `#[test]` functions are compiled with `cfg(test)`, so `Debug` and
+    // `PartialEq` are available here (but not in `cargo build`)
+    let settings: Settings = Settings { threads: 4 };
+    println!("{:?}", settings);
+    assert_eq!(settings, Settings { threads: 4 });
+    println!("cbrt(27) = {:.3}", unsafe { cbrt(27.0) });
+});
+
+/// ## Configuration Report
+/// `cfg!` turns any configuration option into a boolean, e.g. to print the
+/// configuration the crate was compiled with. Options with a value are
+/// compared against each possible value.
+macro_rules! first_enabled {
+    ($key: ident: $($value: literal),+) => ({
+        let mut found: &str = "other";
+        $(if cfg!($key = $value) { found = $value; })+
+        found
+    });
+}
+
+runnable!(configuration_report, {
+    let report: [(&str, &str); 7] = [
+        ("target_os", first_enabled!(target_os:
+            "linux", "macos", "windows", "android", "ios", "freebsd")),
+        ("target_family", first_enabled!(target_family: "unix", "windows")),
+        ("target_arch", first_enabled!(target_arch:
+            "x86_64", "x86", "aarch64", "arm", "riscv64", "wasm32")),
+        ("target_env", first_enabled!(target_env: "gnu", "musl", "msvc")),
+        ("target_endian", first_enabled!(target_endian: "little", "big")),
+        ("target_pointer_width",
+            first_enabled!(target_pointer_width: "64", "32", "16")),
+        ("panic", first_enabled!(panic: "unwind", "abort")),
+    ];
+    for (key, value) in report {
+        println!("{:<22} {}", key, value);
+    }
+    println!("{:<22} {}", "debug_assertions", cfg!(debug_assertions));
+    println!("{:<22} {}", "test", cfg!(test));
+    println!("{:<22} {}", "target_feature sse2",
+        cfg!(target_feature = "sse2"));
+});
\ No newline at end of file
@@ -0,0 +1,15 @@
+/// # Testing Techniques
+/// The basics of testing are covered in unit_testing.rs. Code talking to
+/// the outside world (the network, the clock, the file system) needs more:
+/// ways to replace those dependencies in tests, and to check how the code
+/// used them.
+///
+/// Each submodule in the `testing` folder covers one of these techniques
+/// (see modules.rs for how directory modules work).
+fn testing() {}
+
+/// ## Mocks
+mod mocks;
+
+/// ## Snapshots
+mod snapshots;
@@ -0,0 +1,269 @@
+/// # Mocks (~ a stunt double)
+/// A `test double` stands in for a real dependency during a test. They come
+/// in several kinds, by how much they do:
+/// - a `stub` returns canned responses
+/// - a `fake` is a working, simplified implementation, like an in-memory
+///   storage (see patterns/dependency_injection.rs)
+/// - a `mock` is scripted with the calls it expects, in order, and checks
+///   that the code under test made exactly those calls
+///
+/// Crates such as `mockall` generate mocks from a trait with a macro. Written
+/// by hand, a mock is just another implementation of the trait, with no
+/// magic: the code under test only needs to be generic over the trait.
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+fn mocks() {}
+
+/// ## The Dependency
+pub trait WeatherService {
+    /// The current temperature in a city, in degrees Celsius.
+    fn temperature(&self, city: &str) -> Result<f64, WeatherError>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WeatherError {
+    UnknownCity(String),
+    /// The service cannot be reached, or its answer makes no sense.
+    Unavailable,
+}
+
+impl fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WeatherError::UnknownCity(city) =>
+                write!(f, "unknown city {}", city),
+            WeatherError::Unavailable =>
+                write!(f, "weather service unavailable"),
+        }
+    }
+}
+
+impl From<io::Error> for WeatherError {
+    fn from(_: io::Error) -> WeatherError { WeatherError::Unavailable }
+}
+
+/// ## The Real Implementation
+/// Asks a weather server over HTTP: `GET /temperature/<city>` answers with
+/// the temperature as plain text, or with `404` for an unknown city. This
+/// is slow, needs the server to be up, and its answers change with the
+/// weather: everything a unit test should not depend on.
+pub struct HttpWeather {
+    /// The address of the server, e.g. `127.0.0.1:8080`.
+    pub address: String,
+}
+
+impl WeatherService for HttpWeather {
+    fn temperature(&self, city: &str) -> Result<f64, WeatherError> {
+        let mut stream: TcpStream = TcpStream::connect(&self.address)?;
+        let request: String =
+            format!("GET /temperature/{} HTTP/1.0\r\n\r\n", city);
+        stream.write_all(request.as_bytes())?;      // in one piece
+        let mut response: String = String::new();
+        stream.read_to_string(&mut response)?;
+        let (head, body) = response.split_once("\r\n\r\n")
+            .ok_or(WeatherError::Unavailable)?;
+        match head.split(' ').nth(1) {
+            Some("200") => body.trim().parse()
+                .map_err(|_| WeatherError::Unavailable),
+            Some("404") => Err(WeatherError::UnknownCity(city.to_string())),
+            _ => Err(WeatherError::Unavailable),
+        }
+    }
+}
+
+/// ## The Code Under Test
+/// Generic over the service, like the `ReportGenerator` of
+/// patterns/dependency_injection.rs.
+pub struct TripPlanner<W> {
+    weather: W,
+}
+
+impl<W: WeatherService> TripPlanner<W> {
+    pub fn new(weather: W) -> TripPlanner<W> { TripPlanner { weather } }
+
+    pub fn advice(&self, city: &str) -> String {
+        match self.weather.temperature(city) {
+            Ok(celsius) if celsius < 5.0 => "bring a coat".to_string(),
+            Ok(celsius) if celsius < 20.0 => "bring a sweater".to_string(),
+            Ok(_) => "bring sunscreen".to_string(),
+            Err(error) => format!("no advice: {}", error),
+        }
+    }
+
+    /// The warmest of `cities`, skipping the unknown ones. The search stops
+    /// at the first other error: the service is down, no need to insist.
+    pub fn warmest<'a>(&self, cities: &[&'a str])
+        -> Result<Option<&'a str>, WeatherError>
+    {
+        let mut warmest: Option<(&str, f64)> = None;
+        for city in cities {
+            match (self.weather.temperature(city), warmest) {
+                (Ok(celsius), Some((_, best))) if celsius <= best => {}
+                (Ok(celsius), _) => warmest = Some((city, celsius)),
+                (Err(WeatherError::UnknownCity(_)), _) => {}
+                (Err(error), _) => return Err(error),
+            }
+        }
+        Ok(warmest.map(|(city, _)| city))
+    }
+}
+
+/// Testing the real implementation needs a real server: here, one started
+/// on a free local port for the duration of the runnable, answering two
+/// requests. The server reads each request up to its end: closing a
+/// connection with unread data resets it, and the client gets an error
+/// instead of the response.
+runnable!(real_service, {
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address: String = listener.local_addr().unwrap().to_string();
+    let server = thread::spawn(move || {
+        for stream in listener.incoming().take(2) {
+            let mut stream: TcpStream = stream.unwrap();
+            let mut request: Vec<u8> = Vec::new();
+            let mut buffer: [u8; 512] = [0; 512];
+            while !request.ends_with(b"\r\n\r\n") {
+                let length: usize = stream.read(&mut buffer).unwrap();
+                if length == 0 { break; }
+                request.extend_from_slice(&buffer[..length]);
+            }
+            let response: &str =
+                if request.starts_with(b"GET /temperature/Rome ") {
+                    "HTTP/1.0 200 OK\r\n\r\n21.5\n"
+                } else {
+                    "HTTP/1.0 404 Not Found\r\n\r\n"
+                };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    let planner = TripPlanner::new(HttpWeather { address });
+    assert_eq!(planner.advice("Rome"), "bring sunscreen");
+    assert_eq!(planner.advice("Atlantis"), "no advice: unknown city Atlantis");
+    server.join().unwrap();
+});
+
+/// ## A Scripted Mock
+/// The mock is given a script of expected calls, each with its canned
+/// response. Every call is recorded, checked against the next line of the
+/// script, and answered with its response.
+///
+/// `temperature` takes `&self`, so the mock updates its script and its
+/// record through `RefCell`s (see interior_mutability.rs). It checks that
+/// the whole script was played when dropped, at the end of the test: unless
+/// the test is already panicking, as a second panic would abort the process.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+
+    #[derive(Default)]
+    struct MockWeather {
+        script: RefCell<VecDeque<(String, Result<f64, WeatherError>)>>,
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl MockWeather {
+        fn expect(self, city: &str, response: Result<f64, WeatherError>)
+            -> MockWeather
+        {
+            self.script.borrow_mut().push_back((city.to_string(), response));
+            self
+        }
+
+        fn calls(&self) -> Vec<String> { self.calls.borrow().clone() }
+    }
+
+    impl WeatherService for MockWeather {
+        fn temperature(&self, city: &str) -> Result<f64, WeatherError> {
+            self.calls.borrow_mut().push(city.to_string());
+            let Some((expected, response)) =
+                self.script.borrow_mut().pop_front()
+            else {
+                panic!("unexpected call temperature({:?})", city);
+            };
+            assert_eq!(city, expected, "wrong argument to temperature");
+            response
+        }
+    }
+
+    impl Drop for MockWeather {
+        fn drop(&mut self) {
+            let script = self.script.borrow();
+            if !script.is_empty() && !thread::panicking() {
+                panic!("expected calls were not made: {:?}", script);
+            }
+        }
+    }
+
+    #[test]
+    fn test_advice() {
+        let mock: MockWeather = MockWeather::default()
+            .expect("Oslo", Ok(-3.0))
+            .expect("Paris", Ok(12.0))
+            .expect("Cairo", Ok(35.0))
+            .expect("Bern", Err(WeatherError::Unavailable));
+        let planner = TripPlanner::new(mock);
+        assert_eq!(planner.advice("Oslo"), "bring a coat");
+        assert_eq!(planner.advice("Paris"), "bring a sweater");
+        assert_eq!(planner.advice("Cairo"), "bring sunscreen");
+        assert_eq!(planner.advice("Bern"),
+            "no advice: weather service unavailable");
+    }
+
+    #[test]
+    fn test_warmest_asks_each_city_once() {
+        let mock: MockWeather = MockWeather::default()
+            .expect("Oslo", Ok(-3.0))
+            .expect("Cairo", Ok(35.0))
+            .expect("Paris", Ok(12.0));
+        let planner = TripPlanner::new(mock);
+        let warmest = planner.warmest(&["Oslo", "Cairo", "Paris"]);
+        assert_eq!(warmest, Ok(Some("Cairo")));
+        assert_eq!(planner.weather.calls(), ["Oslo", "Cairo", "Paris"]);
+    }
+
+    #[test]
+    fn test_warmest_skips_unknown_cities() {
+        let unknown = || Err(WeatherError::UnknownCity("?".to_string()));
+        let mock: MockWeather = MockWeather::default()
+            .expect("Atlantis", unknown())
+            .expect("Oslo", Ok(-3.0))
+            .expect("El Dorado", unknown());
+        let planner = TripPlanner::new(mock);
+        let warmest = planner.warmest(&["Atlantis", "Oslo", "El Dorado"]);
+        assert_eq!(warmest, Ok(Some("Oslo")));
+    }
+
+    /// The record shows what did not happen, too: no call after the outage.
+    #[test]
+    fn test_warmest_stops_when_unavailable() {
+        let mock: MockWeather = MockWeather::default()
+            .expect("Oslo", Ok(-3.0))
+            .expect("Cairo", Err(WeatherError::Unavailable));
+        let planner = TripPlanner::new(mock);
+        let warmest = planner.warmest(&["Oslo", "Cairo", "Paris"]);
+        assert_eq!(warmest, Err(WeatherError::Unavailable));
+        assert_eq!(planner.weather.calls(), ["Oslo", "Cairo"]);
+    }
+
+    /// The mock fails the test by itself, when the script is not followed.
+    #[test]
+    #[should_panic(expected = "unexpected call temperature(\"Paris\")")]
+    fn test_unexpected_call() {
+        let planner = TripPlanner::new(MockWeather::default());
+        planner.advice("Paris");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected calls were not made")]
+    fn test_missing_call() {
+        let mock: MockWeather = MockWeather::default()
+            .expect("Oslo", Ok(-3.0))
+            .expect("Paris", Ok(12.0));
+        let planner = TripPlanner::new(mock);
+        planner.advice("Oslo");
+    }
+}
@@ -0,0 +1,57 @@
+/// # Snapshot Testing (~ a photo of the expected result)
+/// A snapshot test records the output of the code once, and compares every
+/// later run against the recording (a golden file, see util/golden.rs). It
+/// checks everything at once, without an assertion per field: the test is
+/// cheap to write, and catches any change, intended or not.
+///
+/// The derived `Debug` output makes a good snapshot of a lesson type: it
+/// follows the structure of the type, so renaming a field, adding a variant
+/// or changing the shape of a tree changes it. The types of the playground
+/// are recorded by the runnables next to them:
+/// - `Point` in methods.rs
+/// - the syntax tree of the expression parser in parser.rs
+/// - the error chains in errors.rs
+use crate::util::golden;
+use crate::util::TestDir;
+
+fn snapshots() {}
+
+/// ## Reading a Mismatch
+/// When the output changes, the test fails with the lines which differ,
+/// numbered, the recorded ones with `-` and the new ones with `+`.
+#[derive(Debug)]
+struct Config {
+    host: String,
+    port: u16,
+    verbose: bool,
+}
+
+runnable!(reading_a_mismatch, {
+    if std::env::var_os("UPDATE_GOLDEN").is_some() { return; }  // no diff
+    let directory: TestDir = TestDir::new("snapshots");
+    let recorded: Config =
+        Config { host: "localhost".to_string(), port: 80, verbose: false };
+    std::fs::write(directory.join("config.txt"), format!("{:#?}\n", recorded))
+        .unwrap();
+
+    let current: Config = Config { port: 8080, ..recorded };
+    let message: String = golden::check(
+        directory.path(), "config", &format!("{:#?}\n", current)
+    ).unwrap_err();
+    println!("{}", message);
+    assert!(message.contains("   3 -     port: 80,\n   3 +     port: 8080,"));
+});
+
+/// ## What Not to Record
+/// A snapshot must be the same on every run, and on every machine. Values
+/// which are not should be left out, or replaced before the comparison
+/// (`redacted`):
+/// - the order of a `HashMap` changes from run to run
+/// - addresses, timings, random numbers and dates
+/// - messages from the operating system: the `Debug` of an `io::Error`
+///   contains an error code and a message which depend on the platform
+///
+/// A snapshot also cannot tell an intended change from a bug: each update
+/// of a golden file is a claim that the new output is right, and deserves
+/// the same review as a change to a hand-written assertion.
+fn what_not_to_record() {}
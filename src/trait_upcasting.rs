@@ -0,0 +1,113 @@
+/// # Trait Upcasting (~ Java Interface Inheritance)
+/// A trait object of a sub-trait can be converted into a trait object of one
+/// of its supertraits (see the supertraits section of traits.rs), like an
+/// object of a Java interface can be used as an object of its parent
+/// interfaces. This conversion is called `trait upcasting`.
+///
+/// The vtable of a sub-trait (see dyn_internals.rs) contains pointers to the
+/// vtables of its supertraits, so upcasting only replaces the vtable pointer
+/// of the fat pointer.
+fn trait_upcasting() {}
+
+trait Person {
+    fn name(&self) -> String;
+}
+trait Student: Person {
+    fn university(&self) -> String;
+}
+trait Programmer {
+    fn main_language(&self) -> String;
+}
+trait ComputerScienceStudent: Student + Programmer {}
+
+struct Alice;
+impl Person for Alice {
+    fn name(&self) -> String { String::from("Alice") }
+}
+impl Student for Alice {
+    fn university(&self) -> String { String::from("Unibo") }
+}
+impl Programmer for Alice {
+    fn main_language(&self) -> String { String::from("Rust") }
+}
+impl ComputerScienceStudent for Alice {}
+
+fn greet(person: &dyn Person) -> String {
+    format!("Hello, {}!", person.name())
+}
+
+/// ## Upcasting Coercions
+/// Since Rust 1.86, `&dyn Student` coerces to `&dyn Person` implicitly,
+/// wherever the supertrait object is expected. The same works for the other
+/// pointer types (`Box`, `Rc`, `Arc`, ...).
+runnable!(upcasting_coercions, {
+    let student: &dyn Student = &Alice;
+    let person: &dyn Person = student;  // upcasting coercion
+    println!("{}", greet(person));
+    println!("{}", greet(student));     // coerced at the call site
+
+    let boxed: Box<dyn Student> = Box::new(Alice);
+    let boxed_person: Box<dyn Person> = boxed;
+    println!("{}", boxed_person.name());
+
+    // Downcasting is not possible: the vtable of `Person` knows nothing
+    // about `Student` (downcasting to a concrete type requires `std::any::Any`)
+    // let back: &dyn Student = person;
+    // ^ Error: expected trait `Student`, found trait `Person`
+});
+
+/// ## Workaround for Older Toolchains
+/// Before Rust 1.86, upcasting was rejected:
+/// ```
+/// let person: &dyn Person = student;
+/// // ^ Error: cannot cast `dyn Student` to `dyn Person`, trait upcasting
+/// //          coercion is experimental
+/// ```
+/// The usual workaround is an `as_supertrait` method in the sub-trait. It
+/// cannot have a default implementation (converting `&Self` into
+/// `&dyn Person` requires `Self: Sized`), so a generic implementation of a
+/// helper trait provides it instead, for every sized type at once.
+trait AsPerson {
+    fn as_person(&self) -> &dyn Person;
+}
+impl<T: Person> AsPerson for T {
+    fn as_person(&self) -> &dyn Person { self }
+}
+
+trait LegacyStudent: Person + AsPerson {
+    fn university(&self) -> String;
+}
+impl LegacyStudent for Alice {
+    fn university(&self) -> String { String::from("Unibo") }
+}
+
+runnable!(upcasting_workaround, {
+    let student: &dyn LegacyStudent = &Alice;
+    // Dispatched through the vtable of `LegacyStudent`, which returns the
+    // concrete type coerced to a `&dyn Person`
+    let person: &dyn Person = student.as_person();
+    println!("{}", greet(person));
+});
+
+/// ## Multiple Supertraits
+/// A trait object of a trait with many supertraits can be upcast to any of
+/// them, following the supertrait hierarchy.
+runnable!(multiple_supertraits, {
+    let cs_student: &dyn ComputerScienceStudent = &Alice;
+    let student: &dyn Student = cs_student;
+    let programmer: &dyn Programmer = cs_student;
+    let person: &dyn Person = cs_student;  // through `Student`
+    println!("{} studies at {} and writes {}",
+        person.name(), student.university(), programmer.main_language());
+
+    // A trait object can only have one non-auto trait: combining traits
+    // requires a sub-trait like `ComputerScienceStudent`
+    // let both: &(dyn Student + Programmer) = &Alice;
+    // ^ Error: only auto traits can be used as additional traits in a trait
+    //          object
+
+    // Auto traits can be added, and dropped by upcasting
+    let sendable: &(dyn Student + Send) = &Alice;
+    let plain: &dyn Person = sendable;
+    println!("{}", greet(plain));
+});
\ No newline at end of file
@@ -71,6 +71,7 @@ runnable!(traits, {
     /// type using the keyword `dyn`.
     /// 
     /// See: https://doc.rust-lang.org/reference/items/traits.html#object-safety
+    /// (and object_safety.rs for the rules, one by one)
     trait HasNoise { fn noise(&self) -> &'static str; }  // Trait Object
 
     impl HasNoise for Cat { fn noise(&self) -> &'static str { "Miao" } }
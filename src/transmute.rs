@@ -0,0 +1,141 @@
+/// # Transmute (~ reinterpret_cast)
+/// `std::mem::transmute::<Src, Dst>(value)` reinterprets the bits of a value
+/// as another type. The only check is that both types have the same size:
+/// everything else (valid values, alignment, lifetimes) is the caller's
+/// responsibility. It is one of the most dangerous functions in Rust, and
+/// most of its uses have a safe (or safer) alternative.
+use std::mem::{align_of, size_of, transmute};
+
+fn transmute_lesson() {}
+
+/// ## Transmute
+/// The sizes are checked at compile time.
+/// ```
+/// let wide: u64 = unsafe { transmute(1u32) };
+/// // ^ Error: cannot transmute between types of different sizes, or
+/// //          dependently-sized types
+/// ```
+runnable!(transmute_bits, {
+    // SAFETY: `f32` and `u32` have the same size, and every bit pattern is a
+    // valid `u32`.
+    let bits: u32 = unsafe { transmute(1.0f32) };
+    assert_eq!(bits, 0x3f80_0000);
+    // SAFETY: every 4-byte pattern is a valid `[u8; 4]`.
+    let bytes: [u8; 4] = unsafe { transmute(bits) };
+    println!("1.0f32 = {:#010x} = {:?}", bits, bytes);
+});
+
+/// ## Safe Alternatives for Numbers
+/// Reinterpreting numbers has dedicated safe methods, which also make the
+/// byte order explicit (`ne` = native endianness, `le`/`be` = little/big).
+runnable!(safe_alternatives_for_numbers, {
+    let bits: u32 = 1.0f32.to_bits();
+    assert_eq!(f32::from_bits(bits), 1.0);
+    let native: [u8; 4] = bits.to_ne_bytes();  // same as `transmute`
+    let little: [u8; 4] = bits.to_le_bytes();  // same on every machine
+    assert_eq!(u32::from_ne_bytes(native), bits);
+    assert_eq!(little, [0x00, 0x00, 0x80, 0x3f]);
+    // Enums to integers: `as`, for field-less enums (see enums.rs)
+    #[derive(Clone, Copy)]
+    enum Level { Low = 1, High = 2 }
+    assert_eq!(Level::High as u8, 2);
+});
+
+/// ## Pointer Casts
+/// Casting a raw pointer with `as` changes the type it points to, like
+/// `transmute` on a reference, but reading through it remains an explicit,
+/// separate `unsafe` operation, where alignment can be handled (e.g. with
+/// `read_unaligned`).
+runnable!(pointer_casts, {
+    let bytes: [u8; 8] = [1, 0, 0, 0, 2, 0, 0, 0];
+    let pointer: *const u32 = bytes.as_ptr() as *const u32;
+    // SAFETY: the 8 bytes are readable, and `read_unaligned` does not need
+    // `pointer` to be aligned to 4 (an array of `u8` is only aligned to 1).
+    let (first, second): (u32, u32) = unsafe {
+        (pointer.read_unaligned(), pointer.add(1).read_unaligned())
+    };
+    assert_eq!((first, second), (u32::from_le_bytes([1, 0, 0, 0]),
+        u32::from_le_bytes([2, 0, 0, 0])));
+});
+
+/// ## Slices with `align_to`
+/// `slice::align_to` splits a slice into an unaligned prefix, an aligned
+/// middle reinterpreted as another type, and a suffix: the alignment is
+/// handled, while the validity of the values is still up to the caller.
+runnable!(slices_with_align_to, {
+    let bytes: Vec<u8> = (0..16).collect();
+    // SAFETY: every 4-byte pattern is a valid `u32`.
+    let (prefix, middle, suffix) = unsafe { bytes[1..].align_to::<u32>() };
+    println!("prefix: {:?}, middle: {:?}, suffix: {:?}",
+        prefix, middle, suffix);
+    assert_eq!(prefix.len() + 4 * middle.len() + suffix.len(), 15);
+});
+
+/// ## Checked Casts (~ bytemuck)
+/// Crates like `bytemuck` make casts safe by restricting them to `Pod`
+/// ("plain old data") types, for which every bit pattern is valid and which
+/// have no padding: the remaining checks (size and alignment) are done at
+/// runtime.
+///
+/// Implementing `Pod` is `unsafe`: it is a promise about the type.
+unsafe trait Pod: Copy {}
+unsafe impl Pod for u8 {}
+unsafe impl Pod for u16 {}
+unsafe impl Pod for u32 {}
+unsafe impl Pod for f32 {}
+
+#[derive(Debug, PartialEq)]
+enum CastError { Misaligned, WrongLength }
+
+fn cast_slice<T: Pod, U: Pod>(slice: &[T]) -> Result<&[U], CastError> {
+    let length: usize = std::mem::size_of_val(slice);
+    if slice.as_ptr() as usize % align_of::<U>() != 0 {
+        return Err(CastError::Misaligned);
+    }
+    if length % size_of::<U>() != 0 { return Err(CastError::WrongLength); }
+    // SAFETY: the pointer is aligned for `U`, the length covers exactly the
+    // bytes of `slice`, and every bit pattern is a valid `U` (`U: Pod`).
+    Ok(unsafe {
+        std::slice::from_raw_parts(slice.as_ptr() as *const U,
+            length / size_of::<U>())
+    })
+}
+
+runnable!(checked_casts, {
+    let words: [u32; 2] = [1, 2];
+    let halves: &[u16] = cast_slice(&words).unwrap();
+    assert_eq!(halves.len(), 4);
+    let floats: &[f32] = cast_slice(&words).unwrap();
+    assert_eq!(floats[0].to_bits(), 1);
+    let bytes: &[u8] = cast_slice(&words).unwrap();
+    assert_eq!(cast_slice::<u8, u32>(&bytes[1..5]),
+        Err(CastError::Misaligned));
+    assert_eq!(cast_slice::<u8, u32>(&bytes[..3]),
+        Err(CastError::WrongLength));
+});
+
+/// ## Hazards
+/// Each of the following compiles (the sizes match) and is undefined
+/// behaviour:
+/// ```
+/// // Invalid values: a `bool` must be 0 or 1
+/// let flag: bool = unsafe { transmute(2u8) };
+///
+/// // Alignment mismatch: both references have the same size, but a `u32`
+/// // must be aligned to 4, while the array is only aligned to 1
+/// let bytes: [u8; 5] = [0; 5];
+/// let number: &u32 = unsafe { transmute(&bytes[1]) };
+///
+/// // Size mismatch behind a pointer: the reference covers 1 byte, the
+/// // `u64` is read as 8 bytes, out of bounds
+/// let byte: u8 = 1;
+/// let number: &u64 = unsafe { transmute(&byte) };
+///
+/// // Lifetime extension: the reference outlives the vector it points into
+/// let dangling: &'static [u8] = unsafe { transmute(vec![1, 2].as_slice()) };
+///
+/// // Layout of `repr(Rust)` types is unspecified: field order may differ
+/// struct Pair(u32, u8);
+/// let raw: (u32, u8) = unsafe { transmute(Pair(1, 2)) };
+/// ```
+fn hazards() {}
\ No newline at end of file
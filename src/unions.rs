@@ -0,0 +1,146 @@
+/// # Unions (~ C unions)
+/// A `union` declares fields like a struct, but all of them share the same
+/// memory: only one of them is meaningful at a time, and the union does not
+/// remember which one. Its size is the size of its largest field.
+///
+/// Unions exist mostly for interoperability with C (see unsafe_code.rs).
+/// In Rust code, enums are the safe alternative (see enums.rs).
+use std::mem::{size_of, ManuallyDrop};
+use std::os::raw::{c_int, c_void};
+
+fn unions() {}
+
+/// ## Type Punning
+/// Writing a field and reading another reinterprets the bits, like
+/// `transmute` (see transmute.rs). `repr(C)` guarantees that every field
+/// starts at offset 0.
+#[repr(C)]
+union FloatBits {
+    float: f32,
+    bits: u32,
+}
+
+runnable!(type_punning, {
+    let value: FloatBits = FloatBits { float: -2.5 };  // exactly one field
+    // SAFETY: every bit pattern of an `f32` is a valid `u32`.
+    let bits: u32 = unsafe { value.bits };
+    println!("-2.5 = {:#034b}", bits);
+    assert_eq!(bits >> 31, 1);  // sign bit
+    assert_eq!(bits, (-2.5f32).to_bits());  // the safe alternative
+});
+
+/// ## Field Access Rules
+/// - Reading a field is `unsafe`: the compiler cannot know whether the bits
+///   form a valid value of that type
+/// - Writing a field is safe, since it cannot break anything by itself
+/// - Fields must be `Copy` or wrapped in `ManuallyDrop`, because the union
+///   cannot know which field to drop
+/// ```
+/// let x: f32 = value.float;
+/// // ^ Error: access to union field is unsafe and requires unsafe function
+/// //          or block
+/// union Text { owned: String, raw: [usize; 3] }
+/// // ^ Error: field must implement `Copy` or be wrapped in
+/// //          `ManuallyDrop<...>` to be used in a union
+/// let both: FloatBits = FloatBits { float: 1.0, bits: 1 };
+/// // ^ Error: union expressions should have exactly one field
+/// ```
+union Text {
+    owned: ManuallyDrop<String>,
+    borrowed: &'static str,
+}
+
+runnable!(field_access_rules, {
+    let mut value: FloatBits = FloatBits { bits: 0 };
+    value.float = 1.0;  // safe write
+    // SAFETY: the last field written is `float`.
+    assert_eq!(unsafe { value.float }, 1.0);
+    // Pattern matching reads a field too
+    // SAFETY: any `u32` is valid.
+    unsafe {
+        match value { FloatBits { bits: 0 } => unreachable!(), _ => {} }
+    }
+
+    let mut text: Text = Text { owned: ManuallyDrop::new("hi".to_string()) };
+    // SAFETY: `owned` is the active field, and is dropped only once (the
+    // union itself never drops it).
+    unsafe {
+        assert_eq!(text.owned.as_str(), "hi");
+        ManuallyDrop::drop(&mut text.owned);
+    }
+    text.borrowed = "static";  // now the active field
+});
+
+/// ## Tagged Unions by Hand vs Enums
+/// C programs pair a union with a `tag` remembering the active field. Every
+/// access must check the tag, and nothing prevents a mismatch.
+#[derive(Clone, Copy, PartialEq)]
+enum Tag { Integer, Float }
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+union Payload { integer: i64, float: f64 }
+
+struct TaggedValue { tag: Tag, payload: Payload }
+
+impl TaggedValue {
+    fn as_f64(&self) -> f64 {
+        // SAFETY: the tag always matches the active field (upheld by the
+        // code creating the value, not by the compiler).
+        match self.tag {
+            Tag::Integer => unsafe { self.payload.integer as f64 },
+            Tag::Float => unsafe { self.payload.float },
+        }
+    }
+}
+
+/// A Rust enum is a tagged union managed by the compiler: the tag is always
+/// consistent, and pattern matching is safe.
+enum Value { Integer(i64), Float(f64) }
+
+impl Value {
+    fn as_f64(&self) -> f64 {
+        match self {
+            Value::Integer(integer) => *integer as f64,
+            Value::Float(float) => *float,
+        }
+    }
+}
+
+runnable!(tagged_unions_vs_enums, {
+    let by_hand: TaggedValue =
+        TaggedValue { tag: Tag::Integer, payload: Payload { integer: 3 } };
+    let enumeration: Value = Value::Integer(3);
+    assert_eq!(by_hand.as_f64(), enumeration.as_f64());
+    // Same size: 8 bytes of payload + a tag padded to 8 bytes
+    assert_eq!(size_of::<TaggedValue>(), size_of::<Value>());
+    assert_eq!(size_of::<TaggedValue>(), 16);
+});
+
+/// ## Unions in C APIs
+/// Unions are required to match C declarations exactly, e.g. the POSIX
+/// `sigval`, which carries either a number or a pointer with a signal:
+/// ```c
+/// union sigval {
+///     int   sival_int;
+///     void *sival_ptr;
+/// };
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy)]
+union SigVal {
+    sival_int: c_int,
+    sival_ptr: *mut c_void,
+}
+
+/// A C function receiving a `union sigval` by value, e.g. a notification
+/// callback (see unsafe_code.rs for calling C functions).
+extern "C" fn on_notify(value: SigVal) -> c_int {
+    // SAFETY: in this example, the sender always uses `sival_int`.
+    unsafe { value.sival_int * 2 }
+}
+
+runnable!(unions_in_c_apis, {
+    assert_eq!(size_of::<SigVal>(), size_of::<*mut c_void>());
+    assert_eq!(on_notify(SigVal { sival_int: 21 }), 42);
+});
\ No newline at end of file
@@ -84,6 +84,57 @@ pub mod implementation {
                 .map_or_else(|err| Ok(()), |ok| Err(()))
         }
 
+        /// ## Property-Based Testing
+        /// Instead of a few hand-picked examples, state properties which hold
+        /// for every input, and let `for_all!` check them against hundreds of
+        /// random ones (see util/proptest.rs). Inputs are `u16`, so that the
+        /// sums fit in a `usize`, even on 32-bit targets.
+        fn num(value: u16) -> Num { Num(value as usize) }
+
+        #[test]
+        fn test_add_is_commutative() {
+            for_all!(|x: u16, y: u16| {
+                num(x).add(&num(y)) == num(y).add(&num(x))
+            });
+        }
+        #[test]
+        fn test_sub_undoes_add() {
+            for_all!(|x: u16, y: u16| {
+                num(x).add(&num(y)).sub(&num(y)) == num(x)
+            });
+        }
+        #[test]
+        fn test_try_sub_fails_when_smaller() {
+            for_all!(cases = 1_000, |x: u16, y: u16| {
+                num(x).try_sub(&num(y)).is_ok() == (x >= y)
+            });
+        }
+
+        /// A wrong property fails, and is reported with its simplest failing
+        /// input: whatever random pair failed first, it shrinks to `(0, 1)`.
+        #[test]
+        #[should_panic(expected = "minimal input (0, 1)")]
+        fn test_sub_never_fails() {
+            for_all!(|x: u16, y: u16| num(x).try_sub(&num(y)).is_ok());
+        }
+        /// A property which panics fails too: `sub` overflows when `x < y`,
+        /// and the panic is shrunk like a `false`.
+        #[test]
+        #[should_panic(expected = "minimal input (0, 1)")]
+        fn test_sub_never_panics() {
+            for_all!(|x: u16, y: u16| { num(x).sub(&num(y)); true });
+        }
+        #[test]
+        fn test_shrinking() {
+            use crate::util::proptest::{check, Config, Failure};
+            let failure: Failure<(u16, u16)> = check(
+                &Config::default(),
+                |(x, y): (u16, u16)| num(x).try_sub(&num(y)).is_ok(),
+            ).unwrap_err();
+            assert!(failure.original.0 < failure.original.1);
+            assert_eq!(failure.shrunk, (0, 1));
+        }
+
         /// ## Pending Tests
         /// You can annotate a test with `#[ignore]` to skip until it is fixed.
         #[test]
@@ -95,7 +146,9 @@ pub mod implementation {
 /// # Documentation Tests
 /// Often developers include code examples in the documentation of their APIs.
 /// Rust treat this example as tests, namely Documentation Tests. These only
-/// work on library crates.
+/// work on library crates (see compile_errors.rs, in the library target).
+/// Attributes such as `no_run` change how they are checked (see doctests.rs,
+/// in the library target too).
 mod documentation_tests {
     #[derive(Debug, PartialEq, Eq)] pub struct Num(usize);
     impl Num {
@@ -131,4 +184,8 @@ mod documentation_tests {
 /// All unit, integration and documentation tests are run when with the command
 /// `cargo test`. Flags and arguments are available for specifying which tests
 /// to run.
+/// Tests run in parallel threads, in any order, so they cannot rely on each
+/// other, nor on setup and teardown hooks (see fixtures.rs).
+/// Many similar tests can be generated from a table of inputs and expected
+/// outputs, one test per row (see `test_cases!` in util.rs, and parser.rs).
 fn tests_in_cargo() {}
\ No newline at end of file
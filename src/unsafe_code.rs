@@ -20,6 +20,7 @@ runnable!(user, {
 /// ## Raw Pointers
 /// Rust references are checked so they always point the a valid memory address.
 /// However, Rust allows developers to use unsafe `raw pointers` (like C).
+/// See unsafe_code/raw_pointers.rs for the details.
 runnable!(raw_pointers, {
     let reference: &u32 = &10;      // Reference: safe to declare; safe to use
     let pointer: *const u32 = &10;  // Pointer: safe to declare; unsafe to use
@@ -35,12 +36,128 @@ runnable!(raw_pointers, {
 /// most efficiency-critical parts of their code.
 /// 
 /// See https://doc.rust-lang.org/rust-by-example/unsafe/asm.html.
+///
+/// Assembly is specific to an architecture, so each variant is gated by
+/// `target_arch` (see target_config.rs), with a pure-Rust fallback so that
+/// the playground compiles everywhere.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 use std::arch::asm;
 
-runnable!(assembly, {
+#[cfg(target_arch = "x86_64")]
+fn load_five() -> u64 {
     let x: u64;
     unsafe { asm!("mov {}, 5", out(reg) x); }
-    assert_eq!(x, 5);
+    x
+}
+#[cfg(target_arch = "aarch64")]
+fn load_five() -> u64 {
+    let x: u64;
+    unsafe { asm!("mov {}, #5", out(reg) x); }
+    x
+}
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn load_five() -> u64 { 5 }
+
+runnable!(assembly, {
+    assert_eq!(load_five(), 5);
+});
+
+/// ### Operands and Register Classes
+/// Operands are written `{}` (or `{name}`) in the template, and bound to
+/// Rust values:
+/// - `in(reg) value`: read by the assembly
+/// - `out(reg) variable`: written by the assembly
+/// - `inout(reg) variable`: both (`inout(reg) input => output` to use
+///   different variables)
+/// - `lateout`: an output written only after all inputs are read, so it may
+///   share a register with an input
+///
+/// `reg` is a `register class`: the compiler picks any general-purpose
+/// register. Other classes exist (e.g. `xmm_reg` on x86, `vreg` on aarch64),
+/// and a specific register can be required by name (e.g. `"rax"`), when an
+/// instruction only works with it.
+///
+/// ### Clobbers and Options
+/// Any register written by the assembly must be declared, even if its value
+/// is not needed: `out("rdx") _` declares a `clobbered` register, and
+/// `clobber_abi("C")` every register a C function call may overwrite.
+/// Options tell the compiler what the assembly does not do, to optimize
+/// around it:
+/// - `nomem`: does not read or write memory
+/// - `nostack`: does not push to the stack
+/// - `pure`: no side effects, the outputs only depend on the inputs (so the
+///   block can be removed if unused, or merged with an identical one)
+/// - `preserves_flags`: does not modify the condition flags
+#[cfg(target_arch = "x86_64")]
+fn add(mut a: u64, b: u64) -> u64 {
+    unsafe {
+        asm!("add {0}, {1}", inout(reg) a, in(reg) b,
+            options(pure, nomem, nostack));
+    }
+    a
+}
+#[cfg(target_arch = "aarch64")]
+fn add(mut a: u64, b: u64) -> u64 {
+    unsafe {
+        asm!("add {0}, {0}, {1}", inout(reg) a, in(reg) b,
+            options(pure, nomem, nostack));
+    }
+    a
+}
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn add(a: u64, b: u64) -> u64 { a.wrapping_add(b) }
+
+/// Full 128-bit product of two `u64`, as `(low, high)`. On x86_64, `mul`
+/// only multiplies `rax`, and writes the result into `rdx:rax`.
+#[cfg(target_arch = "x86_64")]
+fn multiply_wide(a: u64, b: u64) -> (u64, u64) {
+    let (low, high): (u64, u64);
+    unsafe {
+        asm!("mul {}", in(reg) b, inlateout("rax") a => low,
+            lateout("rdx") high, options(pure, nomem, nostack));
+    }
+    (low, high)
+}
+/// On aarch64, two instructions compute the low and high halves. `low` is
+/// written before `b` is read again, so it cannot be a `lateout`.
+#[cfg(target_arch = "aarch64")]
+fn multiply_wide(a: u64, b: u64) -> (u64, u64) {
+    let (low, high): (u64, u64);
+    unsafe {
+        asm!("mul {low}, {a}, {b}", "umulh {high}, {a}, {b}",
+            a = in(reg) a, b = in(reg) b,
+            low = out(reg) low, high = lateout(reg) high,
+            options(pure, nomem, nostack));
+    }
+    (low, high)
+}
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn multiply_wide(a: u64, b: u64) -> (u64, u64) {
+    let product: u128 = a as u128 * b as u128;
+    (product as u64, (product >> 64) as u64)
+}
+
+/// Only the low half: `rdx` is still overwritten, so it is clobbered.
+#[cfg(target_arch = "x86_64")]
+fn multiply_low(a: u64, b: u64) -> u64 {
+    let low: u64;
+    unsafe {
+        asm!("mul {}", in(reg) b, inlateout("rax") a => low,
+            out("rdx") _, options(pure, nomem, nostack));
+    }
+    low
+}
+#[cfg(not(target_arch = "x86_64"))]
+fn multiply_low(a: u64, b: u64) -> u64 { multiply_wide(a, b).0 }
+
+runnable!(operands_and_clobbers, {
+    assert_eq!(add(40, 2), 42);
+    assert_eq!(multiply_wide(u64::MAX, 2), (u64::MAX - 1, 1));
+    assert_eq!(multiply_low(u64::MAX, 2), u64::MAX - 1);
+    // The fallback, for comparison
+    let product: u128 = u64::MAX as u128 * 2;
+    assert_eq!(multiply_wide(u64::MAX, 2),
+        (product as u64, (product >> 64) as u64));
 });
 
 /// ## Foreign Function Interface (FFI) - C
@@ -49,6 +166,8 @@ runnable!(assembly, {
 /// into safe Rust code, creating Foreign Function Interfaces.
 ///
 /// You can leverage conditional compilation for os interoperability.
+///
+/// See reverse_ffi.rs for calling Rust from C.
 // Declare the following C-like structure
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -70,4 +189,15 @@ extern {
 }
 
 // Foreign Function Interface
-fn cos(z: Complex) -> Complex { unsafe { ccosf(z) } }
\ No newline at end of file
+fn cos(z: Complex) -> Complex { unsafe { ccosf(z) } }
+
+/// ## Raw Pointers in Depth
+/// Each submodule in the `unsafe_code` folder covers one unsafe topic (see
+/// modules.rs for how directory modules work).
+mod raw_pointers;
+
+/// ## NonNull
+mod non_null;
+
+/// ## UnsafeCell
+mod unsafe_cell;
\ No newline at end of file
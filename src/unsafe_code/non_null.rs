@@ -0,0 +1,151 @@
+/// # NonNull
+/// `std::ptr::NonNull<T>` is a `*mut T` which is never null. It is the raw
+/// pointer used inside the standard collections (`Box`, `Vec`, `Rc`, ...),
+/// because it differs from `*mut T` in two ways:
+/// - `niche optimization`: null is an invalid value, so `Option<NonNull<T>>`
+///   uses it for `None`, and is as small as a pointer (see layout.rs)
+/// - `covariance`: `NonNull<T>` is covariant in `T`, like `&T`, while
+///   `*mut T` is invariant (see variance.rs)
+///
+/// Like any raw pointer, it may still be dangling or unaligned.
+use std::alloc::{self, Layout};
+use std::marker::PhantomData;
+use std::mem::{size_of, ManuallyDrop};
+use std::ops::{Deref, DerefMut};
+use std::ptr::{self, NonNull};
+
+fn non_null() {}
+
+/// ## Niche Optimization
+runnable!(niche_optimization, {
+    assert_eq!(size_of::<Option<NonNull<u64>>>(), size_of::<*mut u64>());
+    assert_eq!(size_of::<Option<*mut u64>>(), 2 * size_of::<*mut u64>());
+
+    let mut value: u64 = 1;
+    let pointer: NonNull<u64> = NonNull::from(&mut value);
+    assert!(NonNull::new(ptr::null_mut::<u64>()).is_none());  // checked
+    // SAFETY: `pointer` comes from a live `&mut`, unused meanwhile.
+    unsafe { *pointer.as_ptr() += 1; }
+    assert_eq!(value, 2);
+});
+
+/// ## Covariance
+/// A container holding a `*mut T` is invariant, which is more restrictive
+/// than needed for a container owning its `T` (like `Box<T>`):
+/// ```
+/// struct RawBox<T> { pointer: *mut T }
+/// fn shrink<'a>(b: RawBox<&'static str>) -> RawBox<&'a str> { b }
+/// // ^ Error: lifetime may not live long enough
+/// //   note: the struct `RawBox<T>` is invariant over the parameter `T`
+/// ```
+/// With `NonNull<T>`, the same conversion is accepted, like for `Box<T>`.
+/// Covariance is only sound for types that never let a shorter-lived value be
+/// written through a longer-lived view: shared mutation (e.g. `Cell<T>`)
+/// must stay invariant.
+runnable!(covariance, {
+    fn shrink<'a>(b: MyBox<&'static str>) -> MyBox<&'a str> { b }
+    fn shrink_std<'a>(b: Box<&'static str>) -> Box<&'a str> { b }
+    let local: String = "local".to_string();
+    let mut texts: MyBox<&str> = shrink(MyBox::new("static"));
+    *texts = &local;  // now holds a shorter-lived reference
+    assert_eq!(*texts, "local");
+});
+
+/// ## A Sound Owning Box
+/// `MyBox<T>` owns a heap-allocated `T`, like `Box<T>`:
+/// - `NonNull<T>` makes it covariant, and `Option<MyBox<T>>` pointer-sized
+/// - `PhantomData<T>` tells the compiler that a `MyBox<T>` owns a `T` (and
+///   may drop it), which the drop checker relies on
+/// - `NonNull<T>` is neither `Send` nor `Sync`, so these are implemented
+///   explicitly, under the same conditions as `Box<T>`
+///
+/// The tests can be checked for undefined behaviour and leaks with Miri:
+/// `cargo +nightly miri test non_null`.
+pub struct MyBox<T> {
+    pointer: NonNull<T>,
+    _owns: PhantomData<T>,
+}
+
+impl<T> MyBox<T> {
+    pub fn new(value: T) -> Self {
+        let layout: Layout = Layout::new::<T>();
+        let pointer: NonNull<T> = if layout.size() == 0 {
+            NonNull::dangling()  // zero-sized types need no memory
+        } else {
+            // SAFETY: `layout` has a non-zero size.
+            let raw: *mut T = unsafe { alloc::alloc(layout) as *mut T };
+            NonNull::new(raw)
+                .unwrap_or_else(|| alloc::handle_alloc_error(layout))
+        };
+        // SAFETY: `pointer` is aligned and valid for writes of a `T`.
+        unsafe { pointer.as_ptr().write(value); }
+        MyBox { pointer, _owns: PhantomData }
+    }
+
+    pub fn into_inner(self) -> T {
+        let this: ManuallyDrop<Self> = ManuallyDrop::new(self);
+        // SAFETY: the value is moved out once, then only the memory is freed
+        // (`Drop` does not run, so the value is not dropped twice).
+        unsafe {
+            let value: T = this.pointer.as_ptr().read();
+            Self::deallocate(this.pointer);
+            value
+        }
+    }
+
+    /// SAFETY: `pointer` must come from `MyBox::new`, and its value must have
+    /// been dropped or moved out.
+    unsafe fn deallocate(pointer: NonNull<T>) {
+        let layout: Layout = Layout::new::<T>();
+        if layout.size() != 0 {
+            alloc::dealloc(pointer.as_ptr() as *mut u8, layout);
+        }
+    }
+}
+
+impl<T> Deref for MyBox<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        // SAFETY: the value is initialized and owned by `self`, so it lives as
+        // long as the borrow of `self`.
+        unsafe { self.pointer.as_ref() }
+    }
+}
+
+impl<T> DerefMut for MyBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as in `deref`, and `&mut self` guarantees unique access.
+        unsafe { self.pointer.as_mut() }
+    }
+}
+
+impl<T> Drop for MyBox<T> {
+    fn drop(&mut self) {
+        // SAFETY: the value is dropped exactly once, then its memory freed.
+        unsafe {
+            ptr::drop_in_place(self.pointer.as_ptr());
+            Self::deallocate(self.pointer);
+        }
+    }
+}
+
+// SAFETY: `MyBox<T>` owns its `T` like `Box<T>`: sending or sharing it is
+// equivalent to sending or sharing the `T`.
+unsafe impl<T: Send> Send for MyBox<T> {}
+unsafe impl<T: Sync> Sync for MyBox<T> {}
+
+runnable!(sound_owning_box, {
+    assert_eq!(size_of::<Option<MyBox<u64>>>(), size_of::<usize>());
+    let mut name: MyBox<String> = MyBox::new("Ferris".to_string());
+    name.push_str(" the crab");  // through `DerefMut`
+    assert_eq!(name.len(), 15);  // through `Deref`
+    let name: String = name.into_inner();
+    assert_eq!(name, "Ferris the crab");
+
+    let unit: MyBox<()> = MyBox::new(());  // zero-sized: no allocation
+    let shared: MyBox<Vec<u8>> = MyBox::new(vec![1, 2, 3]);
+    let sum: u32 = std::thread::spawn(move || {
+        shared.iter().map(|&byte| byte as u32).sum()  // `MyBox` is `Send`
+    }).join().unwrap();
+    assert_eq!(sum, 6);
+});
\ No newline at end of file
@@ -0,0 +1,226 @@
+/// # Raw Pointers (~ C pointers)
+/// `*const T` and `*mut T` are pointers without any compile-time guarantee:
+/// they may be null, dangling, unaligned or aliased. Creating, copying and
+/// comparing them is safe; every operation that accesses memory through them
+/// is `unsafe`, and its safety conditions are documented in `std::ptr`.
+///
+/// `Miri` (`cargo +nightly miri test`) runs the tests in an interpreter that
+/// detects most violations of these rules.
+use std::alloc::{self, Layout};
+use std::ptr;
+
+fn raw_pointers() {}
+
+/// ## Creating Pointers
+/// - From references: `ptr::from_ref` and `ptr::from_mut` (clearer than `as`
+///   casts, which can also silently change the pointed type)
+/// - From places, without creating an intermediate reference: `&raw const`
+///   and `&raw mut` (needed for unaligned or uninitialized fields)
+/// - Without any memory behind: `ptr::null`, and `ptr::dangling` (non-null and
+///   aligned, e.g. for empty collections)
+runnable!(creating_pointers, {
+    let mut number: u32 = 10;
+    let read_only: *const u32 = ptr::from_ref(&number);
+    let read_write: *mut u32 = ptr::from_mut(&mut number);
+    let place: *const u32 = &raw const number;
+    assert_eq!(read_only, place);
+
+    let null: *const u32 = ptr::null();
+    let dangling: *const u64 = ptr::dangling();
+    assert!(null.is_null() && !dangling.is_null());
+    assert_eq!(dangling as usize, std::mem::align_of::<u64>());
+    // SAFETY: `read_write` points to `number`, which is alive, and no
+    // reference to `number` is used while writing.
+    unsafe { *read_write += 1; }
+    assert_eq!(number, 11);
+});
+
+/// ## Pointer Arithmetic
+/// `add(n)` and `sub(n)` move a pointer by `n` elements (not bytes), and
+/// `offset(n)` accepts a signed count. The result must stay within the same
+/// allocation (or one past its end), even if it is never dereferenced:
+/// otherwise the arithmetic itself is undefined behaviour. `wrapping_add`
+/// has no such condition, but the result may not be dereferenceable.
+runnable!(pointer_arithmetic, {
+    let numbers: [u16; 4] = [10, 20, 30, 40];
+    let start: *const u16 = numbers.as_ptr();
+    // SAFETY: every pointer stays within `numbers` or one past its end.
+    unsafe {
+        let third: *const u16 = start.add(2);
+        assert_eq!(*third, 30);
+        assert_eq!(*third.offset(-1), 20);
+        let end: *const u16 = start.add(numbers.len());  // not dereferenced
+        assert_eq!(end.offset_from(start), 4);
+        assert_eq!(end as usize - start as usize, 8);  // bytes
+    }
+    // let outside: *const u16 = unsafe { start.add(5) };
+    // ^ UB: out of the allocation, even without reading
+    let outside: *const u16 = start.wrapping_add(5);  // fine, never read
+});
+
+/// ## Reading and Writing
+/// - `read` copies the value out, without moving it: for non-`Copy` types,
+///   there are now two owners, and only one of them may be dropped
+/// - `write` overwrites the value without dropping the old one
+/// - `copy_nonoverlapping(source, destination, count)` is C's `memcpy`, and
+///   `copy` is `memmove` (the two ranges may overlap)
+runnable!(reading_and_writing, {
+    let mut names: [String; 2] = ["a".to_string(), "b".to_string()];
+    let pointer: *mut String = names.as_mut_ptr();
+    // SAFETY: both elements are initialized. The first one is read out,
+    // then replaced by `write` without being dropped, so each `String` still
+    // has exactly one owner.
+    unsafe {
+        let first: String = pointer.read();
+        pointer.write("c".to_string());
+        assert_eq!(first, "a");
+    }
+    assert_eq!(names, ["c", "b"]);
+
+    let source: [u8; 4] = [1, 2, 3, 4];
+    let mut destination: [u8; 6] = [0; 6];
+    // SAFETY: 4 bytes are readable from `source` and writable into
+    // `destination` from index 1, and the two arrays are distinct.
+    unsafe {
+        ptr::copy_nonoverlapping(source.as_ptr(),
+            destination.as_mut_ptr().add(1), source.len());
+    }
+    assert_eq!(destination, [0, 1, 2, 3, 4, 0]);
+    // SAFETY: in bounds. The ranges overlap, so `copy` is required.
+    unsafe {
+        ptr::copy(destination.as_ptr().add(1), destination.as_mut_ptr(), 4);
+    }
+    assert_eq!(destination, [1, 2, 3, 4, 4, 0]);
+});
+
+/// ## Null and Dangling Pointers
+/// Dereferencing a null or dangling pointer is undefined behaviour. Unlike
+/// references, nothing prevents a raw pointer from outliving its target:
+/// ```
+/// let pointer: *const String = {
+///     let text: String = "gone".to_string();
+///     ptr::from_ref(&text)
+/// };  // `text` is dropped here
+/// unsafe { println!("{}", *pointer); }
+/// // ^ UB: use after free (Miri: pointer to an allocation that was freed)
+/// ```
+/// `as_ref` turns a possibly-null pointer into an `Option<&T>`, but still
+/// requires the pointer to be valid when it is not null.
+runnable!(null_and_dangling_pointers, {
+    let value: u8 = 7;
+    let pointers: [*const u8; 2] = [ptr::null(), &value];
+    for pointer in pointers {
+        // SAFETY: each pointer is either null or points to `value`.
+        match unsafe { pointer.as_ref() } {
+            Some(value) => println!("points to {}", value),
+            None => println!("null"),
+        }
+    }
+});
+
+/// ## Provenance
+/// A pointer is more than an address: it also carries its `provenance`, the
+/// allocation (and range) it was derived from, and may only access that
+/// memory. Two pointers with the same address are not interchangeable:
+/// ```
+/// let a: [u8; 1] = [1];
+/// let b: [u8; 1] = [2];
+/// let past_a: *const u8 = a.as_ptr().wrapping_add(1);
+/// if past_a == b.as_ptr() {       // may be true: `b` follows `a` in memory
+///     unsafe { println!("{}", *past_a); }
+///     // ^ UB: `past_a` may only access `a`, even at the address of `b`
+/// }
+/// ```
+/// Casting a pointer to an integer and back loses the provenance. The
+/// `strict provenance` APIs manipulate the address while keeping it:
+/// - `addr()`: the address, as a `usize`
+/// - `with_addr(address)` and `map_addr(f)`: a new address, same provenance
+/// - `ptr::without_provenance(address)`: a pointer that must never be read
+///
+/// Miri checks these rules (with `-Zmiri-strict-provenance`).
+runnable!(provenance, {
+    let numbers: [u32; 4] = [1, 2, 3, 4];
+    let pointer: *const u32 = numbers.as_ptr();
+    // Tag the pointer in its lowest bit (always 0, since it is aligned to 4)
+    let tagged: *const u32 = pointer.map_addr(|address| address | 1);
+    assert_eq!(tagged.addr() & 1, 1);
+    let untagged: *const u32 = tagged.map_addr(|address| address & !1);
+    // SAFETY: same address and provenance as `pointer`.
+    assert_eq!(unsafe { *untagged }, 1);
+    let sentinel: *const u32 = ptr::without_provenance(usize::MAX);
+    assert_eq!(sentinel.addr(), usize::MAX);  // fine, as long as not read
+});
+
+/// ## A Tiny Vec
+/// A growable array built on raw memory: a pointer to the buffer, its
+/// capacity and the number of initialized elements (see maybe_uninit.rs).
+/// Only the first `length` elements may be read or dropped.
+struct TinyVec<T> {
+    buffer: *mut T,
+    capacity: usize,
+    length: usize,
+}
+
+impl<T> TinyVec<T> {
+    fn new() -> Self {
+        assert!(std::mem::size_of::<T>() != 0, "zero-sized types unsupported");
+        TinyVec { buffer: ptr::dangling_mut(), capacity: 0, length: 0 }
+    }
+
+    fn push(&mut self, value: T) {
+        if self.length == self.capacity { self.grow(); }
+        // SAFETY: `length < capacity`, so the slot is inside the buffer, and
+        // it is uninitialized, so nothing needs to be dropped.
+        unsafe { self.buffer.add(self.length).write(value); }
+        self.length += 1;
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        // SAFETY: the first `length` elements are initialized, and the
+        // reference borrows `self`, so the buffer outlives it.
+        (index < self.length).then(|| unsafe { &*self.buffer.add(index) })
+    }
+
+    fn grow(&mut self) {
+        let capacity: usize = if self.capacity == 0 { 4 } else {
+            2 * self.capacity
+        };
+        let layout: Layout = Layout::array::<T>(capacity).unwrap();
+        // SAFETY: `layout` has a non-zero size. A previous buffer was
+        // allocated with the layout of `self.capacity` elements.
+        let buffer: *mut u8 = unsafe {
+            if self.capacity == 0 {
+                alloc::alloc(layout)
+            } else {
+                let old: Layout = Layout::array::<T>(self.capacity).unwrap();
+                alloc::realloc(self.buffer as *mut u8, old, layout.size())
+            }
+        };
+        if buffer.is_null() { alloc::handle_alloc_error(layout); }
+        self.buffer = buffer as *mut T;
+        self.capacity = capacity;
+    }
+}
+
+impl<T> Drop for TinyVec<T> {
+    fn drop(&mut self) {
+        // SAFETY: drop the initialized elements once, then free the buffer
+        // with the layout it was allocated with.
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.buffer, self.length));
+            if self.capacity > 0 {
+                let layout: Layout = Layout::array::<T>(self.capacity).unwrap();
+                alloc::dealloc(self.buffer as *mut u8, layout);
+            }
+        }
+    }
+}
+
+runnable!(tiny_vec, {
+    let mut words: TinyVec<String> = TinyVec::new();
+    for i in 0..10 { words.push(format!("word {}", i)); }
+    assert_eq!((words.length, words.capacity), (10, 16));
+    assert_eq!(words.get(9).map(String::as_str), Some("word 9"));
+    assert_eq!(words.get(10), None);
+});  // the 10 strings and the buffer are freed here
\ No newline at end of file
@@ -0,0 +1,169 @@
+/// # UnsafeCell
+/// The compiler assumes that the value behind a shared reference `&T` never
+/// changes while the reference is alive, and optimizes accordingly. Mutating
+/// it anyway (e.g. by casting `&T` into `*mut T`) is undefined behaviour.
+///
+/// `std::cell::UnsafeCell<T>` is the only exception: it is the primitive
+/// telling the compiler that its content may change behind a `&UnsafeCell<T>`.
+/// Every type with `interior mutability` (`Cell`, `RefCell`, `Mutex`,
+/// atomics, `OnceCell`, ...) is built on top of it.
+use std::cell::{Cell, UnsafeCell};
+use std::ops::{Deref, DerefMut};
+
+fn unsafe_cell() {}
+
+/// ## Mutation Through Shared References
+/// `UnsafeCell::get(&self)` returns a `*mut T`: writing through it is
+/// allowed, as long as the caller ensures that no other reference to the
+/// content is alive at the same time.
+/// ```
+/// let number: u32 = 1;
+/// let shared: &u32 = &number;
+/// unsafe { *(shared as *const u32 as *mut u32) = 2; }
+/// // ^ Error: assigning to `&T` is undefined behavior, consider using an
+/// //          `UnsafeCell`
+/// ```
+runnable!(mutation_through_shared_references, {
+    let cell: UnsafeCell<u32> = UnsafeCell::new(1);
+    let shared: &UnsafeCell<u32> = &cell;
+    let also_shared: &UnsafeCell<u32> = &cell;
+    // SAFETY: no reference to the content exists during the writes.
+    unsafe {
+        *shared.get() += 1;
+        *also_shared.get() += 1;
+    }
+    assert_eq!(cell.into_inner(), 3);
+});
+
+/// ## MyCell
+/// A toy `Cell<T>`: values are only copied in and out, so no reference to
+/// the content ever escapes, and every access is sound.
+///
+/// `UnsafeCell<T>` is not `Sync`, and neither is `MyCell<T>`: two threads
+/// could otherwise write at the same time (see marker_traits.rs).
+pub struct MyCell<T> { value: UnsafeCell<T> }
+
+impl<T: Copy> MyCell<T> {
+    pub fn new(value: T) -> Self { MyCell { value: UnsafeCell::new(value) } }
+    pub fn get(&self) -> T {
+        // SAFETY: single-threaded (`!Sync`), and no reference to the content
+        // outlives this copy.
+        unsafe { *self.value.get() }
+    }
+    pub fn set(&self, value: T) {
+        // SAFETY: as in `get`.
+        unsafe { *self.value.get() = value; }
+    }
+}
+
+runnable!(my_cell, {
+    let counter: MyCell<u32> = MyCell::new(0);
+    let increment = || counter.set(counter.get() + 1);  // captures `&counter`
+    increment();
+    increment();
+    assert_eq!(counter.get(), 2);
+});
+
+/// ## MyRefCell
+/// A toy `RefCell<T>`, which lends references to its content, checking the
+/// borrowing rules at runtime instead of compile time (see ownership.rs):
+/// any number of shared borrows, or a single mutable one. The state is kept
+/// in a `Cell`, and each borrow is a guard restoring it when dropped.
+const UNUSED: isize = 0;
+const WRITING: isize = -1;
+
+pub struct MyRefCell<T> {
+    value: UnsafeCell<T>,
+    state: Cell<isize>,  // UNUSED, WRITING, or the number of readers
+}
+
+pub struct MyRef<'a, T> { cell: &'a MyRefCell<T> }
+pub struct MyRefMut<'a, T> { cell: &'a MyRefCell<T> }
+
+impl<T> MyRefCell<T> {
+    pub fn new(value: T) -> Self {
+        MyRefCell { value: UnsafeCell::new(value), state: Cell::new(UNUSED) }
+    }
+
+    pub fn try_borrow(&self) -> Option<MyRef<'_, T>> {
+        if self.state.get() == WRITING { return None; }
+        self.state.set(self.state.get() + 1);
+        Some(MyRef { cell: self })
+    }
+
+    pub fn try_borrow_mut(&self) -> Option<MyRefMut<'_, T>> {
+        if self.state.get() != UNUSED { return None; }
+        self.state.set(WRITING);
+        Some(MyRefMut { cell: self })
+    }
+
+    pub fn borrow(&self) -> MyRef<'_, T> {
+        self.try_borrow().expect("already mutably borrowed")
+    }
+
+    pub fn borrow_mut(&self) -> MyRefMut<'_, T> {
+        self.try_borrow_mut().expect("already borrowed")
+    }
+}
+
+impl<T> Deref for MyRef<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        // SAFETY: while this guard exists, the state counts it as a reader,
+        // so no `MyRefMut` can exist.
+        unsafe { &*self.cell.value.get() }
+    }
+}
+
+impl<T> Drop for MyRef<'_, T> {
+    fn drop(&mut self) { self.cell.state.set(self.cell.state.get() - 1); }
+}
+
+impl<T> Deref for MyRefMut<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        // SAFETY: while this guard exists, the state is `WRITING`, so no
+        // other guard can exist.
+        unsafe { &*self.cell.value.get() }
+    }
+}
+
+impl<T> DerefMut for MyRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as in `deref`, and `&mut self` makes this borrow unique.
+        unsafe { &mut *self.cell.value.get() }
+    }
+}
+
+impl<T> Drop for MyRefMut<'_, T> {
+    fn drop(&mut self) { self.cell.state.set(UNUSED); }
+}
+
+runnable!(my_ref_cell, {
+    let names: MyRefCell<Vec<&str>> = MyRefCell::new(vec!["a"]);
+    names.borrow_mut().push("b");  // the guard is dropped at the `;`
+    {
+        let first: MyRef<Vec<&str>> = names.borrow();
+        let second: MyRef<Vec<&str>> = names.borrow();  // many readers
+        assert_eq!(first.len() + second.len(), 4);
+        assert!(names.try_borrow_mut().is_none());  // no writer meanwhile
+    }
+    let writer: MyRefMut<Vec<&str>> = names.borrow_mut();
+    assert!(names.try_borrow().is_none());  // no reader meanwhile
+    drop(writer);
+    assert_eq!(*names.borrow(), ["a", "b"]);
+});
+
+/// ## The Standard Cells
+/// The standard library provides the same abstractions, tested and
+/// optimized (see recursion.rs for a `RefCell` cache and variance.rs for
+/// the invariance of `Cell<T>`):
+/// - `Cell<T>`: copy or swap values in and out, no runtime cost
+/// - `RefCell<T>`: borrows checked at runtime, panics on conflicts
+/// - `Mutex<T>`, `RwLock<T>`, atomics: the thread-safe (`Sync`) equivalents
+runnable!(standard_cells, {
+    let cell: std::cell::RefCell<Vec<u8>> = std::cell::RefCell::new(vec![]);
+    cell.borrow_mut().push(1);
+    let reader = cell.borrow();
+    assert!(cell.try_borrow_mut().is_err());
+});
\ No newline at end of file
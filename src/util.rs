@@ -8,15 +8,183 @@
         #[test] fn $name(){ 
             let test_name = stringify!($name);
             println!("{} [start]", test_name);
-            let start_time = std::time::Instant::now();
+            let start_stats = $crate::allocator::thread_stats();
+            let start_time = ::std::time::Instant::now();
             $exp 
-            let end_time = std::time::Instant::now();
+            let end_time = ::std::time::Instant::now();
+            let end_stats = $crate::allocator::thread_stats();
             println!(
                 "{} [end]: took {} ms...", 
                 test_name,
                 end_time.duration_since(start_time).as_millis()
             );
+            // Enabled by the `verbose-harness` feature (see features.rs)
+            if cfg!(feature = "verbose-harness") {
+                println!(
+                    "{} [details]: thread {:?}, took {:?}",
+                    test_name,
+                    ::std::thread::current().name().unwrap_or("unnamed"),
+                    end_time.duration_since(start_time)
+                );
+            }
+            // Also needs the `count-allocations` feature (see allocator.rs)
+            if cfg!(all(
+                feature = "verbose-harness", feature = "count-allocations"
+            )) {
+                let stats = end_stats - start_stats;
+                println!(
+                    "{} [memory]: {} allocations, {} reallocations, {} bytes",
+                    test_name, stats.allocations, stats.reallocations,
+                    stats.bytes
+                );
+            }
         }
     );
 }
+
+/// Measure the average time taken to evaluate an expression over many
+/// iterations, print it and return it.
+///
+/// Note: like `runnable!`, this is a macro defined for this playground. Tests
+///       are compiled without optimizations, so only compare timings with
+///       each other, never with the timings of a release build.
+///
+/// The result of each evaluation goes through `black_box`, so that the
+/// optimizer cannot delete the work (see black_box.rs).
+#[macro_export] macro_rules! benchmark {
+    ($label: expr, $iterations: expr, $exp: expr) => ({
+        let iterations: u32 = $iterations;
+        let start_time = ::std::time::Instant::now();
+        for _ in 0..iterations { ::std::hint::black_box($exp); }
+        let average = start_time.elapsed() / iterations;
+        println!("{}: {:?} per iteration ({} iterations)",
+            $label, average, iterations);
+        average
+    });
+}
+
+/// Expand a table of test cases into one `#[test]` function per row, in a
+/// module named after the table:
+/// `test_cases!(squares, |x: u32| x * x, [(zero, 0, 0), (two, 2, 4)])`
+/// defines the tests `squares::zero` and `squares::two`, each asserting that
+/// the function maps the input of its row to the expected output. Unlike a
+/// loop over the rows in a single test, each row passes or fails on its own,
+/// and can be run alone by name. Functions of several arguments take them
+/// as a tuple.
+///
+/// Note: like `runnable!`, this is a macro defined for this playground (the
+///       `rstest` and `test-case` crates offer the same, as attributes).
+#[macro_export] macro_rules! test_cases {
+    ($group: ident, $function: expr,
+        [$(($name: ident, $input: expr, $expected: expr)),+ $(,)?]) => (
+        #[cfg(test)]
+        mod $group {
+            use super::*;
+            $(
+                #[test] fn $name() {
+                    assert_eq!(($function)($input), $expected,
+                        "input: {}", stringify!($input));
+                }
+            )+
+        }
+    );
+}
+
+/// Assert at compile time that a type implements some traits, e.g.
+/// `static_assert_impl!(Vec<u8>: Send + Sync)` (see static_assertions.rs).
+///
+/// Note: like `runnable!`, this is a macro defined for this playground.
+#[macro_export] macro_rules! static_assert_impl {
+    ($type: ty: $($bounds: tt)+) => (
+        const _: fn() = || {
+            fn assert_impl<T: ?Sized + $($bounds)+>() {}
+            assert_impl::<$type>();
+        };
+    );
+}
+/// Assert at compile time that two types have the same size and alignment,
+/// e.g. `static_assert_layout!(Meters, f64)` (see repr_transparent.rs).
+///
+/// Note: like `runnable!`, this is a macro defined for this playground.
+#[macro_export] macro_rules! static_assert_layout {
+    ($a: ty, $b: ty) => (
+        const _: () = assert!(
+            ::std::mem::size_of::<$a>() == ::std::mem::size_of::<$b>()
+                && ::std::mem::align_of::<$a>() == ::std::mem::align_of::<$b>(),
+            concat!("different layouts: ", stringify!($a), ", ", stringify!($b))
+        );
+    );
+}
+
+/// A small pseudo-random number generator (xorshift64*), for lessons which
+/// need randomness without depending on the `rand` crate. The same seed
+/// always gives the same sequence, so tests using it are reproducible.
+///
+/// Note: like `runnable!`, this is defined for this playground. It is not
+///       suitable for cryptography.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.max(1))            // an all-zero state would stay zero
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// A number in `0..bound` (slightly biased for huge bounds).
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// A number in `0.0..1.0`, from the 53 high bits (an `f64` mantissa).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// A fresh, empty directory for one test, under the temporary directory of
+/// the system, removed with its content when dropped: even if the test
+/// panics (see fixtures.rs).
+///
+/// Note: like `runnable!`, this is defined for this playground (the
+///       `tempfile` crate provides the same, as `TempDir`).
+#[derive(Debug)]
+pub struct TestDir(std::path::PathBuf);
+
+impl TestDir {
+    /// Tests run in parallel, and so may several test processes: the name
+    /// of the directory is made unique with the process id and a counter.
+    pub fn new(prefix: &str) -> TestDir {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!(
+            "{}-{}-{}", prefix, std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_dir_all(&path);   // left by a crashed run
+        std::fs::create_dir_all(&path).expect("cannot create a test dir");
+        TestDir(path)
+    }
+
+    pub fn path(&self) -> &std::path::Path { &self.0 }
+
+    pub fn join(&self, name: &str) -> std::path::PathBuf { self.0.join(name) }
+}
+
+impl Drop for TestDir {
+    /// Errors are ignored: panicking in `drop` while the test is already
+    /// panicking would abort the whole process.
+    fn drop(&mut self) { let _ = std::fs::remove_dir_all(&self.0); }
+}
+
+/// Property-based testing helpers (see util/proptest.rs).
+#[macro_use] pub mod proptest;
+/// Golden files and snapshot assertions (see util/golden.rs).
+#[macro_use] pub mod golden;
 // -----------------------------------------------------------------------------
\ No newline at end of file
@@ -0,0 +1,81 @@
+/// # Golden Files (~ the `insta` and `goldenfile` crates)
+/// A golden file stores the expected output of a test, next to the code,
+/// instead of a string literal inside the test. Outputs too long to write
+/// by hand (a pretty-printed tree, a report) are recorded once, reviewed,
+/// and committed: the test then fails whenever the output changes.
+///
+/// When a change is intended, the files are regenerated instead of edited:
+/// `UPDATE_GOLDEN=1 cargo test`, then `git diff src/snapshots` shows what
+/// changed, for review like any other diff.
+///
+/// Note: like `runnable!`, this is defined for this playground (see
+///       testing/snapshots.rs for its use).
+use std::path::{Path, PathBuf};
+
+/// The golden files of the playground, one `<name>.txt` per snapshot.
+pub fn directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("snapshots")
+}
+
+/// Compare `actual` with the golden file `name` in `directory`, or replace
+/// the file if the `UPDATE_GOLDEN` environment variable is set. The error
+/// lists the lines which differ, and how to update the file.
+pub fn check(directory: &Path, name: &str, actual: &str)
+    -> Result<(), String>
+{
+    let path: PathBuf = directory.join(format!("{}.txt", name));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+        return std::fs::write(&path, actual).map_err(|e| e.to_string());
+    }
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        return Err(format!("missing golden file {}: create it with \
+            UPDATE_GOLDEN=1", path.display()));
+    };
+    // Git may check text files out with Windows line endings
+    let expected: String = expected.replace("\r\n", "\n");
+    if expected == actual { return Ok(()); }
+
+    let mut message: String =
+        format!("golden file {} does not match:\n", path.display());
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    for line in 0..expected.len().max(actual.len()) {
+        match (expected.get(line), actual.get(line)) {
+            (Some(old), Some(new)) if old == new => {}
+            (old, new) => {
+                if let Some(old) = old {
+                    message += &format!("{:>4} - {}\n", line + 1, old);
+                }
+                if let Some(new) = new {
+                    message += &format!("{:>4} + {}\n", line + 1, new);
+                }
+            }
+        }
+    }
+    Err(message + "if the change is intended, update it with UPDATE_GOLDEN=1")
+}
+
+/// ## The Assertions
+/// `assert_golden!("name", text)` checks a string against the golden file
+/// `src/snapshots/name.txt`, and panics with the differences.
+#[macro_export] macro_rules! assert_golden {
+    ($name: expr, $actual: expr) => {{
+        let directory = $crate::util::golden::directory();
+        let actual: &str = &$actual;
+        if let Err(message) =
+            $crate::util::golden::check(&directory, $name, actual)
+        {
+            panic!("{}", message);
+        }
+    }};
+}
+
+/// `assert_debug_snapshot!("name", value)` checks the pretty-printed `Debug`
+/// output of a value (`{:#?}`), one field per line, so that the differences
+/// point at the fields which changed.
+#[macro_export] macro_rules! assert_debug_snapshot {
+    ($name: expr, $value: expr) => {
+        $crate::assert_golden!($name, format!("{:#?}\n", $value))
+    };
+}
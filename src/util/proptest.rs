@@ -0,0 +1,222 @@
+/// # Property-Based Testing (~ the `proptest` and `quickcheck` crates)
+/// A unit test checks one example chosen by its author. A property test
+/// states something which must hold for every input (`a + b == b + a`), and
+/// checks it against many random inputs. When an input breaks the property,
+/// it is `shrunk`: simpler and simpler variants are tried, and the simplest
+/// one which still fails is reported, instead of a random-looking value.
+///
+/// Note: like `runnable!`, this is defined for this playground (see
+///       unit_testing.rs for its use).
+use super::Rng;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// ## Arbitrary Values
+/// How to generate random values of a type, and how to simplify them.
+pub trait Arbitrary: Clone + fmt::Debug {
+    fn arbitrary(rng: &mut Rng) -> Self;
+
+    /// Simpler variants of `self`, the most aggressive first. The default
+    /// cannot simplify anything.
+    fn shrink(&self) -> Vec<Self> { Vec::new() }
+}
+
+/// Integers favour the edge cases, where bugs hide: one value in eight is
+/// `0`, `1`, `MIN` or `MAX`. They shrink towards zero: to zero itself, to
+/// their opposite if negative, then closer and closer to themselves.
+macro_rules! arbitrary_integers {
+    ($($type: ty),+) => {$(
+        impl Arbitrary for $type {
+            fn arbitrary(rng: &mut Rng) -> $type {
+                let edges: [$type; 4] = [0, 1, <$type>::MIN, <$type>::MAX];
+                match rng.below(8) {
+                    0 => edges[rng.below(4) as usize],
+                    _ => rng.next_u64() as $type,
+                }
+            }
+
+            fn shrink(&self) -> Vec<$type> {
+                if *self == 0 { return Vec::new(); }
+                let mut candidates: Vec<$type> = vec![0];
+                match self.checked_neg() {
+                    Some(opposite) if opposite > *self =>
+                        candidates.push(opposite),
+                    _ => {}
+                }
+                let mut step: $type = *self / 2;
+                while step != 0 {
+                    candidates.push(*self - step);  // e.g. 100: 50, 75, ..., 99
+                    step /= 2;
+                }
+                candidates
+            }
+        }
+    )+};
+}
+
+arbitrary_integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng) -> bool { rng.below(2) == 1 }
+
+    fn shrink(&self) -> Vec<bool> {
+        if *self { vec![false] } else { Vec::new() }
+    }
+}
+
+/// Vectors shrink by dropping elements (all, half, then one at a time),
+/// then by shrinking one element at a time.
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary(rng: &mut Rng) -> Vec<T> {
+        let length: u64 = rng.below(16);
+        (0..length).map(|_| T::arbitrary(rng)).collect()
+    }
+
+    fn shrink(&self) -> Vec<Vec<T>> {
+        if self.is_empty() { return Vec::new(); }
+        let mut candidates: Vec<Vec<T>> = vec![Vec::new()];
+        if self.len() > 2 {
+            candidates.push(self[..self.len() / 2].to_vec());
+            candidates.push(self[self.len() / 2..].to_vec());
+        }
+        for index in 0..self.len() {
+            let mut smaller: Vec<T> = self.clone();
+            smaller.remove(index);
+            candidates.push(smaller);
+        }
+        for (index, value) in self.iter().enumerate() {
+            for simpler in value.shrink() {
+                let mut candidate: Vec<T> = self.clone();
+                candidate[index] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/// Tuples hold the arguments of a property: they shrink one element at a
+/// time, keeping the others.
+macro_rules! arbitrary_tuples {
+    ($(($($name: ident: $index: tt),+)),+) => {$(
+        impl<$($name: Arbitrary),+> Arbitrary for ($($name,)+) {
+            fn arbitrary(rng: &mut Rng) -> ($($name,)+) {
+                ($($name::arbitrary(rng),)+)
+            }
+
+            fn shrink(&self) -> Vec<($($name,)+)> {
+                let mut candidates: Vec<($($name,)+)> = Vec::new();
+                $(
+                    for simpler in self.$index.shrink() {
+                        let mut candidate = self.clone();
+                        candidate.$index = simpler;
+                        candidates.push(candidate);
+                    }
+                )+
+                candidates
+            }
+        }
+    )+};
+}
+
+arbitrary_tuples!(
+    (A: 0),
+    (A: 0, B: 1),
+    (A: 0, B: 1, C: 2),
+    (A: 0, B: 1, C: 2, D: 3)
+);
+
+/// ## Running a Property
+pub struct Config {
+    /// The number of random inputs to try.
+    pub cases: u32,
+    /// The seed of the random inputs: the same seed tries the same inputs,
+    /// so that a failure can be replayed.
+    pub seed: u64,
+    /// The maximum number of successful shrinking steps.
+    pub max_shrinks: u32,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { cases: 256, seed: 0x5eed, max_shrinks: 1_000 }
+    }
+}
+
+/// A failed property: the first failing input, and its simplest variant.
+#[derive(Debug)]
+pub struct Failure<T> {
+    pub seed: u64,
+    /// The number of inputs which passed before the failure.
+    pub passed: u32,
+    pub original: T,
+    pub shrunk: T,
+    pub shrinks: u32,
+}
+
+impl<T: fmt::Debug> fmt::Display for Failure<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "property failed after {} passing cases (seed {:#x}): \
+            minimal input {:?}, shrunk from {:?} in {} steps",
+            self.passed, self.seed, self.shrunk, self.original, self.shrinks)
+    }
+}
+
+/// Check `property` against `config.cases` random inputs. On failure,
+/// shrink greedily: move to the first simpler variant which still fails,
+/// until none does.
+pub fn check<T: Arbitrary>(config: &Config, property: impl Fn(T) -> bool)
+    -> Result<(), Failure<T>>
+{
+    let mut rng: Rng = Rng::new(config.seed);
+    for passed in 0..config.cases {
+        let input: T = T::arbitrary(&mut rng);
+        if holds(&property, input.clone()) { continue; }
+
+        let mut shrunk: T = input.clone();
+        let mut shrinks: u32 = 0;
+        while shrinks < config.max_shrinks {
+            let simpler: Option<T> = shrunk.shrink().into_iter()
+                .find(|candidate| !holds(&property, candidate.clone()));
+            match simpler {
+                Some(simpler) => { shrunk = simpler; shrinks += 1; }
+                None => break,
+            }
+        }
+        return Err(Failure {
+            seed: config.seed, passed, original: input, shrunk, shrinks,
+        });
+    }
+    Ok(())
+}
+
+/// A property which panics fails, like one returning `false`: its input is
+/// shrunk and reported with the seed, instead of the panic ending the test
+/// with a random-looking input. Each panic still prints its message.
+fn holds<T>(property: &impl Fn(T) -> bool, input: T) -> bool {
+    panic::catch_unwind(AssertUnwindSafe(|| property(input)))
+        .unwrap_or(false)
+}
+
+/// ## The `for_all!` Macro
+/// `for_all!(|x: u8, y: u8| x.wrapping_add(y) == y.wrapping_add(x))` checks
+/// a boolean property of typed arguments, and panics with the minimal
+/// failing input. The number of cases can be given first:
+/// `for_all!(cases = 1000, |x: u8| ...)`.
+#[macro_export] macro_rules! for_all {
+    (cases = $cases: expr, |$($arg: ident: $type: ty),+ $(,)?| $body: expr)
+    => {{
+        let config = $crate::util::proptest::Config {
+            cases: $cases,
+            ..::std::default::Default::default()
+        };
+        let property = |($($arg,)+): ($($type,)+)| -> bool { $body };
+        if let Err(failure) = $crate::util::proptest::check(&config, property)
+        {
+            panic!("{}", failure);
+        }
+    }};
+    (|$($arg: ident: $type: ty),+ $(,)?| $body: expr) => {
+        $crate::for_all!(cases = 256, |$($arg: $type),+| $body)
+    };
+}
@@ -0,0 +1,111 @@
+/// # Variance and Subtyping
+/// Rust has no subtyping between structs, but it has subtyping between
+/// lifetimes: if `'long: 'short` (see ownership.rs), then `'long` is a
+/// subtype of `'short`, as a longer lifetime can be used wherever a shorter
+/// one is expected.
+///
+/// `Variance` tells how the subtyping of a type parameter carries over to a
+/// type built from it. Given `'long: 'short`, a type `F<T>` is:
+/// - `covariant` in `T`: `F<&'long U>` can be used as `F<&'short U>`
+/// - `contravariant` in `T`: `F<&'short U>` can be used as `F<&'long U>`
+/// - `invariant` in `T`: no conversion is possible in either direction
+///
+/// The compiler infers the variance of a type from its fields, so variance
+/// is rarely written down. Still, it explains many lifetime errors.
+use std::cell::Cell;
+use std::marker::PhantomData;
+
+fn variance() {}
+
+/// ## Covariance of &'a T
+/// Shared references are covariant: a `&'static str` can always be used
+/// where a `&'a str` is expected, shrinking its lifetime.
+runnable!(covariance, {
+    fn shrink<'a>(x: &'static str) -> &'a str { x }
+    fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+        if x.len() > y.len() { x } else { y }
+    }
+
+    let local: String = String::from("local string");
+    // `&'static str` is shrunk to the lifetime of `&local`
+    let result: &str = longest("static", &local);
+    println!("longest: {}", result);
+});
+
+/// ## Invariance of &'a mut T
+/// Mutable references are covariant in their own lifetime, but invariant in
+/// the type they point to. Otherwise, a `&mut &'static str` could be used as
+/// a `&mut &'a str`, and a short-lived reference could be written through it
+/// into a variable that expects a `'static` one.
+fn assign<T>(input: &mut T, value: T) { *input = value; }
+
+runnable!(invariance_of_mutable_references, {
+    let mut hello: &'static str = "hello";
+    {
+        let world: String = String::from("world");
+        // assign(&mut hello, &world);
+        // ^ Error: `world` does not live long enough
+        //   Here `T = &'static str`, because `&mut T` is invariant in `T`, so
+        //   `&world` would need to be `'static`. If this compiled, `hello`
+        //   would point to `world` after it is freed.
+    }
+    println!("hello: {}", hello);
+
+    // fn shrink<'a, 'b>(x: &'b mut &'static str) -> &'b mut &'a str { x }
+    // ^ Error: lifetime may not live long enough
+});
+
+/// ## Invariance of Cell<&'a T>
+/// The same reasoning applies to every type allowing mutation through a
+/// shared reference (`Cell`, `RefCell`, `Mutex`, ...): they are invariant in
+/// their content, because `set` is a write, just like `*input = value`.
+runnable!(invariance_of_cells, {
+    let cell: Cell<&'static str> = Cell::new("static");
+    // fn shrink<'a>(x: Cell<&'static str>) -> Cell<&'a str> { x }
+    // ^ Error: lifetime may not live long enough
+    {
+        let local: String = String::from("local");
+        // cell.set(&local);
+        // ^ Error: `local` does not live long enough
+    }
+    println!("cell: {}", cell.get());
+});
+
+/// ## Variance and PhantomData
+/// A type with a `PhantomData<X>` field has the same variance as if it had a
+/// field of type `X` (see generics.rs). Choosing `X` declares the variance of
+/// a wrapper that does not store its type parameter directly:
+/// - `PhantomData<T>`: covariant (the wrapper behaves as if it owns a `T`)
+/// - `PhantomData<fn(T)>`: contravariant (the wrapper consumes `T`s)
+/// - `PhantomData<fn(T) -> T>` or `PhantomData<*mut T>` or
+///   `PhantomData<Cell<T>>`: invariant
+struct Covariant<T>(PhantomData<T>);
+struct Contravariant<T>(PhantomData<fn(T)>);
+struct Invariant<T>(PhantomData<fn(T) -> T>);
+
+fn shrink_covariant<'a>(x: Covariant<&'static str>) -> Covariant<&'a str> {
+    x
+}
+fn grow_contravariant<'a>(
+    x: Contravariant<&'a str>
+) -> Contravariant<&'static str> {
+    x
+}
+// fn shrink_invariant<'a>(x: Invariant<&'static str>) -> Invariant<&'a str> {
+//     x
+// }
+// ^ Error: lifetime may not live long enough
+
+/// Contravariance is what makes functions flexible: a function accepting
+/// any `&'a str` can be used where a function accepting only `&'static str`
+/// is expected, because it can also handle `'static` strings.
+runnable!(phantom_variance, {
+    fn print_any<'a>(s: &'a str) { println!("{}", s); }
+    let print_static: fn(&'static str) = print_any;
+    print_static("contravariance");
+
+    let covariant: Covariant<&str> = shrink_covariant(Covariant(PhantomData));
+    let contravariant: Contravariant<&'static str> =
+        grow_contravariant(Contravariant(PhantomData));
+    let invariant: Invariant<&'static str> = Invariant(PhantomData);
+});
\ No newline at end of file
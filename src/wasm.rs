@@ -0,0 +1,93 @@
+/// # WebAssembly (~ compiling C with Emscripten)
+/// `WebAssembly` (wasm) is a portable bytecode run by browsers and by
+/// standalone runtimes (wasmtime, wasmer, Node.js). Rust compiles to it like
+/// to any other target (see target_config.rs):
+/// - `wasm32-unknown-unknown`: no operating system at all, the host provides
+///   everything through imported functions (the usual target for browsers)
+/// - `wasm32-wasip1`/`wasm32-wasip2`: the WebAssembly System Interface, a
+///   standard set of imports for files, clocks, environment variables, ...
+///
+/// The workspace member `playground-wasm` is a library built as a wasm
+/// module, exporting a few functions (see its `src/lib.rs` for the build
+/// command and how to load it from JavaScript). It also builds natively, so
+/// this lesson calls it directly.
+use playground_wasm::{
+    platform, wasm_add, wasm_alloc, wasm_count_vowels, wasm_fibonacci,
+    wasm_free, wasm_parallelism,
+};
+
+fn wasm() {}
+
+/// ## What Differs
+/// On `wasm32-unknown-unknown`, the standard library compiles, but:
+/// - no threads: `std::thread::spawn` fails, everything is single-threaded
+///   (and `Mutex` never blocks)
+/// - no files, no environment variables, no processes, no network
+/// - no clock: `std::time::Instant::now()` panics
+/// - no standard output: `println!` prints nothing
+/// - panics abort (no unwinding): a panic traps, and the host sees an error
+/// - `usize` and pointers are 32 bits (`target_pointer_width = "32"`), the
+///   memory of the module being a single `linear memory` of at most 4 GiB
+///
+/// Binaries are usually built as libraries (`crate-type = ["cdylib"]`):
+/// the host calls the exported functions, instead of running `main`.
+fn what_differs() {}
+
+/// ## Shims with `cfg(target_arch = "wasm32")`
+/// Code shared between native and wasm builds hides the missing APIs behind
+/// functions with one implementation per target, like `playground-wasm`
+/// does for the clock (imported from the host) and for threads:
+/// ```
+/// #[cfg(target_arch = "wasm32")]
+/// pub fn parallelism() -> usize { 1 }
+/// #[cfg(not(target_arch = "wasm32"))]
+/// pub fn parallelism() -> usize {
+///     std::thread::available_parallelism().map_or(1, |count| count.get())
+/// }
+/// ```
+/// The same applies to files, which can be embedded in the module instead
+/// (see embedded_assets.rs).
+#[cfg(not(target_arch = "wasm32"))]
+fn load_banner() -> String {
+    let path: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/banner.txt");
+    std::fs::read_to_string(path).unwrap_or_default()
+}
+#[cfg(target_arch = "wasm32")]
+fn load_banner() -> String {
+    include_str!("../assets/banner.txt").to_string()
+}
+
+runnable!(shims, {
+    println!("now: {} ms since the epoch", platform::now_millis());
+    println!("parallelism: {}", platform::parallelism());
+    assert!(platform::parallelism() >= 1);
+    assert!(load_banner().contains('_'));
+});
+
+/// ## Exported Functions
+/// The exports of a module only take and return numbers. On the JavaScript
+/// side, `i64`/`u64` values become `BigInt`s.
+runnable!(exported_functions, {
+    assert_eq!(wasm_add(40, 2), 42);
+    assert_eq!(wasm_fibonacci(50), 12_586_269_025);
+    println!("parallelism seen by the module: {}", wasm_parallelism());
+});
+
+/// ## Passing Data Through Linear Memory
+/// To pass a string, the host allocates a buffer inside the module, writes
+/// the bytes into it, and passes the pointer (an offset into the linear
+/// memory) and the length. Tools like `wasm-bindgen` generate this glue
+/// code, for strings, structs and closures.
+runnable!(passing_data_through_linear_memory, {
+    let text: &[u8] = "WebAssembly module".as_bytes();
+    // What the host does, step by step
+    let buffer: *mut u8 = wasm_alloc(text.len());
+    // SAFETY: the buffer has room for `text.len()` bytes, and is freed once
+    // with the same length.
+    unsafe {
+        buffer.copy_from_nonoverlapping(text.as_ptr(), text.len());
+        assert_eq!(wasm_count_vowels(buffer, text.len()), 6);
+        wasm_free(buffer, text.len());
+        assert_eq!(wasm_count_vowels([0xff].as_ptr(), 1), -1);  // not UTF-8
+    }
+});
\ No newline at end of file
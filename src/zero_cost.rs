@@ -0,0 +1,82 @@
+/// # Zero-Cost Abstractions: Iterators vs Loops
+/// "What you don't use, you don't pay for. What you do use, you couldn't
+/// hand code any better" (Bjarne Stroustrup). Iterator chains are the
+/// flagship example in Rust: `filter`, `map` and `sum` are structs and
+/// closures (see iterator_adapters.rs), yet, once optimized, they compile to
+/// the same machine code as a hand-written loop, or better.
+///
+/// This lesson computes the same aggregation three ways, and measures them.
+fn zero_cost() {}
+
+/// ## Three Ways to Aggregate
+/// The sum of the squares of the even numbers of a slice.
+///
+/// A manual index loop: every `values[i]` is bounds-checked (`i <
+/// values.len()`, or panic), unless the optimizer can prove that `i` is in
+/// bounds. Here it can, from the loop condition, but any other indexing
+/// pattern may keep a check (and a branch) per access.
+pub fn index_loop(values: &[u64]) -> u64 {
+    let mut total: u64 = 0;
+    let mut i: usize = 0;
+    while i < values.len() {
+        if values[i] % 2 == 0 { total += values[i] * values[i]; }
+        i += 1;
+    }
+    total
+}
+
+/// A `for` loop over the slice: the slice iterator yields references
+/// without indexing, so there are no bounds checks to eliminate.
+pub fn for_loop(values: &[u64]) -> u64 {
+    let mut total: u64 = 0;
+    for value in values {
+        if value % 2 == 0 { total += value * value; }
+    }
+    total
+}
+
+/// An iterator chain: the same loop as `for_loop`, once every adapter and
+/// closure has been inlined into `sum`.
+pub fn iterator_chain(values: &[u64]) -> u64 {
+    values.iter()
+        .filter(|value| *value % 2 == 0)
+        .map(|value| value * value)
+        .sum()
+}
+
+runnable!(three_ways_to_aggregate, {
+    let values: Vec<u64> = (0..1_000).collect();
+    let expected: u64 = (0..1_000u64).step_by(2).map(|x| x * x).sum();
+    assert_eq!(index_loop(&values), expected);
+    assert_eq!(for_loop(&values), expected);
+    assert_eq!(iterator_chain(&values), expected);
+    assert_eq!(iterator_chain(&[]), 0);
+});
+
+/// ## Measurements
+/// `cargo test` builds without optimizations (`opt-level = 0`): nothing is
+/// inlined, so every `next`, every closure call and every `Option` returned
+/// is a real function call. In this build, the `for` loop (which calls
+/// `Iterator::next` too) and even more the chain are typically several times
+/// slower than the index loop: the abstractions are only free once the
+/// optimizer removed them.
+///
+/// With `cargo test --release zero_cost -- --nocapture`, the three versions
+/// usually run within noise of each other, and all get vectorized. This is
+/// also why debug builds of iterator-heavy code can feel slow, and why
+/// `[profile.dev] opt-level = 1` is a common setting.
+///
+/// `benchmark!` (see util.rs) only averages the wall-clock time of a few
+/// iterations: differences of a few percent are noise.
+runnable!(measurements, {
+    let values: Vec<u64> = (0..100_000).collect();
+    let build: &str = if cfg!(debug_assertions) { "debug" } else { "release" };
+    println!("{} build", build);
+    let index = benchmark!("index loop", 50, index_loop(&values));
+    let for_ = benchmark!("for loop", 50, for_loop(&values));
+    let chain = benchmark!("iterator chain", 50, iterator_chain(&values));
+    for (label, average) in [("for loop", for_), ("iterator chain", chain)] {
+        println!("{}: {:.2}x index loop", label,
+            average.as_secs_f64() / index.as_secs_f64().max(1e-9));
+    }
+});
@@ -0,0 +1,197 @@
+/// # A Custom Test Harness
+/// `#[test]` functions do not run themselves: the compiler collects them,
+/// and links a `main` from `libtest`, the default `harness`, which runs
+/// them in threads, captures their output, and prints the summary. With
+/// `harness = false` (see Cargo.toml), a test target is a plain program
+/// instead: its `main` decides what to run, and the test fails if it exits
+/// with a non-zero code. This is how `criterion`, or `cargo-nextest`, take
+/// over from `libtest`.
+///
+/// The runnables of the playground (see `runnable!` in src/util.rs) are
+/// `#[test]` functions of the binary, which an integration test cannot
+/// call. So this harness works like `cargo-nextest`: it asks the unit test
+/// executable of the binary for its list of tests (`--list`), then runs
+/// each one in its own process (`--exact`). A crash, such as a stack
+/// overflow (see src/fuzzing.rs), then only takes one test down.
+///
+/// It runs with `cargo test --test custom_harness`, or with
+/// `cargo run -- --run-all`. Arguments filter the runnables by name:
+/// `cargo test --test custom_harness -- patterns::`.
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// ## Finding the Tests
+/// The `deps` folder holds every build of the unit tests of the binary:
+/// older ones, and ones with other features. Only Cargo knows which one
+/// matches the current sources and features: `cargo test --no-run` builds
+/// it (or finds it up to date), and `--message-format=json` reports its
+/// path. This target is compiled with the features of the package, so it
+/// asks for the same ones.
+fn unit_tests() -> Option<PathBuf> {
+    let cargo: String =
+        std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let features: Vec<&str> = [
+        ("tokio", cfg!(feature = "tokio")),
+        ("extra-lessons", cfg!(feature = "extra-lessons")),
+        ("verbose-harness", cfg!(feature = "verbose-harness")),
+        ("count-allocations", cfg!(feature = "count-allocations")),
+        ("native-c", cfg!(feature = "native-c")),
+    ].into_iter().filter(|(_, enabled)| *enabled).map(|(name, _)| name)
+        .collect();
+    let output: Output = Command::new(cargo)
+        .args(["test", "--no-run", "--message-format=json"])
+        .args(["--bin", "rust_plauground", "--manifest-path"])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .args(["--features", &features.join(",")])
+        .output()
+        .ok()?;
+    if !output.status.success() { return None; }
+    String::from_utf8_lossy(&output.stdout).lines()
+        .filter(|line| line.contains(r#""reason":"compiler-artifact""#))
+        .find_map(|line| json_string(line, "executable"))
+        .map(PathBuf::from)
+}
+
+/// The string value of `key` in one line of JSON, unescaped: enough for
+/// the messages of Cargo, without a JSON parser.
+fn json_string(line: &str, key: &str) -> Option<String> {
+    let start: usize = line.find(&format!(r#""{}":""#, key))? + key.len() + 4;
+    let mut value: String = String::new();
+    let mut characters = line[start..].chars();
+    loop {
+        match characters.next()? {
+            '"' => return Some(value),
+            '\\' => value.push(characters.next()?),   // `\\` or `\"`
+            character => value.push(character),
+        }
+    }
+}
+
+/// The names of the tests, with `libtest`'s own protocol: `--list` prints
+/// one `path::name: test` line per test.
+fn list(executable: &Path, ignored: bool) -> Vec<String> {
+    let mut command: Command = Command::new(executable);
+    command.args(["--list", "--format", "terse"]);
+    if ignored { command.arg("--ignored"); }
+    let Ok(output) = command.output() else { return Vec::new() };
+    String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(|line| line.strip_suffix(": test"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// ## Running the Tests
+struct Outcome {
+    name: String,
+    passed: bool,
+    duration: Duration,
+    /// The panic message, for failures.
+    message: String,
+}
+
+fn run(executable: &Path, name: &str) -> Outcome {
+    let start: Instant = Instant::now();
+    let output: Output = Command::new(executable)
+        .args(["--exact", name, "--test-threads", "1", "--nocapture"])
+        .output()
+        .expect("cannot run the test");
+    let stderr: String = String::from_utf8_lossy(&output.stderr).into();
+    let message: String = stderr.lines()
+        .skip_while(|line| !line.contains("panicked at"))
+        .take(2)
+        .collect::<Vec<&str>>()
+        .join(" ");
+    Outcome {
+        name: name.to_string(),
+        passed: output.status.success(),
+        duration: start.elapsed(),
+        message: if message.is_empty() { stderr } else { message },
+    }
+}
+
+/// Run the tests on a few threads, each taking the next test from a shared
+/// counter until none are left.
+fn run_all(executable: &Path, names: &[String]) -> Vec<Outcome> {
+    let next: AtomicUsize = AtomicUsize::new(0);
+    let outcomes: Mutex<Vec<Outcome>> = Mutex::new(Vec::new());
+    let threads: usize =
+        std::thread::available_parallelism().map_or(4, |count| count.get());
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let index: usize = next.fetch_add(1, Ordering::Relaxed);
+                let Some(name) = names.get(index) else { break };
+                let outcome: Outcome = run(executable, name);
+                report(&outcome);
+                outcomes.lock().unwrap().push(outcome);
+            });
+        }
+    });
+    let mut outcomes: Vec<Outcome> = outcomes.into_inner().unwrap();
+    outcomes.sort_by(|a, b| a.name.cmp(&b.name));
+    outcomes
+}
+
+/// ## Reporting
+/// ANSI escape codes color the output, unless it is not a terminal, or the
+/// `NO_COLOR` environment variable is set (see https://no-color.org).
+fn paint(text: &str, color: u8) -> String {
+    let colored: bool = std::io::stdout().is_terminal()
+        && std::env::var_os("NO_COLOR").is_none();
+    if colored { format!("\x1b[{}m{}\x1b[0m", color, text) }
+    else { text.to_string() }
+}
+
+const GREEN: u8 = 32;
+const RED: u8 = 31;
+
+fn report(outcome: &Outcome) {
+    let status: String = if outcome.passed { paint("ok", GREEN) }
+        else { paint("FAILED", RED) };
+    println!("{} ... {} ({} ms)", outcome.name, status,
+        outcome.duration.as_millis());
+}
+
+fn main() {
+    let filters: Vec<String> = std::env::args().skip(1)
+        .filter(|argument| !argument.starts_with('-'))
+        .collect();
+    let Some(executable) = unit_tests() else {
+        println!("{}: `cargo test --no-run` failed",
+            paint("cannot build the unit tests", RED));
+        std::process::exit(101);
+    };
+
+    let names: Vec<String> = list(&executable, false);
+    let ignored: Vec<String> = list(&executable, true);
+    let (names, skipped): (Vec<String>, Vec<String>) =
+        names.into_iter()
+            .filter(|name| {
+                filters.is_empty() || filters.iter().any(|f| name.contains(f))
+            })
+            .partition(|name| !ignored.contains(name));
+    println!("running {} runnables from {}", names.len(),
+        executable.display());
+
+    let start: Instant = Instant::now();
+    let outcomes: Vec<Outcome> = run_all(&executable, &names);
+    let failures: Vec<&Outcome> =
+        outcomes.iter().filter(|outcome| !outcome.passed).collect();
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for failure in &failures {
+            println!("    {}: {}", paint(&failure.name, RED), failure.message);
+        }
+    }
+    let result: String = if failures.is_empty() { paint("ok", GREEN) }
+        else { paint("FAILED", RED) };
+    println!("\ntest result: {}. {} passed; {} failed; {} ignored; \
+        finished in {:.2}s",
+        result, outcomes.len() - failures.len(), failures.len(),
+        skipped.len(), start.elapsed().as_secs_f64());
+    if !failures.is_empty() { std::process::exit(101); }  // like libtest
+}
@@ -14,8 +14,9 @@
 /// In any other aspect, they are treated the same as unit tests (see
 /// `unit_testing.rs`).
 
-// use rust_template::unit_testing::implementation;
-// ^ Error: `rust_template` is a binary crate, not a library crate
+// use rust_plauground::unit_testing::implementation;
+// ^ Error: only the library target (src/lib.rs) can be imported, not the
+//          modules of the binary (see non_exhaustive.rs for an example)
 
 mod integration_test_module;    // define and import shared utilities
 
@@ -23,6 +24,7 @@ mod integration_test_module;    // define and import shared utilities
 fn integration_test(){
     integration_test_module::utility_function();
 
-    // You could test `rust_template` here, if it was a library crate
+    // You could test the public API of `rust_plauground` here (see
+    // tests/non_exhaustive.rs)
     // ...
 }
\ No newline at end of file
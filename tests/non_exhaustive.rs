@@ -0,0 +1,24 @@
+/// # Non-Exhaustive Types from an Integration Test
+/// Integration tests are downstream crates of the library target, so the
+/// rules of `#[non_exhaustive]` apply to them (see src/non_exhaustive.rs).
+use rust_plauground::{Event, LogLevel, LoggerConfig};
+
+#[test]
+fn matching_requires_a_wildcard() {
+    let severe: Vec<bool> = [LogLevel::Error, LogLevel::Info].iter()
+        .map(|level| match level {
+            LogLevel::Error => true,
+            _ => false,  // required: new levels may be added
+        })
+        .collect();
+    assert_eq!(severe, vec![true, false]);
+}
+
+#[test]
+fn building_requires_constructors() {
+    let config: LoggerConfig = LoggerConfig::new(LogLevel::Error);
+    assert_eq!(config.level, LogLevel::Error);
+    assert!(!config.colored);
+    assert!(matches!(Event::message(LogLevel::Info, "hi"),
+        Event::Message { level: LogLevel::Info, .. }));
+}
\ No newline at end of file